@@ -0,0 +1,1394 @@
+//! Admin command-line tool for operating a TrendXBet deployment.
+
+mod output;
+
+use anyhow::{anyhow, bail, Context, Result};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use output::*;
+use solana_sdk::{
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::{read_keypair_file, Keypair, Signer},
+};
+use std::cell::RefCell;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+use trendxbet::{
+    BetState, BetStatus, ExposureLimits, MarketKind, MatchIndexKey, MatchStatus, OracleState,
+    PrivatePool, SecurityUtils, LINE_SCALE, PUSH_RESULT,
+};
+use trendxbet_client::{instructions, Submission, TrendXBetClient};
+
+#[derive(Parser)]
+#[command(
+    name = "trendxbet-cli",
+    version,
+    about = "Operate a TrendXBet deployment"
+)]
+struct Cli {
+    #[command(flatten)]
+    config: Config,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Args)]
+struct Config {
+    /// RPC endpoint URL or moniker (localhost, devnet, testnet, mainnet-beta)
+    #[arg(
+        short = 'u',
+        long,
+        global = true,
+        env = "TRENDXBET_RPC_URL",
+        default_value = "localhost"
+    )]
+    url: String,
+
+    /// Admin keypair file, also used as fee payer
+    #[arg(short = 'k', long, global = true, env = "TRENDXBET_KEYPAIR")]
+    keypair: Option<PathBuf>,
+
+    /// TrendXBet program id
+    #[arg(long, global = true, env = "TRENDXBET_PROGRAM_ID")]
+    program_id: Option<String>,
+
+    /// Simulate transactions instead of sending them
+    #[arg(long, global = true)]
+    dry_run: bool,
+
+    /// Output format
+    #[arg(short = 'o', long, global = true, value_enum, default_value = "table")]
+    output: OutputFormat,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Initialize the platform global state and treasury
+    Initialize {
+        /// Admin authority (defaults to the keypair)
+        #[arg(long)]
+        admin: Option<String>,
+    },
+    /// Show the platform global state
+    Global,
+    /// Show the treasury state
+    Treasury,
+    /// Show a user profile and betting statistics
+    User {
+        /// Wallet authority of the user
+        authority: String,
+    },
+    /// Show the profile holding a username, in any casing
+    Whois { username: String },
+    /// List matches, optionally filtered by status, league or start day
+    Matches {
+        #[arg(long, value_enum)]
+        status: Option<StatusArg>,
+        /// Only matches listed in this league's index
+        #[arg(long)]
+        league: Option<u32>,
+        /// Only matches listed as starting on the UTC day of this unix time
+        #[arg(long)]
+        day: Option<i64>,
+    },
+    /// Show a single match
+    Match {
+        /// Match identifier
+        match_id: String,
+    },
+    /// Create a new match
+    CreateMatch {
+        /// Name of team 1
+        #[arg(long)]
+        team1: String,
+        /// Name of team 2
+        #[arg(long)]
+        team2: String,
+        /// Start time as unix seconds, or `+SECS` relative to now
+        #[arg(long)]
+        start: String,
+        /// End time as unix seconds, or `+SECS` relative to the start time
+        #[arg(long)]
+        end: String,
+        /// Match description
+        #[arg(long, default_value = "")]
+        description: String,
+        /// Match identifier (a fresh one is generated when omitted)
+        #[arg(long)]
+        match_id: Option<String>,
+        /// Make it an over/under market on combined points, e.g. 2.5
+        #[arg(long, conflicts_with = "spread")]
+        totals: Option<String>,
+        /// Make it a handicap market, adding this many points to team 1, e.g. -1.5
+        #[arg(long, allow_hyphen_values = true)]
+        spread: Option<String>,
+        /// League the match is listed under in the registry
+        #[arg(long)]
+        league: Option<u32>,
+        /// Creator's cut of the pool in basis points, for community markets
+        #[arg(long, default_value_t = 0)]
+        creator_fee: u16,
+    },
+    /// Create a market on whether an asset ends above a strike price
+    CreatePriceMarket {
+        /// Asset label, e.g. SOL/USD
+        #[arg(long)]
+        asset: String,
+        /// Price feed account the market resolves from
+        #[arg(long)]
+        feed: String,
+        /// Strike price in the feed's units (price * 10^expo)
+        #[arg(long, allow_hyphen_values = true)]
+        strike: i64,
+        /// Start time as unix seconds, or `+SECS` relative to now
+        #[arg(long)]
+        start: String,
+        /// Resolution time as unix seconds, or `+SECS` relative to the start time
+        #[arg(long)]
+        end: String,
+        /// Market description
+        #[arg(long, default_value = "")]
+        description: String,
+        /// Market identifier (a fresh one is generated when omitted)
+        #[arg(long)]
+        match_id: Option<String>,
+        /// League the market is listed under in the registry
+        #[arg(long)]
+        league: Option<u32>,
+        /// Creator's cut of the pool in basis points, for community markets
+        #[arg(long, default_value_t = 0)]
+        creator_fee: u16,
+    },
+    /// Settle a price market on the price its feed published at the resolution time
+    ResolvePriceMarket { match_id: String },
+    /// Move a match to a new status
+    UpdateMatchStatus {
+        match_id: String,
+        #[arg(value_enum)]
+        status: StatusArg,
+    },
+    /// Close betting on a scheduled match
+    CloseBetting { match_id: String },
+    /// Set when a match takes bets and the bonus weight of the earliest ones
+    SetBettingWindow {
+        match_id: String,
+        /// Opening time as unix seconds, or `+SECS` from now
+        #[arg(long)]
+        opens: String,
+        /// Closing time as unix seconds, or `+SECS` from now
+        #[arg(long)]
+        closes: String,
+        /// Extra pool weight of a bet placed at opening, in basis points
+        #[arg(long, default_value_t = 0)]
+        early_bonus: u16,
+    },
+    /// Advance a match to the status implied by the clock and collect the bounty
+    Crank { match_id: String },
+    /// Void a match whose oracles missed their deadline
+    VoidMatch { match_id: String },
+    /// Refund every open bet on a voided or cancelled match
+    RefundBets { match_id: String },
+    /// Pay a community market's creator their fee and return their bond
+    SettleCreator { match_id: String },
+    /// Void a community market for abuse, forfeiting its creator's bond
+    VoidForAbuse { match_id: String },
+    /// Register an oracle authority for a match
+    RegisterOracle {
+        match_id: String,
+        oracle_authority: String,
+    },
+    /// Validate an oracle report and settle the match when final
+    ValidateOracle {
+        match_id: String,
+        oracle_authority: String,
+        /// Peer oracle authorities counted as confirmations (defaults to every
+        /// other oracle that has reported on the match)
+        #[arg(long = "peer")]
+        peers: Vec<String>,
+    },
+    /// Show an oracle's bonded stake and reputation
+    OracleProfile {
+        /// Oracle authority
+        authority: String,
+    },
+    /// Bond oracle stake from the keypair, creating its profile if needed
+    BondStake {
+        /// Amount in lamports
+        amount: u64,
+    },
+    /// Start unbonding oracle stake held by the keypair
+    UnbondStake {
+        /// Amount in lamports
+        amount: u64,
+    },
+    /// Withdraw oracle stake whose unbonding period is over
+    WithdrawStake,
+    /// Reward or slash every unresolved oracle report on a settled or pushed match
+    ResolveOracles { match_id: String },
+    /// Create or replace the arbiter set that decides disputes
+    SetArbiters {
+        /// Votes needed on one side to resolve a dispute
+        quorum: u8,
+        /// Arbiter authorities
+        #[arg(long = "arbiter", required = true)]
+        arbiters: Vec<String>,
+    },
+    /// Show the dispute of a match result
+    Dispute { match_id: String },
+    /// Bond the dispute bond from the keypair to challenge a match result
+    OpenDispute {
+        match_id: String,
+        /// Team the keypair claims won (0 = team1, 1 = team2)
+        proposed_team: u8,
+    },
+    /// Vote on a dispute as an arbiter
+    VoteDispute {
+        match_id: String,
+        /// Keep the oracle result instead of upholding the dispute
+        #[arg(long)]
+        reject: bool,
+    },
+    /// Expire a dispute the arbiters left undecided past its voting deadline
+    ExpireDispute { match_id: String },
+    /// Update house edge, bet limits, crank bounty, price feed program, dispute terms and betting defaults
+    UpdateConfig {
+        /// House edge in basis points
+        #[arg(long)]
+        house_edge: Option<u16>,
+        /// Minimum bet in lamports
+        #[arg(long)]
+        min_bet: Option<u64>,
+        /// Maximum bet in lamports
+        #[arg(long)]
+        max_bet: Option<u64>,
+        /// Lamports paid to whoever cranks a match status
+        #[arg(long)]
+        crank_bounty: Option<u64>,
+        /// Program that owns the price feeds price markets resolve from
+        #[arg(long)]
+        price_feed_program: Option<String>,
+        /// Seconds a settled result can be disputed
+        #[arg(long)]
+        dispute_window: Option<i64>,
+        /// Lamports a challenger bonds to dispute a result
+        #[arg(long)]
+        dispute_bond: Option<u64>,
+        /// Seconds before the end of a new match at which betting closes
+        #[arg(long)]
+        betting_cutoff: Option<u32>,
+        /// Extra pool weight of the earliest bets on new matches, in basis points
+        #[arg(long)]
+        early_bet_bonus: Option<u16>,
+        /// Lamports anyone but the admin bonds to create a market
+        #[arg(long)]
+        creator_bond: Option<u64>,
+        /// Highest creator fee of a community market, in basis points
+        #[arg(long)]
+        max_creator_fee: Option<u16>,
+        /// Share of a stake withdrawn before betting closes that is kept, in basis points
+        #[arg(long)]
+        cancellation_fee: Option<u16>,
+    },
+    /// Replace the exposure caps, 0 disabling a cap
+    SetExposureLimits {
+        /// Largest total pool of a match in lamports
+        #[arg(long, default_value_t = 0)]
+        max_match_pool: u64,
+        /// Largest lamport difference between the two outcome pools
+        #[arg(long, default_value_t = 0)]
+        max_imbalance: u64,
+        /// Largest total stake of one user on a match in lamports
+        #[arg(long, default_value_t = 0)]
+        max_user_stake: u64,
+    },
+    /// Create the match registry so new matches are listed in its indexes
+    CreateRegistry,
+    /// Authorise a promoter to issue bonuses
+    AddPromoter { promoter: String },
+    /// Withdraw a promoter's authorisation to issue bonuses
+    RemovePromoter { promoter: String },
+    /// Credit a user a bonus funded by the keypair as promoter
+    IssueBonus {
+        /// Wallet authority of the user
+        user: String,
+        /// Amount in lamports
+        amount: u64,
+        /// Times the bonus must be staked before its winnings can be withdrawn
+        #[arg(long, default_value_t = 1)]
+        wagering: u8,
+    },
+    /// Chart a match's pools and odds after every bet, funded by the keypair
+    CreateOddsHistory { match_id: String },
+    /// Show a charted match's pools and odds, oldest first
+    OddsHistory { match_id: String },
+    /// Make a match the keypair organises private to invited bettors
+    CreatePrivatePool {
+        match_id: String,
+        /// Bettor invited to the pool
+        #[arg(long = "member")]
+        members: Vec<String>,
+        /// Code letting anyone who knows it join the pool
+        #[arg(long)]
+        invite_code: Option<String>,
+        /// Charge no house edge on the pool's bets
+        #[arg(long)]
+        waive_house_edge: bool,
+    },
+    /// Replace the invite code and members of a private pool the keypair organises
+    UpdatePrivatePool {
+        match_id: String,
+        /// Bettor invited to the pool
+        #[arg(long = "member")]
+        members: Vec<String>,
+        /// Code letting anyone who knows it join the pool
+        #[arg(long)]
+        invite_code: Option<String>,
+    },
+    /// Join a private pool as the keypair with its invite code
+    JoinPrivatePool {
+        match_id: String,
+        invite_code: String,
+    },
+    /// Close betting on a private pool the keypair organises
+    ClosePrivatePool { match_id: String },
+    /// Cancel a private pool the keypair organises so its bets are refunded
+    CancelPrivatePool { match_id: String },
+    /// Record every privileged action in the on-chain audit log
+    EnableAuditLog,
+    /// Show the audit log, oldest entry first
+    AuditLog {
+        /// Only show this page of the log
+        #[arg(long)]
+        page: Option<u32>,
+    },
+    /// Pause the platform
+    Pause,
+    /// Unpause the platform
+    Unpause,
+    /// Withdraw accumulated platform fees to the admin
+    WithdrawFees {
+        /// Amount in lamports
+        amount: u64,
+    },
+    /// Withdraw treasury funds in an emergency
+    EmergencyWithdraw {
+        /// Amount in lamports
+        amount: u64,
+    },
+    /// Upgrade a program account to the current layout version
+    MigrateAccount {
+        /// Address of the global, user, match, bet, treasury, oracle, oracle profile, price market,
+        /// arbiter set, dispute, match registry, match index, username, session key, promoter or
+        /// audit log account
+        account: String,
+    },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum StatusArg {
+    Scheduled,
+    Live,
+    Ended,
+    Settled,
+    Cancelled,
+    Voided,
+}
+
+impl From<StatusArg> for MatchStatus {
+    fn from(status: StatusArg) -> Self {
+        match status {
+            StatusArg::Scheduled => MatchStatus::Scheduled,
+            StatusArg::Live => MatchStatus::Live,
+            StatusArg::Ended => MatchStatus::Ended,
+            StatusArg::Settled => MatchStatus::Settled,
+            StatusArg::Cancelled => MatchStatus::Cancelled,
+            StatusArg::Voided => MatchStatus::Voided,
+        }
+    }
+}
+
+impl Command {
+    /// Check if the command only reads accounts
+    fn is_read_only(&self) -> bool {
+        matches!(
+            self,
+            Command::Global
+                | Command::Treasury
+                | Command::User { .. }
+                | Command::Whois { .. }
+                | Command::Matches { .. }
+                | Command::Match { .. }
+                | Command::OddsHistory { .. }
+                | Command::AuditLog { .. }
+                | Command::OracleProfile { .. }
+                | Command::Dispute { .. }
+        )
+    }
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let config = &cli.config;
+    let client = TrendXBetClient::new(
+        resolve_url(&config.url),
+        resolve_program_id(config.program_id.as_deref())?,
+        match load_keypair(config.keypair.as_ref()) {
+            // Read-only commands never sign, so a missing wallet is not an error
+            Err(_) if cli.command.is_read_only() => Keypair::new(),
+            keypair => keypair?,
+        },
+    );
+    let program_id = client.program_id;
+    let admin = client.payer_pubkey();
+    let format = config.output;
+
+    // Setup a dry run could not create, simulated together with the instruction that needs it
+    let pending_setup: RefCell<Vec<(&str, Instruction)>> = RefCell::new(Vec::new());
+
+    let submit = |action: &str, ix: Instruction, details: &[(&str, String)]| -> Result<()> {
+        let (setup_actions, mut batch): (Vec<&str>, Vec<Instruction>) =
+            pending_setup.take().into_iter().unzip();
+        batch.push(ix);
+        let mut details = details.to_vec();
+        if !setup_actions.is_empty() {
+            details.push(("setup", setup_actions.join(", ")));
+        }
+
+        let submission = client.submit(&batch, &[], config.dry_run)?;
+        print_submission(action, &submission, &details, format);
+        match submission {
+            Submission::Simulated(result) if !result.is_ok() => bail!("Simulation failed"),
+            _ => Ok(()),
+        }
+    };
+
+    // Send a setup instruction ahead of the one that needs it, or hold it for a dry run
+    let send_setup = |action: &'static str, ix: Instruction| -> Result<()> {
+        if config.dry_run {
+            pending_setup.borrow_mut().push((action, ix));
+            Ok(())
+        } else {
+            submit(action, ix, &[])
+        }
+    };
+
+    // Index pages a match moves between, creating the new status's page first if needed
+    let index_move = |match_id: &Pubkey, new_status: MatchStatus| {
+        let (pages, setup) =
+            client.status_index_move(&client.match_state(match_id)?, &new_status)?;
+        for instruction in setup {
+            send_setup("create_match_index_page", instruction)?;
+        }
+        Ok::<_, anyhow::Error>(pages)
+    };
+
+    // Index pages a new match is listed on, creating missing pages first
+    let listing = |start_time: i64, league: Option<u32>| {
+        let Some((listing, setup)) = client.match_listing(start_time, league)? else {
+            if league.is_some() {
+                bail!("Leagues are only recorded in the match registry, which does not exist");
+            }
+            return Ok(None);
+        };
+        for instruction in setup {
+            send_setup("create_match_index_page", instruction)?;
+        }
+        Ok(Some(listing))
+    };
+
+    // Audit log page a privileged action is recorded on, creating it first if needed
+    let audit_log = || {
+        let (page, setup) = client.audit_log()?;
+        for instruction in setup {
+            send_setup("create_audit_log_page", instruction)?;
+        }
+        Ok::<_, anyhow::Error>(page)
+    };
+
+    match cli.command {
+        Command::Initialize { admin: new_admin } => {
+            let new_admin = match new_admin {
+                Some(key) => parse_pubkey(&key)?,
+                None => admin,
+            };
+            submit(
+                "initialize",
+                instructions::initialize(&program_id, &admin, new_admin),
+                &[("admin", new_admin.to_string())],
+            )
+        }
+        Command::Global => {
+            let address = SecurityUtils::generate_platform_pda(&program_id).0;
+            print_global_state(
+                &GlobalStateView::new(&address, &client.global_state()?),
+                format,
+            );
+            Ok(())
+        }
+        Command::Treasury => {
+            let address = SecurityUtils::generate_treasury_pda(&program_id).0;
+            print_treasury_state(
+                &TreasuryStateView::new(&address, &client.treasury_state()?),
+                format,
+            );
+            Ok(())
+        }
+        Command::User { authority } => {
+            let authority = parse_pubkey(&authority)?;
+            let address = SecurityUtils::generate_user_pda(&authority, &program_id).0;
+            print_user_state(
+                &UserStateView::new(&address, &client.user_state(&authority)?),
+                format,
+            );
+            Ok(())
+        }
+        Command::Whois { username } => {
+            let (address, state) = client.user_by_username(&username)?;
+            print_user_state(&UserStateView::new(&address, &state), format);
+            Ok(())
+        }
+        Command::Matches {
+            status,
+            league,
+            day,
+        } => {
+            let status = status.map(MatchStatus::from);
+            let mut matches = client.all_matches()?;
+            matches.retain(|m| status.as_ref().is_none_or(|s| m.status == *s));
+            // League and day filters read the registry indexes
+            let keys = league
+                .map(MatchIndexKey::League)
+                .into_iter()
+                .chain(day.map(MatchIndexKey::start_day));
+            for key in keys {
+                let listed = client.match_index(key)?;
+                matches.retain(|m| listed.contains(&m.match_id));
+            }
+            matches.sort_by_key(|m| m.start_time);
+            print_matches(
+                &matches.iter().map(MatchStateView::new).collect::<Vec<_>>(),
+                format,
+            );
+            Ok(())
+        }
+        Command::Match { match_id } => {
+            let state = client.match_state(&parse_pubkey(&match_id)?)?;
+            print_match(&MatchStateView::new(&state), format);
+            Ok(())
+        }
+        Command::CreateMatch {
+            team1,
+            team2,
+            start,
+            end,
+            description,
+            match_id,
+            totals,
+            spread,
+            league,
+            creator_fee,
+        } => {
+            let match_id = match match_id {
+                Some(key) => parse_pubkey(&key)?,
+                None => Keypair::new().pubkey(),
+            };
+            let start_time = parse_time(&start, unix_now())?;
+            let end_time = parse_time(&end, start_time)?;
+            let line_market = match (totals, spread) {
+                (Some(line), _) => Some((MarketKind::Totals, parse_line(&line)?)),
+                (_, Some(line)) => Some((MarketKind::Spread, parse_line(&line)?)),
+                _ => None,
+            };
+            let listing = listing(start_time, league)?;
+            let instruction = match line_market {
+                Some((market_kind, line)) => instructions::create_line_market(
+                    &program_id,
+                    &admin,
+                    &match_id,
+                    team1,
+                    team2,
+                    market_kind,
+                    line,
+                    start_time,
+                    end_time,
+                    description,
+                    creator_fee,
+                    listing,
+                ),
+                None => instructions::create_match(
+                    &program_id,
+                    &admin,
+                    &match_id,
+                    team1,
+                    team2,
+                    start_time,
+                    end_time,
+                    description,
+                    creator_fee,
+                    listing,
+                ),
+            };
+            submit(
+                "create_match",
+                instruction,
+                &[
+                    ("match_id", match_id.to_string()),
+                    (
+                        "match_state",
+                        SecurityUtils::generate_match_pda(&match_id, &program_id)
+                            .0
+                            .to_string(),
+                    ),
+                    ("start_time", start_time.to_string()),
+                    ("end_time", end_time.to_string()),
+                ],
+            )
+        }
+        Command::CreatePriceMarket {
+            asset,
+            feed,
+            strike,
+            start,
+            end,
+            description,
+            match_id,
+            league,
+            creator_fee,
+        } => {
+            let match_id = match match_id {
+                Some(key) => parse_pubkey(&key)?,
+                None => Keypair::new().pubkey(),
+            };
+            let start_time = parse_time(&start, unix_now())?;
+            let end_time = parse_time(&end, start_time)?;
+            let listing = listing(start_time, league)?;
+            submit(
+                "create_price_market",
+                instructions::create_price_market(
+                    &program_id,
+                    &admin,
+                    &match_id,
+                    &parse_pubkey(&feed)?,
+                    asset,
+                    strike,
+                    start_time,
+                    end_time,
+                    description,
+                    creator_fee,
+                    listing,
+                ),
+                &[
+                    ("match_id", match_id.to_string()),
+                    (
+                        "price_market",
+                        SecurityUtils::generate_price_market_pda(&match_id, &program_id)
+                            .0
+                            .to_string(),
+                    ),
+                    ("resolution_time", end_time.to_string()),
+                ],
+            )
+        }
+        Command::ResolvePriceMarket { match_id } => {
+            let match_id = parse_pubkey(&match_id)?;
+            let market = client.price_market(&match_id)?;
+            let pages = index_move(&match_id, MatchStatus::Settled)?;
+            submit(
+                "resolve_price_market",
+                instructions::resolve_price_market(
+                    &program_id,
+                    &admin,
+                    &match_id,
+                    &market.price_feed,
+                    pages,
+                ),
+                &[],
+            )
+        }
+        Command::UpdateMatchStatus { match_id, status } => {
+            let match_id = parse_pubkey(&match_id)?;
+            let pages = index_move(&match_id, status.into())?;
+            submit(
+                "update_match_status",
+                instructions::update_match_status(
+                    &program_id,
+                    &admin,
+                    &match_id,
+                    status.into(),
+                    pages,
+                    audit_log()?,
+                ),
+                &[],
+            )
+        }
+        Command::CloseBetting { match_id } => submit(
+            "close_match_betting",
+            instructions::close_match_betting(
+                &program_id,
+                &admin,
+                &parse_pubkey(&match_id)?,
+                audit_log()?,
+            ),
+            &[],
+        ),
+        Command::SetBettingWindow {
+            match_id,
+            opens,
+            closes,
+            early_bonus,
+        } => {
+            let match_id = parse_pubkey(&match_id)?;
+            let now = unix_now();
+            let opens_at = parse_time(&opens, now)?;
+            let closes_at = parse_time(&closes, now)?;
+            submit(
+                "set_betting_window",
+                instructions::set_betting_window(
+                    &program_id,
+                    &admin,
+                    &match_id,
+                    opens_at,
+                    closes_at,
+                    early_bonus,
+                    audit_log()?,
+                ),
+                &[
+                    ("match_id", match_id.to_string()),
+                    ("betting_opens_at", opens_at.to_string()),
+                    ("betting_closes_at", closes_at.to_string()),
+                    ("early_bet_bonus (bps)", early_bonus.to_string()),
+                ],
+            )
+        }
+        Command::Crank { match_id } => {
+            let match_id = parse_pubkey(&match_id)?;
+            let now = client.rpc.get_clock()?.unix_timestamp;
+            let new_status = client
+                .match_state(&match_id)?
+                .crank_status(now)
+                .ok_or_else(|| anyhow!("Match {} has nothing to crank", match_id))?;
+            let pages = index_move(&match_id, new_status)?;
+            submit(
+                "crank_match",
+                instructions::crank_match(&program_id, &admin, &match_id, pages),
+                &[],
+            )
+        }
+        Command::VoidMatch { match_id } => {
+            let match_id = parse_pubkey(&match_id)?;
+            let pages = index_move(&match_id, MatchStatus::Voided)?;
+            submit(
+                "void_match",
+                instructions::void_match(&program_id, &admin, &match_id, pages),
+                &[],
+            )
+        }
+        Command::RefundBets { match_id } => {
+            let match_id = parse_pubkey(&match_id)?;
+            let open_bets: Vec<BetState> = client
+                .match_bets(&match_id)?
+                .into_iter()
+                .map(|(_, bet)| bet)
+                .filter(|bet| bet.status == BetStatus::Active)
+                .collect();
+            if open_bets.is_empty() {
+                bail!("No open bets to refund on match {}", match_id);
+            }
+            for bet in open_bets {
+                submit(
+                    "refund_bet",
+                    instructions::refund_bet(&program_id, &admin, &bet.bettor, &match_id),
+                    &[
+                        ("bettor", bet.bettor.to_string()),
+                        ("amount", bet.amount.to_string()),
+                    ],
+                )?;
+            }
+            Ok(())
+        }
+        Command::SettleCreator { match_id } => {
+            let match_id = parse_pubkey(&match_id)?;
+            let state = client.match_state(&match_id)?;
+            if !state.has_creator() {
+                bail!("Match {} is not a community market", match_id);
+            }
+            submit(
+                "settle_creator",
+                instructions::settle_creator(&program_id, &admin, &match_id, &state.creator),
+                &[("creator", state.creator.to_string())],
+            )
+        }
+        Command::VoidForAbuse { match_id } => {
+            let match_id = parse_pubkey(&match_id)?;
+            let pages = index_move(&match_id, MatchStatus::Voided)?;
+            submit(
+                "void_market_for_abuse",
+                instructions::void_market_for_abuse(
+                    &program_id,
+                    &admin,
+                    &match_id,
+                    pages,
+                    audit_log()?,
+                ),
+                &[],
+            )
+        }
+        Command::RegisterOracle {
+            match_id,
+            oracle_authority,
+        } => {
+            let match_id = parse_pubkey(&match_id)?;
+            let oracle_authority = parse_pubkey(&oracle_authority)?;
+            submit(
+                "register_oracle",
+                instructions::register_oracle(
+                    &program_id,
+                    &admin,
+                    &match_id,
+                    &oracle_authority,
+                    audit_log()?,
+                ),
+                &[(
+                    "oracle_state",
+                    SecurityUtils::generate_oracle_pda(&oracle_authority, &match_id, &program_id)
+                        .0
+                        .to_string(),
+                )],
+            )
+        }
+        Command::ValidateOracle {
+            match_id,
+            oracle_authority,
+            peers,
+        } => {
+            let match_id = parse_pubkey(&match_id)?;
+            let oracle_authority = parse_pubkey(&oracle_authority)?;
+            let peers = if peers.is_empty() {
+                client
+                    .match_oracles(&match_id)?
+                    .into_iter()
+                    .map(|(_, oracle)| oracle)
+                    .filter(|oracle| {
+                        oracle.oracle_authority != oracle_authority
+                            && oracle.reported_result.is_some()
+                    })
+                    .map(|oracle| oracle.oracle_authority)
+                    .collect()
+            } else {
+                peers
+                    .iter()
+                    .map(|peer| parse_pubkey(peer))
+                    .collect::<Result<Vec<_>>>()?
+            };
+            // A pushed line market is voided, any other result settles the match
+            let report: OracleState = client.fetch(
+                &SecurityUtils::generate_oracle_pda(&oracle_authority, &match_id, &program_id).0,
+            )?;
+            let new_status = match report.reported_result {
+                Some(PUSH_RESULT) => MatchStatus::Voided,
+                _ => MatchStatus::Settled,
+            };
+            let pages = index_move(&match_id, new_status)?;
+            submit(
+                "validate_oracle_update",
+                instructions::validate_oracle_update(
+                    &program_id,
+                    &admin,
+                    &match_id,
+                    &oracle_authority,
+                    &peers,
+                    pages,
+                    audit_log()?,
+                ),
+                &[],
+            )
+        }
+        Command::OracleProfile { authority } => {
+            let authority = parse_pubkey(&authority)?;
+            let address = SecurityUtils::generate_oracle_profile_pda(&authority, &program_id).0;
+            print_oracle_profile(
+                &OracleProfileView::new(&address, &client.oracle_profile(&authority)?),
+                format,
+            );
+            Ok(())
+        }
+        Command::BondStake { amount } => {
+            if client.oracle_profile(&admin).is_err() {
+                submit(
+                    "create_oracle_profile",
+                    instructions::create_oracle_profile(&program_id, &admin),
+                    &[],
+                )?;
+            }
+            submit(
+                "bond_oracle_stake",
+                instructions::bond_oracle_stake(&program_id, &admin, amount),
+                &[("amount", amount.to_string())],
+            )
+        }
+        Command::UnbondStake { amount } => submit(
+            "unbond_oracle_stake",
+            instructions::unbond_oracle_stake(&program_id, &admin, amount),
+            &[("amount", amount.to_string())],
+        ),
+        Command::WithdrawStake => submit(
+            "withdraw_oracle_stake",
+            instructions::withdraw_oracle_stake(&program_id, &admin),
+            &[],
+        ),
+        Command::ResolveOracles { match_id } => {
+            let match_id = parse_pubkey(&match_id)?;
+            let unresolved: Vec<Pubkey> = client
+                .match_oracles(&match_id)?
+                .into_iter()
+                .map(|(_, oracle)| oracle)
+                .filter(|oracle| !oracle.is_resolved && oracle.commitment.is_some())
+                .map(|oracle| oracle.oracle_authority)
+                .collect();
+            if unresolved.is_empty() {
+                bail!("No unresolved oracle reports on match {}", match_id);
+            }
+            for oracle_authority in unresolved {
+                submit(
+                    "resolve_oracle_report",
+                    instructions::resolve_oracle_report(
+                        &program_id,
+                        &admin,
+                        &match_id,
+                        &oracle_authority,
+                    ),
+                    &[("oracle_authority", oracle_authority.to_string())],
+                )?;
+            }
+            Ok(())
+        }
+        Command::SetArbiters { quorum, arbiters } => {
+            let arbiters = arbiters
+                .iter()
+                .map(|key| parse_pubkey(key))
+                .collect::<Result<Vec<_>>>()?;
+            let details = [("quorum", quorum.to_string())];
+            if client.arbiter_set().is_err() {
+                submit(
+                    "create_arbiter_set",
+                    instructions::create_arbiter_set(
+                        &program_id,
+                        &admin,
+                        arbiters,
+                        quorum,
+                        audit_log()?,
+                    ),
+                    &details,
+                )
+            } else {
+                submit(
+                    "update_arbiter_set",
+                    instructions::update_arbiter_set(
+                        &program_id,
+                        &admin,
+                        arbiters,
+                        quorum,
+                        audit_log()?,
+                    ),
+                    &details,
+                )
+            }
+        }
+        Command::Dispute { match_id } => {
+            let match_id = parse_pubkey(&match_id)?;
+            let address = SecurityUtils::generate_dispute_pda(&match_id, &program_id).0;
+            print_dispute(
+                &DisputeView::new(&address, &client.dispute(&match_id)?),
+                format,
+            );
+            Ok(())
+        }
+        Command::OpenDispute {
+            match_id,
+            proposed_team,
+        } => {
+            let match_id = parse_pubkey(&match_id)?;
+            submit(
+                "open_dispute",
+                instructions::open_dispute(&program_id, &admin, &match_id, proposed_team),
+                &[
+                    ("match_id", match_id.to_string()),
+                    ("proposed_team", proposed_team.to_string()),
+                ],
+            )
+        }
+        Command::VoteDispute { match_id, reject } => {
+            let match_id = parse_pubkey(&match_id)?;
+            let challenger = client.dispute(&match_id)?.challenger;
+            submit(
+                "vote_dispute",
+                instructions::vote_dispute(&program_id, &admin, &match_id, &challenger, !reject),
+                &[
+                    ("match_id", match_id.to_string()),
+                    ("uphold", (!reject).to_string()),
+                ],
+            )
+        }
+        Command::ExpireDispute { match_id } => {
+            let match_id = parse_pubkey(&match_id)?;
+            let challenger = client.dispute(&match_id)?.challenger;
+            submit(
+                "expire_dispute",
+                instructions::expire_dispute(&program_id, &admin, &match_id, &challenger),
+                &[("match_id", match_id.to_string())],
+            )
+        }
+        Command::UpdateConfig {
+            house_edge,
+            min_bet,
+            max_bet,
+            crank_bounty,
+            price_feed_program,
+            dispute_window,
+            dispute_bond,
+            betting_cutoff,
+            early_bet_bonus,
+            creator_bond,
+            max_creator_fee,
+            cancellation_fee,
+        } => {
+            if house_edge.is_none()
+                && min_bet.is_none()
+                && max_bet.is_none()
+                && crank_bounty.is_none()
+                && price_feed_program.is_none()
+                && dispute_window.is_none()
+                && dispute_bond.is_none()
+                && betting_cutoff.is_none()
+                && early_bet_bonus.is_none()
+                && creator_bond.is_none()
+                && max_creator_fee.is_none()
+                && cancellation_fee.is_none()
+            {
+                bail!(
+                    "Nothing to update: pass --house-edge, --min-bet, --max-bet, --crank-bounty, --price-feed-program, --dispute-window, --dispute-bond, --betting-cutoff, --early-bet-bonus, --creator-bond, --max-creator-fee or --cancellation-fee"
+                );
+            }
+            let price_feed_program = price_feed_program
+                .map(|key| parse_pubkey(&key))
+                .transpose()?;
+            submit(
+                "update_platform_config",
+                instructions::update_platform_config(
+                    &program_id,
+                    &admin,
+                    house_edge,
+                    min_bet,
+                    max_bet,
+                    crank_bounty,
+                    price_feed_program,
+                    dispute_window,
+                    dispute_bond,
+                    betting_cutoff,
+                    early_bet_bonus,
+                    creator_bond,
+                    max_creator_fee,
+                    cancellation_fee,
+                    audit_log()?,
+                ),
+                &[],
+            )
+        }
+        Command::SetExposureLimits {
+            max_match_pool,
+            max_imbalance,
+            max_user_stake,
+        } => submit(
+            "update_exposure_limits",
+            instructions::update_exposure_limits(
+                &program_id,
+                &admin,
+                ExposureLimits {
+                    max_match_pool,
+                    max_pool_imbalance: max_imbalance,
+                    max_user_match_stake: max_user_stake,
+                },
+                audit_log()?,
+            ),
+            &[
+                ("max_match_pool", max_match_pool.to_string()),
+                ("max_pool_imbalance", max_imbalance.to_string()),
+                ("max_user_match_stake", max_user_stake.to_string()),
+            ],
+        ),
+        Command::CreateRegistry => submit(
+            "create_match_registry",
+            instructions::create_match_registry(&program_id, &admin),
+            &[(
+                "registry",
+                SecurityUtils::generate_match_registry_pda(&program_id)
+                    .0
+                    .to_string(),
+            )],
+        ),
+        Command::AddPromoter { promoter } => {
+            let promoter = parse_pubkey(&promoter)?;
+            submit(
+                "add_promoter",
+                instructions::add_promoter(&program_id, &admin, &promoter, audit_log()?),
+                &[("promoter", promoter.to_string())],
+            )
+        }
+        Command::RemovePromoter { promoter } => {
+            let promoter = parse_pubkey(&promoter)?;
+            submit(
+                "remove_promoter",
+                instructions::remove_promoter(&program_id, &admin, &promoter, audit_log()?),
+                &[("promoter", promoter.to_string())],
+            )
+        }
+        Command::IssueBonus {
+            user,
+            amount,
+            wagering,
+        } => {
+            let user = parse_pubkey(&user)?;
+            submit(
+                "issue_bonus",
+                instructions::issue_bonus(&program_id, &admin, &user, amount, wagering),
+                &[
+                    ("user", user.to_string()),
+                    ("amount", amount.to_string()),
+                    ("wagering_multiplier", wagering.to_string()),
+                ],
+            )
+        }
+        Command::CreateOddsHistory { match_id } => {
+            let match_id = parse_pubkey(&match_id)?;
+            let address = SecurityUtils::generate_odds_history_pda(&match_id, &program_id).0;
+            submit(
+                "create_odds_history",
+                instructions::create_odds_history(&program_id, &admin, &match_id),
+                &[("odds_history", address.to_string())],
+            )
+        }
+        Command::OddsHistory { match_id } => {
+            let match_id = parse_pubkey(&match_id)?;
+            let history = client
+                .odds_history(&match_id)?
+                .ok_or_else(|| anyhow!("match {} has no odds history", match_id))?;
+            let views: Vec<OddsPointView> =
+                history.series().iter().map(OddsPointView::new).collect();
+            print_odds_history(&views, format);
+            Ok(())
+        }
+        Command::CreatePrivatePool {
+            match_id,
+            members,
+            invite_code,
+            waive_house_edge,
+        } => {
+            let match_id = parse_pubkey(&match_id)?;
+            let members = members
+                .iter()
+                .map(|key| parse_pubkey(key))
+                .collect::<Result<Vec<_>>>()?;
+            let invite_code_hash = invite_code
+                .as_deref()
+                .map(|code| PrivatePool::invite_code_hash(&match_id, code));
+            submit(
+                "create_private_pool",
+                instructions::create_private_pool(
+                    &program_id,
+                    &admin,
+                    &match_id,
+                    invite_code_hash,
+                    members,
+                    waive_house_edge,
+                ),
+                &[("waives_house_edge", waive_house_edge.to_string())],
+            )
+        }
+        Command::UpdatePrivatePool {
+            match_id,
+            members,
+            invite_code,
+        } => {
+            let match_id = parse_pubkey(&match_id)?;
+            let members = members
+                .iter()
+                .map(|key| parse_pubkey(key))
+                .collect::<Result<Vec<_>>>()?;
+            let invite_code_hash = invite_code
+                .as_deref()
+                .map(|code| PrivatePool::invite_code_hash(&match_id, code));
+            submit(
+                "update_private_pool",
+                instructions::update_private_pool(
+                    &program_id,
+                    &admin,
+                    &match_id,
+                    invite_code_hash,
+                    members,
+                ),
+                &[],
+            )
+        }
+        Command::JoinPrivatePool {
+            match_id,
+            invite_code,
+        } => {
+            let match_id = parse_pubkey(&match_id)?;
+            submit(
+                "join_private_pool",
+                instructions::join_private_pool(&program_id, &admin, &match_id, invite_code),
+                &[],
+            )
+        }
+        Command::ClosePrivatePool { match_id } => {
+            let match_id = parse_pubkey(&match_id)?;
+            submit(
+                "close_private_pool_betting",
+                instructions::close_private_pool_betting(&program_id, &admin, &match_id),
+                &[],
+            )
+        }
+        Command::CancelPrivatePool { match_id } => {
+            let match_id = parse_pubkey(&match_id)?;
+            let pages = index_move(&match_id, MatchStatus::Cancelled)?;
+            submit(
+                "cancel_private_pool",
+                instructions::cancel_private_pool(&program_id, &admin, &match_id, pages),
+                &[],
+            )
+        }
+        Command::EnableAuditLog => submit(
+            "enable_audit_log",
+            instructions::enable_audit_log(&program_id, &admin),
+            &[(
+                "audit_log",
+                SecurityUtils::generate_audit_log_pda(0, &program_id)
+                    .0
+                    .to_string(),
+            )],
+        ),
+        Command::AuditLog { page } => {
+            let pages = match page {
+                Some(page) => vec![client
+                    .audit_log_page(page)?
+                    .ok_or_else(|| anyhow!("audit log page {} does not exist", page))?],
+                None => {
+                    let mut pages = Vec::new();
+                    for page in 0u32.. {
+                        match client.audit_log_page(page)? {
+                            Some(state) => pages.push(state),
+                            None => break,
+                        }
+                    }
+                    pages
+                }
+            };
+            let views: Vec<AuditEntryView> = pages
+                .iter()
+                .flat_map(|page| page.entries.iter().map(AuditEntryView::new))
+                .collect();
+            print_audit_log(&views, format);
+            Ok(())
+        }
+        Command::Pause => submit(
+            "pause_platform",
+            instructions::pause_platform(&program_id, &admin, audit_log()?),
+            &[],
+        ),
+        Command::Unpause => submit(
+            "unpause_platform",
+            instructions::unpause_platform(&program_id, &admin, audit_log()?),
+            &[],
+        ),
+        Command::WithdrawFees { amount } => submit(
+            "withdraw_platform_fees",
+            instructions::withdraw_platform_fees(&program_id, &admin, amount, audit_log()?),
+            &[("amount", amount.to_string())],
+        ),
+        Command::EmergencyWithdraw { amount } => submit(
+            "emergency_withdraw",
+            instructions::emergency_withdraw(&program_id, &admin, amount, audit_log()?),
+            &[("amount", amount.to_string())],
+        ),
+        Command::MigrateAccount { account } => {
+            let account = parse_pubkey(&account)?;
+            submit(
+                "migrate_account",
+                instructions::migrate_account(&program_id, &admin, &account, audit_log()?),
+                &[("account", account.to_string())],
+            )
+        }
+    }
+}
+
+/// Expand cluster monikers into RPC URLs
+fn resolve_url(url: &str) -> String {
+    match url {
+        "localhost" | "l" => "http://127.0.0.1:8899",
+        "devnet" | "d" => "https://api.devnet.solana.com",
+        "testnet" | "t" => "https://api.testnet.solana.com",
+        "mainnet-beta" | "m" => "https://api.mainnet-beta.solana.com",
+        other => other,
+    }
+    .to_string()
+}
+
+fn resolve_program_id(program_id: Option<&str>) -> Result<Pubkey> {
+    program_id.map(parse_pubkey).unwrap_or(Ok(trendxbet::ID))
+}
+
+/// Load the admin keypair, defaulting to the Solana CLI wallet
+fn load_keypair(path: Option<&PathBuf>) -> Result<Keypair> {
+    let path = match path {
+        Some(path) => path.clone(),
+        None => PathBuf::from(std::env::var("HOME").context("HOME is not set")?)
+            .join(".config/solana/id.json"),
+    };
+    read_keypair_file(&path)
+        .map_err(|e| anyhow!("Failed to read keypair {}: {}", path.display(), e))
+}
+
+fn parse_pubkey(value: &str) -> Result<Pubkey> {
+    Pubkey::from_str(value).map_err(|e| anyhow!("Invalid public key {}: {}", value, e))
+}
+
+/// Parse an absolute unix timestamp or a `+SECS` offset from `base`
+fn parse_time(value: &str, base: i64) -> Result<i64> {
+    match value.strip_prefix('+') {
+        Some(offset) => {
+            let offset: i64 = offset
+                .parse()
+                .with_context(|| format!("Invalid time offset {}", value))?;
+            base.checked_add(offset)
+                .ok_or_else(|| anyhow!("Time offset {} overflows", value))
+        }
+        None => value
+            .parse()
+            .with_context(|| format!("Invalid unix timestamp {}", value)),
+    }
+}
+
+/// Parse a line in points, e.g. `2.5` or `-1.5`, into `LINE_SCALE` units
+fn parse_line(value: &str) -> Result<i32> {
+    let points: f64 = value
+        .parse()
+        .with_context(|| format!("Invalid line {}", value))?;
+    let scaled = points * LINE_SCALE as f64;
+    if scaled.fract() != 0.0 || scaled.abs() > i32::MAX as f64 {
+        bail!(
+            "Line {} must be a multiple of 1/{} of a point",
+            value,
+            LINE_SCALE
+        );
+    }
+    Ok(scaled as i32)
+}
+
+fn unix_now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or_default()
+}
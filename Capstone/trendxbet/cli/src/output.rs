@@ -0,0 +1,832 @@
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+use trendxbet::{
+    AuditAction, AuditEntry, DisputeState, DisputeStatus, GlobalState, MarketKind, MatchScore,
+    MatchState, MatchStatus, OddsPoint, OracleProfile, TreasuryState, UserState, BASIS_POINTS,
+    LINE_SCALE,
+};
+use trendxbet_client::Submission;
+
+/// Output format selected on the command line
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum OutputFormat {
+    Table,
+    Json,
+}
+
+/// Serializable view of `GlobalState`
+#[derive(Serialize)]
+pub struct GlobalStateView {
+    pub address: String,
+    pub admin: String,
+    pub house_edge: u16,
+    pub min_bet_amount: u64,
+    pub max_bet_amount: u64,
+    pub total_volume: u64,
+    pub total_fees_collected: u64,
+    pub platform_created_at: i64,
+    pub is_paused: bool,
+    pub crank_bounty: u64,
+    pub price_feed_program: String,
+    pub dispute_window: i64,
+    pub dispute_bond: u64,
+    pub max_match_pool: u64,
+    pub max_pool_imbalance: u64,
+    pub max_user_match_stake: u64,
+    pub betting_cutoff: u32,
+    pub early_bet_bonus: u16,
+    pub creator_bond: u64,
+    pub max_creator_fee: u16,
+    pub cancellation_fee: u16,
+    pub has_audit_log: bool,
+    pub audit_entries: u64,
+}
+
+impl GlobalStateView {
+    pub fn new(address: &Pubkey, state: &GlobalState) -> Self {
+        Self {
+            address: address.to_string(),
+            admin: state.admin.to_string(),
+            house_edge: state.house_edge,
+            min_bet_amount: state.min_bet_amount,
+            max_bet_amount: state.max_bet_amount,
+            total_volume: state.total_volume,
+            total_fees_collected: state.total_fees_collected,
+            platform_created_at: state.platform_created_at,
+            is_paused: state.is_paused,
+            crank_bounty: state.crank_bounty,
+            price_feed_program: state.price_feed_program.to_string(),
+            dispute_window: state.dispute_window,
+            dispute_bond: state.dispute_bond,
+            max_match_pool: state.exposure_limits.max_match_pool,
+            max_pool_imbalance: state.exposure_limits.max_pool_imbalance,
+            max_user_match_stake: state.exposure_limits.max_user_match_stake,
+            betting_cutoff: state.betting_cutoff,
+            early_bet_bonus: state.early_bet_bonus,
+            creator_bond: state.creator_bond,
+            max_creator_fee: state.max_creator_fee,
+            cancellation_fee: state.cancellation_fee,
+            has_audit_log: state.has_audit_log,
+            audit_entries: state.audit_entries,
+        }
+    }
+
+    fn rows(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("address", self.address.clone()),
+            ("admin", self.admin.clone()),
+            ("house_edge (bps)", self.house_edge.to_string()),
+            ("min_bet_amount", self.min_bet_amount.to_string()),
+            ("max_bet_amount", self.max_bet_amount.to_string()),
+            ("total_volume", self.total_volume.to_string()),
+            (
+                "total_fees_collected",
+                self.total_fees_collected.to_string(),
+            ),
+            ("platform_created_at", self.platform_created_at.to_string()),
+            ("is_paused", self.is_paused.to_string()),
+            ("crank_bounty", self.crank_bounty.to_string()),
+            ("price_feed_program", self.price_feed_program.clone()),
+            ("dispute_window (s)", self.dispute_window.to_string()),
+            ("dispute_bond", self.dispute_bond.to_string()),
+            ("max_match_pool", self.max_match_pool.to_string()),
+            ("max_pool_imbalance", self.max_pool_imbalance.to_string()),
+            (
+                "max_user_match_stake",
+                self.max_user_match_stake.to_string(),
+            ),
+            ("betting_cutoff (s)", self.betting_cutoff.to_string()),
+            ("early_bet_bonus (bps)", self.early_bet_bonus.to_string()),
+            ("creator_bond", self.creator_bond.to_string()),
+            ("max_creator_fee (bps)", self.max_creator_fee.to_string()),
+            ("cancellation_fee (bps)", self.cancellation_fee.to_string()),
+            ("has_audit_log", self.has_audit_log.to_string()),
+            ("audit_entries", self.audit_entries.to_string()),
+        ]
+    }
+}
+
+/// Serializable view of `TreasuryState`
+#[derive(Serialize)]
+pub struct TreasuryStateView {
+    pub address: String,
+    pub authority: String,
+    pub total_deposits: u64,
+    pub total_withdrawals: u64,
+    pub platform_fees: u64,
+    pub pending_payouts: u64,
+    pub last_fee_collection: i64,
+    pub net_balance: i64,
+    pub available_balance: i64,
+    pub utilization_bps: u64,
+    pub coverage_ratio_bps: Option<u64>,
+    pub is_healthy: bool,
+}
+
+impl TreasuryStateView {
+    pub fn new(address: &Pubkey, state: &TreasuryState) -> Self {
+        Self {
+            address: address.to_string(),
+            authority: state.authority.to_string(),
+            total_deposits: state.total_deposits,
+            total_withdrawals: state.total_withdrawals,
+            platform_fees: state.platform_fees,
+            pending_payouts: state.pending_payouts,
+            last_fee_collection: state.last_fee_collection,
+            net_balance: state.net_balance(),
+            available_balance: state.available_balance(),
+            utilization_bps: state.utilization_bps(),
+            coverage_ratio_bps: state.coverage_ratio_bps(),
+            is_healthy: state.is_healthy(),
+        }
+    }
+
+    fn rows(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("address", self.address.clone()),
+            ("authority", self.authority.clone()),
+            ("total_deposits", self.total_deposits.to_string()),
+            ("total_withdrawals", self.total_withdrawals.to_string()),
+            ("platform_fees", self.platform_fees.to_string()),
+            ("pending_payouts", self.pending_payouts.to_string()),
+            ("last_fee_collection", self.last_fee_collection.to_string()),
+            ("net_balance", self.net_balance.to_string()),
+            ("available_balance", self.available_balance.to_string()),
+            ("utilization", percent(self.utilization_bps as i64)),
+            (
+                "coverage_ratio",
+                optional(self.coverage_ratio_bps.map(|bps| percent(bps as i64))),
+            ),
+            ("is_healthy", self.is_healthy.to_string()),
+        ]
+    }
+}
+
+/// Serializable view of `UserState`
+#[derive(Serialize)]
+pub struct UserStateView {
+    pub address: String,
+    pub authority: String,
+    pub username: String,
+    pub balance: u64,
+    pub bonus_balance: u64,
+    pub wagering_remaining: u64,
+    pub locked_bonus_winnings: u64,
+    pub total_bets_placed: u64,
+    pub total_bets_won: u64,
+    pub total_volume: u64,
+    pub total_winnings: u64,
+    pub net_profit: i64,
+    pub win_rate_bps: u64,
+    pub roi_bps: i64,
+    pub average_odds_bps: u64,
+    pub profile_created_at: i64,
+}
+
+impl UserStateView {
+    pub fn new(address: &Pubkey, state: &UserState) -> Self {
+        Self {
+            address: address.to_string(),
+            authority: state.authority.to_string(),
+            username: state.username.clone(),
+            balance: state.balance,
+            bonus_balance: state.bonus_balance,
+            wagering_remaining: state.wagering_remaining,
+            locked_bonus_winnings: state.locked_bonus_winnings,
+            total_bets_placed: state.total_bets_placed,
+            total_bets_won: state.total_bets_won,
+            total_volume: state.total_volume,
+            total_winnings: state.total_winnings,
+            net_profit: state.net_profit(),
+            win_rate_bps: state.win_rate_bps(),
+            roi_bps: state.roi_bps(),
+            average_odds_bps: state.average_odds_bps(),
+            profile_created_at: state.profile_created_at,
+        }
+    }
+
+    fn rows(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("address", self.address.clone()),
+            ("authority", self.authority.clone()),
+            ("username", self.username.clone()),
+            ("balance", self.balance.to_string()),
+            ("bonus_balance", self.bonus_balance.to_string()),
+            ("wagering_remaining", self.wagering_remaining.to_string()),
+            (
+                "locked_bonus_winnings",
+                self.locked_bonus_winnings.to_string(),
+            ),
+            ("total_bets_placed", self.total_bets_placed.to_string()),
+            ("total_bets_won", self.total_bets_won.to_string()),
+            ("total_volume", self.total_volume.to_string()),
+            ("total_winnings", self.total_winnings.to_string()),
+            ("net_profit", self.net_profit.to_string()),
+            ("win_rate", percent(self.win_rate_bps as i64)),
+            ("roi", percent(self.roi_bps)),
+            ("average_odds", percent(self.average_odds_bps as i64)),
+            ("profile_created_at", self.profile_created_at.to_string()),
+        ]
+    }
+}
+
+/// Serializable view of `OracleProfile`
+#[derive(Serialize)]
+pub struct OracleProfileView {
+    pub address: String,
+    pub authority: String,
+    pub stake: u64,
+    pub unbonding_amount: u64,
+    pub unbonding_at: Option<i64>,
+    pub reputation_bps: u16,
+    pub reports_agreed: u32,
+    pub reports_slashed: u32,
+    pub total_rewards: u64,
+    pub total_slashed: u64,
+    pub created_at: i64,
+}
+
+impl OracleProfileView {
+    pub fn new(address: &Pubkey, state: &OracleProfile) -> Self {
+        Self {
+            address: address.to_string(),
+            authority: state.authority.to_string(),
+            stake: state.stake,
+            unbonding_amount: state.unbonding_amount,
+            unbonding_at: (state.unbonding_amount > 0).then_some(state.unbonding_at),
+            reputation_bps: state.reputation,
+            reports_agreed: state.reports_agreed,
+            reports_slashed: state.reports_slashed,
+            total_rewards: state.total_rewards,
+            total_slashed: state.total_slashed,
+            created_at: state.created_at,
+        }
+    }
+
+    fn rows(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("address", self.address.clone()),
+            ("authority", self.authority.clone()),
+            ("stake", self.stake.to_string()),
+            ("unbonding_amount", self.unbonding_amount.to_string()),
+            ("unbonding_at", optional(self.unbonding_at)),
+            ("reputation", percent(self.reputation_bps as i64)),
+            ("reports_agreed", self.reports_agreed.to_string()),
+            ("reports_slashed", self.reports_slashed.to_string()),
+            ("total_rewards", self.total_rewards.to_string()),
+            ("total_slashed", self.total_slashed.to_string()),
+            ("created_at", self.created_at.to_string()),
+        ]
+    }
+}
+
+/// Serializable view of `DisputeState`
+#[derive(Serialize)]
+pub struct DisputeView {
+    pub address: String,
+    pub match_id: String,
+    pub challenger: String,
+    pub bond: u64,
+    pub original_team: u8,
+    pub proposed_team: u8,
+    pub status: &'static str,
+    pub votes_uphold: u8,
+    pub votes_reject: u8,
+    pub opened_at: i64,
+    pub resolved_at: Option<i64>,
+}
+
+impl DisputeView {
+    pub fn new(address: &Pubkey, state: &DisputeState) -> Self {
+        Self {
+            address: address.to_string(),
+            match_id: state.match_id.to_string(),
+            challenger: state.challenger.to_string(),
+            bond: state.bond,
+            original_team: state.original_team,
+            proposed_team: state.proposed_team,
+            status: match state.status {
+                DisputeStatus::Open => "open",
+                DisputeStatus::Upheld => "upheld",
+                DisputeStatus::Rejected => "rejected",
+                DisputeStatus::Expired => "expired",
+            },
+            votes_uphold: state.votes_uphold,
+            votes_reject: state.votes_reject,
+            opened_at: state.opened_at,
+            resolved_at: state.resolved_at,
+        }
+    }
+
+    fn rows(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("address", self.address.clone()),
+            ("match_id", self.match_id.clone()),
+            ("challenger", self.challenger.clone()),
+            ("bond", self.bond.to_string()),
+            ("original_team", self.original_team.to_string()),
+            ("proposed_team", self.proposed_team.to_string()),
+            ("status", self.status.to_string()),
+            ("votes_uphold", self.votes_uphold.to_string()),
+            ("votes_reject", self.votes_reject.to_string()),
+            ("opened_at", self.opened_at.to_string()),
+            ("resolved_at", optional(self.resolved_at)),
+        ]
+    }
+}
+
+/// Serializable view of `MatchState`
+#[derive(Serialize)]
+pub struct MatchStateView {
+    pub match_id: String,
+    pub market: &'static str,
+    pub team1: String,
+    pub team2: String,
+    pub description: String,
+    pub start_time: i64,
+    pub end_time: i64,
+    pub betting_opens_at: i64,
+    pub betting_closes_at: i64,
+    pub early_bet_bonus: u16,
+    pub status: &'static str,
+    pub total_pool: u64,
+    pub team1_pool: u64,
+    pub team2_pool: u64,
+    pub total_bets: u64,
+    pub line: Option<String>,
+    pub winning_team: Option<u8>,
+    pub final_score: Option<String>,
+    pub score: Option<String>,
+    pub is_betting_closed: bool,
+    pub is_settled: bool,
+    pub dispute_deadline: Option<i64>,
+    pub is_disputed: bool,
+    pub league: Option<u32>,
+    pub is_indexed: bool,
+    pub creator: Option<String>,
+    pub creator_bond: u64,
+    pub creator_fee: u16,
+    pub is_creator_settled: bool,
+    pub is_private: bool,
+    pub waives_house_edge: bool,
+    pub has_odds_history: bool,
+}
+
+impl MatchStateView {
+    pub fn new(state: &MatchState) -> Self {
+        Self {
+            match_id: state.match_id.to_string(),
+            market: market_name(&state.market_kind),
+            team1: state.team1.clone(),
+            team2: state.team2.clone(),
+            description: state.description.clone(),
+            start_time: state.start_time,
+            end_time: state.end_time,
+            betting_opens_at: state.betting_opens_at,
+            betting_closes_at: state.betting_closes_at,
+            early_bet_bonus: state.early_bet_bonus,
+            status: status_name(&state.status),
+            total_pool: state.total_pool,
+            team1_pool: state.team1_pool,
+            team2_pool: state.team2_pool,
+            total_bets: state.total_bets,
+            line: state.is_line_market().then(|| format_line(state.line)),
+            winning_team: state.winning_team,
+            final_score: state.final_score.clone(),
+            score: state.match_score.as_ref().map(format_score),
+            is_betting_closed: state.is_betting_closed,
+            is_settled: state.is_settled,
+            dispute_deadline: (state.dispute_deadline > 0).then_some(state.dispute_deadline),
+            is_disputed: state.is_disputed,
+            league: (state.league > 0).then_some(state.league),
+            is_indexed: state.is_indexed,
+            creator: state.has_creator().then(|| state.creator.to_string()),
+            creator_bond: state.creator_bond,
+            creator_fee: state.creator_fee,
+            is_creator_settled: state.is_creator_settled,
+            is_private: state.is_private,
+            waives_house_edge: state.waives_house_edge,
+            has_odds_history: state.has_odds_history,
+        }
+    }
+
+    fn rows(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("match_id", self.match_id.clone()),
+            ("market", self.market.to_string()),
+            ("team1", self.team1.clone()),
+            ("team2", self.team2.clone()),
+            ("description", self.description.clone()),
+            ("start_time", self.start_time.to_string()),
+            ("end_time", self.end_time.to_string()),
+            ("betting_opens_at", self.betting_opens_at.to_string()),
+            ("betting_closes_at", self.betting_closes_at.to_string()),
+            ("early_bet_bonus (bps)", self.early_bet_bonus.to_string()),
+            ("status", self.status.to_string()),
+            ("total_pool", self.total_pool.to_string()),
+            ("team1_pool", self.team1_pool.to_string()),
+            ("team2_pool", self.team2_pool.to_string()),
+            ("total_bets", self.total_bets.to_string()),
+            ("line", optional(self.line.clone())),
+            ("winning_team", optional(self.winning_team)),
+            ("final_score", optional(self.final_score.clone())),
+            ("score", optional(self.score.clone())),
+            ("is_betting_closed", self.is_betting_closed.to_string()),
+            ("is_settled", self.is_settled.to_string()),
+            ("dispute_deadline", optional(self.dispute_deadline)),
+            ("is_disputed", self.is_disputed.to_string()),
+            ("league", optional(self.league)),
+            ("is_indexed", self.is_indexed.to_string()),
+            ("creator", optional(self.creator.clone())),
+            ("creator_bond", self.creator_bond.to_string()),
+            ("creator_fee (bps)", self.creator_fee.to_string()),
+            ("is_creator_settled", self.is_creator_settled.to_string()),
+            ("is_private", self.is_private.to_string()),
+            ("waives_house_edge", self.waives_house_edge.to_string()),
+            ("has_odds_history", self.has_odds_history.to_string()),
+        ]
+    }
+}
+
+/// Serializable view of an `OddsPoint`
+#[derive(Serialize)]
+pub struct OddsPointView {
+    pub timestamp: i64,
+    pub team1_pool: u64,
+    pub team2_pool: u64,
+    pub team1_odds: u64,
+    pub team2_odds: u64,
+}
+
+impl OddsPointView {
+    pub fn new(point: &OddsPoint) -> Self {
+        Self {
+            timestamp: point.timestamp,
+            team1_pool: point.team1_pool,
+            team2_pool: point.team2_pool,
+            team1_odds: point.team1_odds,
+            team2_odds: point.team2_odds,
+        }
+    }
+}
+
+/// Serializable view of an `AuditEntry`
+#[derive(Serialize)]
+pub struct AuditEntryView {
+    pub sequence: u64,
+    pub action: &'static str,
+    pub actor: String,
+    pub target: Option<String>,
+    pub params_hash: String,
+    pub timestamp: i64,
+}
+
+impl AuditEntryView {
+    pub fn new(entry: &AuditEntry) -> Self {
+        Self {
+            sequence: entry.sequence,
+            action: audit_action_name(&entry.action),
+            actor: entry.actor.to_string(),
+            target: entry.target.map(|target| target.to_string()),
+            params_hash: entry
+                .params_hash
+                .iter()
+                .map(|byte| format!("{:02x}", byte))
+                .collect(),
+            timestamp: entry.timestamp,
+        }
+    }
+}
+
+/// Human readable name of an audited action
+pub fn audit_action_name(action: &AuditAction) -> &'static str {
+    match action {
+        AuditAction::ConfigUpdate => "config_update",
+        AuditAction::ExposureLimitsUpdate => "exposure_limits_update",
+        AuditAction::Pause => "pause",
+        AuditAction::Unpause => "unpause",
+        AuditAction::FeeWithdrawal => "fee_withdrawal",
+        AuditAction::EmergencyWithdrawal => "emergency_withdrawal",
+        AuditAction::OracleRegistration => "oracle_registration",
+        AuditAction::ArbiterSetUpdate => "arbiter_set_update",
+        AuditAction::PromoterAddition => "promoter_addition",
+        AuditAction::PromoterRemoval => "promoter_removal",
+        AuditAction::MarketVoid => "market_void",
+        AuditAction::MatchStatusUpdate => "match_status_update",
+        AuditAction::BettingClosure => "betting_closure",
+        AuditAction::BettingWindowUpdate => "betting_window_update",
+        AuditAction::AccountMigration => "account_migration",
+        AuditAction::OracleValidation => "oracle_validation",
+    }
+}
+
+/// Human readable name of a market kind
+pub fn market_name(kind: &MarketKind) -> &'static str {
+    match kind {
+        MarketKind::TeamMatch => "team_match",
+        MarketKind::PriceTrend => "price_trend",
+        MarketKind::Totals => "totals",
+        MarketKind::Spread => "spread",
+    }
+}
+
+/// Line in points, e.g. `-1.5`
+pub fn format_line(line: i32) -> String {
+    let points = line as f64 / LINE_SCALE as f64;
+    format!("{}", points)
+}
+
+/// Structured score as `home-away`, followed by the periods if any
+pub fn format_score(score: &MatchScore) -> String {
+    let total = format!("{}-{}", score.home, score.away);
+    if score.periods.is_empty() {
+        return total;
+    }
+    let periods: Vec<String> = score
+        .periods
+        .iter()
+        .map(|period| format!("{}-{}", period.home, period.away))
+        .collect();
+    format!("{} ({})", total, periods.join(", "))
+}
+
+/// Human readable name of a match status
+pub fn status_name(status: &MatchStatus) -> &'static str {
+    match status {
+        MatchStatus::Scheduled => "scheduled",
+        MatchStatus::Live => "live",
+        MatchStatus::Ended => "ended",
+        MatchStatus::Settled => "settled",
+        MatchStatus::Cancelled => "cancelled",
+        MatchStatus::Voided => "voided",
+    }
+}
+
+/// Render a basis point value as a percentage with two decimals
+fn percent(bps: i64) -> String {
+    let sign = if bps < 0 { "-" } else { "" };
+    let bps = bps.unsigned_abs();
+    let bps_per_percent = BASIS_POINTS / 100;
+    format!(
+        "{}{}.{:02}%",
+        sign,
+        bps / bps_per_percent,
+        bps % bps_per_percent
+    )
+}
+
+fn optional<T: ToString>(value: Option<T>) -> String {
+    value
+        .map(|v| v.to_string())
+        .unwrap_or_else(|| "-".to_string())
+}
+
+/// Print a serializable value as pretty JSON
+fn print_json<T: Serialize>(value: &T) {
+    println!(
+        "{}",
+        serde_json::to_string_pretty(value).expect("views always serialize")
+    );
+}
+
+/// Print two-column key/value rows
+fn print_key_values(rows: &[(&str, String)]) {
+    let width = rows.iter().map(|(key, _)| key.len()).max().unwrap_or(0);
+    for (key, value) in rows {
+        println!("{:<width$}  {}", key, value, width = width);
+    }
+}
+
+/// Print a column-aligned table with a header row
+fn print_table(headers: &[&str], rows: &[Vec<String>]) {
+    let mut widths: Vec<usize> = headers.iter().map(|h| h.len()).collect();
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.len());
+        }
+    }
+
+    let render = |cells: Vec<&str>| {
+        cells
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+            .collect::<Vec<_>>()
+            .join("  ")
+    };
+
+    println!("{}", render(headers.to_vec()));
+    println!(
+        "{}",
+        widths
+            .iter()
+            .map(|width| "-".repeat(*width))
+            .collect::<Vec<_>>()
+            .join("  ")
+    );
+    for row in rows {
+        println!("{}", render(row.iter().map(String::as_str).collect()));
+    }
+}
+
+pub fn print_global_state(view: &GlobalStateView, format: OutputFormat) {
+    match format {
+        OutputFormat::Json => print_json(view),
+        OutputFormat::Table => print_key_values(&view.rows()),
+    }
+}
+
+pub fn print_treasury_state(view: &TreasuryStateView, format: OutputFormat) {
+    match format {
+        OutputFormat::Json => print_json(view),
+        OutputFormat::Table => print_key_values(&view.rows()),
+    }
+}
+
+pub fn print_user_state(view: &UserStateView, format: OutputFormat) {
+    match format {
+        OutputFormat::Json => print_json(view),
+        OutputFormat::Table => print_key_values(&view.rows()),
+    }
+}
+
+pub fn print_oracle_profile(view: &OracleProfileView, format: OutputFormat) {
+    match format {
+        OutputFormat::Json => print_json(view),
+        OutputFormat::Table => print_key_values(&view.rows()),
+    }
+}
+
+pub fn print_dispute(view: &DisputeView, format: OutputFormat) {
+    match format {
+        OutputFormat::Json => print_json(view),
+        OutputFormat::Table => print_key_values(&view.rows()),
+    }
+}
+
+pub fn print_match(view: &MatchStateView, format: OutputFormat) {
+    match format {
+        OutputFormat::Json => print_json(view),
+        OutputFormat::Table => print_key_values(&view.rows()),
+    }
+}
+
+pub fn print_matches(views: &[MatchStateView], format: OutputFormat) {
+    match format {
+        OutputFormat::Json => print_json(&views),
+        OutputFormat::Table => print_table(
+            &[
+                "match_id", "team1", "team2", "start", "end", "status", "pool", "bets", "winner",
+            ],
+            &views
+                .iter()
+                .map(|view| {
+                    vec![
+                        view.match_id.clone(),
+                        view.team1.clone(),
+                        view.team2.clone(),
+                        view.start_time.to_string(),
+                        view.end_time.to_string(),
+                        view.status.to_string(),
+                        view.total_pool.to_string(),
+                        view.total_bets.to_string(),
+                        optional(view.winning_team),
+                    ]
+                })
+                .collect::<Vec<_>>(),
+        ),
+    }
+}
+
+pub fn print_odds_history(views: &[OddsPointView], format: OutputFormat) {
+    match format {
+        OutputFormat::Json => print_json(&views),
+        OutputFormat::Table => print_table(
+            &[
+                "timestamp",
+                "team1_pool",
+                "team2_pool",
+                "team1_odds (bps)",
+                "team2_odds (bps)",
+            ],
+            &views
+                .iter()
+                .map(|view| {
+                    vec![
+                        view.timestamp.to_string(),
+                        view.team1_pool.to_string(),
+                        view.team2_pool.to_string(),
+                        view.team1_odds.to_string(),
+                        view.team2_odds.to_string(),
+                    ]
+                })
+                .collect::<Vec<_>>(),
+        ),
+    }
+}
+
+pub fn print_audit_log(views: &[AuditEntryView], format: OutputFormat) {
+    match format {
+        OutputFormat::Json => print_json(&views),
+        OutputFormat::Table => print_table(
+            &[
+                "sequence",
+                "action",
+                "actor",
+                "target",
+                "params_hash",
+                "timestamp",
+            ],
+            &views
+                .iter()
+                .map(|view| {
+                    vec![
+                        view.sequence.to_string(),
+                        view.action.to_string(),
+                        view.actor.clone(),
+                        optional(view.target.clone()),
+                        view.params_hash.clone(),
+                        view.timestamp.to_string(),
+                    ]
+                })
+                .collect::<Vec<_>>(),
+        ),
+    }
+}
+
+/// Serializable summary of a submitted or simulated transaction
+#[derive(Serialize)]
+struct SubmissionView<'a> {
+    action: &'a str,
+    simulated: bool,
+    signature: Option<String>,
+    success: bool,
+    error: Option<String>,
+    units_consumed: Option<u64>,
+    logs: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    details: Vec<(String, String)>,
+}
+
+/// Print the outcome of an instruction, with extra `details` such as created addresses
+pub fn print_submission(
+    action: &str,
+    submission: &Submission,
+    details: &[(&str, String)],
+    format: OutputFormat,
+) {
+    let details: Vec<(String, String)> = details
+        .iter()
+        .map(|(key, value)| (key.to_string(), value.clone()))
+        .collect();
+
+    let view = match submission {
+        Submission::Confirmed(signature) => SubmissionView {
+            action,
+            simulated: false,
+            signature: Some(signature.to_string()),
+            success: true,
+            error: None,
+            units_consumed: None,
+            logs: Vec::new(),
+            details,
+        },
+        Submission::Simulated(result) => SubmissionView {
+            action,
+            simulated: true,
+            signature: None,
+            success: result.is_ok(),
+            error: result.err.as_ref().map(|err| err.to_string()),
+            units_consumed: result.units_consumed,
+            logs: result.logs.clone(),
+            details,
+        },
+    };
+
+    match format {
+        OutputFormat::Json => print_json(&view),
+        OutputFormat::Table => {
+            let mut rows = vec![("action", view.action.to_string())];
+            match &view.signature {
+                Some(signature) => rows.push(("signature", signature.clone())),
+                None => {
+                    rows.push(("simulated", "true".to_string()));
+                    rows.push(("success", view.success.to_string()));
+                    rows.push(("error", optional(view.error.clone())));
+                    rows.push(("units_consumed", optional(view.units_consumed)));
+                }
+            }
+            for (key, value) in &view.details {
+                rows.push((key.as_str(), value.clone()));
+            }
+            print_key_values(&rows);
+
+            if !view.logs.is_empty() {
+                println!();
+                for log in &view.logs {
+                    println!("  {}", log);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,1332 @@
+use anchor_lang::{system_program, InstructionData, ToAccountMetas};
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+};
+use trendxbet::{
+    accounts, instruction, ExposureLimits, MarketKind, MatchIndexKey, MatchScore, MatchStatus,
+    ReportEvidence, SecurityUtils,
+};
+
+/// Assemble an instruction from Anchor's generated account and data types
+fn build(
+    program_id: &Pubkey,
+    accounts: impl ToAccountMetas,
+    data: impl InstructionData,
+) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+/// Registry index pages a new match is listed on
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MatchListing {
+    /// Page of the Scheduled status index with room for the match
+    pub status_index: Pubkey,
+    /// Page of the match's start-day index with room for it
+    pub start_day_index: Pubkey,
+    /// Page of the match's league index, if it belongs to one
+    pub league_index: Option<Pubkey>,
+}
+
+/// Status index pages an indexed match moves between when its status changes
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct StatusIndexMove {
+    /// Page of the current status's index that lists the match
+    pub old_status_index: Pubkey,
+    /// Page of the new status's index with room for the match
+    pub new_status_index: Pubkey,
+}
+
+/// Accounts a bet on a match must pass when the match needs them
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct BetAccounts {
+    /// Invite list of the match, when it is private
+    pub private_pool: Option<Pubkey>,
+    /// Odds history of the match, when it is charted
+    pub odds_history: Option<Pubkey>,
+}
+
+/// Session `authority` signs with for `bettor`, or `None` when the bettor signs itself
+fn session_for(program_id: &Pubkey, authority: &Pubkey, bettor: &Pubkey) -> Option<Pubkey> {
+    (authority != bettor)
+        .then(|| SecurityUtils::generate_session_pda(bettor, authority, program_id).0)
+}
+
+/// Initialize the platform and treasury
+pub fn initialize(program_id: &Pubkey, payer: &Pubkey, admin: Pubkey) -> Instruction {
+    build(
+        program_id,
+        accounts::Initialize {
+            global_state: SecurityUtils::generate_platform_pda(program_id).0,
+            treasury: SecurityUtils::generate_treasury_pda(program_id).0,
+            admin: *payer,
+            system_program: system_program::ID,
+        },
+        instruction::Initialize { admin },
+    )
+}
+
+/// Create a new match keyed by `match_id`; anyone but the admin bonds for it
+/// and may take `creator_fee` basis points of the pool
+#[allow(clippy::too_many_arguments)]
+pub fn create_match(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    match_id: &Pubkey,
+    team1: String,
+    team2: String,
+    start_time: i64,
+    end_time: i64,
+    description: String,
+    creator_fee: u16,
+    listing: Option<MatchListing>,
+) -> Instruction {
+    build(
+        program_id,
+        accounts::CreateMatch {
+            match_state: SecurityUtils::generate_match_pda(match_id, program_id).0,
+            global_state: SecurityUtils::generate_platform_pda(program_id).0,
+            match_id: *match_id,
+            authority: *authority,
+            system_program: system_program::ID,
+            registry: listing.map(|_| SecurityUtils::generate_match_registry_pda(program_id).0),
+            status_index: listing.map(|listing| listing.status_index),
+            start_day_index: listing.map(|listing| listing.start_day_index),
+            league_index: listing.and_then(|listing| listing.league_index),
+        },
+        instruction::CreateMatch {
+            team1,
+            team2,
+            start_time,
+            end_time,
+            description,
+            creator_fee,
+        },
+    )
+}
+
+/// Create a totals or spread market between two teams; `line` is in `LINE_SCALE` units
+#[allow(clippy::too_many_arguments)]
+pub fn create_line_market(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    match_id: &Pubkey,
+    team1: String,
+    team2: String,
+    market_kind: MarketKind,
+    line: i32,
+    start_time: i64,
+    end_time: i64,
+    description: String,
+    creator_fee: u16,
+    listing: Option<MatchListing>,
+) -> Instruction {
+    build(
+        program_id,
+        accounts::CreateMatch {
+            match_state: SecurityUtils::generate_match_pda(match_id, program_id).0,
+            global_state: SecurityUtils::generate_platform_pda(program_id).0,
+            match_id: *match_id,
+            authority: *authority,
+            system_program: system_program::ID,
+            registry: listing.map(|_| SecurityUtils::generate_match_registry_pda(program_id).0),
+            status_index: listing.map(|listing| listing.status_index),
+            start_day_index: listing.map(|listing| listing.start_day_index),
+            league_index: listing.and_then(|listing| listing.league_index),
+        },
+        instruction::CreateLineMarket {
+            team1,
+            team2,
+            market_kind,
+            line,
+            start_time,
+            end_time,
+            description,
+            creator_fee,
+        },
+    )
+}
+
+/// Create a price-trend market keyed by `match_id` that resolves from `price_feed`
+#[allow(clippy::too_many_arguments)]
+pub fn create_price_market(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    match_id: &Pubkey,
+    price_feed: &Pubkey,
+    asset: String,
+    strike_price: i64,
+    start_time: i64,
+    end_time: i64,
+    description: String,
+    creator_fee: u16,
+    listing: Option<MatchListing>,
+) -> Instruction {
+    build(
+        program_id,
+        accounts::CreatePriceMarket {
+            match_state: SecurityUtils::generate_match_pda(match_id, program_id).0,
+            price_market: SecurityUtils::generate_price_market_pda(match_id, program_id).0,
+            global_state: SecurityUtils::generate_platform_pda(program_id).0,
+            price_feed: *price_feed,
+            match_id: *match_id,
+            authority: *authority,
+            system_program: system_program::ID,
+            registry: listing.map(|_| SecurityUtils::generate_match_registry_pda(program_id).0),
+            status_index: listing.map(|listing| listing.status_index),
+            start_day_index: listing.map(|listing| listing.start_day_index),
+            league_index: listing.and_then(|listing| listing.league_index),
+        },
+        instruction::CreatePriceMarket {
+            asset,
+            strike_price,
+            start_time,
+            end_time,
+            description,
+            creator_fee,
+        },
+    )
+}
+
+/// Settle a price-trend market on the price its feed published at end time
+pub fn resolve_price_market(
+    program_id: &Pubkey,
+    resolver: &Pubkey,
+    match_id: &Pubkey,
+    price_feed: &Pubkey,
+    index_move: Option<StatusIndexMove>,
+) -> Instruction {
+    build(
+        program_id,
+        accounts::ResolvePriceMarket {
+            match_state: SecurityUtils::generate_match_pda(match_id, program_id).0,
+            price_market: SecurityUtils::generate_price_market_pda(match_id, program_id).0,
+            global_state: SecurityUtils::generate_platform_pda(program_id).0,
+            price_feed: *price_feed,
+            match_id: *match_id,
+            resolver: *resolver,
+            old_status_index: index_move.map(|pages| pages.old_status_index),
+            new_status_index: index_move.map(|pages| pages.new_status_index),
+        },
+        instruction::ResolvePriceMarket {},
+    )
+}
+
+/// Move a match to a new status, recorded on `audit_log` once the audit log is enabled
+pub fn update_match_status(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    match_id: &Pubkey,
+    status: MatchStatus,
+    index_move: Option<StatusIndexMove>,
+    audit_log: Option<Pubkey>,
+) -> Instruction {
+    build(
+        program_id,
+        accounts::UpdateMatchStatus {
+            match_state: SecurityUtils::generate_match_pda(match_id, program_id).0,
+            global_state: SecurityUtils::generate_platform_pda(program_id).0,
+            match_id: *match_id,
+            admin: *admin,
+            old_status_index: index_move.map(|pages| pages.old_status_index),
+            new_status_index: index_move.map(|pages| pages.new_status_index),
+            audit_log,
+        },
+        instruction::UpdateMatchStatus { status },
+    )
+}
+
+/// Close betting on a scheduled match, recorded on `audit_log` once the audit log is enabled
+pub fn close_match_betting(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    match_id: &Pubkey,
+    audit_log: Option<Pubkey>,
+) -> Instruction {
+    build(
+        program_id,
+        accounts::CloseMatchBetting {
+            match_state: SecurityUtils::generate_match_pda(match_id, program_id).0,
+            global_state: SecurityUtils::generate_platform_pda(program_id).0,
+            match_id: *match_id,
+            admin: *admin,
+            audit_log,
+        },
+        instruction::CloseMatchBetting {},
+    )
+}
+
+/// Set when a match takes bets and the bonus weight of the earliest ones, recorded on
+/// `audit_log` once the audit log is enabled
+pub fn set_betting_window(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    match_id: &Pubkey,
+    opens_at: i64,
+    closes_at: i64,
+    early_bet_bonus: u16,
+    audit_log: Option<Pubkey>,
+) -> Instruction {
+    build(
+        program_id,
+        accounts::SetBettingWindow {
+            match_state: SecurityUtils::generate_match_pda(match_id, program_id).0,
+            global_state: SecurityUtils::generate_platform_pda(program_id).0,
+            match_id: *match_id,
+            admin: *admin,
+            audit_log,
+        },
+        instruction::SetBettingWindow {
+            opens_at,
+            closes_at,
+            early_bet_bonus,
+        },
+    )
+}
+
+/// Advance a match to the status implied by the clock, paying `cranker` the bounty
+pub fn crank_match(
+    program_id: &Pubkey,
+    cranker: &Pubkey,
+    match_id: &Pubkey,
+    index_move: Option<StatusIndexMove>,
+) -> Instruction {
+    let treasury = SecurityUtils::generate_treasury_pda(program_id).0;
+    build(
+        program_id,
+        accounts::CrankMatch {
+            match_state: SecurityUtils::generate_match_pda(match_id, program_id).0,
+            global_state: SecurityUtils::generate_platform_pda(program_id).0,
+            treasury,
+            treasury_account: treasury,
+            match_id: *match_id,
+            cranker: *cranker,
+            old_status_index: index_move.map(|pages| pages.old_status_index),
+            new_status_index: index_move.map(|pages| pages.new_status_index),
+        },
+        instruction::CrankMatch {},
+    )
+}
+
+/// Void a match whose oracles missed their deadline
+pub fn void_match(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    match_id: &Pubkey,
+    index_move: Option<StatusIndexMove>,
+) -> Instruction {
+    build(
+        program_id,
+        accounts::VoidMatch {
+            match_state: SecurityUtils::generate_match_pda(match_id, program_id).0,
+            match_id: *match_id,
+            authority: *authority,
+            old_status_index: index_move.map(|pages| pages.old_status_index),
+            new_status_index: index_move.map(|pages| pages.new_status_index),
+        },
+        instruction::VoidMatch {},
+    )
+}
+
+/// Pay a community market's creator their fee and return their bond
+pub fn settle_creator(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    match_id: &Pubkey,
+    creator: &Pubkey,
+) -> Instruction {
+    let treasury = SecurityUtils::generate_treasury_pda(program_id).0;
+    build(
+        program_id,
+        accounts::SettleCreator {
+            match_state: SecurityUtils::generate_match_pda(match_id, program_id).0,
+            treasury,
+            treasury_account: treasury,
+            creator: *creator,
+            match_id: *match_id,
+            authority: *authority,
+        },
+        instruction::SettleCreator {},
+    )
+}
+
+/// Void a community market for abuse, forfeiting its creator's bond, recorded on
+/// `audit_log` once the audit log is enabled
+pub fn void_market_for_abuse(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    match_id: &Pubkey,
+    index_move: Option<StatusIndexMove>,
+    audit_log: Option<Pubkey>,
+) -> Instruction {
+    let treasury = SecurityUtils::generate_treasury_pda(program_id).0;
+    build(
+        program_id,
+        accounts::VoidMarketForAbuse {
+            match_state: SecurityUtils::generate_match_pda(match_id, program_id).0,
+            global_state: SecurityUtils::generate_platform_pda(program_id).0,
+            treasury,
+            treasury_account: treasury,
+            match_id: *match_id,
+            admin: *admin,
+            old_status_index: index_move.map(|pages| pages.old_status_index),
+            new_status_index: index_move.map(|pages| pages.new_status_index),
+            audit_log,
+        },
+        instruction::VoidMarketForAbuse {},
+    )
+}
+
+/// Let `session_key` bet for `authority`'s profile until `expires_at`, each bet at most
+/// `max_bet_amount` and `spend_limit` in total; `permissions` is a mask of
+/// `SESSION_PLACE_BET` and `SESSION_CANCEL_BET`
+#[allow(clippy::too_many_arguments)]
+pub fn create_session_key(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    session_key: &Pubkey,
+    expires_at: i64,
+    max_bet_amount: u64,
+    spend_limit: u64,
+    permissions: u8,
+) -> Instruction {
+    build(
+        program_id,
+        accounts::CreateSessionKey {
+            session: SecurityUtils::generate_session_pda(authority, session_key, program_id).0,
+            user_state: SecurityUtils::generate_user_pda(authority, program_id).0,
+            authority: *authority,
+            system_program: system_program::ID,
+        },
+        instruction::CreateSessionKey {
+            session_key: *session_key,
+            expires_at,
+            max_bet_amount,
+            spend_limit,
+            permissions,
+        },
+    )
+}
+
+/// Revoke `authority`'s session key, signed by `signer`: the profile owner or the session key
+pub fn revoke_session_key(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    session_key: &Pubkey,
+    signer: &Pubkey,
+) -> Instruction {
+    build(
+        program_id,
+        accounts::RevokeSessionKey {
+            session: SecurityUtils::generate_session_pda(authority, session_key, program_id).0,
+            authority: *authority,
+            signer: *signer,
+        },
+        instruction::RevokeSessionKey {},
+    )
+}
+
+/// Bet `amount` on `predicted_team` for `bettor`, signed by `authority`: the bettor
+/// itself or a session key the bettor granted, which also pays the bet's rent
+#[allow(clippy::too_many_arguments)]
+pub fn place_bet(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    bettor: &Pubkey,
+    match_id: &Pubkey,
+    amount: u64,
+    predicted_team: u8,
+    odds_accepted: u64,
+    use_bonus: bool,
+    bet_accounts: BetAccounts,
+) -> Instruction {
+    build(
+        program_id,
+        accounts::PlaceBet {
+            bet_state: SecurityUtils::generate_bet_pda(bettor, match_id, program_id).0,
+            user_state: SecurityUtils::generate_user_pda(bettor, program_id).0,
+            match_state: SecurityUtils::generate_match_pda(match_id, program_id).0,
+            global_state: SecurityUtils::generate_platform_pda(program_id).0,
+            treasury: SecurityUtils::generate_treasury_pda(program_id).0,
+            match_id: *match_id,
+            bettor: *bettor,
+            authority: *authority,
+            system_program: system_program::ID,
+            session: session_for(program_id, authority, bettor),
+            private_pool: bet_accounts.private_pool,
+            odds_history: bet_accounts.odds_history,
+        },
+        instruction::PlaceBet {
+            amount,
+            predicted_team,
+            odds_accepted,
+            use_bonus,
+        },
+    )
+}
+
+/// Cancel `bettor`'s open bet, signed by the bettor or a session key it granted
+pub fn cancel_bet(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    bettor: &Pubkey,
+    match_id: &Pubkey,
+    bet_accounts: BetAccounts,
+) -> Instruction {
+    build(
+        program_id,
+        accounts::CancelBet {
+            bet_state: SecurityUtils::generate_bet_pda(bettor, match_id, program_id).0,
+            user_state: SecurityUtils::generate_user_pda(bettor, program_id).0,
+            match_state: SecurityUtils::generate_match_pda(match_id, program_id).0,
+            treasury: SecurityUtils::generate_treasury_pda(program_id).0,
+            match_id: *match_id,
+            bettor: *bettor,
+            authority: *authority,
+            session: session_for(program_id, authority, bettor),
+            odds_history: bet_accounts.odds_history,
+        },
+        instruction::CancelBet {},
+    )
+}
+
+/// Change `bettor`'s open bet to `amount` on `predicted_team`, signed by the bettor
+/// or a session key it granted
+#[allow(clippy::too_many_arguments)]
+pub fn modify_bet(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    bettor: &Pubkey,
+    match_id: &Pubkey,
+    amount: u64,
+    predicted_team: u8,
+    odds_accepted: u64,
+    use_bonus: bool,
+    bet_accounts: BetAccounts,
+) -> Instruction {
+    build(
+        program_id,
+        accounts::ModifyBet {
+            bet_state: SecurityUtils::generate_bet_pda(bettor, match_id, program_id).0,
+            user_state: SecurityUtils::generate_user_pda(bettor, program_id).0,
+            match_state: SecurityUtils::generate_match_pda(match_id, program_id).0,
+            global_state: SecurityUtils::generate_platform_pda(program_id).0,
+            treasury: SecurityUtils::generate_treasury_pda(program_id).0,
+            match_id: *match_id,
+            bettor: *bettor,
+            authority: *authority,
+            session: session_for(program_id, authority, bettor),
+            private_pool: bet_accounts.private_pool,
+            odds_history: bet_accounts.odds_history,
+        },
+        instruction::ModifyBet {
+            amount,
+            predicted_team,
+            odds_accepted,
+            use_bonus,
+        },
+    )
+}
+
+/// Refund `bettor`'s stake on a voided or cancelled match
+pub fn refund_bet(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    bettor: &Pubkey,
+    match_id: &Pubkey,
+) -> Instruction {
+    build(
+        program_id,
+        accounts::RefundBet {
+            bet_state: SecurityUtils::generate_bet_pda(bettor, match_id, program_id).0,
+            user_state: SecurityUtils::generate_user_pda(bettor, program_id).0,
+            match_state: SecurityUtils::generate_match_pda(match_id, program_id).0,
+            treasury: SecurityUtils::generate_treasury_pda(program_id).0,
+            match_id: *match_id,
+            bettor: *bettor,
+            authority: *authority,
+        },
+        instruction::RefundBet {},
+    )
+}
+
+/// Register an oracle authority for a match, recorded on `audit_log` once the audit log is enabled
+pub fn register_oracle(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    match_id: &Pubkey,
+    oracle_authority: &Pubkey,
+    audit_log: Option<Pubkey>,
+) -> Instruction {
+    build(
+        program_id,
+        accounts::RegisterOracle {
+            oracle_state: SecurityUtils::generate_oracle_pda(
+                oracle_authority,
+                match_id,
+                program_id,
+            )
+            .0,
+            global_state: SecurityUtils::generate_platform_pda(program_id).0,
+            match_state: SecurityUtils::generate_match_pda(match_id, program_id).0,
+            oracle_profile: SecurityUtils::generate_oracle_profile_pda(
+                oracle_authority,
+                program_id,
+            )
+            .0,
+            oracle_authority: *oracle_authority,
+            match_id: *match_id,
+            admin: *admin,
+            authority: *admin,
+            system_program: system_program::ID,
+            audit_log,
+        },
+        instruction::RegisterOracle {
+            oracle_authority: *oracle_authority,
+        },
+    )
+}
+
+/// Commit an oracle to a result without publishing it, see `OracleState::commitment`
+pub fn commit_match_result(
+    program_id: &Pubkey,
+    oracle_authority: &Pubkey,
+    match_id: &Pubkey,
+    commitment: [u8; 32],
+) -> Instruction {
+    build(
+        program_id,
+        accounts::CommitMatchResult {
+            oracle_state: SecurityUtils::generate_oracle_pda(
+                oracle_authority,
+                match_id,
+                program_id,
+            )
+            .0,
+            match_state: SecurityUtils::generate_match_pda(match_id, program_id).0,
+            global_state: SecurityUtils::generate_platform_pda(program_id).0,
+            oracle_authority: *oracle_authority,
+            match_id: *match_id,
+            authority: *oracle_authority,
+        },
+        instruction::CommitMatchResult { commitment },
+    )
+}
+
+/// Reveal the result an oracle committed to, with the salt it was committed with
+/// and the evidence it relied on
+#[allow(clippy::too_many_arguments)]
+pub fn reveal_match_result(
+    program_id: &Pubkey,
+    oracle_authority: &Pubkey,
+    match_id: &Pubkey,
+    winning_team: u8,
+    final_score: String,
+    match_score: Option<MatchScore>,
+    salt: [u8; 32],
+    evidence: Option<ReportEvidence>,
+) -> Instruction {
+    build(
+        program_id,
+        accounts::RevealMatchResult {
+            oracle_state: SecurityUtils::generate_oracle_pda(
+                oracle_authority,
+                match_id,
+                program_id,
+            )
+            .0,
+            match_state: SecurityUtils::generate_match_pda(match_id, program_id).0,
+            global_state: SecurityUtils::generate_platform_pda(program_id).0,
+            oracle_authority: *oracle_authority,
+            match_id: *match_id,
+            authority: *oracle_authority,
+        },
+        instruction::RevealMatchResult {
+            winning_team,
+            final_score,
+            match_score,
+            salt,
+            evidence,
+        },
+    )
+}
+
+/// Validate an oracle's report and settle the match once it is final,
+/// counting each of `peer_authorities` that reported the same result as a confirmation,
+/// recorded on `audit_log` once the audit log is enabled
+pub fn validate_oracle_update(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    match_id: &Pubkey,
+    oracle_authority: &Pubkey,
+    peer_authorities: &[Pubkey],
+    index_move: Option<StatusIndexMove>,
+    audit_log: Option<Pubkey>,
+) -> Instruction {
+    let mut ix = build(
+        program_id,
+        accounts::ValidateOracleUpdate {
+            oracle_state: SecurityUtils::generate_oracle_pda(
+                oracle_authority,
+                match_id,
+                program_id,
+            )
+            .0,
+            match_state: SecurityUtils::generate_match_pda(match_id, program_id).0,
+            global_state: SecurityUtils::generate_platform_pda(program_id).0,
+            oracle_authority: *oracle_authority,
+            match_id: *match_id,
+            admin: *admin,
+            old_status_index: index_move.map(|pages| pages.old_status_index),
+            new_status_index: index_move.map(|pages| pages.new_status_index),
+            audit_log,
+        },
+        instruction::ValidateOracleUpdate {},
+    );
+    ix.accounts.extend(peer_authorities.iter().map(|peer| {
+        AccountMeta::new_readonly(
+            SecurityUtils::generate_oracle_pda(peer, match_id, program_id).0,
+            false,
+        )
+    }));
+    ix
+}
+
+/// Create the profile that holds an oracle's bonded stake and reputation
+pub fn create_oracle_profile(program_id: &Pubkey, oracle_authority: &Pubkey) -> Instruction {
+    build(
+        program_id,
+        accounts::CreateOracleProfile {
+            oracle_profile: SecurityUtils::generate_oracle_profile_pda(
+                oracle_authority,
+                program_id,
+            )
+            .0,
+            authority: *oracle_authority,
+            system_program: system_program::ID,
+        },
+        instruction::CreateOracleProfile {},
+    )
+}
+
+/// Bond `amount` lamports of oracle stake
+pub fn bond_oracle_stake(
+    program_id: &Pubkey,
+    oracle_authority: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    build(
+        program_id,
+        accounts::BondOracleStake {
+            oracle_profile: SecurityUtils::generate_oracle_profile_pda(
+                oracle_authority,
+                program_id,
+            )
+            .0,
+            authority: *oracle_authority,
+            system_program: system_program::ID,
+        },
+        instruction::BondOracleStake { amount },
+    )
+}
+
+/// Start unbonding `amount` lamports of oracle stake
+pub fn unbond_oracle_stake(
+    program_id: &Pubkey,
+    oracle_authority: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    build(
+        program_id,
+        accounts::UnbondOracleStake {
+            oracle_profile: SecurityUtils::generate_oracle_profile_pda(
+                oracle_authority,
+                program_id,
+            )
+            .0,
+            authority: *oracle_authority,
+        },
+        instruction::UnbondOracleStake { amount },
+    )
+}
+
+/// Withdraw oracle stake whose unbonding period is over
+pub fn withdraw_oracle_stake(program_id: &Pubkey, oracle_authority: &Pubkey) -> Instruction {
+    build(
+        program_id,
+        accounts::WithdrawOracleStake {
+            oracle_profile: SecurityUtils::generate_oracle_profile_pda(
+                oracle_authority,
+                program_id,
+            )
+            .0,
+            authority: *oracle_authority,
+        },
+        instruction::WithdrawOracleStake {},
+    )
+}
+
+/// Reward or slash an oracle's report on a settled or pushed match
+pub fn resolve_oracle_report(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    match_id: &Pubkey,
+    oracle_authority: &Pubkey,
+) -> Instruction {
+    let treasury = SecurityUtils::generate_treasury_pda(program_id).0;
+    build(
+        program_id,
+        accounts::ResolveOracleReport {
+            oracle_state: SecurityUtils::generate_oracle_pda(
+                oracle_authority,
+                match_id,
+                program_id,
+            )
+            .0,
+            oracle_profile: SecurityUtils::generate_oracle_profile_pda(
+                oracle_authority,
+                program_id,
+            )
+            .0,
+            match_state: SecurityUtils::generate_match_pda(match_id, program_id).0,
+            treasury,
+            treasury_account: treasury,
+            oracle_authority: *oracle_authority,
+            match_id: *match_id,
+            authority: *authority,
+        },
+        instruction::ResolveOracleReport {},
+    )
+}
+
+/// Create the arbiter set that decides disputes, recorded on `audit_log` once the audit
+/// log is enabled
+pub fn create_arbiter_set(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    arbiters: Vec<Pubkey>,
+    quorum: u8,
+    audit_log: Option<Pubkey>,
+) -> Instruction {
+    build(
+        program_id,
+        accounts::CreateArbiterSet {
+            arbiter_set: SecurityUtils::generate_arbiter_set_pda(program_id).0,
+            global_state: SecurityUtils::generate_platform_pda(program_id).0,
+            admin: *admin,
+            system_program: system_program::ID,
+            audit_log,
+        },
+        instruction::CreateArbiterSet { arbiters, quorum },
+    )
+}
+
+/// Replace the arbiters and quorum, recorded on `audit_log` once the audit log is enabled
+pub fn update_arbiter_set(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    arbiters: Vec<Pubkey>,
+    quorum: u8,
+    audit_log: Option<Pubkey>,
+) -> Instruction {
+    build(
+        program_id,
+        accounts::UpdateArbiterSet {
+            arbiter_set: SecurityUtils::generate_arbiter_set_pda(program_id).0,
+            global_state: SecurityUtils::generate_platform_pda(program_id).0,
+            admin: *admin,
+            audit_log,
+        },
+        instruction::UpdateArbiterSet { arbiters, quorum },
+    )
+}
+
+/// Bond the dispute bond to challenge a match result in favour of `proposed_team`
+pub fn open_dispute(
+    program_id: &Pubkey,
+    challenger: &Pubkey,
+    match_id: &Pubkey,
+    proposed_team: u8,
+) -> Instruction {
+    build(
+        program_id,
+        accounts::OpenDispute {
+            dispute_state: SecurityUtils::generate_dispute_pda(match_id, program_id).0,
+            match_state: SecurityUtils::generate_match_pda(match_id, program_id).0,
+            global_state: SecurityUtils::generate_platform_pda(program_id).0,
+            arbiter_set: SecurityUtils::generate_arbiter_set_pda(program_id).0,
+            match_id: *match_id,
+            challenger: *challenger,
+            system_program: system_program::ID,
+        },
+        instruction::OpenDispute { proposed_team },
+    )
+}
+
+/// Vote as an arbiter to uphold or reject a dispute
+pub fn vote_dispute(
+    program_id: &Pubkey,
+    arbiter: &Pubkey,
+    match_id: &Pubkey,
+    challenger: &Pubkey,
+    uphold: bool,
+) -> Instruction {
+    let treasury = SecurityUtils::generate_treasury_pda(program_id).0;
+    build(
+        program_id,
+        accounts::VoteDispute {
+            dispute_state: SecurityUtils::generate_dispute_pda(match_id, program_id).0,
+            arbiter_set: SecurityUtils::generate_arbiter_set_pda(program_id).0,
+            match_state: SecurityUtils::generate_match_pda(match_id, program_id).0,
+            treasury,
+            treasury_account: treasury,
+            challenger: *challenger,
+            match_id: *match_id,
+            arbiter: *arbiter,
+        },
+        instruction::VoteDispute { uphold },
+    )
+}
+
+/// Expire a dispute the arbiters left undecided past its voting deadline, refunding `challenger`
+pub fn expire_dispute(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    match_id: &Pubkey,
+    challenger: &Pubkey,
+) -> Instruction {
+    build(
+        program_id,
+        accounts::ExpireDispute {
+            dispute_state: SecurityUtils::generate_dispute_pda(match_id, program_id).0,
+            match_state: SecurityUtils::generate_match_pda(match_id, program_id).0,
+            challenger: *challenger,
+            match_id: *match_id,
+            authority: *authority,
+        },
+        instruction::ExpireDispute {},
+    )
+}
+
+/// Authorise `promoter` to issue bonuses, recorded on `audit_log` once the audit log is
+/// enabled
+pub fn add_promoter(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    promoter: &Pubkey,
+    audit_log: Option<Pubkey>,
+) -> Instruction {
+    build(
+        program_id,
+        accounts::AddPromoter {
+            promoter_account: SecurityUtils::generate_promoter_pda(promoter, program_id).0,
+            global_state: SecurityUtils::generate_platform_pda(program_id).0,
+            admin: *admin,
+            system_program: system_program::ID,
+            audit_log,
+        },
+        instruction::AddPromoter {
+            promoter: *promoter,
+        },
+    )
+}
+
+/// Withdraw `promoter`'s authorisation to issue bonuses, recorded on `audit_log` once the
+/// audit log is enabled
+pub fn remove_promoter(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    promoter: &Pubkey,
+    audit_log: Option<Pubkey>,
+) -> Instruction {
+    build(
+        program_id,
+        accounts::RemovePromoter {
+            promoter_account: SecurityUtils::generate_promoter_pda(promoter, program_id).0,
+            global_state: SecurityUtils::generate_platform_pda(program_id).0,
+            admin: *admin,
+            audit_log,
+        },
+        instruction::RemovePromoter {},
+    )
+}
+
+/// Credit `user` a bonus funded by `promoter`, to be staked `wagering_multiplier`
+/// times before its winnings unlock
+pub fn issue_bonus(
+    program_id: &Pubkey,
+    promoter: &Pubkey,
+    user: &Pubkey,
+    amount: u64,
+    wagering_multiplier: u8,
+) -> Instruction {
+    let treasury = SecurityUtils::generate_treasury_pda(program_id).0;
+    build(
+        program_id,
+        accounts::IssueBonus {
+            promoter_account: SecurityUtils::generate_promoter_pda(promoter, program_id).0,
+            user_state: SecurityUtils::generate_user_pda(user, program_id).0,
+            treasury,
+            user: *user,
+            authority: *promoter,
+            treasury_account: treasury,
+            system_program: system_program::ID,
+        },
+        instruction::IssueBonus {
+            amount,
+            wagering_multiplier,
+        },
+    )
+}
+
+/// Make a match private to `members` and whoever knows the invite code, optionally
+/// without a house edge; `invite_code_hash` is `PrivatePool::invite_code_hash`
+pub fn create_private_pool(
+    program_id: &Pubkey,
+    organiser: &Pubkey,
+    match_id: &Pubkey,
+    invite_code_hash: Option<[u8; 32]>,
+    members: Vec<Pubkey>,
+    waives_house_edge: bool,
+) -> Instruction {
+    build(
+        program_id,
+        accounts::CreatePrivatePool {
+            private_pool: SecurityUtils::generate_private_pool_pda(match_id, program_id).0,
+            match_state: SecurityUtils::generate_match_pda(match_id, program_id).0,
+            global_state: SecurityUtils::generate_platform_pda(program_id).0,
+            match_id: *match_id,
+            organiser: *organiser,
+            system_program: system_program::ID,
+        },
+        instruction::CreatePrivatePool {
+            invite_code_hash,
+            members,
+            waives_house_edge,
+        },
+    )
+}
+
+/// Replace a private pool's invite code and members
+pub fn update_private_pool(
+    program_id: &Pubkey,
+    organiser: &Pubkey,
+    match_id: &Pubkey,
+    invite_code_hash: Option<[u8; 32]>,
+    members: Vec<Pubkey>,
+) -> Instruction {
+    build(
+        program_id,
+        accounts::UpdatePrivatePool {
+            private_pool: SecurityUtils::generate_private_pool_pda(match_id, program_id).0,
+            match_id: *match_id,
+            organiser: *organiser,
+        },
+        instruction::UpdatePrivatePool {
+            invite_code_hash,
+            members,
+        },
+    )
+}
+
+/// Join a private pool with its invite code
+pub fn join_private_pool(
+    program_id: &Pubkey,
+    bettor: &Pubkey,
+    match_id: &Pubkey,
+    invite_code: String,
+) -> Instruction {
+    build(
+        program_id,
+        accounts::JoinPrivatePool {
+            private_pool: SecurityUtils::generate_private_pool_pda(match_id, program_id).0,
+            match_id: *match_id,
+            bettor: *bettor,
+        },
+        instruction::JoinPrivatePool { invite_code },
+    )
+}
+
+/// Close betting on a private pool as its organiser
+pub fn close_private_pool_betting(
+    program_id: &Pubkey,
+    organiser: &Pubkey,
+    match_id: &Pubkey,
+) -> Instruction {
+    build(
+        program_id,
+        accounts::ClosePrivatePoolBetting {
+            private_pool: SecurityUtils::generate_private_pool_pda(match_id, program_id).0,
+            match_state: SecurityUtils::generate_match_pda(match_id, program_id).0,
+            match_id: *match_id,
+            organiser: *organiser,
+        },
+        instruction::ClosePrivatePoolBetting {},
+    )
+}
+
+/// Cancel a private pool as its organiser so every bet on it can be refunded
+pub fn cancel_private_pool(
+    program_id: &Pubkey,
+    organiser: &Pubkey,
+    match_id: &Pubkey,
+    index_move: Option<StatusIndexMove>,
+) -> Instruction {
+    build(
+        program_id,
+        accounts::CancelPrivatePool {
+            private_pool: SecurityUtils::generate_private_pool_pda(match_id, program_id).0,
+            match_state: SecurityUtils::generate_match_pda(match_id, program_id).0,
+            match_id: *match_id,
+            organiser: *organiser,
+            old_status_index: index_move.map(|pages| pages.old_status_index),
+            new_status_index: index_move.map(|pages| pages.new_status_index),
+        },
+        instruction::CancelPrivatePool {},
+    )
+}
+
+/// Start charting a match's pools and odds after every bet; `payer` funds the history
+pub fn create_odds_history(program_id: &Pubkey, payer: &Pubkey, match_id: &Pubkey) -> Instruction {
+    build(
+        program_id,
+        accounts::CreateOddsHistory {
+            odds_history: SecurityUtils::generate_odds_history_pda(match_id, program_id).0,
+            match_state: SecurityUtils::generate_match_pda(match_id, program_id).0,
+            match_id: *match_id,
+            payer: *payer,
+            system_program: system_program::ID,
+        },
+        instruction::CreateOddsHistory {},
+    )
+}
+
+/// Create the match registry that lists matches in paged indexes
+pub fn create_match_registry(program_id: &Pubkey, admin: &Pubkey) -> Instruction {
+    build(
+        program_id,
+        accounts::CreateMatchRegistry {
+            registry: SecurityUtils::generate_match_registry_pda(program_id).0,
+            global_state: SecurityUtils::generate_platform_pda(program_id).0,
+            admin: *admin,
+            system_program: system_program::ID,
+        },
+        instruction::CreateMatchRegistry {},
+    )
+}
+
+/// Add page `page` to the index `key`; pages past the first follow a full one
+pub fn create_match_index_page(
+    program_id: &Pubkey,
+    payer: &Pubkey,
+    key: MatchIndexKey,
+    page: u32,
+) -> Instruction {
+    build(
+        program_id,
+        accounts::CreateMatchIndexPage {
+            index_page: SecurityUtils::generate_match_index_pda(&key, page, program_id).0,
+            previous_page: page.checked_sub(1).map(|previous| {
+                SecurityUtils::generate_match_index_pda(&key, previous, program_id).0
+            }),
+            registry: SecurityUtils::generate_match_registry_pda(program_id).0,
+            payer: *payer,
+            system_program: system_program::ID,
+        },
+        instruction::CreateMatchIndexPage { key, page },
+    )
+}
+
+/// Update house edge, bet limits, crank bounty, price feed program, dispute terms,
+/// the default betting cutoff and early-bet bonus, community market terms and the
+/// cancellation fee, recorded on `audit_log` once the audit log is enabled
+#[allow(clippy::too_many_arguments)]
+pub fn update_platform_config(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    house_edge: Option<u16>,
+    min_bet_amount: Option<u64>,
+    max_bet_amount: Option<u64>,
+    crank_bounty: Option<u64>,
+    price_feed_program: Option<Pubkey>,
+    dispute_window: Option<i64>,
+    dispute_bond: Option<u64>,
+    betting_cutoff: Option<u32>,
+    early_bet_bonus: Option<u16>,
+    creator_bond: Option<u64>,
+    max_creator_fee: Option<u16>,
+    cancellation_fee: Option<u16>,
+    audit_log: Option<Pubkey>,
+) -> Instruction {
+    build(
+        program_id,
+        accounts::UpdatePlatformConfig {
+            global_state: SecurityUtils::generate_platform_pda(program_id).0,
+            admin: *admin,
+            audit_log,
+        },
+        instruction::UpdatePlatformConfig {
+            house_edge,
+            min_bet_amount,
+            max_bet_amount,
+            crank_bounty,
+            price_feed_program,
+            dispute_window,
+            dispute_bond,
+            betting_cutoff,
+            early_bet_bonus,
+            creator_bond,
+            max_creator_fee,
+            cancellation_fee,
+        },
+    )
+}
+
+/// Set the per-match pool, pool imbalance and per-user stake caps
+pub fn update_exposure_limits(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    limits: ExposureLimits,
+    audit_log: Option<Pubkey>,
+) -> Instruction {
+    build(
+        program_id,
+        accounts::UpdateExposureLimits {
+            global_state: SecurityUtils::generate_platform_pda(program_id).0,
+            admin: *admin,
+            audit_log,
+        },
+        instruction::UpdateExposureLimits { limits },
+    )
+}
+
+/// Withdraw accumulated platform fees to the admin
+pub fn withdraw_platform_fees(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    amount: u64,
+    audit_log: Option<Pubkey>,
+) -> Instruction {
+    let treasury = SecurityUtils::generate_treasury_pda(program_id).0;
+    build(
+        program_id,
+        accounts::WithdrawPlatformFees {
+            global_state: SecurityUtils::generate_platform_pda(program_id).0,
+            treasury,
+            admin: *admin,
+            treasury_account: treasury,
+            system_program: system_program::ID,
+            audit_log,
+        },
+        instruction::WithdrawPlatformFees { amount },
+    )
+}
+
+/// Pause the platform
+pub fn pause_platform(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    audit_log: Option<Pubkey>,
+) -> Instruction {
+    build(
+        program_id,
+        accounts::PausePlatform {
+            global_state: SecurityUtils::generate_platform_pda(program_id).0,
+            admin: *admin,
+            audit_log,
+        },
+        instruction::PausePlatform {},
+    )
+}
+
+/// Unpause the platform
+pub fn unpause_platform(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    audit_log: Option<Pubkey>,
+) -> Instruction {
+    build(
+        program_id,
+        accounts::UnpausePlatform {
+            global_state: SecurityUtils::generate_platform_pda(program_id).0,
+            admin: *admin,
+            audit_log,
+        },
+        instruction::UnpausePlatform {},
+    )
+}
+
+/// Withdraw treasury funds in an emergency
+pub fn emergency_withdraw(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    amount: u64,
+    audit_log: Option<Pubkey>,
+) -> Instruction {
+    let treasury = SecurityUtils::generate_treasury_pda(program_id).0;
+    build(
+        program_id,
+        accounts::EmergencyWithdraw {
+            global_state: SecurityUtils::generate_platform_pda(program_id).0,
+            treasury,
+            admin: *admin,
+            treasury_account: treasury,
+            system_program: system_program::ID,
+            audit_log,
+        },
+        instruction::EmergencyWithdraw { amount },
+    )
+}
+
+/// Start recording every privileged action in the paged audit log
+pub fn enable_audit_log(program_id: &Pubkey, admin: &Pubkey) -> Instruction {
+    build(
+        program_id,
+        accounts::EnableAuditLog {
+            audit_log: SecurityUtils::generate_audit_log_pda(0, program_id).0,
+            global_state: SecurityUtils::generate_platform_pda(program_id).0,
+            admin: *admin,
+            system_program: system_program::ID,
+        },
+        instruction::EnableAuditLog {},
+    )
+}
+
+/// Create page `page` of the audit log once the previous page is full
+pub fn create_audit_log_page(program_id: &Pubkey, payer: &Pubkey, page: u32) -> Instruction {
+    build(
+        program_id,
+        accounts::CreateAuditLogPage {
+            audit_log: SecurityUtils::generate_audit_log_pda(page, program_id).0,
+            global_state: SecurityUtils::generate_platform_pda(program_id).0,
+            payer: *payer,
+            system_program: system_program::ID,
+        },
+        instruction::CreateAuditLogPage { page },
+    )
+}
+
+/// Upgrade a program account to the current layout version, recorded on `audit_log` once
+/// the audit log is enabled
+pub fn migrate_account(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    account: &Pubkey,
+    audit_log: Option<Pubkey>,
+) -> Instruction {
+    build(
+        program_id,
+        accounts::MigrateAccount {
+            account: *account,
+            global_state: SecurityUtils::generate_platform_pda(program_id).0,
+            admin: *admin,
+            system_program: system_program::ID,
+            audit_log,
+        },
+        instruction::MigrateAccount {},
+    )
+}
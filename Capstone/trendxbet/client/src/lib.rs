@@ -0,0 +1,389 @@
+//! Off-chain helpers shared by the TrendXBet operator tools: a small JSON-RPC
+//! client, instruction builders and typed account fetching.
+
+pub mod instructions;
+pub mod rpc;
+
+use anchor_lang::{AccountDeserialize, Discriminator};
+use anyhow::{anyhow, bail, Context, Result};
+use solana_sdk::{
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::{Keypair, Signature, Signer},
+    transaction::Transaction,
+};
+use trendxbet::{
+    ArbiterSet, AuditLogPage, BetState, DisputeState, GlobalState, MatchIndexKey, MatchIndexPage,
+    MatchRegistry, MatchState, MatchStatus, OddsHistory, OracleProfile, OracleState, PriceMarket,
+    SecurityUtils, TreasuryState, UserState, UsernameRecord,
+};
+
+use crate::instructions::{BetAccounts, MatchListing, StatusIndexMove};
+
+pub use rpc::{RpcAccount, RpcClient, SimulationResult};
+
+/// Result of submitting (or simulating) a transaction
+#[derive(Debug, Clone)]
+pub enum Submission {
+    /// Transaction landed on chain
+    Confirmed(Signature),
+    /// Transaction was only simulated
+    Simulated(SimulationResult),
+}
+
+/// RPC connection bound to a TrendXBet deployment and a fee payer
+pub struct TrendXBetClient {
+    pub rpc: RpcClient,
+    pub program_id: Pubkey,
+    pub payer: Keypair,
+}
+
+impl TrendXBetClient {
+    /// Create a client for `program_id` paying fees with `payer`
+    pub fn new(url: impl Into<String>, program_id: Pubkey, payer: Keypair) -> Self {
+        Self {
+            rpc: RpcClient::new(url),
+            program_id,
+            payer,
+        }
+    }
+
+    /// Get the fee payer public key
+    pub fn payer_pubkey(&self) -> Pubkey {
+        self.payer.pubkey()
+    }
+
+    /// Sign `instructions` with the payer plus `extra_signers` and submit them,
+    /// or only simulate when `dry_run` is set
+    pub fn submit(
+        &self,
+        instructions: &[Instruction],
+        extra_signers: &[&Keypair],
+        dry_run: bool,
+    ) -> Result<Submission> {
+        let blockhash = self.rpc.get_latest_blockhash()?;
+
+        let mut signers: Vec<&Keypair> = vec![&self.payer];
+        signers.extend(
+            extra_signers
+                .iter()
+                .filter(|signer| signer.pubkey() != self.payer.pubkey()),
+        );
+
+        let transaction = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&self.payer.pubkey()),
+            &signers,
+            blockhash,
+        );
+
+        if dry_run {
+            return Ok(Submission::Simulated(
+                self.rpc.simulate_transaction(&transaction)?,
+            ));
+        }
+
+        self.rpc
+            .send_and_confirm_transaction(&transaction)
+            .map(Submission::Confirmed)
+    }
+
+    /// Fetch and deserialize a program account
+    pub fn fetch<T: AccountDeserialize>(&self, address: &Pubkey) -> Result<T> {
+        let account = self
+            .rpc
+            .get_account(address)?
+            .ok_or_else(|| anyhow!("Account {} not found", address))?;
+
+        if account.owner != self.program_id {
+            bail!(
+                "Account {} is owned by {}, expected {}",
+                address,
+                account.owner,
+                self.program_id
+            );
+        }
+
+        T::try_deserialize(&mut account.data.as_slice())
+            .with_context(|| format!("Failed to deserialize account {}", address))
+    }
+
+    /// Fetch a program account, or `None` when it does not exist
+    pub fn fetch_optional<T: AccountDeserialize>(&self, address: &Pubkey) -> Result<Option<T>> {
+        if self.rpc.get_account(address)?.is_none() {
+            return Ok(None);
+        }
+        self.fetch(address).map(Some)
+    }
+
+    /// Fetch every program account of type `T`, keyed by address
+    pub fn fetch_all<T: AccountDeserialize + Discriminator>(&self) -> Result<Vec<(Pubkey, T)>> {
+        self.fetch_filtered(&[])
+    }
+
+    /// Fetch program accounts of type `T` whose data matches every
+    /// `(offset, bytes)` filter, with offsets counted after the discriminator
+    pub fn fetch_filtered<T: AccountDeserialize + Discriminator>(
+        &self,
+        filters: &[(usize, &[u8])],
+    ) -> Result<Vec<(Pubkey, T)>> {
+        let mut memcmp = vec![(0, T::DISCRIMINATOR)];
+        memcmp.extend(
+            filters
+                .iter()
+                .map(|(offset, bytes)| (offset + T::DISCRIMINATOR.len(), *bytes)),
+        );
+
+        self.rpc
+            .get_program_accounts(&self.program_id, &memcmp)?
+            .into_iter()
+            .map(|account| {
+                let state = T::try_deserialize(&mut account.data.as_slice())
+                    .with_context(|| format!("Failed to deserialize account {}", account.pubkey))?;
+                Ok((account.pubkey, state))
+            })
+            .collect()
+    }
+
+    /// Fetch the platform global state
+    pub fn global_state(&self) -> Result<GlobalState> {
+        self.fetch(&SecurityUtils::generate_platform_pda(&self.program_id).0)
+    }
+
+    /// Fetch the platform treasury state
+    pub fn treasury_state(&self) -> Result<TreasuryState> {
+        self.fetch(&SecurityUtils::generate_treasury_pda(&self.program_id).0)
+    }
+
+    /// Fetch a user profile by its wallet authority
+    pub fn user_state(&self, authority: &Pubkey) -> Result<UserState> {
+        self.fetch(&SecurityUtils::generate_user_pda(authority, &self.program_id).0)
+    }
+
+    /// Fetch the record of whoever holds `username`, in any casing
+    pub fn username_record(&self, username: &str) -> Result<UsernameRecord> {
+        self.fetch(&SecurityUtils::generate_username_pda(username, &self.program_id).0)
+    }
+
+    /// Fetch the profile holding `username`, keyed by address
+    pub fn user_by_username(&self, username: &str) -> Result<(Pubkey, UserState)> {
+        let record = self.username_record(username)?;
+        Ok((record.user_state, self.fetch(&record.user_state)?))
+    }
+
+    /// Fetch a match by its match identifier
+    pub fn match_state(&self, match_id: &Pubkey) -> Result<MatchState> {
+        self.fetch(&SecurityUtils::generate_match_pda(match_id, &self.program_id).0)
+    }
+
+    /// Private pool and odds history a bet on `match_id` has to pass
+    pub fn bet_accounts(&self, match_id: &Pubkey) -> Result<BetAccounts> {
+        let match_state = self.match_state(match_id)?;
+        Ok(BetAccounts {
+            private_pool: match_state
+                .is_private
+                .then(|| SecurityUtils::generate_private_pool_pda(match_id, &self.program_id).0),
+            odds_history: match_state
+                .has_odds_history
+                .then(|| SecurityUtils::generate_odds_history_pda(match_id, &self.program_id).0),
+        })
+    }
+
+    /// Fetch the odds history of a match, or `None` when it is not charted
+    pub fn odds_history(&self, match_id: &Pubkey) -> Result<Option<OddsHistory>> {
+        self.fetch_optional(&SecurityUtils::generate_odds_history_pda(match_id, &self.program_id).0)
+    }
+
+    /// Fetch every oracle assignment held by `oracle_authority`
+    pub fn oracle_assignments(
+        &self,
+        oracle_authority: &Pubkey,
+    ) -> Result<Vec<(Pubkey, OracleState)>> {
+        // `oracle_authority` is the first field of `OracleState`
+        self.fetch_filtered(&[(0, oracle_authority.as_ref())])
+    }
+
+    /// Fetch every oracle assignment for `match_id`
+    pub fn match_oracles(&self, match_id: &Pubkey) -> Result<Vec<(Pubkey, OracleState)>> {
+        // `match_id` follows the 32-byte `oracle_authority`
+        self.fetch_filtered(&[(32, match_id.as_ref())])
+    }
+
+    /// Fetch the stake and reputation profile of `oracle_authority`
+    pub fn oracle_profile(&self, oracle_authority: &Pubkey) -> Result<OracleProfile> {
+        self.fetch(
+            &SecurityUtils::generate_oracle_profile_pda(oracle_authority, &self.program_id).0,
+        )
+    }
+
+    /// Fetch the price market settled by `match_id`
+    pub fn price_market(&self, match_id: &Pubkey) -> Result<PriceMarket> {
+        self.fetch(&SecurityUtils::generate_price_market_pda(match_id, &self.program_id).0)
+    }
+
+    /// Fetch the arbiter set that decides disputes
+    pub fn arbiter_set(&self) -> Result<ArbiterSet> {
+        self.fetch(&SecurityUtils::generate_arbiter_set_pda(&self.program_id).0)
+    }
+
+    /// Fetch the dispute of the result of `match_id`
+    pub fn dispute(&self, match_id: &Pubkey) -> Result<DisputeState> {
+        self.fetch(&SecurityUtils::generate_dispute_pda(match_id, &self.program_id).0)
+    }
+
+    /// Fetch every bet placed on `match_id`
+    pub fn match_bets(&self, match_id: &Pubkey) -> Result<Vec<(Pubkey, BetState)>> {
+        // `match_id` follows the 32-byte `bettor`
+        self.fetch_filtered(&[(32, match_id.as_ref())])
+    }
+
+    /// Fetch every match created on the platform
+    pub fn all_matches(&self) -> Result<Vec<MatchState>> {
+        Ok(self
+            .fetch_all::<MatchState>()?
+            .into_iter()
+            .map(|(_, state)| state)
+            .collect())
+    }
+
+    /// Fetch the match registry, or `None` when indexing is not enabled
+    pub fn match_registry(&self) -> Result<Option<MatchRegistry>> {
+        self.fetch_optional(&SecurityUtils::generate_match_registry_pda(&self.program_id).0)
+    }
+
+    /// Fetch page `page` of the audit log, or `None` when it does not exist
+    pub fn audit_log_page(&self, page: u32) -> Result<Option<AuditLogPage>> {
+        self.fetch_optional(&SecurityUtils::generate_audit_log_pda(page, &self.program_id).0)
+    }
+
+    /// Audit log page the next privileged action is recorded on, plus the
+    /// instruction creating it when the previous page is full; `None` when the
+    /// audit log is not enabled
+    pub fn audit_log(&self) -> Result<(Option<Pubkey>, Vec<Instruction>)> {
+        let global_state = self.global_state()?;
+        if !global_state.has_audit_log {
+            return Ok((None, Vec::new()));
+        }
+
+        let page = global_state.audit_page();
+        let address = SecurityUtils::generate_audit_log_pda(page, &self.program_id).0;
+        let mut setup = Vec::new();
+        if self.audit_log_page(page)?.is_none() {
+            setup.push(instructions::create_audit_log_page(
+                &self.program_id,
+                &self.payer_pubkey(),
+                page,
+            ));
+        }
+        Ok((Some(address), setup))
+    }
+
+    /// Fetch the pages of index `key` in order, up to the first one not created yet
+    pub fn match_index_pages(&self, key: MatchIndexKey) -> Result<Vec<(Pubkey, MatchIndexPage)>> {
+        let mut pages = Vec::new();
+        for page in 0u32.. {
+            let address = SecurityUtils::generate_match_index_pda(&key, page, &self.program_id).0;
+            match self.fetch_optional::<MatchIndexPage>(&address)? {
+                Some(state) => pages.push((address, state)),
+                None => break,
+            }
+        }
+        Ok(pages)
+    }
+
+    /// Fetch every match listed under index `key`
+    pub fn match_index(&self, key: MatchIndexKey) -> Result<Vec<Pubkey>> {
+        Ok(self
+            .match_index_pages(key)?
+            .into_iter()
+            .flat_map(|(_, page)| page.matches)
+            .collect())
+    }
+
+    /// Page of index `key` with room for another match, plus the instruction
+    /// creating it when it does not exist yet
+    pub fn index_page_with_room(
+        &self,
+        key: MatchIndexKey,
+    ) -> Result<(Pubkey, Option<Instruction>)> {
+        let pages = self.match_index_pages(key)?;
+        if let Some((address, _)) = pages.iter().find(|(_, page)| !page.is_full()) {
+            return Ok((*address, None));
+        }
+
+        // Every existing page is full, so the next one is created first
+        let page = pages.len() as u32;
+        Ok((
+            SecurityUtils::generate_match_index_pda(&key, page, &self.program_id).0,
+            Some(instructions::create_match_index_page(
+                &self.program_id,
+                &self.payer_pubkey(),
+                key,
+                page,
+            )),
+        ))
+    }
+
+    /// Pages listing a new match starting at `start_time`, plus the instructions
+    /// creating any missing page; `None` when the registry does not exist
+    pub fn match_listing(
+        &self,
+        start_time: i64,
+        league: Option<u32>,
+    ) -> Result<Option<(MatchListing, Vec<Instruction>)>> {
+        if self.match_registry()?.is_none() {
+            return Ok(None);
+        }
+
+        let mut setup = Vec::new();
+        let mut page_with_room = |key| -> Result<Pubkey> {
+            let (address, create) = self.index_page_with_room(key)?;
+            setup.extend(create);
+            Ok(address)
+        };
+
+        let listing = MatchListing {
+            status_index: page_with_room(MatchIndexKey::status(&MatchStatus::Scheduled))?,
+            start_day_index: page_with_room(MatchIndexKey::start_day(start_time))?,
+            league_index: league
+                .map(|league| page_with_room(MatchIndexKey::League(league)))
+                .transpose()?,
+        };
+        Ok(Some((listing, setup)))
+    }
+
+    /// Status index pages moving `match_state` to `new_status`, plus the
+    /// instruction creating the new status's page when needed; `None` when the
+    /// match is not indexed or keeps its status
+    pub fn status_index_move(
+        &self,
+        match_state: &MatchState,
+        new_status: &MatchStatus,
+    ) -> Result<(Option<StatusIndexMove>, Vec<Instruction>)> {
+        if !match_state.is_indexed || match_state.status == *new_status {
+            return Ok((None, Vec::new()));
+        }
+
+        let old_status_index = self
+            .match_index_pages(MatchIndexKey::status(&match_state.status))?
+            .into_iter()
+            .find(|(_, page)| page.matches.contains(&match_state.match_id))
+            .map(|(address, _)| address)
+            .ok_or_else(|| {
+                anyhow!(
+                    "Match {} is not listed in its status index",
+                    match_state.match_id
+                )
+            })?;
+        let (new_status_index, create) =
+            self.index_page_with_room(MatchIndexKey::status(new_status))?;
+
+        Ok((
+            Some(StatusIndexMove {
+                old_status_index,
+                new_status_index,
+            }),
+            create.into_iter().collect(),
+        ))
+    }
+}
@@ -0,0 +1,262 @@
+use anyhow::{anyhow, bail, Context, Result};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde_json::{json, Value};
+use solana_sdk::{
+    clock::Clock, hash::Hash, pubkey::Pubkey, signature::Signature, sysvar,
+    transaction::Transaction,
+};
+use std::str::FromStr;
+use std::thread::sleep;
+use std::time::{Duration, Instant};
+
+/// Commitment used for every read and preflight check
+const COMMITMENT: &str = "confirmed";
+
+/// How long to wait for a sent transaction to reach `COMMITMENT`
+const CONFIRM_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Raw account returned by the RPC node
+#[derive(Debug, Clone)]
+pub struct RpcAccount {
+    pub pubkey: Pubkey,
+    pub owner: Pubkey,
+    pub lamports: u64,
+    pub data: Vec<u8>,
+}
+
+/// Outcome of `simulateTransaction`
+#[derive(Debug, Clone)]
+pub struct SimulationResult {
+    pub err: Option<Value>,
+    pub logs: Vec<String>,
+    pub units_consumed: Option<u64>,
+}
+
+impl SimulationResult {
+    /// Check if the simulated transaction succeeded
+    pub fn is_ok(&self) -> bool {
+        self.err.is_none()
+    }
+}
+
+/// Minimal blocking JSON-RPC client for a Solana node
+pub struct RpcClient {
+    url: String,
+    agent: ureq::Agent,
+}
+
+impl RpcClient {
+    /// Create a client for the given RPC endpoint
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            agent: ureq::AgentBuilder::new()
+                .timeout(Duration::from_secs(30))
+                .build(),
+        }
+    }
+
+    /// Get the endpoint this client talks to
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Perform a raw JSON-RPC call and return its `result` field
+    pub fn call(&self, method: &str, params: Value) -> Result<Value> {
+        let request = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": method,
+            "params": params,
+        });
+
+        let response: Value = self
+            .agent
+            .post(&self.url)
+            .send_json(request)
+            .with_context(|| format!("RPC request {} to {} failed", method, self.url))?
+            .into_json()
+            .with_context(|| format!("Invalid JSON in {} response", method))?;
+
+        if let Some(error) = response.get("error") {
+            bail!("RPC {} returned error: {}", method, error);
+        }
+
+        response
+            .get("result")
+            .cloned()
+            .ok_or_else(|| anyhow!("RPC {} response has no result", method))
+    }
+
+    /// Fetch the latest blockhash
+    pub fn get_latest_blockhash(&self) -> Result<Hash> {
+        let result = self.call("getLatestBlockhash", json!([{ "commitment": COMMITMENT }]))?;
+        let blockhash = result["value"]["blockhash"]
+            .as_str()
+            .ok_or_else(|| anyhow!("Missing blockhash in response"))?;
+        Hash::from_str(blockhash).map_err(|e| anyhow!("Invalid blockhash {}: {}", blockhash, e))
+    }
+
+    /// Fetch an account, returning `None` if it does not exist
+    pub fn get_account(&self, pubkey: &Pubkey) -> Result<Option<RpcAccount>> {
+        let result = self.call(
+            "getAccountInfo",
+            json!([pubkey.to_string(), { "encoding": "base64", "commitment": COMMITMENT }]),
+        )?;
+
+        match result.get("value") {
+            Some(Value::Null) | None => Ok(None),
+            Some(value) => parse_account(*pubkey, value).map(Some),
+        }
+    }
+
+    /// Fetch all accounts owned by `program_id` matching every `(offset, bytes)` filter
+    pub fn get_program_accounts(
+        &self,
+        program_id: &Pubkey,
+        filters: &[(usize, &[u8])],
+    ) -> Result<Vec<RpcAccount>> {
+        let filters: Vec<Value> = filters
+            .iter()
+            .map(|(offset, bytes)| {
+                json!({
+                    "memcmp": {
+                        "offset": offset,
+                        "bytes": STANDARD.encode(bytes),
+                        "encoding": "base64",
+                    }
+                })
+            })
+            .collect();
+
+        let result = self.call(
+            "getProgramAccounts",
+            json!([
+                program_id.to_string(),
+                { "encoding": "base64", "commitment": COMMITMENT, "filters": filters }
+            ]),
+        )?;
+
+        result
+            .as_array()
+            .ok_or_else(|| anyhow!("getProgramAccounts did not return an array"))?
+            .iter()
+            .map(|entry| {
+                let pubkey = entry["pubkey"]
+                    .as_str()
+                    .ok_or_else(|| anyhow!("Missing pubkey in getProgramAccounts entry"))?;
+                let pubkey = Pubkey::from_str(pubkey)
+                    .map_err(|e| anyhow!("Invalid pubkey {}: {}", pubkey, e))?;
+                parse_account(pubkey, &entry["account"])
+            })
+            .collect()
+    }
+
+    /// Read the cluster clock sysvar
+    pub fn get_clock(&self) -> Result<Clock> {
+        let account = self
+            .get_account(&sysvar::clock::ID)?
+            .ok_or_else(|| anyhow!("Clock sysvar not found"))?;
+        bincode::deserialize(&account.data).context("Invalid clock sysvar data")
+    }
+
+    /// Simulate a signed transaction without submitting it
+    pub fn simulate_transaction(&self, transaction: &Transaction) -> Result<SimulationResult> {
+        let result = self.call(
+            "simulateTransaction",
+            json!([
+                encode_transaction(transaction)?,
+                { "encoding": "base64", "sigVerify": true, "commitment": COMMITMENT }
+            ]),
+        )?;
+
+        let value = &result["value"];
+        Ok(SimulationResult {
+            err: match &value["err"] {
+                Value::Null => None,
+                err => Some(err.clone()),
+            },
+            logs: value["logs"]
+                .as_array()
+                .map(|logs| {
+                    logs.iter()
+                        .filter_map(|log| log.as_str().map(str::to_string))
+                        .collect()
+                })
+                .unwrap_or_default(),
+            units_consumed: value["unitsConsumed"].as_u64(),
+        })
+    }
+
+    /// Submit a signed transaction without waiting for confirmation
+    pub fn send_transaction(&self, transaction: &Transaction) -> Result<Signature> {
+        let result = self.call(
+            "sendTransaction",
+            json!([
+                encode_transaction(transaction)?,
+                { "encoding": "base64", "preflightCommitment": COMMITMENT }
+            ]),
+        )?;
+
+        let signature = result
+            .as_str()
+            .ok_or_else(|| anyhow!("sendTransaction did not return a signature"))?;
+        Signature::from_str(signature)
+            .map_err(|e| anyhow!("Invalid signature {}: {}", signature, e))
+    }
+
+    /// Submit a signed transaction and wait until it is confirmed
+    pub fn send_and_confirm_transaction(&self, transaction: &Transaction) -> Result<Signature> {
+        let signature = self.send_transaction(transaction)?;
+        let started = Instant::now();
+
+        while started.elapsed() < CONFIRM_TIMEOUT {
+            let result = self.call(
+                "getSignatureStatuses",
+                json!([[signature.to_string()], { "searchTransactionHistory": false }]),
+            )?;
+            let status = &result["value"][0];
+
+            if !status.is_null() {
+                if !status["err"].is_null() {
+                    bail!("Transaction {} failed: {}", signature, status["err"]);
+                }
+                if matches!(
+                    status["confirmationStatus"].as_str(),
+                    Some("confirmed") | Some("finalized")
+                ) {
+                    return Ok(signature);
+                }
+            }
+
+            sleep(Duration::from_millis(500));
+        }
+
+        bail!("Timed out waiting for confirmation of {}", signature)
+    }
+}
+
+/// Serialize a transaction to the base64 wire format
+fn encode_transaction(transaction: &Transaction) -> Result<String> {
+    let bytes = bincode::serialize(transaction).context("Failed to serialize transaction")?;
+    Ok(STANDARD.encode(bytes))
+}
+
+/// Decode an `{ data: [b64, "base64"], owner, lamports }` account object
+fn parse_account(pubkey: Pubkey, value: &Value) -> Result<RpcAccount> {
+    let data = value["data"][0]
+        .as_str()
+        .ok_or_else(|| anyhow!("Missing data for account {}", pubkey))?;
+    let owner = value["owner"]
+        .as_str()
+        .ok_or_else(|| anyhow!("Missing owner for account {}", pubkey))?;
+
+    Ok(RpcAccount {
+        pubkey,
+        owner: Pubkey::from_str(owner).map_err(|e| anyhow!("Invalid owner {}: {}", owner, e))?,
+        lamports: value["lamports"].as_u64().unwrap_or(0),
+        data: STANDARD
+            .decode(data)
+            .with_context(|| format!("Invalid base64 data for account {}", pubkey))?,
+    })
+}
@@ -0,0 +1,142 @@
+//! Oracle reporter daemon: watches the matches an oracle is registered for and
+//! commits to their result once their `end_time` has passed, revealing it once commitments close.
+
+mod reporter;
+mod server;
+mod source;
+
+use anyhow::{anyhow, bail, Context, Result};
+use clap::{Args, Parser, Subcommand};
+use reporter::{Reporter, SubmissionLog};
+use solana_sdk::{pubkey::Pubkey, signature::read_keypair_file};
+use source::{HttpSource, JsonFileSource, ResultSource};
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::thread::sleep;
+use std::time::Duration;
+use trendxbet_client::TrendXBetClient;
+
+#[derive(Parser)]
+#[command(
+    name = "trendxbet-oracle",
+    version,
+    about = "Report TrendXBet match results"
+)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Watch assigned matches and submit their results
+    Run(RunArgs),
+    /// Serve a result file over HTTP as a stand-in for a stats provider
+    ServeResults {
+        /// JSON result file of `{ "<match_id>": { winning_team, final_score, score? } }`
+        #[arg(long)]
+        file: PathBuf,
+        /// Address to listen on
+        #[arg(long, default_value = "127.0.0.1:8787")]
+        bind: String,
+    },
+}
+
+#[derive(Args)]
+struct RunArgs {
+    /// RPC endpoint URL or `localhost`
+    #[arg(
+        short = 'u',
+        long,
+        env = "TRENDXBET_RPC_URL",
+        default_value = "localhost"
+    )]
+    url: String,
+
+    /// Oracle authority keypair, also used as fee payer
+    #[arg(short = 'k', long, env = "TRENDXBET_ORACLE_KEYPAIR")]
+    keypair: PathBuf,
+
+    /// TrendXBet program id
+    #[arg(long, env = "TRENDXBET_PROGRAM_ID")]
+    program_id: Option<String>,
+
+    /// Result source: `file:<path>` or `http://<host>[:port]`
+    #[arg(short = 's', long)]
+    source: String,
+
+    /// Seconds between polls, also the retry interval for failed submissions
+    #[arg(long, default_value_t = 15)]
+    interval: u64,
+
+    /// Append every submission attempt as a JSON line to this file
+    #[arg(long)]
+    submission_log: Option<PathBuf>,
+
+    /// Simulate submissions instead of sending them
+    #[arg(long)]
+    dry_run: bool,
+
+    /// Poll once and exit
+    #[arg(long)]
+    once: bool,
+}
+
+fn main() -> Result<()> {
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info")).init();
+
+    match Cli::parse().command {
+        Command::Run(args) => run(args),
+        Command::ServeResults { file, bind } => server::serve(file, &bind),
+    }
+}
+
+fn run(args: RunArgs) -> Result<()> {
+    let keypair = read_keypair_file(&args.keypair)
+        .map_err(|e| anyhow!("Failed to read keypair {}: {}", args.keypair.display(), e))?;
+    let program_id = match &args.program_id {
+        Some(id) => {
+            Pubkey::from_str(id).map_err(|e| anyhow!("Invalid program id {}: {}", id, e))?
+        }
+        None => trendxbet::ID,
+    };
+    let url = match args.url.as_str() {
+        "localhost" | "l" => "http://127.0.0.1:8899".to_string(),
+        other => other.to_string(),
+    };
+
+    let mut reporter = Reporter::new(
+        TrendXBetClient::new(url, program_id, keypair),
+        parse_source(&args.source)?,
+        SubmissionLog::open(args.submission_log.as_ref())?,
+        args.dry_run,
+    );
+    log::info!("Starting {}", reporter.describe());
+
+    loop {
+        if let Err(err) = reporter.poll() {
+            if args.once {
+                return Err(err).context("Poll failed");
+            }
+            log::error!("Poll failed: {:#}", err);
+        }
+
+        if args.once {
+            return Ok(());
+        }
+        sleep(Duration::from_secs(args.interval));
+    }
+}
+
+fn parse_source(source: &str) -> Result<Box<dyn ResultSource>> {
+    if let Some(path) = source.strip_prefix("file:") {
+        return Ok(Box::new(JsonFileSource::new(PathBuf::from(path))));
+    }
+    if source.starts_with("http://") || source.starts_with("https://") {
+        return Ok(Box::new(HttpSource::new(source)));
+    }
+    bail!(
+        "Unknown result source {}, expected file:<path> or http(s)://...",
+        source
+    )
+}
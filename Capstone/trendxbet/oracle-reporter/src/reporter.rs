@@ -0,0 +1,353 @@
+use crate::source::{MatchResult, ResultSource};
+use anyhow::{Context, Result};
+use serde::Serialize;
+use solana_sdk::hash::hashv;
+use solana_sdk::pubkey::Pubkey;
+use std::collections::{HashMap, HashSet};
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use trendxbet::{MatchState, MatchStatus, OracleState, ORACLE_COMMIT_WINDOW, ORACLE_UPDATE_WINDOW};
+use trendxbet_client::{instructions, Submission, TrendXBetClient};
+
+/// One line of the submission log
+#[derive(Serialize)]
+struct SubmissionRecord<'a> {
+    /// Cluster time when the submission was attempted
+    timestamp: i64,
+    oracle_authority: String,
+    match_id: String,
+    /// "commit" or "reveal"
+    action: &'static str,
+    winning_team: u8,
+    final_score: &'a str,
+    attempt: u32,
+    /// "confirmed", "simulated" or "failed"
+    outcome: &'static str,
+    signature: Option<String>,
+    error: Option<String>,
+}
+
+/// Append-only JSON lines log of every submission attempt
+pub struct SubmissionLog {
+    file: Option<File>,
+}
+
+impl SubmissionLog {
+    /// Open the log at `path`, or log to stderr only when `path` is `None`
+    pub fn open(path: Option<&PathBuf>) -> Result<Self> {
+        let file = match path {
+            Some(path) => Some(
+                OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(path)
+                    .with_context(|| format!("Failed to open submission log {}", path.display()))?,
+            ),
+            None => None,
+        };
+        Ok(Self { file })
+    }
+
+    fn record(&mut self, record: &SubmissionRecord) {
+        let line = serde_json::to_string(record).expect("records always serialize");
+        log::info!("submission {}", line);
+
+        if let Some(file) = &mut self.file {
+            if let Err(err) = writeln!(file, "{}", line).and_then(|_| file.flush()) {
+                log::error!("Failed to write submission log: {}", err);
+            }
+        }
+    }
+}
+
+/// Where a match stands from the reporter's point of view
+#[derive(Debug, PartialEq, Eq)]
+enum Phase {
+    /// `end_time` has not passed yet
+    NotEnded,
+    /// The match ended but nobody has moved it to `Ended` yet
+    AwaitingEndedStatus,
+    /// Commitments are open
+    Committing,
+    /// Commitments are closed and reveals are open
+    Revealing,
+    /// `ORACLE_UPDATE_WINDOW` has passed
+    WindowClosed,
+    /// The match was settled or cancelled
+    Closed,
+}
+
+fn phase(match_state: &MatchState, now: i64) -> Phase {
+    match match_state.status {
+        MatchStatus::Settled | MatchStatus::Cancelled | MatchStatus::Voided => Phase::Closed,
+        _ if now < match_state.end_time => Phase::NotEnded,
+        _ if now > match_state.end_time.saturating_add(ORACLE_UPDATE_WINDOW) => Phase::WindowClosed,
+        MatchStatus::Ended if now > match_state.end_time.saturating_add(ORACLE_COMMIT_WINDOW) => {
+            Phase::Revealing
+        }
+        MatchStatus::Ended => Phase::Committing,
+        _ => Phase::AwaitingEndedStatus,
+    }
+}
+
+/// Watches the matches this oracle is registered for and reports their results
+pub struct Reporter {
+    client: TrendXBetClient,
+    source: Box<dyn ResultSource>,
+    log: SubmissionLog,
+    dry_run: bool,
+    /// Submission attempts per match and action
+    attempts: HashMap<(Pubkey, &'static str), u32>,
+    /// Matches that need no further attention
+    finished: HashSet<Pubkey>,
+    /// Notices already logged, so each is only printed once per match
+    notices: HashSet<(Pubkey, &'static str)>,
+}
+
+impl Reporter {
+    pub fn new(
+        client: TrendXBetClient,
+        source: Box<dyn ResultSource>,
+        log: SubmissionLog,
+        dry_run: bool,
+    ) -> Self {
+        Self {
+            client,
+            source,
+            log,
+            dry_run,
+            attempts: HashMap::new(),
+            finished: HashSet::new(),
+            notices: HashSet::new(),
+        }
+    }
+
+    /// Describe the reporter configuration for the startup log
+    pub fn describe(&self) -> String {
+        format!(
+            "oracle {} on {} reading results from {}",
+            self.client.payer_pubkey(),
+            self.client.rpc.url(),
+            self.source.describe()
+        )
+    }
+
+    /// Run one pass over every assigned match
+    pub fn poll(&mut self) -> Result<()> {
+        let oracle_authority = self.client.payer_pubkey();
+        let now = self.client.rpc.get_clock()?.unix_timestamp;
+        let assignments = self.client.oracle_assignments(&oracle_authority)?;
+
+        log::debug!("{} oracle assignments at t={}", assignments.len(), now);
+
+        for (_, oracle_state) in assignments {
+            let match_id = oracle_state.match_id;
+            if self.finished.contains(&match_id) {
+                continue;
+            }
+            if let Err(err) = self.process(&oracle_state, now) {
+                log::warn!("Match {}: {:#}", match_id, err);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn process(&mut self, oracle_state: &OracleState, now: i64) -> Result<()> {
+        let match_id = oracle_state.match_id;
+
+        if oracle_state.reported_result.is_some() {
+            self.notice(match_id, "reported", "result already reported on chain");
+            self.finished.insert(match_id);
+            return Ok(());
+        }
+
+        let match_state = self.client.match_state(&match_id)?;
+        match phase(&match_state, now) {
+            Phase::NotEnded => return Ok(()),
+            Phase::Closed => {
+                self.notice(
+                    match_id,
+                    "closed",
+                    "match settled or cancelled, nothing to report",
+                );
+                self.finished.insert(match_id);
+                return Ok(());
+            }
+            Phase::WindowClosed => {
+                log::error!(
+                    "Match {}: oracle update window closed at {} without a report",
+                    match_id,
+                    match_state.end_time.saturating_add(ORACLE_UPDATE_WINDOW)
+                );
+                self.finished.insert(match_id);
+                return Ok(());
+            }
+            Phase::AwaitingEndedStatus => {
+                // The crank is permissionless, so move the match along ourselves
+                self.crank(&match_state, now);
+                return Ok(());
+            }
+            Phase::Committing if oracle_state.commitment.is_some() => {
+                self.notice(match_id, "committed", "result committed, waiting to reveal");
+                return Ok(());
+            }
+            Phase::Revealing if oracle_state.commitment.is_none() => {
+                log::error!(
+                    "Match {}: commit window closed at {} without a commitment",
+                    match_id,
+                    match_state.end_time.saturating_add(ORACLE_COMMIT_WINDOW)
+                );
+                self.finished.insert(match_id);
+                return Ok(());
+            }
+            Phase::Committing | Phase::Revealing => {}
+        }
+
+        let Some(result) = self.source.fetch(&match_id)? else {
+            self.notice(
+                match_id,
+                "unpublished",
+                "result not yet published by source",
+            );
+            return Ok(());
+        };
+        result.validate()?;
+        let side = result.reported_side(&match_state)?;
+
+        // The reveal must repeat exactly what was committed, so the source is expected
+        // not to change its published result in between
+        let action = if oracle_state.commitment.is_none() {
+            "commit"
+        } else {
+            "reveal"
+        };
+        self.submit(match_id, action, &result, side, now)?;
+        Ok(())
+    }
+
+    /// Salt for `match_id`, derived from the oracle keypair so it survives restarts
+    /// without being guessable by anyone else
+    fn salt(&self, match_id: &Pubkey) -> [u8; 32] {
+        hashv(&[&self.client.payer.to_bytes(), match_id.as_ref()]).to_bytes()
+    }
+
+    fn crank(&mut self, match_state: &MatchState, now: i64) {
+        let match_id = match_state.match_id;
+        let new_status = match_state.crank_status(now).unwrap_or(MatchStatus::Ended);
+        // Indexed matches also move between status index pages
+        let (index_move, mut batch) = match self.client.status_index_move(match_state, &new_status)
+        {
+            Ok(pages) => pages,
+            Err(err) => {
+                log::warn!("Match {}: status index lookup failed: {:#}", match_id, err);
+                return;
+            }
+        };
+        batch.push(instructions::crank_match(
+            &self.client.program_id,
+            &self.client.payer_pubkey(),
+            &match_id,
+            index_move,
+        ));
+        match self.client.submit(&batch, &[], self.dry_run) {
+            Ok(Submission::Confirmed(signature)) => {
+                log::info!("Match {}: cranked to Ended ({})", match_id, signature)
+            }
+            Ok(Submission::Simulated(_)) => self.notice(
+                match_id,
+                "awaiting",
+                "end time passed, crank simulated (dry run)",
+            ),
+            // Someone else may have cranked first; the next poll re-reads the status
+            Err(err) => log::warn!("Match {}: crank failed: {:#}", match_id, err),
+        }
+    }
+
+    fn submit(
+        &mut self,
+        match_id: Pubkey,
+        action: &'static str,
+        result: &MatchResult,
+        side: u8,
+        now: i64,
+    ) -> Result<()> {
+        let oracle_authority = self.client.payer_pubkey();
+        let attempt = {
+            let attempts = self.attempts.entry((match_id, action)).or_insert(0);
+            *attempts += 1;
+            *attempts
+        };
+
+        let program_id = self.client.program_id;
+        let match_score = result.score.as_ref().map(|score| score.to_match_score());
+        let salt = self.salt(&match_id);
+        let evidence = result
+            .evidence
+            .as_ref()
+            .map(|evidence| evidence.to_report_evidence())
+            .transpose()?;
+        let instruction = if action == "commit" {
+            let commitment = OracleState::commitment(
+                &oracle_authority,
+                &match_id,
+                side,
+                &result.final_score,
+                &match_score,
+                &salt,
+            )?;
+            instructions::commit_match_result(&program_id, &oracle_authority, &match_id, commitment)
+        } else {
+            instructions::reveal_match_result(
+                &program_id,
+                &oracle_authority,
+                &match_id,
+                side,
+                result.final_score.clone(),
+                match_score,
+                salt,
+                evidence,
+            )
+        };
+
+        let (outcome, signature, error) =
+            match self.client.submit(&[instruction], &[], self.dry_run) {
+                // A confirmed commitment still has to be revealed on a later poll
+                Ok(Submission::Confirmed(signature)) => {
+                    if action == "reveal" {
+                        self.finished.insert(match_id);
+                    }
+                    ("confirmed", Some(signature.to_string()), None)
+                }
+                // A simulated commitment is never stored, so there is nothing to reveal
+                Ok(Submission::Simulated(simulation)) => {
+                    self.finished.insert(match_id);
+                    let error = simulation.err.map(|err| err.to_string());
+                    ("simulated", None, error)
+                }
+                // Left unfinished so the next poll retries while the window is open
+                Err(err) => ("failed", None, Some(format!("{:#}", err))),
+            };
+
+        self.log.record(&SubmissionRecord {
+            timestamp: now,
+            oracle_authority: oracle_authority.to_string(),
+            match_id: match_id.to_string(),
+            action,
+            winning_team: side,
+            final_score: &result.final_score,
+            attempt,
+            outcome,
+            signature,
+            error,
+        });
+        Ok(())
+    }
+
+    fn notice(&mut self, match_id: Pubkey, kind: &'static str, message: &str) {
+        if self.notices.insert((match_id, kind)) {
+            log::info!("Match {}: {}", match_id, message);
+        }
+    }
+}
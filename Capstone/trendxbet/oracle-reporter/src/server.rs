@@ -0,0 +1,50 @@
+use crate::source::load_result_book;
+use anyhow::{anyhow, Result};
+use std::path::PathBuf;
+use tiny_http::{Header, Response, Server};
+
+/// Serve a result file over HTTP in the format `HttpSource` expects, standing in
+/// for a real stats provider during local testing
+pub fn serve(file: PathBuf, bind: &str) -> Result<()> {
+    let server = Server::http(bind).map_err(|e| anyhow!("Failed to bind {}: {}", bind, e))?;
+    log::info!("Serving results from {} on http://{}", file.display(), bind);
+
+    let json_header =
+        Header::from_bytes("Content-Type", "application/json").expect("static header is valid");
+
+    for request in server.incoming_requests() {
+        let match_id = request
+            .url()
+            .strip_prefix("/results/")
+            .map(|id| id.trim_end_matches('/').to_string());
+
+        let response = match match_id {
+            None => Response::from_string("not found").with_status_code(404),
+            Some(match_id) => match load_result_book(&file) {
+                Ok(book) => match book.get(&match_id) {
+                    Some(result) => Response::from_string(
+                        serde_json::to_string(result).expect("results always serialize"),
+                    )
+                    .with_header(json_header.clone()),
+                    None => Response::from_string("result not published").with_status_code(404),
+                },
+                Err(err) => {
+                    log::error!("{:#}", err);
+                    Response::from_string("result file unreadable").with_status_code(500)
+                }
+            },
+        };
+
+        log::debug!(
+            "{} {} -> {}",
+            request.method(),
+            request.url(),
+            response.status_code().0
+        );
+        if let Err(err) = request.respond(response) {
+            log::warn!("Failed to respond: {}", err);
+        }
+    }
+
+    Ok(())
+}
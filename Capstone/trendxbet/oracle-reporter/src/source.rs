@@ -0,0 +1,228 @@
+use anyhow::{anyhow, bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use solana_sdk::hash::hash;
+use solana_sdk::pubkey::Pubkey;
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+use trendxbet::{
+    MatchScore, MatchState, PeriodScore, ReportEvidence, MAX_EVIDENCE_URI_LENGTH, MAX_SCORE_LENGTH,
+    MAX_SCORE_PERIODS, PUSH_RESULT,
+};
+
+/// A match result as published by a result source
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MatchResult {
+    /// Winning team (0 = team1, 1 = team2)
+    pub winning_team: u8,
+    /// Final score as displayed to users
+    pub final_score: String,
+    /// Structured score, required for totals and spread markets
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub score: Option<ScoreReport>,
+    /// Source the result was taken from, attached to the reveal
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub evidence: Option<EvidenceReport>,
+}
+
+/// Where a result was published and a hash of what was published there
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EvidenceReport {
+    pub uri: String,
+    /// Hex-encoded SHA-256 of the source content
+    pub sha256: String,
+}
+
+impl EvidenceReport {
+    /// Evidence for `content` fetched from `uri`
+    pub fn of(uri: impl Into<String>, content: &[u8]) -> Self {
+        Self {
+            uri: uri.into(),
+            sha256: hash(content)
+                .to_bytes()
+                .iter()
+                .map(|byte| format!("{:02x}", byte))
+                .collect(),
+        }
+    }
+
+    /// The evidence in the program's representation
+    pub fn to_report_evidence(&self) -> Result<ReportEvidence> {
+        if self.sha256.len() != 64 || !self.sha256.is_ascii() {
+            bail!("evidence sha256 must be 64 hex characters");
+        }
+        let mut content_hash = [0u8; 32];
+        for (i, byte) in content_hash.iter_mut().enumerate() {
+            *byte = u8::from_str_radix(&self.sha256[2 * i..2 * i + 2], 16)
+                .with_context(|| format!("evidence sha256 is not hex: {}", self.sha256))?;
+        }
+        Ok(ReportEvidence {
+            uri: self.uri.clone(),
+            content_hash,
+        })
+    }
+}
+
+/// Structured score as published by a result source; home is team 1
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScoreReport {
+    pub home: u16,
+    pub away: u16,
+    /// Per-period `[home, away]` points
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub periods: Vec<[u16; 2]>,
+}
+
+impl ScoreReport {
+    /// The score in the program's representation
+    pub fn to_match_score(&self) -> MatchScore {
+        MatchScore {
+            home: self.home,
+            away: self.away,
+            periods: self
+                .periods
+                .iter()
+                .map(|[home, away]| PeriodScore {
+                    home: *home,
+                    away: *away,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl MatchResult {
+    /// Reject results the program would refuse anyway
+    pub fn validate(&self) -> Result<()> {
+        if self.winning_team > 1 {
+            bail!("winning_team must be 0 or 1, got {}", self.winning_team);
+        }
+        if self.final_score.len() > MAX_SCORE_LENGTH {
+            bail!(
+                "final_score is {} bytes, maximum is {}",
+                self.final_score.len(),
+                MAX_SCORE_LENGTH
+            );
+        }
+        if let Some(score) = &self.score {
+            if score.periods.len() > MAX_SCORE_PERIODS {
+                bail!(
+                    "score has {} periods, maximum is {}",
+                    score.periods.len(),
+                    MAX_SCORE_PERIODS
+                );
+            }
+        }
+        if let Some(evidence) = &self.evidence {
+            if evidence.uri.is_empty() || evidence.uri.len() > MAX_EVIDENCE_URI_LENGTH {
+                bail!(
+                    "evidence uri must be 1 to {} bytes",
+                    MAX_EVIDENCE_URI_LENGTH
+                );
+            }
+            evidence.to_report_evidence()?;
+        }
+        Ok(())
+    }
+
+    /// Side to report for `match_state`: line markets settle on what the score
+    /// implies rather than on the winner, `PUSH_RESULT` when it lands on the line
+    pub fn reported_side(&self, match_state: &MatchState) -> Result<u8> {
+        if !match_state.is_line_market() {
+            return Ok(self.winning_team);
+        }
+        let score = self
+            .score
+            .as_ref()
+            .ok_or_else(|| anyhow!("line markets need a structured score"))?;
+        Ok(match_state
+            .score_outcome(&score.to_match_score())
+            .unwrap_or(PUSH_RESULT))
+    }
+}
+
+/// Where the reporter looks up match results
+pub trait ResultSource {
+    /// Short description used in logs
+    fn describe(&self) -> String;
+
+    /// Look up the result for `match_id`, returning `None` if it is not published yet
+    fn fetch(&self, match_id: &Pubkey) -> Result<Option<MatchResult>>;
+}
+
+/// Results keyed by match id, the format shared by the JSON file source and the stand-in server
+pub type ResultBook = HashMap<String, MatchResult>;
+
+/// Read a result book from disk
+pub fn load_result_book(path: &PathBuf) -> Result<ResultBook> {
+    let contents =
+        fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    serde_json::from_str(&contents)
+        .with_context(|| format!("Invalid result file {}", path.display()))
+}
+
+/// Reads results from a JSON file of `{ "<match_id>": { winning_team, final_score, score? } }`,
+/// re-reading it on every lookup so operators can edit it while the daemon runs
+pub struct JsonFileSource {
+    path: PathBuf,
+}
+
+impl JsonFileSource {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+}
+
+impl ResultSource for JsonFileSource {
+    fn describe(&self) -> String {
+        format!("file {}", self.path.display())
+    }
+
+    fn fetch(&self, match_id: &Pubkey) -> Result<Option<MatchResult>> {
+        Ok(load_result_book(&self.path)?.remove(&match_id.to_string()))
+    }
+}
+
+/// Reads results from `GET {base_url}/results/{match_id}`, where 404 means not published yet.
+/// Results published without evidence cite the URL and a hash of the response body
+pub struct HttpSource {
+    base_url: String,
+    agent: ureq::Agent,
+}
+
+impl HttpSource {
+    pub fn new(base_url: impl Into<String>) -> Self {
+        Self {
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            agent: ureq::AgentBuilder::new()
+                .timeout(Duration::from_secs(10))
+                .build(),
+        }
+    }
+}
+
+impl ResultSource for HttpSource {
+    fn describe(&self) -> String {
+        format!("http {}", self.base_url)
+    }
+
+    fn fetch(&self, match_id: &Pubkey) -> Result<Option<MatchResult>> {
+        let url = format!("{}/results/{}", self.base_url, match_id);
+        match self.agent.get(&url).call() {
+            Ok(response) => {
+                let body = response
+                    .into_string()
+                    .with_context(|| format!("Failed to read result from {}", url))?;
+                let mut result: MatchResult = serde_json::from_str(&body)
+                    .with_context(|| format!("Invalid result JSON from {}", url))?;
+                if result.evidence.is_none() {
+                    result.evidence = Some(EvidenceReport::of(url, body.as_bytes()));
+                }
+                Ok(Some(result))
+            }
+            Err(ureq::Error::Status(404, _)) => Ok(None),
+            Err(err) => Err(anyhow!("Request to {} failed: {}", url, err)),
+        }
+    }
+}
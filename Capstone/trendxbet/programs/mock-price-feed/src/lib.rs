@@ -0,0 +1,93 @@
+//! Test-only price feed that writes accounts in the Pyth price account layout,
+//! so price-trend markets can be exercised on localnet and in tests.
+//!
+//! Anyone may set the price of any account this program owns. Create the
+//! account with the system program (at least `PRICE_ACCOUNT_SIZE` bytes, owned
+//! by this program) and call `set_price` to publish.
+
+#![allow(unexpected_cfgs)]
+#![allow(deprecated)]
+
+use anchor_lang::prelude::*;
+
+declare_id!("DZi4b5YPnLtvERQyk5vKyd4t2nvtv2VBabarz5YF3NB6");
+
+/// Bytes written by the mock, up to the end of the aggregate price
+pub const PRICE_ACCOUNT_SIZE: usize = 240;
+
+/// Aggregate price status codes, as published by Pyth
+pub const STATUS_UNKNOWN: u32 = 0;
+pub const STATUS_TRADING: u32 = 1;
+pub const STATUS_HALTED: u32 = 2;
+
+const MAGIC: u32 = 0xa1b2_c3d4;
+const VERSION: u32 = 2;
+const ACCOUNT_TYPE_PRICE: u32 = 3;
+
+/// Price published to a mock feed; the value is `price * 10^expo`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PriceUpdate {
+    pub price: i64,
+    pub conf: u64,
+    pub expo: i32,
+    pub publish_time: i64,
+    pub status: u32,
+}
+
+#[program]
+pub mod mock_price_feed {
+    use super::*;
+
+    pub fn set_price(ctx: Context<SetPrice>, update: PriceUpdate) -> Result<()> {
+        let mut data = ctx.accounts.price_account.try_borrow_mut_data()?;
+        write_price_account(&mut data, &update)?;
+
+        msg!("Mock price set: {}e{}", update.price, update.expo);
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct SetPrice<'info> {
+    /// CHECK: Raw Pyth-layout account, written byte for byte
+    #[account(mut, owner = crate::ID)]
+    pub price_account: UncheckedAccount<'info>,
+
+    pub authority: Signer<'info>,
+}
+
+#[error_code]
+pub enum MockPriceFeedError {
+    #[msg("Price account is smaller than the Pyth price layout")]
+    AccountTooSmall,
+}
+
+/// Write `update` into `data` using the Pyth v2 price account layout
+pub fn write_price_account(data: &mut [u8], update: &PriceUpdate) -> Result<()> {
+    require!(
+        data.len() >= PRICE_ACCOUNT_SIZE,
+        MockPriceFeedError::AccountTooSmall
+    );
+
+    put(data, 0, &MAGIC.to_le_bytes());
+    put(data, 4, &VERSION.to_le_bytes());
+    put(data, 8, &ACCOUNT_TYPE_PRICE.to_le_bytes());
+    put(data, 12, &(PRICE_ACCOUNT_SIZE as u32).to_le_bytes());
+    put(data, 20, &update.expo.to_le_bytes());
+    put(data, 96, &update.publish_time.to_le_bytes());
+    put(data, 208, &update.price.to_le_bytes());
+    put(data, 216, &update.conf.to_le_bytes());
+    put(data, 224, &update.status.to_le_bytes());
+    Ok(())
+}
+
+/// Build a fresh price account holding `update`
+pub fn price_account_data(update: &PriceUpdate) -> Vec<u8> {
+    let mut data = vec![0; PRICE_ACCOUNT_SIZE];
+    write_price_account(&mut data, update).expect("buffer is large enough");
+    data
+}
+
+fn put(data: &mut [u8], offset: usize, bytes: &[u8]) {
+    data[offset..offset + bytes.len()].copy_from_slice(bytes);
+}
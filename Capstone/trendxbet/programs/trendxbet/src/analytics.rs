@@ -0,0 +1,66 @@
+use crate::constants::*;
+
+/// Deterministic integer analytics shared by the program and off-chain clients.
+/// Ratios are returned in basis points (10_000 = 100%) and rounded down.
+pub struct Analytics;
+
+impl Analytics {
+    /// `numerator / denominator` in basis points, zero when the denominator is zero.
+    /// Computed exactly in `u128` so ratios such as 3/5 do not lose a basis point
+    /// to binary rounding.
+    pub fn ratio_bps(numerator: u64, denominator: u64) -> u64 {
+        if denominator == 0 {
+            return 0;
+        }
+        let bps = numerator as u128 * BASIS_POINTS as u128 / denominator as u128;
+        u64::try_from(bps).unwrap_or(u64::MAX)
+    }
+
+    /// Signed `numerator / denominator` in basis points, zero when the denominator is zero
+    pub fn signed_ratio_bps(numerator: i128, denominator: u64) -> i64 {
+        let magnitude = u64::try_from(numerator.unsigned_abs()).unwrap_or(u64::MAX);
+        let bps = i64::try_from(Self::ratio_bps(magnitude, denominator)).unwrap_or(i64::MAX);
+        if numerator < 0 {
+            -bps
+        } else {
+            bps
+        }
+    }
+
+    /// Share of bets won
+    pub fn win_rate_bps(bets_won: u64, bets_placed: u64) -> u64 {
+        Self::ratio_bps(bets_won, bets_placed)
+    }
+
+    /// Return on investment: `(winnings - volume) / volume`, negative for a net loss
+    pub fn roi_bps(total_winnings: u64, total_volume: u64) -> i64 {
+        Self::signed_ratio_bps(total_winnings as i128 - total_volume as i128, total_volume)
+    }
+
+    /// Mean of the odds accepted across `bet_count` bets
+    pub fn average_odds_bps(total_odds_bps: u64, bet_count: u64) -> u64 {
+        total_odds_bps.checked_div(bet_count).unwrap_or(0)
+    }
+
+    /// Share of deposits committed to pending payouts
+    pub fn utilization_bps(pending_payouts: u64, total_deposits: u64) -> u64 {
+        Self::ratio_bps(pending_payouts, total_deposits)
+    }
+
+    /// How many times the net treasury balance covers pending payouts;
+    /// `None` when nothing is pending
+    pub fn coverage_ratio_bps(net_balance: i64, pending_payouts: u64) -> Option<u64> {
+        if pending_payouts == 0 {
+            return None;
+        }
+        Some(Self::ratio_bps(net_balance.max(0) as u64, pending_payouts))
+    }
+
+    /// Oracle confidence: confirmations over the required minimum, capped at 100%
+    pub fn confidence_bps(confirmations: u8, required_confirmations: u8) -> u64 {
+        if required_confirmations == 0 {
+            return BASIS_POINTS;
+        }
+        Self::ratio_bps(confirmations as u64, required_confirmations as u64).min(BASIS_POINTS)
+    }
+}
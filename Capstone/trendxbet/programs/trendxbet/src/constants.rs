@@ -1,3 +1,5 @@
+use anchor_lang::prelude::*;
+
 // Platform Constants
 pub const PLATFORM_SEED: &[u8] = b"platform";
 pub const USER_SEED: &[u8] = b"user";
@@ -5,28 +7,108 @@ pub const MATCH_SEED: &[u8] = b"match";
 pub const BET_SEED: &[u8] = b"bet";
 pub const TREASURY_SEED: &[u8] = b"treasury";
 pub const ORACLE_SEED: &[u8] = b"oracle";
+pub const PRICE_MARKET_SEED: &[u8] = b"price_market";
+pub const ORACLE_PROFILE_SEED: &[u8] = b"oracle_profile";
+pub const DISPUTE_SEED: &[u8] = b"dispute";
+pub const ARBITER_SET_SEED: &[u8] = b"arbiters";
+pub const MATCH_REGISTRY_SEED: &[u8] = b"match_registry";
+pub const MATCH_INDEX_SEED: &[u8] = b"match_index";
+pub const USERNAME_SEED: &[u8] = b"username";
+pub const SESSION_SEED: &[u8] = b"session";
+pub const PROMOTER_SEED: &[u8] = b"promoter";
+pub const PRIVATE_POOL_SEED: &[u8] = b"private_pool";
+pub const ODDS_HISTORY_SEED: &[u8] = b"odds_history";
+pub const AUDIT_LOG_SEED: &[u8] = b"audit_log";
 
 // Platform Configuration
 pub const MAX_USERNAME_LENGTH: usize = 32;
 pub const MAX_TEAM_NAME_LENGTH: usize = 64;
 pub const MAX_DESCRIPTION_LENGTH: usize = 256;
 pub const MAX_SCORE_LENGTH: usize = 32;
+pub const MAX_EVIDENCE_URI_LENGTH: usize = 200;
+pub const MAX_ASSET_LENGTH: usize = 16;
+pub const MAX_SCORE_PERIODS: usize = 4;
 
 // Betting Configuration
 pub const DEFAULT_HOUSE_EDGE: u16 = 500; // 5% (in basis points)
 pub const MIN_BET_AMOUNT: u64 = 1_000_000; // 0.001 SOL
 pub const MAX_BET_AMOUNT: u64 = 100_000_000_000; // 100 SOL
 pub const BASIS_POINTS: u64 = 10_000;
+pub const MAX_CANCELLATION_FEE: u16 = 1_000; // 10% ceiling on the fee kept from withdrawn stakes
 
 // Oracle Configuration
 pub const MIN_ORACLE_CONFIRMATIONS: u8 = 2;
-pub const ORACLE_UPDATE_WINDOW: i64 = 3600; // 1 hour in seconds
+pub const ORACLE_UPDATE_WINDOW: i64 = 3600; // 1 hour in seconds, commits then reveals
+pub const ORACLE_COMMIT_WINDOW: i64 = 1800; // first 30 minutes of the update window take commitments only
 pub const MAX_ORACLE_DEVIATION: u64 = 1000; // 10% in basis points
+pub const PUSH_RESULT: u8 = 2; // reported when a line market lands exactly on its line
+
+// Oracle Staking Configuration
+pub const MIN_ORACLE_STAKE: u64 = 1_000_000_000; // 1 SOL bonded to be registered for a match
+pub const ORACLE_UNBONDING_PERIOD: i64 = 604_800; // 7 days, well past the settlement window
+pub const ORACLE_FEE_SHARE_BPS: u64 = 2_000; // 20% of a match's fees, split across its revealed reports
+pub const ORACLE_SLASH_BPS: u64 = 1_000; // 10% of bonded stake for a report against consensus
+pub const ORACLE_INITIAL_REPUTATION: u16 = 5_000; // reputation is in basis points
+pub const MIN_ORACLE_REPUTATION: u16 = 2_000;
+pub const ORACLE_REPUTATION_GAIN: u16 = 100;
+pub const ORACLE_REPUTATION_PENALTY: u16 = 1_000;
+
+// Dispute Configuration
+pub const DEFAULT_DISPUTE_WINDOW: i64 = 3600; // 1 hour to challenge an oracle result
+pub const MAX_DISPUTE_WINDOW: i64 = 259_200; // 3 days
+pub const DEFAULT_DISPUTE_BOND: u64 = 500_000_000; // 0.5 SOL
+pub const DISPUTE_VOTING_PERIOD: i64 = 259_200; // 3 days for the arbiters to reach quorum
+pub const MAX_ARBITERS: usize = 7;
+
+// Match Registry Configuration
+pub const MATCH_INDEX_PAGE_SIZE: usize = 32; // matches listed per index page
+pub const SECONDS_PER_DAY: i64 = 86_400; // start-day indexes bucket matches by UTC day
+
+// Session Key Configuration
+pub const MAX_SESSION_DURATION: i64 = 604_800; // 7 days
+pub const SESSION_PLACE_BET: u8 = 1 << 0;
+pub const SESSION_CANCEL_BET: u8 = 1 << 1;
+pub const SESSION_PERMISSIONS: u8 = SESSION_PLACE_BET | SESSION_CANCEL_BET; // withdrawals are never delegated
+
+// Promotions Configuration
+pub const MAX_WAGERING_MULTIPLIER: u8 = 50; // most times a bonus must be staked before its winnings unlock
+
+// Community Market Configuration
+pub const DEFAULT_CREATOR_BOND: u64 = 1_000_000_000; // 1 SOL, refunded unless the market is voided for abuse
+pub const DEFAULT_MAX_CREATOR_FEE: u16 = 200; // 2% of the pool, in basis points
+pub const MAX_CREATOR_FEE: u16 = 1_000; // 10% ceiling on the configurable creator fee cap
+
+// Private Pool Configuration
+pub const MAX_POOL_MEMBERS: usize = 32; // invited bettors per private pool, besides the organiser
+
+// Odds History Configuration
+pub const ODDS_HISTORY_CAPACITY: usize = 64; // points kept per match before the oldest is overwritten
+pub const ODDS_HISTORY_PAGE_SIZE: usize = 24; // points returned per read, within the 1 KiB return data limit
+
+// Audit Log Configuration
+pub const AUDIT_LOG_PAGE_SIZE: usize = 32; // privileged actions recorded per audit log page
+
+// Line Market Configuration
+pub const LINE_SCALE: i64 = 10; // lines are quoted in tenths of a point
+
+// Crank Configuration
+pub const DEFAULT_CRANK_BOUNTY: u64 = 100_000; // 0.0001 SOL
+pub const MAX_CRANK_BOUNTY: u64 = 10_000_000; // 0.01 SOL
+
+// Price Feed Configuration
+pub const PYTH_ORACLE_PROGRAM_ID: Pubkey = pubkey!("FsJ3A3u2vn5cTVofAjvy6y5kwABJAqYWpe4975bi2epH");
+pub const PRICE_RESOLUTION_TOLERANCE: i64 = 300; // 5 minutes after resolution time to publish the price
+pub const MAX_PRICE_CONFIDENCE: u64 = 100; // 1% of the price, in basis points
+
+// Treasury Configuration
+pub const MAX_HEALTHY_UTILIZATION: u64 = 8_000; // 80% in basis points
 
 // Time Configuration
 pub const MIN_BETTING_DURATION: i64 = 3600; // 1 hour minimum betting period
 pub const MAX_BETTING_DURATION: i64 = 2_592_000; // 30 days maximum betting period
 pub const SETTLEMENT_WINDOW: i64 = 86_400; // 24 hours settlement window
+pub const LIVE_BETTING_WINDOW: i64 = 900; // 15 minutes of live betting before the crank closes it
+pub const MAX_EARLY_BET_BONUS: u16 = 5_000; // 50% extra pool weight for the earliest bets, in basis points
 
 // Account Space Calculations
 pub const GLOBAL_STATE_SPACE: usize = 8 + // discriminator
@@ -38,7 +120,24 @@ pub const GLOBAL_STATE_SPACE: usize = 8 + // discriminator
     8 + // total_fees_collected
     8 + // platform_created_at
     1 + // is_paused
-    1; // bump
+    1 + // bump
+    1 + // version
+    8 + // crank_bounty
+    32 + // price_feed_program
+    8 + // dispute_window
+    8 + // dispute_bond
+    EXPOSURE_LIMITS_SPACE + // exposure_limits
+    4 + // betting_cutoff
+    2 + // early_bet_bonus
+    8 + // creator_bond
+    2 + // max_creator_fee
+    2 + // cancellation_fee
+    1 + // has_audit_log
+    8; // audit_entries
+
+pub const EXPOSURE_LIMITS_SPACE: usize = 8 + // max_match_pool
+    8 + // max_pool_imbalance
+    8; // max_user_match_stake
 
 pub const USER_STATE_SPACE: usize = 8 + // discriminator
     32 + // authority
@@ -50,7 +149,20 @@ pub const USER_STATE_SPACE: usize = 8 + // discriminator
     8 + // total_winnings
     8 + // profile_created_at
     1 + // bump
-    32; // reserved
+    8 + // total_odds_accepted
+    1 + // version
+    1 + // has_username_record
+    8 + // bonus_balance
+    8 + // wagering_remaining
+    8 + // locked_bonus_winnings
+    22; // reserved
+
+pub const MATCH_SCORE_SPACE: usize = 2 + // home
+    2 + // away
+    4 + MAX_SCORE_PERIODS * (2 + 2); // periods
+
+pub const REPORT_EVIDENCE_SPACE: usize = 4 + MAX_EVIDENCE_URI_LENGTH + // uri
+    32; // content_hash
 
 pub const MATCH_STATE_SPACE: usize = 8 + // discriminator
     32 + // match_id (Pubkey)
@@ -64,14 +176,38 @@ pub const MATCH_STATE_SPACE: usize = 8 + // discriminator
     8 + // team1_pool
     8 + // team2_pool
     8 + // total_bets
-    1 + // winning_team (optional)
-    4 + MAX_SCORE_LENGTH + // final_score (optional)
-    8 + // oracle_result_time
+    1 + 1 + // winning_team (optional)
+    1 + 4 + MAX_SCORE_LENGTH + // final_score (optional)
+    1 + 8 + // oracle_result_time (optional)
     1 + // is_betting_closed
     1 + // is_settled
     8 + // match_created_at
     1 + // bump
-    64; // reserved
+    1 + // version
+    1 + // oracle_reports
+    1 + // market_kind
+    4 + // line
+    1 + MATCH_SCORE_SPACE + // match_score (optional)
+    8 + // platform_fees
+    8 + // dispute_deadline
+    1 + // is_disputed
+    8 + // betting_opens_at
+    8 + // betting_closes_at
+    2 + // early_bet_bonus
+    8 + // team1_bonus_weight
+    8 + // team2_bonus_weight
+    4 + // league
+    1 + // is_indexed
+    32 + // creator
+    8 + // creator_bond
+    2 + // creator_fee
+    1 + // is_creator_settled
+    1 + // is_private
+    1 + // waives_house_edge
+    1 + // has_odds_history
+    1 + // is_result_overturned
+    1 + // is_crank_bounty_paid
+    5; // reserved
 
 pub const BET_STATE_SPACE: usize = 8 + // discriminator
     32 + // bettor
@@ -82,10 +218,15 @@ pub const BET_STATE_SPACE: usize = 8 + // discriminator
     8 + // potential_payout
     1 + // status
     8 + // bet_placed_at
-    8 + // settled_at (optional)
-    8 + // payout_amount (optional)
+    1 + 8 + // settled_at (optional)
+    1 + 8 + // payout_amount (optional)
     1 + // bump
-    32; // reserved
+    8 + // platform_fee
+    1 + // version
+    8 + // bonus_weight
+    8 + // bonus_stake
+    8 + // locked_stake
+    7; // reserved
 
 pub const TREASURY_STATE_SPACE: usize = 8 + // discriminator
     32 + // authority
@@ -95,16 +236,162 @@ pub const TREASURY_STATE_SPACE: usize = 8 + // discriminator
     8 + // pending_payouts
     8 + // last_fee_collection
     1 + // bump
-    64; // reserved
+    1 + // version
+    8 + // unearned_fees
+    55; // reserved
 
 pub const ORACLE_STATE_SPACE: usize = 8 + // discriminator
     32 + // oracle_authority
     32 + // match_id
-    1 + // reported_result
-    4 + MAX_SCORE_LENGTH + // reported_score
-    8 + // report_time
+    1 + 1 + // reported_result (optional)
+    1 + 4 + MAX_SCORE_LENGTH + // reported_score (optional)
+    1 + 8 + // report_time (optional)
     1 + // is_validated
-    8 + // validation_time
-    4 + // confirmations
+    1 + 8 + // validation_time (optional)
+    1 + // confirmations
+    1 + // bump
+    1 + // version
+    1 + MATCH_SCORE_SPACE + // reported_match_score (optional)
+    1 + 32 + // commitment (optional)
+    1 + 8 + // commit_time (optional)
+    1 + // is_resolved
+    1 + REPORT_EVIDENCE_SPACE + // evidence (optional)
+    31; // reserved
+
+pub const PRICE_MARKET_SPACE: usize = 8 + // discriminator
+    32 + // match_id
+    32 + // price_feed
+    32 + // feed_program
+    4 + MAX_ASSET_LENGTH + // asset
+    8 + // strike_price
+    4 + // expo
+    1 + 8 + // resolved_price (Option<i64>)
+    1 + 8 + // resolved_publish_time (Option<i64>)
+    1 + // bump
+    1 + // version
+    32; // reserved
+
+pub const ORACLE_PROFILE_SPACE: usize = 8 + // discriminator
+    32 + // authority
+    8 + // stake
+    8 + // unbonding_amount
+    8 + // unbonding_at
+    2 + // reputation
+    4 + // reports_agreed
+    4 + // reports_slashed
+    8 + // total_rewards
+    8 + // total_slashed
+    8 + // created_at
+    1 + // bump
+    1 + // version
+    32; // reserved
+
+pub const ARBITER_SET_SPACE: usize = 8 + // discriminator
+    4 + MAX_ARBITERS * 32 + // arbiters
+    1 + // quorum
     1 + // bump
+    1 + // version
     32; // reserved
+
+pub const DISPUTE_STATE_SPACE: usize = 8 + // discriminator
+    32 + // match_id
+    32 + // challenger
+    8 + // bond
+    1 + // original_team
+    1 + // proposed_team
+    1 + // status
+    1 + // votes_uphold
+    1 + // votes_reject
+    4 + MAX_ARBITERS * 32 + // voters
+    8 + // opened_at
+    1 + 8 + // resolved_at (optional)
+    1 + // bump
+    1 + // version
+    32; // reserved
+
+pub const MATCH_REGISTRY_SPACE: usize = 8 + // discriminator
+    8 + // total_matches
+    1 + // bump
+    1 + // version
+    32; // reserved
+
+pub const MATCH_INDEX_KEY_SPACE: usize = 1 + 8; // variant tag and largest value
+
+pub const MATCH_INDEX_PAGE_SPACE: usize = 8 + // discriminator
+    MATCH_INDEX_KEY_SPACE + // key
+    4 + // page
+    4 + MATCH_INDEX_PAGE_SIZE * 32 + // matches
+    1 + // bump
+    1 + // version
+    16; // reserved
+
+pub const USERNAME_RECORD_SPACE: usize = 8 + // discriminator
+    32 + // authority
+    32 + // user_state
+    4 + MAX_USERNAME_LENGTH + // username
+    8 + // claimed_at
+    1 + // bump
+    1 + // version
+    16; // reserved
+
+pub const SESSION_KEY_SPACE: usize = 8 + // discriminator
+    32 + // authority
+    32 + // session_key
+    8 + // expires_at
+    8 + // max_bet_amount
+    8 + // spend_limit
+    8 + // spent
+    1 + // permissions
+    8 + // created_at
+    1 + // bump
+    1 + // version
+    16; // reserved
+
+pub const PROMOTER_SPACE: usize = 8 + // discriminator
+    32 + // authority
+    8 + // total_issued
+    8 + // bonuses_issued
+    8 + // added_at
+    1 + // bump
+    1 + // version
+    16; // reserved
+
+pub const PRIVATE_POOL_SPACE: usize = 8 + // discriminator
+    32 + // match_id
+    32 + // organiser
+    1 + 32 + // invite_code_hash (optional)
+    4 + 32 * MAX_POOL_MEMBERS + // members
+    8 + // created_at
+    1 + // bump
+    1 + // version
+    16; // reserved
+
+pub const ODDS_POINT_SPACE: usize = 8 + // timestamp
+    8 + // team1_pool
+    8 + // team2_pool
+    8 + // team1_odds
+    8; // team2_odds
+
+pub const AUDIT_ENTRY_SPACE: usize = 8 + // sequence
+    1 + // action
+    32 + // actor
+    1 + 32 + // target
+    32 + // params_hash
+    8; // timestamp
+
+pub const AUDIT_LOG_PAGE_SPACE: usize = 8 + // discriminator
+    4 + // page
+    4 + AUDIT_ENTRY_SPACE * AUDIT_LOG_PAGE_SIZE + // entries
+    1 + // bump
+    1 + // version
+    16; // reserved
+
+pub const ODDS_HISTORY_SPACE: usize = 8 + // discriminator
+    32 + // match_id
+    ODDS_POINT_SPACE * ODDS_HISTORY_CAPACITY + // points
+    2 + // head
+    2 + // len
+    8 + // created_at
+    1 + // bump
+    1 + // version
+    16; // reserved
@@ -38,7 +38,7 @@ pub enum TrendXBetError {
     #[msg("Bet not found or already settled")]
     BetNotFound,
 
-    #[msg("Cannot cancel bet - match has started")]
+    #[msg("Cannot cancel bet - betting has closed")]
     CannotCancelBet,
 
     #[msg("Oracle update window expired")]
@@ -121,4 +121,276 @@ pub enum TrendXBetError {
 
     #[msg("Emergency withdrawal not authorized")]
     EmergencyWithdrawalNotAuthorized,
+
+    #[msg("Unsupported account layout version - outdated accounts must be migrated first")]
+    UnsupportedAccountVersion,
+
+    #[msg("Account is already on the current layout")]
+    AccountAlreadyMigrated,
+
+    #[msg("Match status is already up to date")]
+    NothingToCrank,
+
+    #[msg("Crank bounty exceeds maximum")]
+    CrankBountyTooHigh,
+
+    #[msg("Oracle deadline has not passed - match cannot be voided yet")]
+    VoidDeadlineNotReached,
+
+    #[msg("Match is not voided or cancelled")]
+    MatchNotVoided,
+
+    #[msg("Operation does not apply to this market type")]
+    InvalidMarketType,
+
+    #[msg("Asset name must be 1 to 16 characters")]
+    AssetNameTooLong,
+
+    #[msg("Invalid price feed account")]
+    InvalidPriceFeed,
+
+    #[msg("Price feed is not trading")]
+    PriceFeedUnavailable,
+
+    #[msg("Price was not published within the resolution window")]
+    PriceNotAtResolutionTime,
+
+    #[msg("Price confidence interval is too wide")]
+    PriceConfidenceTooWide,
+
+    #[msg("Line markets require a structured score")]
+    ScoreRequired,
+
+    #[msg("Reported result does not follow from the reported score")]
+    ScoreMismatch,
+
+    #[msg("Score has too many periods")]
+    InvalidScore,
+
+    #[msg("Invalid line for this market type")]
+    InvalidLine,
+
+    #[msg("Oracle commit window has closed")]
+    CommitWindowClosed,
+
+    #[msg("Oracle reveals are not open yet")]
+    RevealWindowNotOpen,
+
+    #[msg("Oracle has not committed a result")]
+    OracleNotCommitted,
+
+    #[msg("Revealed result does not match the commitment")]
+    CommitmentMismatch,
+
+    #[msg("Oracle does not have enough stake bonded")]
+    InsufficientOracleStake,
+
+    #[msg("Oracle reputation is too low to be registered")]
+    OracleReputationTooLow,
+
+    #[msg("Oracle stake is still unbonding")]
+    StakeStillUnbonding,
+
+    #[msg("Oracles have not reached consensus on this match")]
+    NoOracleConsensus,
+
+    #[msg("Oracle report has already been resolved")]
+    OracleReportAlreadyResolved,
+    #[msg("Result can still be disputed")]
+    DisputeWindowOpen,
+
+    #[msg("Dispute window has closed")]
+    DisputeWindowClosed,
+
+    #[msg("Result is under dispute")]
+    ResultUnderDispute,
+
+    #[msg("Signer is not an arbiter")]
+    NotAnArbiter,
+
+    #[msg("Arbiter has already voted on this dispute")]
+    AlreadyVoted,
+
+    #[msg("Invalid arbiter set")]
+    InvalidArbiterSet,
+
+    #[msg("Dispute is not open")]
+    DisputeNotOpen,
+
+    #[msg("Invalid oracle report evidence")]
+    InvalidEvidence,
+
+    #[msg("Bet would take the match pool over its cap")]
+    MatchPoolCapExceeded,
+
+    #[msg("Bet would leave the outcome pools too imbalanced")]
+    PoolImbalanceCapExceeded,
+
+    #[msg("Bet would take the user's stake on this match over its cap")]
+    UserMatchStakeCapExceeded,
+
+    #[msg("Early bet bonus is too high")]
+    InvalidEarlyBetBonus,
+
+    #[msg("Betting window cannot change once bets are placed")]
+    BettingWindowLocked,
+
+    #[msg("Match index page is full")]
+    IndexPageFull,
+
+    #[msg("Match index page does not match the match")]
+    MatchIndexMismatch,
+
+    #[msg("Match index pages are required")]
+    MatchIndexRequired,
+
+    #[msg("Previous match index page is not full")]
+    IndexPageNotFull,
+
+    #[msg("Username record is required")]
+    UsernameRecordRequired,
+
+    #[msg("Username record does not belong to the profile")]
+    UsernameRecordMismatch,
+
+    #[msg("Invalid session key configuration")]
+    InvalidSessionConfig,
+
+    #[msg("Session key has expired")]
+    SessionExpired,
+
+    #[msg("Session key is not permitted this instruction")]
+    SessionNotPermitted,
+
+    #[msg("Session key spend limit exceeded")]
+    SessionLimitExceeded,
+
+    #[msg("Wagering multiplier exceeds the maximum")]
+    InvalidWageringRequirement,
+
+    #[msg("Funds are locked until the bonus wagering requirement is met")]
+    BonusFundsLocked,
+
+    #[msg("Creator fee exceeds the platform maximum")]
+    InvalidCreatorFee,
+
+    #[msg("Market creator has already been settled")]
+    CreatorAlreadySettled,
+
+    #[msg("Private pool member list is full")]
+    PrivatePoolFull,
+
+    #[msg("Invite code does not match the private pool")]
+    InvalidInviteCode,
+
+    #[msg("Private pool account is required")]
+    PrivatePoolRequired,
+
+    #[msg("Bettor is not invited to the private pool")]
+    NotInvitedToPool,
+
+    #[msg("Bet modification leaves the stake and side unchanged")]
+    BetNotModified,
+
+    #[msg("Cancellation fee exceeds the maximum allowed")]
+    InvalidCancellationFee,
+
+    #[msg("Odds history account required for this match")]
+    OddsHistoryRequired,
+
+    #[msg("Amount must be greater than zero")]
+    ZeroAmount,
+
+    #[msg("Minimum bet must be above zero and below the maximum bet")]
+    InvalidBetLimits,
+
+    #[msg("Dispute window is out of range")]
+    InvalidDisputeWindow,
+
+    #[msg("Dispute bond must be greater than zero")]
+    InvalidDisputeBond,
+
+    #[msg("Betting cutoff must leave every match time to take bets")]
+    InvalidBettingCutoff,
+
+    #[msg("Exposure cap is below the minimum bet")]
+    InvalidExposureLimit,
+
+    #[msg("Platform is not paused")]
+    PlatformNotPaused,
+
+    #[msg("Odds moved below the odds accepted")]
+    SlippageExceeded,
+
+    #[msg("Match has not been settled")]
+    MatchNotSettled,
+
+    #[msg("Match has not ended yet")]
+    MatchNotEnded,
+
+    #[msg("No oracle result has been validated for this match")]
+    ResultNotReported,
+
+    #[msg("Bet has already been settled, refunded or cancelled")]
+    BetNotActive,
+
+    #[msg("Bet has no unclaimed winnings")]
+    NoWinningsToClaim,
+
+    #[msg("Cannot modify bet - betting has closed")]
+    CannotModifyBet,
+
+    #[msg("Username must be letters, digits and underscores")]
+    InvalidUsername,
+
+    #[msg("Team name cannot be empty")]
+    InvalidTeamName,
+
+    #[msg("Teams must be different")]
+    IdenticalTeams,
+
+    #[msg("Match duration is out of range")]
+    InvalidMatchDuration,
+
+    #[msg("Betting must open before it closes and close by the match end")]
+    InvalidBettingWindow,
+
+    #[msg("Final score too long")]
+    FinalScoreTooLong,
+
+    #[msg("Oracle has not revealed a result")]
+    ResultNotRevealed,
+
+    #[msg("Peer oracle report passed more than once")]
+    DuplicatePeerOracle,
+
+    #[msg("Peer oracle report is for another match")]
+    PeerOracleMatchMismatch,
+
+    #[msg("Dispute must propose the other winner")]
+    ProposedWinnerUnchanged,
+
+    #[msg("Match has no community creator")]
+    NotCommunityMarket,
+
+    #[msg("No oracle stake is unbonding")]
+    NothingUnbonding,
+
+    #[msg("Audit log page is required")]
+    AuditLogRequired,
+
+    #[msg("Audit log page is not the one the next entry goes on")]
+    AuditLogMismatch,
+
+    #[msg("Audit log page is full")]
+    AuditLogPageFull,
+
+    #[msg("Audit log is not enabled")]
+    AuditLogNotEnabled,
+
+    #[msg("Dispute voting deadline has passed")]
+    DisputeVotingClosed,
+
+    #[msg("Dispute voting deadline has not passed")]
+    DisputeVotingOpen,
 }
@@ -1,3 +1,4 @@
+use crate::state::{ExposureLimits, MarketKind, MatchIndexKey, MatchScore, ReportEvidence};
 use anchor_lang::prelude::*;
 
 /// Event emitted when the platform is initialized
@@ -66,6 +67,17 @@ pub struct MatchStatusUpdated {
     pub timestamp: i64,
 }
 
+/// Event emitted when a match's betting window is configured
+#[event]
+pub struct BettingWindowUpdated {
+    pub match_id: Pubkey,
+    pub betting_opens_at: i64,
+    pub betting_closes_at: i64,
+    pub early_bet_bonus: u16,
+    pub admin: Pubkey,
+    pub timestamp: i64,
+}
+
 /// Event emitted when betting is closed for a match
 #[event]
 pub struct MatchBettingClosed {
@@ -87,6 +99,8 @@ pub struct BetPlaced {
     pub predicted_team: u8,
     pub odds_at_time: u64,
     pub potential_payout: u64,
+    pub bonus_weight: u64,
+    pub bonus_stake: u64,
     pub timestamp: i64,
 }
 
@@ -131,13 +145,24 @@ pub struct OracleRegistered {
     pub timestamp: i64,
 }
 
-/// Event emitted when match result is updated by oracle
+/// Event emitted when an oracle reveals its match result
 #[event]
 pub struct MatchResultUpdated {
     pub match_id: Pubkey,
     pub oracle_authority: Pubkey,
     pub winning_team: u8,
     pub final_score: String,
+    pub match_score: Option<MatchScore>,
+    pub evidence: Option<ReportEvidence>,
+    pub timestamp: i64,
+}
+
+/// Event emitted when an oracle commits to a result it will reveal later
+#[event]
+pub struct OracleResultCommitted {
+    pub match_id: Pubkey,
+    pub oracle_authority: Pubkey,
+    pub commitment: [u8; 32],
     pub timestamp: i64,
 }
 
@@ -161,6 +186,33 @@ pub struct PlatformConfigUpdated {
     pub new_min_bet: Option<u64>,
     pub old_max_bet: Option<u64>,
     pub new_max_bet: Option<u64>,
+    pub old_crank_bounty: Option<u64>,
+    pub new_crank_bounty: Option<u64>,
+    pub old_price_feed_program: Option<Pubkey>,
+    pub new_price_feed_program: Option<Pubkey>,
+    pub old_dispute_window: Option<i64>,
+    pub new_dispute_window: Option<i64>,
+    pub old_dispute_bond: Option<u64>,
+    pub new_dispute_bond: Option<u64>,
+    pub old_betting_cutoff: Option<u32>,
+    pub new_betting_cutoff: Option<u32>,
+    pub old_early_bet_bonus: Option<u16>,
+    pub new_early_bet_bonus: Option<u16>,
+    pub old_creator_bond: Option<u64>,
+    pub new_creator_bond: Option<u64>,
+    pub old_max_creator_fee: Option<u16>,
+    pub new_max_creator_fee: Option<u16>,
+    pub old_cancellation_fee: Option<u16>,
+    pub new_cancellation_fee: Option<u16>,
+    pub timestamp: i64,
+}
+
+/// Event emitted when the exposure caps are replaced
+#[event]
+pub struct ExposureLimitsUpdated {
+    pub admin: Pubkey,
+    pub old_limits: ExposureLimits,
+    pub new_limits: ExposureLimits,
     pub timestamp: i64,
 }
 
@@ -223,3 +275,364 @@ pub struct AdminAction {
     pub target: Option<Pubkey>,
     pub timestamp: i64,
 }
+
+/// Event emitted when an account is upgraded to the current layout
+#[event]
+pub struct AccountMigrated {
+    pub account: Pubkey,
+    pub account_type: String, // "global", "user", "match", "bet", "treasury", "oracle", "oracle_profile", "price_market", "arbiter_set", "dispute"
+    pub from_version: u8,
+    pub to_version: u8,
+    pub admin: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Event emitted when a match status is advanced by the permissionless crank
+#[event]
+pub struct MatchCranked {
+    pub match_id: Pubkey,
+    pub cranker: Pubkey,
+    pub bounty: u64,
+    pub timestamp: i64,
+}
+
+/// Event emitted when a match is voided after the oracle deadline passed
+#[event]
+pub struct MatchVoided {
+    pub match_id: Pubkey,
+    pub voided_by: Pubkey,
+    pub oracle_reports: u8,
+    pub total_pool: u64,
+    pub total_bets: u64,
+    pub timestamp: i64,
+}
+
+/// Event emitted when a bet on a voided or cancelled match is refunded
+#[event]
+pub struct BetRefunded {
+    pub bet_id: Pubkey,
+    pub bettor: Pubkey,
+    pub match_id: Pubkey,
+    pub amount: u64,
+    pub refunded_by: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Event emitted when a price-trend market is created
+#[event]
+pub struct PriceMarketCreated {
+    pub match_id: Pubkey,
+    pub price_feed: Pubkey,
+    pub asset: String,
+    pub strike_price: i64,
+    pub expo: i32,
+    pub resolution_time: i64,
+    pub timestamp: i64,
+}
+
+/// Event emitted when a price-trend market is resolved from its price feed
+#[event]
+pub struct PriceMarketResolved {
+    pub match_id: Pubkey,
+    pub price: i64,
+    pub conf: u64,
+    pub expo: i32,
+    pub publish_time: i64,
+    pub strike_price: i64,
+    pub winning_team: u8,
+    pub resolver: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Event emitted when a totals or spread market is created
+#[event]
+pub struct LineMarketCreated {
+    pub match_id: Pubkey,
+    pub market_kind: MarketKind,
+    pub line: i32,
+    pub timestamp: i64,
+}
+
+/// Event emitted when an oracle bonds stake
+#[event]
+pub struct OracleStakeBonded {
+    pub oracle_authority: Pubkey,
+    pub amount: u64,
+    pub total_stake: u64,
+    pub timestamp: i64,
+}
+
+/// Event emitted when an oracle starts unbonding stake
+#[event]
+pub struct OracleStakeUnbonding {
+    pub oracle_authority: Pubkey,
+    pub amount: u64,
+    pub available_at: i64,
+    pub timestamp: i64,
+}
+
+/// Event emitted when an oracle withdraws unbonded stake
+#[event]
+pub struct OracleStakeWithdrawn {
+    pub oracle_authority: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+/// Event emitted when an oracle report is rewarded or slashed against consensus
+#[event]
+pub struct OracleReportResolved {
+    pub match_id: Pubkey,
+    pub oracle_authority: Pubkey,
+    pub agreed: bool,
+    pub reward: u64,
+    pub slashed: u64,
+    pub reputation: u16,
+    pub timestamp: i64,
+}
+
+/// Event emitted when the arbiter set is created or replaced
+#[event]
+pub struct ArbiterSetUpdated {
+    pub admin: Pubkey,
+    pub arbiters: Vec<Pubkey>,
+    pub quorum: u8,
+    pub timestamp: i64,
+}
+
+/// Event emitted when a bonded dispute of a match result is opened
+#[event]
+pub struct DisputeOpened {
+    pub match_id: Pubkey,
+    pub challenger: Pubkey,
+    pub bond: u64,
+    pub original_team: u8,
+    pub proposed_team: u8,
+    pub timestamp: i64,
+}
+
+/// Event emitted when an arbiter votes on a dispute
+#[event]
+pub struct DisputeVoteCast {
+    pub match_id: Pubkey,
+    pub arbiter: Pubkey,
+    pub uphold: bool,
+    pub votes_uphold: u8,
+    pub votes_reject: u8,
+    pub timestamp: i64,
+}
+
+/// Event emitted when the arbiters resolve a dispute
+#[event]
+pub struct DisputeResolved {
+    pub match_id: Pubkey,
+    pub challenger: Pubkey,
+    pub upheld: bool,
+    pub winning_team: u8,
+    pub bond: u64,
+    pub timestamp: i64,
+}
+
+/// Event emitted when a dispute the arbiters left undecided expires
+#[event]
+pub struct DisputeExpired {
+    pub match_id: Pubkey,
+    pub challenger: Pubkey,
+    pub bond: u64,
+    pub timestamp: i64,
+}
+
+/// Event emitted when the match registry is created
+#[event]
+pub struct MatchRegistryCreated {
+    pub admin: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Event emitted when a page is added to a match index
+#[event]
+pub struct MatchIndexPageCreated {
+    pub index_page: Pubkey,
+    pub key: MatchIndexKey,
+    pub page: u32,
+    pub payer: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Event emitted when a username is claimed for a profile
+#[event]
+pub struct UsernameClaimed {
+    pub user: Pubkey,
+    pub user_state: Pubkey,
+    pub username: String,
+    pub timestamp: i64,
+}
+
+/// Event emitted when a profile gives up its username
+#[event]
+pub struct UsernameReleased {
+    pub user: Pubkey,
+    pub user_state: Pubkey,
+    pub username: String,
+    pub timestamp: i64,
+}
+
+/// Event emitted when a user delegates betting to a session key
+#[event]
+pub struct SessionKeyCreated {
+    pub user: Pubkey,
+    pub session_key: Pubkey,
+    pub expires_at: i64,
+    pub max_bet_amount: u64,
+    pub spend_limit: u64,
+    pub permissions: u8,
+    pub timestamp: i64,
+}
+
+/// Event emitted when a session key is revoked
+#[event]
+pub struct SessionKeyRevoked {
+    pub user: Pubkey,
+    pub session_key: Pubkey,
+    pub revoked_by: Pubkey,
+    pub spent: u64,
+    pub timestamp: i64,
+}
+
+/// Event emitted when the admin authorises a promoter
+#[event]
+pub struct PromoterAdded {
+    pub promoter: Pubkey,
+    pub admin: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Event emitted when the admin removes a promoter
+#[event]
+pub struct PromoterRemoved {
+    pub promoter: Pubkey,
+    pub admin: Pubkey,
+    pub total_issued: u64,
+    pub timestamp: i64,
+}
+
+/// Event emitted when a promoter credits a user's bonus balance
+#[event]
+pub struct BonusIssued {
+    pub user: Pubkey,
+    pub promoter: Pubkey,
+    pub amount: u64,
+    pub wagering_requirement: u64,
+    pub bonus_balance: u64,
+    pub timestamp: i64,
+}
+
+/// Event emitted when a community member creates a market
+#[event]
+pub struct CommunityMarketCreated {
+    pub match_id: Pubkey,
+    pub creator: Pubkey,
+    pub creator_bond: u64,
+    pub creator_fee: u16,
+    pub timestamp: i64,
+}
+
+/// Event emitted when a market creator is paid their fee and bond back
+#[event]
+pub struct CreatorSettled {
+    pub match_id: Pubkey,
+    pub creator: Pubkey,
+    pub fee_amount: u64,
+    pub bond_refunded: u64,
+    pub timestamp: i64,
+}
+
+/// Event emitted when the admin voids a community market for abuse
+#[event]
+pub struct MarketVoidedForAbuse {
+    pub match_id: Pubkey,
+    pub creator: Pubkey,
+    pub admin: Pubkey,
+    pub bond_forfeited: u64,
+    pub total_pool: u64,
+    pub timestamp: i64,
+}
+
+/// Event emitted when a match is made private
+#[event]
+pub struct PrivatePoolCreated {
+    pub match_id: Pubkey,
+    pub organiser: Pubkey,
+    pub members: u32,
+    pub has_invite_code: bool,
+    pub waives_house_edge: bool,
+    pub timestamp: i64,
+}
+
+/// Event emitted when the organiser replaces a private pool's invites
+#[event]
+pub struct PrivatePoolUpdated {
+    pub match_id: Pubkey,
+    pub organiser: Pubkey,
+    pub members: u32,
+    pub has_invite_code: bool,
+    pub timestamp: i64,
+}
+
+/// Event emitted when a bettor joins a private pool with its invite code
+#[event]
+pub struct PrivatePoolJoined {
+    pub match_id: Pubkey,
+    pub member: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Event emitted when the organiser cancels a private pool so its bets are refunded
+#[event]
+pub struct PrivatePoolCancelled {
+    pub match_id: Pubkey,
+    pub organiser: Pubkey,
+    pub total_pool: u64,
+    pub total_bets: u64,
+    pub timestamp: i64,
+}
+
+/// Event emitted when an open bet's stake or side is changed
+#[event]
+pub struct BetModified {
+    pub bet_id: Pubkey,
+    pub bettor: Pubkey,
+    pub match_id: Pubkey,
+    pub old_amount: u64,
+    pub new_amount: u64,
+    pub old_team: u8,
+    pub new_team: u8,
+    pub odds_at_time: u64,
+    pub potential_payout: u64,
+    pub cancellation_fee: u64,
+    pub timestamp: i64,
+}
+
+/// Event emitted when a match starts recording its odds history
+#[event]
+pub struct OddsHistoryCreated {
+    pub match_id: Pubkey,
+    pub payer: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Event emitted when privileged actions start being recorded in the audit log
+#[event]
+pub struct AuditLogEnabled {
+    pub admin: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Event emitted when a page is added to the audit log
+#[event]
+pub struct AuditLogPageCreated {
+    pub audit_log: Pubkey,
+    pub page: u32,
+    pub payer: Pubkey,
+    pub timestamp: i64,
+}
@@ -1,9 +1,8 @@
 use anchor_lang::prelude::*;
-use anchor_lang::system_program::{transfer, Transfer};
 use crate::state::*;
 use crate::constants::*;
 use crate::error::TrendXBetError;
-use crate::utils::{ValidationUtils, TimeUtils};
+use crate::utils::{ValidationUtils, TimeUtils, AuditUtils};
 use crate::events::*;
 
 // Update Platform Config
@@ -13,18 +12,33 @@ pub struct UpdatePlatformConfig<'info> {
         mut,
         seeds = [PLATFORM_SEED],
         bump = global_state.bump,
-        has_one = admin @ TrendXBetError::Unauthorized
+        has_one = admin @ TrendXBetError::Unauthorized,
+        constraint = global_state.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
     )]
     pub global_state: Account<'info, GlobalState>,
     
     pub admin: Signer<'info>,
+    
+    /// Current audit log page, required once the audit log is enabled
+    #[account(mut, constraint = audit_log.is_current_version() @ TrendXBetError::UnsupportedAccountVersion)]
+    pub audit_log: Option<Account<'info, AuditLogPage>>,
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn update_platform_config(
     ctx: Context<UpdatePlatformConfig>,
     house_edge: Option<u16>,
     min_bet_amount: Option<u64>,
     max_bet_amount: Option<u64>,
+    crank_bounty: Option<u64>,
+    price_feed_program: Option<Pubkey>,
+    dispute_window: Option<i64>,
+    dispute_bond: Option<u64>,
+    betting_cutoff: Option<u32>,
+    early_bet_bonus: Option<u16>,
+    creator_bond: Option<u64>,
+    max_creator_fee: Option<u16>,
+    cancellation_fee: Option<u16>,
 ) -> Result<()> {
     let global_state = &mut ctx.accounts.global_state;
     let current_time = TimeUtils::get_current_timestamp();
@@ -33,6 +47,15 @@ pub fn update_platform_config(
     let old_house_edge = if house_edge.is_some() { Some(global_state.house_edge) } else { None };
     let old_min_bet = if min_bet_amount.is_some() { Some(global_state.min_bet_amount) } else { None };
     let old_max_bet = if max_bet_amount.is_some() { Some(global_state.max_bet_amount) } else { None };
+    let old_crank_bounty = if crank_bounty.is_some() { Some(global_state.crank_bounty) } else { None };
+    let old_price_feed_program = if price_feed_program.is_some() { Some(global_state.price_feed_program) } else { None };
+    let old_dispute_window = if dispute_window.is_some() { Some(global_state.dispute_window) } else { None };
+    let old_dispute_bond = if dispute_bond.is_some() { Some(global_state.dispute_bond) } else { None };
+    let old_betting_cutoff = if betting_cutoff.is_some() { Some(global_state.betting_cutoff) } else { None };
+    let old_early_bet_bonus = if early_bet_bonus.is_some() { Some(global_state.early_bet_bonus) } else { None };
+    let old_creator_bond = if creator_bond.is_some() { Some(global_state.creator_bond) } else { None };
+    let old_max_creator_fee = if max_creator_fee.is_some() { Some(global_state.max_creator_fee) } else { None };
+    let old_cancellation_fee = if cancellation_fee.is_some() { Some(global_state.cancellation_fee) } else { None };
     
     // Validate new values
     if let Some(edge) = house_edge {
@@ -40,16 +63,83 @@ pub fn update_platform_config(
     }
     
     if let Some(min_amount) = min_bet_amount {
-        require!(min_amount > 0, TrendXBetError::BetAmountTooLow);
+        require!(min_amount > 0, TrendXBetError::InvalidBetLimits);
     }
     
     if let Some(max_amount) = max_bet_amount {
         let min_amount = min_bet_amount.unwrap_or(global_state.min_bet_amount);
-        require!(max_amount > min_amount, TrendXBetError::InvalidTimeConfiguration);
+        require_gt!(max_amount, min_amount, TrendXBetError::InvalidBetLimits);
+    }
+    
+    if let Some(bounty) = crank_bounty {
+        require_gte!(MAX_CRANK_BOUNTY, bounty, TrendXBetError::CrankBountyTooHigh);
+    }
+    
+    if let Some(window) = dispute_window {
+        require_gte!(window, 0, TrendXBetError::InvalidDisputeWindow);
+        require_gte!(MAX_DISPUTE_WINDOW, window, TrendXBetError::InvalidDisputeWindow);
+    }
+    
+    if let Some(bond) = dispute_bond {
+        require!(bond > 0, TrendXBetError::InvalidDisputeBond);
+    }
+    
+    // The cutoff must leave every valid match some time to take bets
+    if let Some(cutoff) = betting_cutoff {
+        require_gt!(MIN_BETTING_DURATION, cutoff as i64, TrendXBetError::InvalidBettingCutoff);
+    }
+    
+    if let Some(bonus) = early_bet_bonus {
+        require_gte!(MAX_EARLY_BET_BONUS, bonus, TrendXBetError::InvalidEarlyBetBonus);
+    }
+    
+    if let Some(fee) = max_creator_fee {
+        require_gte!(MAX_CREATOR_FEE, fee, TrendXBetError::InvalidCreatorFee);
+    }
+    
+    if let Some(fee) = cancellation_fee {
+        require_gte!(MAX_CANCELLATION_FEE, fee, TrendXBetError::InvalidCancellationFee);
     }
     
     // Update configuration
-    global_state.update_config(house_edge, min_bet_amount, max_bet_amount);
+    global_state.update_config(
+        house_edge,
+        min_bet_amount,
+        max_bet_amount,
+        crank_bounty,
+        price_feed_program,
+        dispute_window,
+        dispute_bond,
+        betting_cutoff,
+        early_bet_bonus,
+        creator_bond,
+        max_creator_fee,
+        cancellation_fee,
+    );
+    
+    let params_hash = AuditLogPage::params_hash(&(
+        house_edge,
+        min_bet_amount,
+        max_bet_amount,
+        crank_bounty,
+        price_feed_program,
+        dispute_window,
+        dispute_bond,
+        betting_cutoff,
+        early_bet_bonus,
+        creator_bond,
+        max_creator_fee,
+        cancellation_fee,
+    ))?;
+    AuditUtils::record(
+        global_state,
+        ctx.accounts.audit_log.as_deref_mut(),
+        AuditAction::ConfigUpdate,
+        ctx.accounts.admin.key(),
+        None,
+        params_hash,
+        current_time,
+    )?;
     
     // Emit event
     emit!(PlatformConfigUpdated {
@@ -60,6 +150,24 @@ pub fn update_platform_config(
         new_min_bet: min_bet_amount,
         old_max_bet,
         new_max_bet: max_bet_amount,
+        old_crank_bounty,
+        new_crank_bounty: crank_bounty,
+        old_price_feed_program,
+        new_price_feed_program: price_feed_program,
+        old_dispute_window,
+        new_dispute_window: dispute_window,
+        old_dispute_bond,
+        new_dispute_bond: dispute_bond,
+        old_betting_cutoff,
+        new_betting_cutoff: betting_cutoff,
+        old_early_bet_bonus,
+        new_early_bet_bonus: early_bet_bonus,
+        old_creator_bond,
+        new_creator_bond: creator_bond,
+        old_max_creator_fee,
+        new_max_creator_fee: max_creator_fee,
+        old_cancellation_fee,
+        new_cancellation_fee: cancellation_fee,
         timestamp: current_time,
     });
     
@@ -67,58 +175,119 @@ pub fn update_platform_config(
     Ok(())
 }
 
+// Update Exposure Limits
+#[derive(Accounts)]
+pub struct UpdateExposureLimits<'info> {
+    #[account(
+        mut,
+        seeds = [PLATFORM_SEED],
+        bump = global_state.bump,
+        has_one = admin @ TrendXBetError::Unauthorized,
+        constraint = global_state.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    pub admin: Signer<'info>,
+    
+    /// Current audit log page, required once the audit log is enabled
+    #[account(mut, constraint = audit_log.is_current_version() @ TrendXBetError::UnsupportedAccountVersion)]
+    pub audit_log: Option<Account<'info, AuditLogPage>>,
+}
+
+pub fn update_exposure_limits(ctx: Context<UpdateExposureLimits>, limits: ExposureLimits) -> Result<()> {
+    let global_state = &mut ctx.accounts.global_state;
+    let current_time = TimeUtils::get_current_timestamp();
+    
+    // A cap below the minimum bet would refuse every bet
+    for cap in [limits.max_match_pool, limits.max_pool_imbalance, limits.max_user_match_stake] {
+        if cap > 0 {
+            require_gte!(cap, global_state.min_bet_amount, TrendXBetError::InvalidExposureLimit);
+        }
+    }
+    
+    let old_limits = global_state.exposure_limits;
+    global_state.exposure_limits = limits;
+    
+    AuditUtils::record(
+        global_state,
+        ctx.accounts.audit_log.as_deref_mut(),
+        AuditAction::ExposureLimitsUpdate,
+        ctx.accounts.admin.key(),
+        None,
+        AuditLogPage::params_hash(&limits)?,
+        current_time,
+    )?;
+    
+    emit!(ExposureLimitsUpdated {
+        admin: ctx.accounts.admin.key(),
+        old_limits,
+        new_limits: limits,
+        timestamp: current_time,
+    });
+    
+    msg!("Exposure limits updated by admin");
+    Ok(())
+}
+
 // Withdraw Platform Fees
 #[derive(Accounts)]
 pub struct WithdrawPlatformFees<'info> {
     #[account(
+        mut,
         seeds = [PLATFORM_SEED],
         bump = global_state.bump,
-        has_one = admin @ TrendXBetError::Unauthorized
+        has_one = admin @ TrendXBetError::Unauthorized,
+        constraint = global_state.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
     )]
     pub global_state: Account<'info, GlobalState>,
     
     #[account(
         mut,
         seeds = [TREASURY_SEED],
-        bump = treasury.bump
+        bump = treasury.bump,
+        constraint = treasury.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
     )]
     pub treasury: Account<'info, TreasuryState>,
     
     #[account(mut)]
     pub admin: Signer<'info>,
     
-    /// CHECK: Treasury account to send funds from
-    #[account(mut)]
+    /// CHECK: Treasury PDA that holds deposited lamports
+    #[account(mut, address = treasury.key() @ TrendXBetError::InvalidAccount)]
     pub treasury_account: UncheckedAccount<'info>,
     
     pub system_program: Program<'info, System>,
+    
+    /// Current audit log page, required once the audit log is enabled
+    #[account(mut, constraint = audit_log.is_current_version() @ TrendXBetError::UnsupportedAccountVersion)]
+    pub audit_log: Option<Account<'info, AuditLogPage>>,
 }
 
 pub fn withdraw_platform_fees(ctx: Context<WithdrawPlatformFees>, amount: u64) -> Result<()> {
     let treasury = &mut ctx.accounts.treasury;
     let current_time = TimeUtils::get_current_timestamp();
     
-    require!(amount > 0, TrendXBetError::BetAmountTooLow);
-    require!(treasury.available_platform_fees() >= amount, TrendXBetError::TreasuryInsufficientFunds);
+    require!(amount > 0, TrendXBetError::ZeroAmount);
+    require_gte!(treasury.available_platform_fees(), amount, TrendXBetError::TreasuryInsufficientFunds);
     
     // Transfer SOL from treasury to admin
-    let treasury_seeds = &[TREASURY_SEED, &[treasury.bump]];
-    let signer_seeds = &[&treasury_seeds[..]];
-    
-    let transfer_ctx = CpiContext::new_with_signer(
-        ctx.accounts.system_program.to_account_info(),
-        Transfer {
-            from: ctx.accounts.treasury_account.to_account_info(),
-            to: ctx.accounts.admin.to_account_info(),
-        },
-        signer_seeds,
-    );
-    transfer(transfer_ctx, amount)?;
+    ctx.accounts.treasury_account.sub_lamports(amount)?;
+    ctx.accounts.admin.add_lamports(amount)?;
     
     // Update treasury
     treasury.withdraw_platform_fees(amount)?;
     treasury.update_fee_collection_time(current_time);
     
+    AuditUtils::record(
+        &mut ctx.accounts.global_state,
+        ctx.accounts.audit_log.as_deref_mut(),
+        AuditAction::FeeWithdrawal,
+        ctx.accounts.admin.key(),
+        None,
+        AuditLogPage::params_hash(&amount)?,
+        current_time,
+    )?;
+    
     // Emit event
     emit!(PlatformFeesWithdrawn {
         admin: ctx.accounts.admin.key(),
@@ -146,11 +315,16 @@ pub struct PausePlatform<'info> {
         mut,
         seeds = [PLATFORM_SEED],
         bump = global_state.bump,
-        has_one = admin @ TrendXBetError::Unauthorized
+        has_one = admin @ TrendXBetError::Unauthorized,
+        constraint = global_state.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
     )]
     pub global_state: Account<'info, GlobalState>,
     
     pub admin: Signer<'info>,
+    
+    /// Current audit log page, required once the audit log is enabled
+    #[account(mut, constraint = audit_log.is_current_version() @ TrendXBetError::UnsupportedAccountVersion)]
+    pub audit_log: Option<Account<'info, AuditLogPage>>,
 }
 
 pub fn pause_platform(ctx: Context<PausePlatform>) -> Result<()> {
@@ -162,6 +336,16 @@ pub fn pause_platform(ctx: Context<PausePlatform>) -> Result<()> {
     // Pause the platform
     global_state.pause();
     
+    AuditUtils::record(
+        global_state,
+        ctx.accounts.audit_log.as_deref_mut(),
+        AuditAction::Pause,
+        ctx.accounts.admin.key(),
+        None,
+        AuditLogPage::params_hash(&())?,
+        current_time,
+    )?;
+    
     // Emit event
     emit!(PlatformPaused {
         admin: ctx.accounts.admin.key(),
@@ -186,22 +370,37 @@ pub struct UnpausePlatform<'info> {
         mut,
         seeds = [PLATFORM_SEED],
         bump = global_state.bump,
-        has_one = admin @ TrendXBetError::Unauthorized
+        has_one = admin @ TrendXBetError::Unauthorized,
+        constraint = global_state.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
     )]
     pub global_state: Account<'info, GlobalState>,
     
     pub admin: Signer<'info>,
+    
+    /// Current audit log page, required once the audit log is enabled
+    #[account(mut, constraint = audit_log.is_current_version() @ TrendXBetError::UnsupportedAccountVersion)]
+    pub audit_log: Option<Account<'info, AuditLogPage>>,
 }
 
 pub fn unpause_platform(ctx: Context<UnpausePlatform>) -> Result<()> {
     let global_state = &mut ctx.accounts.global_state;
     let current_time = TimeUtils::get_current_timestamp();
     
-    require!(!global_state.is_operational(), TrendXBetError::AlreadyInitialized);
+    require!(!global_state.is_operational(), TrendXBetError::PlatformNotPaused);
     
     // Unpause the platform
     global_state.unpause();
     
+    AuditUtils::record(
+        global_state,
+        ctx.accounts.audit_log.as_deref_mut(),
+        AuditAction::Unpause,
+        ctx.accounts.admin.key(),
+        None,
+        AuditLogPage::params_hash(&())?,
+        current_time,
+    )?;
+    
     // Emit event
     emit!(PlatformUnpaused {
         admin: ctx.accounts.admin.key(),
@@ -223,56 +422,63 @@ pub fn unpause_platform(ctx: Context<UnpausePlatform>) -> Result<()> {
 #[derive(Accounts)]
 pub struct EmergencyWithdraw<'info> {
     #[account(
+        mut,
         seeds = [PLATFORM_SEED],
         bump = global_state.bump,
-        has_one = admin @ TrendXBetError::Unauthorized
+        has_one = admin @ TrendXBetError::Unauthorized,
+        constraint = global_state.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
     )]
     pub global_state: Account<'info, GlobalState>,
     
     #[account(
         mut,
         seeds = [TREASURY_SEED],
-        bump = treasury.bump
+        bump = treasury.bump,
+        constraint = treasury.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
     )]
     pub treasury: Account<'info, TreasuryState>,
     
     #[account(mut)]
     pub admin: Signer<'info>,
     
-    /// CHECK: Treasury account to send funds from
-    #[account(mut)]
+    /// CHECK: Treasury PDA that holds deposited lamports
+    #[account(mut, address = treasury.key() @ TrendXBetError::InvalidAccount)]
     pub treasury_account: UncheckedAccount<'info>,
     
     pub system_program: Program<'info, System>,
+    
+    /// Current audit log page, required once the audit log is enabled
+    #[account(mut, constraint = audit_log.is_current_version() @ TrendXBetError::UnsupportedAccountVersion)]
+    pub audit_log: Option<Account<'info, AuditLogPage>>,
 }
 
 pub fn emergency_withdraw(ctx: Context<EmergencyWithdraw>, amount: u64) -> Result<()> {
     let treasury = &mut ctx.accounts.treasury;
     let current_time = TimeUtils::get_current_timestamp();
     
-    require!(amount > 0, TrendXBetError::BetAmountTooLow);
-    require!(treasury.has_sufficient_funds(amount), TrendXBetError::TreasuryInsufficientFunds);
+    require!(amount > 0, TrendXBetError::ZeroAmount);
+    require_gte!(u64::try_from(treasury.available_balance()).unwrap_or(0), amount, TrendXBetError::TreasuryInsufficientFunds);
     
     // This is an emergency function - should only be used in critical situations
     // Consider adding additional checks or multi-sig requirements
     
     // Transfer SOL from treasury to admin
-    let treasury_seeds = &[TREASURY_SEED, &[treasury.bump]];
-    let signer_seeds = &[&treasury_seeds[..]];
-    
-    let transfer_ctx = CpiContext::new_with_signer(
-        ctx.accounts.system_program.to_account_info(),
-        Transfer {
-            from: ctx.accounts.treasury_account.to_account_info(),
-            to: ctx.accounts.admin.to_account_info(),
-        },
-        signer_seeds,
-    );
-    transfer(transfer_ctx, amount)?;
+    ctx.accounts.treasury_account.sub_lamports(amount)?;
+    ctx.accounts.admin.add_lamports(amount)?;
     
     // Update treasury
     treasury.record_withdrawal(amount)?;
     
+    AuditUtils::record(
+        &mut ctx.accounts.global_state,
+        ctx.accounts.audit_log.as_deref_mut(),
+        AuditAction::EmergencyWithdrawal,
+        ctx.accounts.admin.key(),
+        None,
+        AuditLogPage::params_hash(&amount)?,
+        current_time,
+    )?;
+    
     // Emit events
     emit!(EmergencyWithdrawal {
         admin: ctx.accounts.admin.key(),
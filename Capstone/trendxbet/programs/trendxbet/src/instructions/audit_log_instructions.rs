@@ -0,0 +1,97 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::error::TrendXBetError;
+use crate::utils::TimeUtils;
+use crate::events::*;
+
+// Enable Audit Log
+#[derive(Accounts)]
+pub struct EnableAuditLog<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = AuditLogPage::LEN,
+        seeds = [AUDIT_LOG_SEED, 0u32.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub audit_log: Box<Account<'info, AuditLogPage>>,
+    
+    #[account(
+        mut,
+        seeds = [PLATFORM_SEED],
+        bump = global_state.bump,
+        has_one = admin @ TrendXBetError::Unauthorized,
+        constraint = global_state.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn enable_audit_log(ctx: Context<EnableAuditLog>) -> Result<()> {
+    let global_state = &mut ctx.accounts.global_state;
+    let current_time = TimeUtils::get_current_timestamp();
+    
+    // Once enabled, every privileged action must pass the current page
+    ctx.accounts.audit_log.initialize(0, ctx.bumps.audit_log);
+    global_state.has_audit_log = true;
+    
+    emit!(AuditLogEnabled {
+        admin: ctx.accounts.admin.key(),
+        timestamp: current_time,
+    });
+    
+    msg!("Audit log enabled");
+    Ok(())
+}
+
+// Create Audit Log Page
+#[derive(Accounts)]
+#[instruction(page: u32)]
+pub struct CreateAuditLogPage<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = AuditLogPage::LEN,
+        seeds = [AUDIT_LOG_SEED, page.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub audit_log: Box<Account<'info, AuditLogPage>>,
+    
+    #[account(
+        seeds = [PLATFORM_SEED],
+        bump = global_state.bump,
+        constraint = global_state.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn create_audit_log_page(ctx: Context<CreateAuditLogPage>, page: u32) -> Result<()> {
+    let global_state = &ctx.accounts.global_state;
+    let current_time = TimeUtils::get_current_timestamp();
+    
+    // Anyone may add a page, but only the one the next entry goes on
+    require!(global_state.has_audit_log, TrendXBetError::AuditLogNotEnabled);
+    require!(page == global_state.audit_page(), TrendXBetError::AuditLogMismatch);
+    
+    ctx.accounts.audit_log.initialize(page, ctx.bumps.audit_log);
+    
+    emit!(AuditLogPageCreated {
+        audit_log: ctx.accounts.audit_log.key(),
+        page,
+        payer: ctx.accounts.payer.key(),
+        timestamp: current_time,
+    });
+    
+    msg!("Audit log page {} created", page);
+    Ok(())
+}
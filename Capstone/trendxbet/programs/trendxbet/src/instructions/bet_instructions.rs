@@ -21,28 +21,31 @@ pub struct PlaceBet<'info> {
         mut,
         seeds = [USER_SEED, bettor.key().as_ref()],
         bump = user_state.bump,
-        has_one = authority @ TrendXBetError::Unauthorized
+        constraint = user_state.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
     )]
     pub user_state: Account<'info, UserState>,
     
     #[account(
         mut,
         seeds = [MATCH_SEED, match_id.key().as_ref()],
-        bump = match_state.bump
+        bump = match_state.bump,
+        constraint = match_state.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
     )]
     pub match_state: Account<'info, MatchState>,
     
     #[account(
         seeds = [PLATFORM_SEED],
         bump = global_state.bump,
-        constraint = global_state.is_operational() @ TrendXBetError::PlatformPaused
+        constraint = global_state.is_operational() @ TrendXBetError::PlatformPaused,
+        constraint = global_state.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
     )]
     pub global_state: Account<'info, GlobalState>,
     
     #[account(
         mut,
         seeds = [TREASURY_SEED],
-        bump = treasury.bump
+        bump = treasury.bump,
+        constraint = treasury.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
     )]
     pub treasury: Account<'info, TreasuryState>,
     
@@ -56,6 +59,32 @@ pub struct PlaceBet<'info> {
     pub authority: Signer<'info>,
     
     pub system_program: Program<'info, System>,
+    
+    /// Session the authority signs with for the bettor; without it the authority must own the profile
+    #[account(
+        mut,
+        seeds = [SESSION_SEED, user_state.authority.as_ref(), authority.key().as_ref()],
+        bump = session.bump,
+        constraint = session.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
+    )]
+    pub session: Option<Account<'info, SessionKey>>,
+    
+    /// Invite list of the match, required when it is private
+    #[account(
+        seeds = [PRIVATE_POOL_SEED, match_id.key().as_ref()],
+        bump = private_pool.bump,
+        constraint = private_pool.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
+    )]
+    pub private_pool: Option<Account<'info, PrivatePool>>,
+    
+    /// Odds history of the match, required when it records one
+    #[account(
+        mut,
+        seeds = [ODDS_HISTORY_SEED, match_id.key().as_ref()],
+        bump = odds_history.bump,
+        constraint = odds_history.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
+    )]
+    pub odds_history: Option<Box<Account<'info, OddsHistory>>>,
 }
 
 pub fn place_bet(
@@ -63,6 +92,7 @@ pub fn place_bet(
     amount: u64,
     predicted_team: u8,
     odds_accepted: u64,
+    use_bonus: bool,
 ) -> Result<()> {
     let bet_state = &mut ctx.accounts.bet_state;
     let user_state = &mut ctx.accounts.user_state;
@@ -75,47 +105,66 @@ pub fn place_bet(
     ValidationUtils::validate_bet_amount(amount, global_state.min_bet_amount, global_state.max_bet_amount)?;
     ValidationUtils::validate_team_selection(predicted_team)?;
     
+    // A session key bets for the profile within its limits
+    match ctx.accounts.session.as_deref_mut() {
+        Some(session) => session.spend(SESSION_PLACE_BET, amount, amount, current_time)?,
+        None => require!(user_state.authority == ctx.accounts.authority.key(), TrendXBetError::Unauthorized),
+    }
+    
     // Check if betting is allowed
     require!(
         match_state.is_betting_allowed(current_time),
         TrendXBetError::BettingClosed
     );
     
-    // Check user has sufficient balance
-    require!(
-        user_state.has_sufficient_balance(amount),
-        TrendXBetError::InsufficientBalance
-    );
+    // Private matches take bets from their invitees only
+    if match_state.is_private {
+        let private_pool = ctx.accounts.private_pool.as_deref().ok_or(TrendXBetError::PrivatePoolRequired)?;
+        require!(private_pool.is_member(&ctx.accounts.bettor.key()), TrendXBetError::NotInvitedToPool);
+    }
+    
+    // Keep the match within its exposure caps; this is the bettor's only stake on it
+    global_state.exposure_limits.check(match_state, predicted_team, amount, amount)?;
+    
+    // Check user has sufficient balance, bonus included if it is to be spent
+    require_gte!(user_state.stakeable_balance(use_bonus), amount, TrendXBetError::InsufficientBalance);
     
     // Calculate current odds
     let current_odds = match_state.get_team_odds(predicted_team);
     
     // Check odds haven't moved too much (slippage protection)
-    require!(
-        current_odds >= odds_accepted,
-        TrendXBetError::OracleDeviationTooHigh
-    );
+    require_gte!(current_odds, odds_accepted, TrendXBetError::SlippageExceeded);
     
-    // Calculate potential payout
+    // Calculate potential payout, net of the house edge and any creator fee
     let potential_payout = MathUtils::calculate_payout(
         amount,
         current_odds,
-        global_state.house_edge,
+        match_state.total_edge(global_state.house_edge),
     )?;
     
     // Calculate platform fee
-    let platform_fee = MathUtils::calculate_platform_fee(amount, global_state.house_edge)?;
+    let platform_fee = MathUtils::calculate_platform_fee(amount, match_state.house_edge(global_state.house_edge))?;
     
-    // Update user balance
-    user_state.subtract_balance(amount)?;
-    user_state.record_bet_placed(amount)?;
+    // Update user balance, spending bonus first when requested
+    let (bonus_stake, locked_stake) = user_state.take_stake(amount, use_bonus)?;
+    user_state.record_bet_placed(amount, current_odds)?;
+    
+    // Earlier bets earn a larger share of the winning pool
+    let bonus_weight = match_state.early_bet_bonus_weight(amount, current_time)?;
     
     // Update match pools
-    match_state.add_bet(amount, predicted_team)?;
+    match_state.add_bet(amount, bonus_weight, predicted_team)?;
+    match_state.add_platform_fee(platform_fee)?;
     
     // Update treasury and global state
     treasury.add_platform_fees(platform_fee)?;
     
+    // Charted matches record the pools after every change
+    if match_state.has_odds_history {
+        let odds_history = ctx.accounts.odds_history.as_deref_mut().ok_or(TrendXBetError::OddsHistoryRequired)?;
+        odds_history.record(match_state, current_time);
+    }
+    
     // Initialize bet state
     bet_state.initialize(
         ctx.accounts.bettor.key(),
@@ -124,6 +173,10 @@ pub fn place_bet(
         predicted_team,
         current_odds,
         potential_payout,
+        platform_fee,
+        bonus_weight,
+        bonus_stake,
+        locked_stake,
         ctx.bumps.bet_state,
         current_time,
     );
@@ -137,6 +190,8 @@ pub fn place_bet(
         predicted_team,
         odds_at_time: current_odds,
         potential_payout,
+        bonus_weight,
+        bonus_stake,
         timestamp: current_time,
     });
     
@@ -151,24 +206,35 @@ pub struct CancelBet<'info> {
         mut,
         seeds = [BET_SEED, bettor.key().as_ref(), match_id.key().as_ref()],
         bump = bet_state.bump,
-        has_one = bettor @ TrendXBetError::Unauthorized
+        has_one = bettor @ TrendXBetError::Unauthorized,
+        constraint = bet_state.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
     )]
     pub bet_state: Account<'info, BetState>,
     
     #[account(
         mut,
         seeds = [USER_SEED, bettor.key().as_ref()],
-        bump = user_state.bump
+        bump = user_state.bump,
+        constraint = user_state.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
     )]
     pub user_state: Account<'info, UserState>,
     
     #[account(
         mut,
         seeds = [MATCH_SEED, match_id.key().as_ref()],
-        bump = match_state.bump
+        bump = match_state.bump,
+        constraint = match_state.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
     )]
     pub match_state: Account<'info, MatchState>,
     
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED],
+        bump = treasury.bump,
+        constraint = treasury.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
+    )]
+    pub treasury: Account<'info, TreasuryState>,
+    
     /// CHECK: Match identifier
     pub match_id: UncheckedAccount<'info>,
     
@@ -176,17 +242,42 @@ pub struct CancelBet<'info> {
     pub bettor: UncheckedAccount<'info>,
     
     pub authority: Signer<'info>,
+    
+    /// Session the authority signs with for the bettor; without it the authority must own the profile
+    #[account(
+        mut,
+        seeds = [SESSION_SEED, user_state.authority.as_ref(), authority.key().as_ref()],
+        bump = session.bump,
+        constraint = session.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
+    )]
+    pub session: Option<Account<'info, SessionKey>>,
+    
+    /// Odds history of the match, required when it records one
+    #[account(
+        mut,
+        seeds = [ODDS_HISTORY_SEED, match_id.key().as_ref()],
+        bump = odds_history.bump,
+        constraint = odds_history.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
+    )]
+    pub odds_history: Option<Box<Account<'info, OddsHistory>>>,
 }
 
 pub fn cancel_bet(ctx: Context<CancelBet>) -> Result<()> {
     let bet_state = &mut ctx.accounts.bet_state;
     let user_state = &mut ctx.accounts.user_state;
     let match_state = &mut ctx.accounts.match_state;
+    let treasury = &mut ctx.accounts.treasury;
     let current_time = TimeUtils::get_current_timestamp();
     
+    // A session key may cancel for the profile if it was granted to
+    match ctx.accounts.session.as_deref() {
+        Some(session) => session.authorize(SESSION_CANCEL_BET, current_time)?,
+        None => require!(user_state.authority == ctx.accounts.authority.key(), TrendXBetError::Unauthorized),
+    }
+    
     // Check if bet can be cancelled
     require!(
-        bet_state.can_be_cancelled(match_state.start_time, current_time),
+        bet_state.can_be_cancelled(match_state.is_betting_allowed(current_time)),
         TrendXBetError::CannotCancelBet
     );
     
@@ -196,11 +287,22 @@ pub fn cancel_bet(ctx: Context<CancelBet>) -> Result<()> {
     // Cancel the bet
     bet_state.cancel(current_time);
     
-    // Refund user
-    user_state.add_balance(amount)?;
+    // Refund user in full, returning any bonus stake to the bonus balance
+    // and locking any staked bonus winnings again
+    user_state.return_stake(amount, bet_state.bonus_stake, bet_state.locked_stake)?;
     
     // Remove from match pools
-    match_state.remove_bet(amount, predicted_team)?;
+    match_state.remove_bet(amount, bet_state.bonus_weight, predicted_team)?;
+    match_state.remove_platform_fee(bet_state.platform_fee);
+    
+    // Return the fee charged at placement, since the full amount is refunded
+    treasury.remove_platform_fees(bet_state.platform_fee)?;
+    
+    // Charted matches record the pools after every change
+    if match_state.has_odds_history {
+        let odds_history = ctx.accounts.odds_history.as_deref_mut().ok_or(TrendXBetError::OddsHistoryRequired)?;
+        odds_history.record(match_state, current_time);
+    }
     
     // Emit event
     emit!(BetCancelled {
@@ -216,40 +318,265 @@ pub fn cancel_bet(ctx: Context<CancelBet>) -> Result<()> {
     Ok(())
 }
 
+// Modify Bet
+#[derive(Accounts)]
+pub struct ModifyBet<'info> {
+    #[account(
+        mut,
+        seeds = [BET_SEED, bettor.key().as_ref(), match_id.key().as_ref()],
+        bump = bet_state.bump,
+        has_one = bettor @ TrendXBetError::Unauthorized,
+        constraint = bet_state.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
+    )]
+    pub bet_state: Account<'info, BetState>,
+    
+    #[account(
+        mut,
+        seeds = [USER_SEED, bettor.key().as_ref()],
+        bump = user_state.bump,
+        constraint = user_state.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
+    )]
+    pub user_state: Account<'info, UserState>,
+    
+    #[account(
+        mut,
+        seeds = [MATCH_SEED, match_id.key().as_ref()],
+        bump = match_state.bump,
+        constraint = match_state.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
+    )]
+    pub match_state: Account<'info, MatchState>,
+    
+    #[account(
+        seeds = [PLATFORM_SEED],
+        bump = global_state.bump,
+        constraint = global_state.is_operational() @ TrendXBetError::PlatformPaused,
+        constraint = global_state.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED],
+        bump = treasury.bump,
+        constraint = treasury.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
+    )]
+    pub treasury: Account<'info, TreasuryState>,
+    
+    /// CHECK: Match identifier
+    pub match_id: UncheckedAccount<'info>,
+    
+    /// CHECK: Bettor account
+    pub bettor: UncheckedAccount<'info>,
+    
+    pub authority: Signer<'info>,
+    
+    /// Session the authority signs with for the bettor; without it the authority must own the profile
+    #[account(
+        mut,
+        seeds = [SESSION_SEED, user_state.authority.as_ref(), authority.key().as_ref()],
+        bump = session.bump,
+        constraint = session.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
+    )]
+    pub session: Option<Account<'info, SessionKey>>,
+    
+    /// Invite list of the match, required to add stake when it is private
+    #[account(
+        seeds = [PRIVATE_POOL_SEED, match_id.key().as_ref()],
+        bump = private_pool.bump,
+        constraint = private_pool.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
+    )]
+    pub private_pool: Option<Account<'info, PrivatePool>>,
+    
+    /// Odds history of the match, required when it records one
+    #[account(
+        mut,
+        seeds = [ODDS_HISTORY_SEED, match_id.key().as_ref()],
+        bump = odds_history.bump,
+        constraint = odds_history.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
+    )]
+    pub odds_history: Option<Box<Account<'info, OddsHistory>>>,
+}
+
+pub fn modify_bet(
+    ctx: Context<ModifyBet>,
+    amount: u64,
+    predicted_team: u8,
+    odds_accepted: u64,
+    use_bonus: bool,
+) -> Result<()> {
+    let bet_state = &mut ctx.accounts.bet_state;
+    let user_state = &mut ctx.accounts.user_state;
+    let match_state = &mut ctx.accounts.match_state;
+    let global_state = &ctx.accounts.global_state;
+    let treasury = &mut ctx.accounts.treasury;
+    let current_time = TimeUtils::get_current_timestamp();
+    
+    // Validate inputs; a bet is withdrawn entirely with `cancel_bet`
+    ValidationUtils::validate_bet_amount(amount, global_state.min_bet_amount, global_state.max_bet_amount)?;
+    ValidationUtils::validate_team_selection(predicted_team)?;
+    
+    // Bets can be changed while they could still be cancelled
+    require!(
+        bet_state.can_be_cancelled(match_state.is_betting_allowed(current_time)),
+        TrendXBetError::CannotModifyBet
+    );
+    
+    let old_amount = bet_state.amount;
+    let old_team = bet_state.predicted_team;
+    let old_odds = bet_state.odds_at_time;
+    let switches_team = predicted_team != old_team;
+    require!(switches_team || amount != old_amount, TrendXBetError::BetNotModified);
+    
+    // Switching side moves the whole stake; otherwise only the difference changes hands
+    let kept = if switches_team { 0 } else { old_amount.min(amount) };
+    let released = old_amount - kept;
+    let added = amount - kept;
+    
+    // A session key must be granted each part of the change; the new total is held to its
+    // per-bet cap and only new stake counts toward its spend
+    match ctx.accounts.session.as_deref_mut() {
+        Some(session) => {
+            if released > 0 {
+                session.authorize(SESSION_CANCEL_BET, current_time)?;
+            }
+            if added > 0 {
+                session.spend(SESSION_PLACE_BET, amount, added, current_time)?;
+            }
+        }
+        None => require!(user_state.authority == ctx.accounts.authority.key(), TrendXBetError::Unauthorized),
+    }
+    
+    // Release the withdrawn stake with its share of the weight and fee, less the cancellation fee
+    let released_weight = MathUtils::calculate_share(bet_state.bonus_weight, released, old_amount)?;
+    let released_fee = MathUtils::calculate_share(bet_state.platform_fee, released, old_amount)?;
+    let released_bonus = bet_state.bonus_stake.min(released);
+    let released_locked = bet_state.locked_stake.min(released - released_bonus);
+    let cancellation_fee = global_state.cancellation_fee_on(released)?;
+    let refund_amount = released
+        .checked_sub(cancellation_fee)
+        .ok_or(TrendXBetError::MathematicalOverflow)?;
+    
+    user_state.return_stake(refund_amount, released_bonus.min(refund_amount), released_locked)?;
+    match_state.remove_bet(released, released_weight, old_team)?;
+    match_state.remove_platform_fee(released_fee);
+    treasury.remove_platform_fees(released_fee)?;
+    treasury.collect_cancellation_fee(cancellation_fee)?;
+    
+    // New stake is held to the same checks as a new bet
+    if added > 0 {
+        if match_state.is_private {
+            let private_pool = ctx.accounts.private_pool.as_deref().ok_or(TrendXBetError::PrivatePoolRequired)?;
+            require!(private_pool.is_member(&ctx.accounts.bettor.key()), TrendXBetError::NotInvitedToPool);
+        }
+        
+        // Exposure is checked against the bettor's whole stake once changed
+        global_state.exposure_limits.check(match_state, predicted_team, added, amount)?;
+        
+        require_gte!(user_state.stakeable_balance(use_bonus), added, TrendXBetError::InsufficientBalance);
+    }
+    
+    // Odds are taken once the withdrawn stake has left the pools
+    let current_odds = match_state.get_team_odds(predicted_team);
+    if added > 0 {
+        require_gte!(current_odds, odds_accepted, TrendXBetError::SlippageExceeded);
+    }
+    
+    // Stake the addition, earning the early-bet bonus and fee of this moment
+    let (added_bonus, added_locked) = user_state.take_stake(added, use_bonus)?;
+    let added_weight = match_state.early_bet_bonus_weight(added, current_time)?;
+    let added_fee = MathUtils::calculate_platform_fee(added, match_state.house_edge(global_state.house_edge))?;
+    
+    match_state.add_bet(added, added_weight, predicted_team)?;
+    match_state.add_platform_fee(added_fee)?;
+    treasury.add_platform_fees(added_fee)?;
+    
+    // Charted matches record the pools after every change
+    if match_state.has_odds_history {
+        let odds_history = ctx.accounts.odds_history.as_deref_mut().ok_or(TrendXBetError::OddsHistoryRequired)?;
+        odds_history.record(match_state, current_time);
+    }
+    
+    // The whole stake is now priced at the current odds
+    let potential_payout = MathUtils::calculate_payout(
+        amount,
+        current_odds,
+        match_state.total_edge(global_state.house_edge),
+    )?;
+    
+    let platform_fee = bet_state.platform_fee - released_fee + added_fee;
+    let bonus_weight = bet_state.bonus_weight - released_weight + added_weight;
+    let bonus_stake = bet_state.bonus_stake - released_bonus + added_bonus;
+    let locked_stake = bet_state.locked_stake - released_locked + added_locked;
+    
+    user_state.record_bet_modified(old_amount, amount, old_odds, current_odds)?;
+    bet_state.modify(
+        amount,
+        predicted_team,
+        current_odds,
+        potential_payout,
+        platform_fee,
+        bonus_weight,
+        bonus_stake,
+        locked_stake,
+    );
+    
+    emit!(BetModified {
+        bet_id: bet_state.key(),
+        bettor: ctx.accounts.bettor.key(),
+        match_id: ctx.accounts.match_id.key(),
+        old_amount,
+        new_amount: amount,
+        old_team,
+        new_team: predicted_team,
+        odds_at_time: current_odds,
+        potential_payout,
+        cancellation_fee,
+        timestamp: current_time,
+    });
+    
+    msg!("Bet modified: {} lamports on team {}", amount, predicted_team);
+    Ok(())
+}
+
 // Settle Bet
 #[derive(Accounts)]
 pub struct SettleBet<'info> {
     #[account(
         mut,
         seeds = [BET_SEED, bettor.key().as_ref(), match_id.key().as_ref()],
-        bump = bet_state.bump
+        bump = bet_state.bump,
+        constraint = bet_state.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
     )]
     pub bet_state: Account<'info, BetState>,
     
     #[account(
         mut,
         seeds = [USER_SEED, bettor.key().as_ref()],
-        bump = user_state.bump
+        bump = user_state.bump,
+        constraint = user_state.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
     )]
     pub user_state: Account<'info, UserState>,
     
     #[account(
         seeds = [MATCH_SEED, match_id.key().as_ref()],
         bump = match_state.bump,
-        constraint = match_state.is_settled @ TrendXBetError::MatchNotStarted
+        constraint = match_state.is_settled @ TrendXBetError::MatchNotSettled,
+        constraint = match_state.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
     )]
     pub match_state: Account<'info, MatchState>,
     
     #[account(
         seeds = [PLATFORM_SEED],
-        bump = global_state.bump
+        bump = global_state.bump,
+        constraint = global_state.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
     )]
     pub global_state: Account<'info, GlobalState>,
     
     #[account(
         mut,
         seeds = [TREASURY_SEED],
-        bump = treasury.bump
+        bump = treasury.bump,
+        constraint = treasury.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
     )]
     pub treasury: Account<'info, TreasuryState>,
     
@@ -270,21 +597,27 @@ pub fn settle_bet(ctx: Context<SettleBet>) -> Result<()> {
     let treasury = &mut ctx.accounts.treasury;
     let current_time = TimeUtils::get_current_timestamp();
     
-    require!(bet_state.status == BetStatus::Active, TrendXBetError::BetNotFound);
+    require!(bet_state.status == BetStatus::Active, TrendXBetError::BetNotActive);
     
-    let winning_team = match_state.winning_team.ok_or(TrendXBetError::MatchNotStarted)?;
-    let winning_pool = match_state.get_winning_pool().ok_or(TrendXBetError::PayoutCalculationFailed)?;
+    let winning_team = match_state.winning_team.ok_or(TrendXBetError::MatchNotSettled)?;
+    // Results can be overturned until the dispute window closes
+    match_state.require_final_result(current_time)?;
+    let winning_weight = match_state.get_winning_weight().ok_or(TrendXBetError::PayoutCalculationFailed)?;
     
     let won = bet_state.is_winning_bet(winning_team);
     
     if won {
-        // Calculate payout using parimutuel system
+        // Calculate payout using parimutuel system; the creator fee is held back for the creator
         let payout = bet_state.calculate_parimutuel_payout(
-            winning_pool,
+            winning_weight,
             match_state.total_pool,
-            global_state.house_edge,
+            match_state.total_edge(global_state.house_edge),
         )?;
         
+        // Bonus stakes are kept by the platform; only the profit on them is paid
+        let (payout, retained) = bet_state.split_bonus_payout(payout);
+        treasury.retain_bonus_stake(retained)?;
+    
         // Settle as won
         bet_state.settle_as_won(payout, current_time);
         
@@ -298,6 +631,12 @@ pub fn settle_bet(ctx: Context<SettleBet>) -> Result<()> {
         bet_state.settle_as_lost(current_time);
     }
     
+    // The fee can no longer be refunded, so it becomes withdrawable
+    treasury.earn_platform_fees(bet_state.platform_fee);
+    
+    // Settled stakes count toward the bettor's bonus wagering requirement
+    user_state.record_wager(bet_state.amount);
+    
     // Emit event
     emit!(BetSettled {
         bet_id: bet_state.key(),
@@ -320,21 +659,24 @@ pub struct ClaimWinnings<'info> {
         mut,
         seeds = [BET_SEED, bettor.key().as_ref(), match_id.key().as_ref()],
         bump = bet_state.bump,
-        has_one = bettor @ TrendXBetError::Unauthorized
+        has_one = bettor @ TrendXBetError::Unauthorized,
+        constraint = bet_state.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
     )]
     pub bet_state: Account<'info, BetState>,
     
     #[account(
         mut,
         seeds = [USER_SEED, bettor.key().as_ref()],
-        bump = user_state.bump
+        bump = user_state.bump,
+        constraint = user_state.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
     )]
     pub user_state: Account<'info, UserState>,
     
     #[account(
         mut,
         seeds = [TREASURY_SEED],
-        bump = treasury.bump
+        bump = treasury.bump,
+        constraint = treasury.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
     )]
     pub treasury: Account<'info, TreasuryState>,
     
@@ -353,7 +695,7 @@ pub fn claim_winnings(ctx: Context<ClaimWinnings>) -> Result<()> {
     let treasury = &mut ctx.accounts.treasury;
     let current_time = TimeUtils::get_current_timestamp();
     
-    require!(bet_state.has_claimable_winnings(), TrendXBetError::BetNotFound);
+    require!(bet_state.has_claimable_winnings(), TrendXBetError::NoWinningsToClaim);
     
     let payout_amount = bet_state.get_claimable_amount();
     
@@ -363,6 +705,9 @@ pub fn claim_winnings(ctx: Context<ClaimWinnings>) -> Result<()> {
     // Add winnings to user balance
     user_state.add_balance(payout_amount)?;
     
+    // Winnings on bonus stakes stay locked until wagering is complete
+    user_state.lock_bonus_winnings(bet_state.bonus_winnings(payout_amount)?)?;
+    
     // Remove from pending payouts
     treasury.remove_pending_payout(payout_amount)?;
     
@@ -378,3 +723,76 @@ pub fn claim_winnings(ctx: Context<ClaimWinnings>) -> Result<()> {
     msg!("Winnings claimed: {} lamports", payout_amount);
     Ok(())
 }
+
+// Refund Bet
+#[derive(Accounts)]
+pub struct RefundBet<'info> {
+    #[account(
+        mut,
+        seeds = [BET_SEED, bettor.key().as_ref(), match_id.key().as_ref()],
+        bump = bet_state.bump,
+        has_one = bettor @ TrendXBetError::Unauthorized,
+        constraint = bet_state.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
+    )]
+    pub bet_state: Account<'info, BetState>,
+    
+    #[account(
+        mut,
+        seeds = [USER_SEED, bettor.key().as_ref()],
+        bump = user_state.bump,
+        constraint = user_state.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
+    )]
+    pub user_state: Account<'info, UserState>,
+    
+    #[account(
+        seeds = [MATCH_SEED, match_id.key().as_ref()],
+        bump = match_state.bump,
+        constraint = match_state.is_refundable() @ TrendXBetError::MatchNotVoided,
+        constraint = match_state.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
+    )]
+    pub match_state: Account<'info, MatchState>,
+    
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED],
+        bump = treasury.bump,
+        constraint = treasury.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
+    )]
+    pub treasury: Account<'info, TreasuryState>,
+    
+    /// CHECK: Match identifier
+    pub match_id: UncheckedAccount<'info>,
+    
+    /// CHECK: Bettor account
+    pub bettor: UncheckedAccount<'info>,
+    
+    pub authority: Signer<'info>,
+}
+
+pub fn refund_bet(ctx: Context<RefundBet>) -> Result<()> {
+    let bet_state = &mut ctx.accounts.bet_state;
+    let user_state = &mut ctx.accounts.user_state;
+    let treasury = &mut ctx.accounts.treasury;
+    let current_time = TimeUtils::get_current_timestamp();
+    
+    require!(bet_state.status == BetStatus::Active, TrendXBetError::BetNotActive);
+    
+    let amount = bet_state.amount;
+    
+    // Refund the full stake, fee included; pools stay as they were for the record
+    bet_state.refund(current_time);
+    user_state.return_stake(amount, bet_state.bonus_stake, bet_state.locked_stake)?;
+    treasury.remove_platform_fees(bet_state.platform_fee)?;
+    
+    emit!(BetRefunded {
+        bet_id: bet_state.key(),
+        bettor: ctx.accounts.bettor.key(),
+        match_id: ctx.accounts.match_id.key(),
+        amount,
+        refunded_by: ctx.accounts.authority.key(),
+        timestamp: current_time,
+    });
+    
+    msg!("Bet refunded: {} lamports", amount);
+    Ok(())
+}
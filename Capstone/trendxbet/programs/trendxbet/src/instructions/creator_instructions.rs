@@ -0,0 +1,188 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::error::TrendXBetError;
+use crate::utils::{TimeUtils, IndexUtils, AuditUtils};
+use crate::events::*;
+
+// Settle Creator
+#[derive(Accounts)]
+pub struct SettleCreator<'info> {
+    #[account(
+        mut,
+        seeds = [MATCH_SEED, match_id.key().as_ref()],
+        bump = match_state.bump,
+        constraint = match_state.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
+    )]
+    pub match_state: Account<'info, MatchState>,
+    
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED],
+        bump = treasury.bump,
+        constraint = treasury.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
+    )]
+    pub treasury: Account<'info, TreasuryState>,
+    
+    /// CHECK: Treasury PDA that holds deposited lamports
+    #[account(mut, address = treasury.key() @ TrendXBetError::InvalidAccount)]
+    pub treasury_account: UncheckedAccount<'info>,
+    
+    /// CHECK: Market creator, paid the fee and refunded the bond
+    #[account(mut, address = match_state.creator @ TrendXBetError::InvalidAccount)]
+    pub creator: UncheckedAccount<'info>,
+    
+    /// CHECK: Match identifier
+    pub match_id: UncheckedAccount<'info>,
+    
+    pub authority: Signer<'info>,
+}
+
+pub fn settle_creator(ctx: Context<SettleCreator>) -> Result<()> {
+    let match_state = &mut ctx.accounts.match_state;
+    let treasury = &mut ctx.accounts.treasury;
+    let current_time = TimeUtils::get_current_timestamp();
+    
+    // A settled market earns the fee once its result is final; a cancelled or
+    // voided one only returns the bond
+    let fee_amount = if match_state.is_settled {
+        match_state.require_final_result(current_time)?;
+        match_state.creator_fee_amount()?
+    } else {
+        require!(match_state.is_refundable(), TrendXBetError::InvalidMatchStatus);
+        0
+    };
+    match_state.settle_creator()?;
+    
+    // The fee was held back from the winners' payouts, so it is still in the treasury
+    if fee_amount > 0 {
+        ctx.accounts.treasury_account.sub_lamports(fee_amount)?;
+        ctx.accounts.creator.add_lamports(fee_amount)?;
+        treasury.record_withdrawal(fee_amount)?;
+    }
+    
+    let bond = match_state.creator_bond;
+    if bond > 0 {
+        match_state.sub_lamports(bond)?;
+        ctx.accounts.creator.add_lamports(bond)?;
+    }
+    
+    emit!(CreatorSettled {
+        match_id: ctx.accounts.match_id.key(),
+        creator: match_state.creator,
+        fee_amount,
+        bond_refunded: bond,
+        timestamp: current_time,
+    });
+    
+    msg!("Creator settled: fee {} - bond {}", fee_amount, bond);
+    Ok(())
+}
+
+// Void Market For Abuse
+#[derive(Accounts)]
+pub struct VoidMarketForAbuse<'info> {
+    #[account(
+        mut,
+        seeds = [MATCH_SEED, match_id.key().as_ref()],
+        bump = match_state.bump,
+        constraint = match_state.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
+    )]
+    pub match_state: Account<'info, MatchState>,
+    
+    #[account(
+        mut,
+        seeds = [PLATFORM_SEED],
+        bump = global_state.bump,
+        has_one = admin @ TrendXBetError::Unauthorized,
+        constraint = global_state.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED],
+        bump = treasury.bump,
+        constraint = treasury.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
+    )]
+    pub treasury: Account<'info, TreasuryState>,
+    
+    /// CHECK: Treasury PDA that holds deposited lamports
+    #[account(mut, address = treasury.key() @ TrendXBetError::InvalidAccount)]
+    pub treasury_account: UncheckedAccount<'info>,
+    
+    /// CHECK: Match identifier
+    pub match_id: UncheckedAccount<'info>,
+    
+    pub admin: Signer<'info>,
+    
+    /// Status index page listing the match, required for indexed matches whose status changes
+    #[account(mut, constraint = old_status_index.is_current_version() @ TrendXBetError::UnsupportedAccountVersion)]
+    pub old_status_index: Option<Account<'info, MatchIndexPage>>,
+    
+    /// Page of the new status's index with room for the match
+    #[account(mut, constraint = new_status_index.is_current_version() @ TrendXBetError::UnsupportedAccountVersion)]
+    pub new_status_index: Option<Account<'info, MatchIndexPage>>,
+    
+    /// Current audit log page, required once the audit log is enabled
+    #[account(mut, constraint = audit_log.is_current_version() @ TrendXBetError::UnsupportedAccountVersion)]
+    pub audit_log: Option<Account<'info, AuditLogPage>>,
+}
+
+pub fn void_market_for_abuse(ctx: Context<VoidMarketForAbuse>) -> Result<()> {
+    let match_state = &mut ctx.accounts.match_state;
+    let treasury = &mut ctx.accounts.treasury;
+    let current_time = TimeUtils::get_current_timestamp();
+    let old_status = match_state.status.clone();
+    
+    // Only an unresolved community market can be voided, and its bets are refunded
+    require!(match_state.is_awaiting_result(), TrendXBetError::InvalidMatchStatus);
+    match_state.settle_creator()?;
+    match_state.void();
+    
+    // Keep the status index in step
+    IndexUtils::move_status(
+        match_state,
+        &old_status,
+        ctx.accounts.old_status_index.as_deref_mut(),
+        ctx.accounts.new_status_index.as_deref_mut(),
+    )?;
+    
+    // The creator's bond is forfeited to the treasury
+    let bond = match_state.creator_bond;
+    if bond > 0 {
+        match_state.sub_lamports(bond)?;
+        ctx.accounts.treasury_account.add_lamports(bond)?;
+        treasury.add_forfeit(bond)?;
+    }
+    
+    let match_id = ctx.accounts.match_id.key();
+    AuditUtils::record(
+        &mut ctx.accounts.global_state,
+        ctx.accounts.audit_log.as_deref_mut(),
+        AuditAction::MarketVoid,
+        ctx.accounts.admin.key(),
+        Some(match_id),
+        AuditLogPage::params_hash(&match_id)?,
+        current_time,
+    )?;
+    
+    emit!(MatchStatusUpdated {
+        match_id: ctx.accounts.match_id.key(),
+        old_status: old_status.code(),
+        new_status: MatchStatus::Voided.code(),
+        timestamp: current_time,
+    });
+    
+    emit!(MarketVoidedForAbuse {
+        match_id: ctx.accounts.match_id.key(),
+        creator: match_state.creator,
+        admin: ctx.accounts.admin.key(),
+        bond_forfeited: bond,
+        total_pool: match_state.total_pool,
+        timestamp: current_time,
+    });
+    
+    msg!("Market voided for abuse: {}", ctx.accounts.match_id.key());
+    Ok(())
+}
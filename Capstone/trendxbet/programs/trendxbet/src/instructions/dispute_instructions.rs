@@ -0,0 +1,364 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use crate::state::*;
+use crate::constants::*;
+use crate::error::TrendXBetError;
+use crate::utils::{TimeUtils, ValidationUtils, AuditUtils};
+use crate::events::*;
+
+// Create Arbiter Set
+#[derive(Accounts)]
+pub struct CreateArbiterSet<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = ArbiterSet::LEN,
+        seeds = [ARBITER_SET_SEED],
+        bump
+    )]
+    pub arbiter_set: Account<'info, ArbiterSet>,
+    
+    #[account(
+        mut,
+        seeds = [PLATFORM_SEED],
+        bump = global_state.bump,
+        has_one = admin @ TrendXBetError::Unauthorized,
+        constraint = global_state.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+    
+    /// Current audit log page, required once the audit log is enabled
+    #[account(mut, constraint = audit_log.is_current_version() @ TrendXBetError::UnsupportedAccountVersion)]
+    pub audit_log: Option<Account<'info, AuditLogPage>>,
+}
+
+pub fn create_arbiter_set(ctx: Context<CreateArbiterSet>, arbiters: Vec<Pubkey>, quorum: u8) -> Result<()> {
+    let arbiter_set = &mut ctx.accounts.arbiter_set;
+    let current_time = TimeUtils::get_current_timestamp();
+    
+    arbiter_set.initialize(arbiters, quorum, ctx.bumps.arbiter_set)?;
+    
+    AuditUtils::record(
+        &mut ctx.accounts.global_state,
+        ctx.accounts.audit_log.as_deref_mut(),
+        AuditAction::ArbiterSetUpdate,
+        ctx.accounts.admin.key(),
+        None,
+        AuditLogPage::params_hash(&(&arbiter_set.arbiters, arbiter_set.quorum))?,
+        current_time,
+    )?;
+    
+    emit!(ArbiterSetUpdated {
+        admin: ctx.accounts.admin.key(),
+        arbiters: arbiter_set.arbiters.clone(),
+        quorum: arbiter_set.quorum,
+        timestamp: current_time,
+    });
+    
+    msg!("Arbiter set created with quorum {}", quorum);
+    Ok(())
+}
+
+// Update Arbiter Set
+#[derive(Accounts)]
+pub struct UpdateArbiterSet<'info> {
+    #[account(
+        mut,
+        seeds = [ARBITER_SET_SEED],
+        bump = arbiter_set.bump,
+        constraint = arbiter_set.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
+    )]
+    pub arbiter_set: Account<'info, ArbiterSet>,
+    
+    #[account(
+        mut,
+        seeds = [PLATFORM_SEED],
+        bump = global_state.bump,
+        has_one = admin @ TrendXBetError::Unauthorized,
+        constraint = global_state.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    pub admin: Signer<'info>,
+    
+    /// Current audit log page, required once the audit log is enabled
+    #[account(mut, constraint = audit_log.is_current_version() @ TrendXBetError::UnsupportedAccountVersion)]
+    pub audit_log: Option<Account<'info, AuditLogPage>>,
+}
+
+pub fn update_arbiter_set(ctx: Context<UpdateArbiterSet>, arbiters: Vec<Pubkey>, quorum: u8) -> Result<()> {
+    let arbiter_set = &mut ctx.accounts.arbiter_set;
+    let current_time = TimeUtils::get_current_timestamp();
+    
+    // Votes already cast on open disputes keep counting
+    arbiter_set.update(arbiters, quorum)?;
+    
+    AuditUtils::record(
+        &mut ctx.accounts.global_state,
+        ctx.accounts.audit_log.as_deref_mut(),
+        AuditAction::ArbiterSetUpdate,
+        ctx.accounts.admin.key(),
+        None,
+        AuditLogPage::params_hash(&(&arbiter_set.arbiters, arbiter_set.quorum))?,
+        current_time,
+    )?;
+    
+    emit!(ArbiterSetUpdated {
+        admin: ctx.accounts.admin.key(),
+        arbiters: arbiter_set.arbiters.clone(),
+        quorum: arbiter_set.quorum,
+        timestamp: current_time,
+    });
+    
+    msg!("Arbiter set updated with quorum {}", quorum);
+    Ok(())
+}
+
+// Open Dispute
+#[derive(Accounts)]
+pub struct OpenDispute<'info> {
+    #[account(
+        init,
+        payer = challenger,
+        space = DisputeState::LEN,
+        seeds = [DISPUTE_SEED, match_id.key().as_ref()],
+        bump
+    )]
+    pub dispute_state: Account<'info, DisputeState>,
+    
+    #[account(
+        mut,
+        seeds = [MATCH_SEED, match_id.key().as_ref()],
+        bump = match_state.bump,
+        constraint = match_state.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
+    )]
+    pub match_state: Account<'info, MatchState>,
+    
+    #[account(
+        seeds = [PLATFORM_SEED],
+        bump = global_state.bump,
+        constraint = global_state.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    /// Arbiters who will decide the dispute; results cannot be disputed before they exist
+    #[account(
+        seeds = [ARBITER_SET_SEED],
+        bump = arbiter_set.bump,
+        constraint = arbiter_set.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
+    )]
+    pub arbiter_set: Account<'info, ArbiterSet>,
+    
+    /// CHECK: Match identifier
+    pub match_id: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub challenger: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn open_dispute(ctx: Context<OpenDispute>, proposed_team: u8) -> Result<()> {
+    let current_time = TimeUtils::get_current_timestamp();
+    let bond = ctx.accounts.global_state.dispute_bond;
+    
+    require!(
+        ctx.accounts.match_state.is_dispute_window_open(current_time),
+        TrendXBetError::DisputeWindowClosed
+    );
+    let original_team = ctx.accounts.match_state.winning_team.ok_or(TrendXBetError::MatchNotSettled)?;
+    ValidationUtils::validate_team_selection(proposed_team)?;
+    require_neq!(proposed_team, original_team, TrendXBetError::ProposedWinnerUnchanged);
+    
+    // The bond is held by the dispute account until the arbiters decide
+    let transfer_ctx = CpiContext::new(
+        ctx.accounts.system_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.challenger.to_account_info(),
+            to: ctx.accounts.dispute_state.to_account_info(),
+        },
+    );
+    transfer(transfer_ctx, bond)?;
+    
+    ctx.accounts.dispute_state.initialize(
+        ctx.accounts.match_id.key(),
+        ctx.accounts.challenger.key(),
+        bond,
+        original_team,
+        proposed_team,
+        ctx.bumps.dispute_state,
+        current_time,
+    );
+    ctx.accounts.match_state.is_disputed = true;
+    
+    emit!(DisputeOpened {
+        match_id: ctx.accounts.match_id.key(),
+        challenger: ctx.accounts.challenger.key(),
+        bond,
+        original_team,
+        proposed_team,
+        timestamp: current_time,
+    });
+    
+    msg!("Dispute opened on match {}", ctx.accounts.match_id.key());
+    Ok(())
+}
+
+// Vote Dispute
+#[derive(Accounts)]
+pub struct VoteDispute<'info> {
+    #[account(
+        mut,
+        seeds = [DISPUTE_SEED, match_id.key().as_ref()],
+        bump = dispute_state.bump,
+        constraint = dispute_state.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
+    )]
+    pub dispute_state: Account<'info, DisputeState>,
+    
+    #[account(
+        seeds = [ARBITER_SET_SEED],
+        bump = arbiter_set.bump,
+        constraint = arbiter_set.is_arbiter(&arbiter.key()) @ TrendXBetError::NotAnArbiter,
+        constraint = arbiter_set.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
+    )]
+    pub arbiter_set: Account<'info, ArbiterSet>,
+    
+    #[account(
+        mut,
+        seeds = [MATCH_SEED, match_id.key().as_ref()],
+        bump = match_state.bump,
+        constraint = match_state.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
+    )]
+    pub match_state: Account<'info, MatchState>,
+    
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED],
+        bump = treasury.bump,
+        constraint = treasury.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
+    )]
+    pub treasury: Account<'info, TreasuryState>,
+    
+    /// CHECK: Treasury PDA that holds deposited lamports
+    #[account(mut, address = treasury.key() @ TrendXBetError::InvalidAccount)]
+    pub treasury_account: UncheckedAccount<'info>,
+    
+    /// CHECK: Challenger, refunded the bond when the dispute is upheld
+    #[account(mut, address = dispute_state.challenger @ TrendXBetError::InvalidAccount)]
+    pub challenger: UncheckedAccount<'info>,
+    
+    /// CHECK: Match identifier
+    pub match_id: UncheckedAccount<'info>,
+    
+    pub arbiter: Signer<'info>,
+}
+
+pub fn vote_dispute(ctx: Context<VoteDispute>, uphold: bool) -> Result<()> {
+    let dispute_state = &mut ctx.accounts.dispute_state;
+    let match_state = &mut ctx.accounts.match_state;
+    let treasury = &mut ctx.accounts.treasury;
+    let current_time = TimeUtils::get_current_timestamp();
+    
+    dispute_state.record_vote(ctx.accounts.arbiter.key(), uphold, ctx.accounts.arbiter_set.quorum, current_time)?;
+    
+    emit!(DisputeVoteCast {
+        match_id: ctx.accounts.match_id.key(),
+        arbiter: ctx.accounts.arbiter.key(),
+        uphold,
+        votes_uphold: dispute_state.votes_uphold,
+        votes_reject: dispute_state.votes_reject,
+        timestamp: current_time,
+    });
+    
+    let upheld = match dispute_state.status {
+        DisputeStatus::Open | DisputeStatus::Expired => {
+            msg!("Dispute vote recorded on match {}", ctx.accounts.match_id.key());
+            return Ok(());
+        }
+        DisputeStatus::Upheld => true,
+        DisputeStatus::Rejected => false,
+    };
+    
+    // Quorum reached: an upheld dispute overturns the result and returns the bond,
+    // a rejected one keeps the result and forfeits the bond to the treasury
+    let bond = dispute_state.bond;
+    dispute_state.sub_lamports(bond)?;
+    if upheld {
+        match_state.resolve_dispute(Some(dispute_state.proposed_team), current_time);
+        ctx.accounts.challenger.add_lamports(bond)?;
+    } else {
+        match_state.resolve_dispute(None, current_time);
+        ctx.accounts.treasury_account.add_lamports(bond)?;
+        treasury.add_forfeit(bond)?;
+    }
+    
+    emit!(DisputeResolved {
+        match_id: ctx.accounts.match_id.key(),
+        challenger: dispute_state.challenger,
+        upheld,
+        winning_team: match_state.winning_team.unwrap_or(dispute_state.original_team),
+        bond,
+        timestamp: current_time,
+    });
+    
+    msg!("Dispute on match {} resolved, upheld: {}", ctx.accounts.match_id.key(), upheld);
+    Ok(())
+}
+
+// Expire Dispute
+#[derive(Accounts)]
+pub struct ExpireDispute<'info> {
+    #[account(
+        mut,
+        seeds = [DISPUTE_SEED, match_id.key().as_ref()],
+        bump = dispute_state.bump,
+        constraint = dispute_state.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
+    )]
+    pub dispute_state: Account<'info, DisputeState>,
+    
+    #[account(
+        mut,
+        seeds = [MATCH_SEED, match_id.key().as_ref()],
+        bump = match_state.bump,
+        constraint = match_state.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
+    )]
+    pub match_state: Account<'info, MatchState>,
+    
+    /// CHECK: Challenger, refunded the bond
+    #[account(mut, address = dispute_state.challenger @ TrendXBetError::InvalidAccount)]
+    pub challenger: UncheckedAccount<'info>,
+    
+    /// CHECK: Match identifier
+    pub match_id: UncheckedAccount<'info>,
+    
+    pub authority: Signer<'info>,
+}
+
+pub fn expire_dispute(ctx: Context<ExpireDispute>) -> Result<()> {
+    let dispute_state = &mut ctx.accounts.dispute_state;
+    let match_state = &mut ctx.accounts.match_state;
+    let current_time = TimeUtils::get_current_timestamp();
+    
+    // Anyone may expire a dispute the arbiters failed to decide in time:
+    // the result stands and the challenger gets the bond back
+    dispute_state.expire(current_time)?;
+    let bond = dispute_state.bond;
+    dispute_state.sub_lamports(bond)?;
+    ctx.accounts.challenger.add_lamports(bond)?;
+    match_state.resolve_dispute(None, current_time);
+    
+    emit!(DisputeExpired {
+        match_id: ctx.accounts.match_id.key(),
+        challenger: dispute_state.challenger,
+        bond,
+        timestamp: current_time,
+    });
+    
+    msg!("Dispute on match {} expired", ctx.accounts.match_id.key());
+    Ok(())
+}
@@ -1,8 +1,9 @@
 use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
 use crate::state::*;
 use crate::constants::*;
 use crate::error::TrendXBetError;
-use crate::utils::{ValidationUtils, TimeUtils};
+use crate::utils::{ValidationUtils, TimeUtils, IndexUtils, AuditUtils};
 use crate::events::*;
 
 // Create Match
@@ -20,7 +21,8 @@ pub struct CreateMatch<'info> {
     #[account(
         seeds = [PLATFORM_SEED],
         bump = global_state.bump,
-        constraint = global_state.is_operational() @ TrendXBetError::PlatformPaused
+        constraint = global_state.is_operational() @ TrendXBetError::PlatformPaused,
+        constraint = global_state.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
     )]
     pub global_state: Account<'info, GlobalState>,
     
@@ -31,6 +33,27 @@ pub struct CreateMatch<'info> {
     pub authority: Signer<'info>,
     
     pub system_program: Program<'info, System>,
+    
+    /// Registry the match is listed in; without it the match is not indexed
+    #[account(
+        mut,
+        seeds = [MATCH_REGISTRY_SEED],
+        bump = registry.bump,
+        constraint = registry.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
+    )]
+    pub registry: Option<Account<'info, MatchRegistry>>,
+    
+    /// Page of the Scheduled status index with room for the match
+    #[account(mut, constraint = status_index.is_current_version() @ TrendXBetError::UnsupportedAccountVersion)]
+    pub status_index: Option<Account<'info, MatchIndexPage>>,
+    
+    /// Page of the index for the match's start day with room for it
+    #[account(mut, constraint = start_day_index.is_current_version() @ TrendXBetError::UnsupportedAccountVersion)]
+    pub start_day_index: Option<Account<'info, MatchIndexPage>>,
+    
+    /// Page of the index of the league the match belongs to, if any
+    #[account(mut, constraint = league_index.is_current_version() @ TrendXBetError::UnsupportedAccountVersion)]
+    pub league_index: Option<Account<'info, MatchIndexPage>>,
 }
 
 pub fn create_match(
@@ -40,10 +63,63 @@ pub fn create_match(
     start_time: i64,
     end_time: i64,
     description: String,
+    creator_fee: u16,
+) -> Result<()> {
+    create_market(ctx, team1, team2, MarketKind::TeamMatch, 0, start_time, end_time, description, creator_fee)
+}
+
+// Create Line Market
+#[allow(clippy::too_many_arguments)]
+pub fn create_line_market(
+    ctx: Context<CreateMatch>,
+    team1: String,
+    team2: String,
+    market_kind: MarketKind,
+    line: i32,
+    start_time: i64,
+    end_time: i64,
+    description: String,
+    creator_fee: u16,
+) -> Result<()> {
+    ValidationUtils::validate_line(market_kind, line)?;
+    create_market(ctx, team1, team2, market_kind, line, start_time, end_time, description, creator_fee)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn create_market(
+    ctx: Context<CreateMatch>,
+    team1: String,
+    team2: String,
+    market_kind: MarketKind,
+    line: i32,
+    start_time: i64,
+    end_time: i64,
+    description: String,
+    creator_fee: u16,
 ) -> Result<()> {
-    let match_state = &mut ctx.accounts.match_state;
     let current_time = TimeUtils::get_current_timestamp();
     
+    // Anyone but the admin creates a community market, bonding against abuse
+    let creator = ctx.accounts.authority.key();
+    let is_community = creator != ctx.accounts.global_state.admin;
+    require!(
+        creator_fee <= ctx.accounts.global_state.max_creator_fee && (is_community || creator_fee == 0),
+        TrendXBetError::InvalidCreatorFee
+    );
+    let creator_bond = if is_community { ctx.accounts.global_state.creator_bond } else { 0 };
+    if creator_bond > 0 {
+        let transfer_ctx = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.authority.to_account_info(),
+                to: ctx.accounts.match_state.to_account_info(),
+            },
+        );
+        transfer(transfer_ctx, creator_bond)?;
+    }
+    
+    let match_state = &mut ctx.accounts.match_state;
+    
     // Validate inputs
     ValidationUtils::validate_team_name(&team1)?;
     ValidationUtils::validate_team_name(&team2)?;
@@ -51,7 +127,7 @@ pub fn create_match(
     ValidationUtils::validate_match_times(start_time, end_time, current_time)?;
     
     // Ensure teams are different
-    require!(team1 != team2, TrendXBetError::InvalidTeam);
+    require!(team1 != team2, TrendXBetError::IdenticalTeams);
     
     // Initialize match
     match_state.initialize(
@@ -65,7 +141,34 @@ pub fn create_match(
         current_time,
     );
     
-    // Emit event
+    // Team 1 takes the over or covers the spread on line markets
+    match_state.market_kind = market_kind;
+    match_state.line = line;
+    
+    if is_community {
+        match_state.set_creator(creator, creator_bond, creator_fee);
+    }
+    
+    // Betting closes the platform's cutoff before the end of the match
+    let global_state = &ctx.accounts.global_state;
+    match_state.configure_betting(
+        start_time,
+        end_time.saturating_sub(global_state.betting_cutoff as i64),
+        global_state.early_bet_bonus,
+    )?;
+    
+    // List the match in the registry's indexes when it is enabled
+    if let Some(registry) = ctx.accounts.registry.as_deref_mut() {
+        IndexUtils::list_match(
+            registry,
+            match_state,
+            ctx.accounts.status_index.as_deref_mut(),
+            ctx.accounts.start_day_index.as_deref_mut(),
+            ctx.accounts.league_index.as_deref_mut(),
+        )?;
+    }
+    
+    // Emit events
     emit!(MatchCreated {
         match_id: ctx.accounts.match_id.key(),
         team1,
@@ -77,6 +180,25 @@ pub fn create_match(
         timestamp: current_time,
     });
     
+    if match_state.is_line_market() {
+        emit!(LineMarketCreated {
+            match_id: ctx.accounts.match_id.key(),
+            market_kind,
+            line,
+            timestamp: current_time,
+        });
+    }
+    
+    if is_community {
+        emit!(CommunityMarketCreated {
+            match_id: ctx.accounts.match_id.key(),
+            creator,
+            creator_bond,
+            creator_fee,
+            timestamp: current_time,
+        });
+    }
+    
     msg!("Match created: {} vs {}", match_state.team1, match_state.team2);
     Ok(())
 }
@@ -87,14 +209,17 @@ pub struct UpdateMatchStatus<'info> {
     #[account(
         mut,
         seeds = [MATCH_SEED, match_id.key().as_ref()],
-        bump = match_state.bump
+        bump = match_state.bump,
+        constraint = match_state.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
     )]
     pub match_state: Account<'info, MatchState>,
     
     #[account(
+        mut,
         seeds = [PLATFORM_SEED],
         bump = global_state.bump,
-        has_one = admin @ TrendXBetError::Unauthorized
+        has_one = admin @ TrendXBetError::Unauthorized,
+        constraint = global_state.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
     )]
     pub global_state: Account<'info, GlobalState>,
     
@@ -102,6 +227,18 @@ pub struct UpdateMatchStatus<'info> {
     pub match_id: UncheckedAccount<'info>,
     
     pub admin: Signer<'info>,
+    
+    /// Status index page listing the match, required for indexed matches whose status changes
+    #[account(mut, constraint = old_status_index.is_current_version() @ TrendXBetError::UnsupportedAccountVersion)]
+    pub old_status_index: Option<Account<'info, MatchIndexPage>>,
+    
+    /// Page of the new status's index with room for the match
+    #[account(mut, constraint = new_status_index.is_current_version() @ TrendXBetError::UnsupportedAccountVersion)]
+    pub new_status_index: Option<Account<'info, MatchIndexPage>>,
+    
+    /// Current audit log page, required once the audit log is enabled
+    #[account(mut, constraint = audit_log.is_current_version() @ TrendXBetError::UnsupportedAccountVersion)]
+    pub audit_log: Option<Account<'info, AuditLogPage>>,
 }
 
 pub fn update_match_status(ctx: Context<UpdateMatchStatus>, status: MatchStatus) -> Result<()> {
@@ -115,10 +252,10 @@ pub fn update_match_status(ctx: Context<UpdateMatchStatus>, status: MatchStatus)
             require!(TimeUtils::has_match_started(match_state.start_time), TrendXBetError::MatchNotStarted);
         },
         (MatchStatus::Live, MatchStatus::Ended) => {
-            require!(TimeUtils::has_match_ended(match_state.end_time), TrendXBetError::MatchNotStarted);
+            require!(TimeUtils::has_match_ended(match_state.end_time), TrendXBetError::MatchNotEnded);
         },
         (MatchStatus::Ended, MatchStatus::Settled) => {
-            require!(match_state.winning_team.is_some(), TrendXBetError::OracleResultExists);
+            require!(match_state.winning_team.is_some(), TrendXBetError::ResultNotReported);
         },
         (_, MatchStatus::Cancelled) => {
             // Admin can cancel at any time
@@ -134,23 +271,30 @@ pub fn update_match_status(ctx: Context<UpdateMatchStatus>, status: MatchStatus)
         match_state.close_betting();
     }
     
+    // Keep the status index in step
+    IndexUtils::move_status(
+        match_state,
+        &old_status,
+        ctx.accounts.old_status_index.as_deref_mut(),
+        ctx.accounts.new_status_index.as_deref_mut(),
+    )?;
+    
+    let match_id = ctx.accounts.match_id.key();
+    AuditUtils::record(
+        &mut ctx.accounts.global_state,
+        ctx.accounts.audit_log.as_deref_mut(),
+        AuditAction::MatchStatusUpdate,
+        ctx.accounts.admin.key(),
+        Some(match_id),
+        AuditLogPage::params_hash(&status)?,
+        current_time,
+    )?;
+    
     // Emit event
     emit!(MatchStatusUpdated {
         match_id: ctx.accounts.match_id.key(),
-        old_status: match old_status {
-            MatchStatus::Scheduled => 0,
-            MatchStatus::Live => 1,
-            MatchStatus::Ended => 2,
-            MatchStatus::Settled => 3,
-            MatchStatus::Cancelled => 4,
-        },
-        new_status: match status {
-            MatchStatus::Scheduled => 0,
-            MatchStatus::Live => 1,
-            MatchStatus::Ended => 2,
-            MatchStatus::Settled => 3,
-            MatchStatus::Cancelled => 4,
-        },
+        old_status: old_status.code(),
+        new_status: status.code(),
         timestamp: current_time,
     });
     
@@ -164,14 +308,17 @@ pub struct CloseMatchBetting<'info> {
     #[account(
         mut,
         seeds = [MATCH_SEED, match_id.key().as_ref()],
-        bump = match_state.bump
+        bump = match_state.bump,
+        constraint = match_state.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
     )]
     pub match_state: Account<'info, MatchState>,
     
     #[account(
+        mut,
         seeds = [PLATFORM_SEED],
         bump = global_state.bump,
-        has_one = admin @ TrendXBetError::Unauthorized
+        has_one = admin @ TrendXBetError::Unauthorized,
+        constraint = global_state.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
     )]
     pub global_state: Account<'info, GlobalState>,
     
@@ -179,6 +326,10 @@ pub struct CloseMatchBetting<'info> {
     pub match_id: UncheckedAccount<'info>,
     
     pub admin: Signer<'info>,
+    
+    /// Current audit log page, required once the audit log is enabled
+    #[account(mut, constraint = audit_log.is_current_version() @ TrendXBetError::UnsupportedAccountVersion)]
+    pub audit_log: Option<Account<'info, AuditLogPage>>,
 }
 
 pub fn close_match_betting(ctx: Context<CloseMatchBetting>) -> Result<()> {
@@ -193,6 +344,17 @@ pub fn close_match_betting(ctx: Context<CloseMatchBetting>) -> Result<()> {
     // Close betting
     match_state.close_betting();
     
+    let match_id = ctx.accounts.match_id.key();
+    AuditUtils::record(
+        &mut ctx.accounts.global_state,
+        ctx.accounts.audit_log.as_deref_mut(),
+        AuditAction::BettingClosure,
+        ctx.accounts.admin.key(),
+        Some(match_id),
+        AuditLogPage::params_hash(&match_id)?,
+        current_time,
+    )?;
+    
     // Emit event
     emit!(MatchBettingClosed {
         match_id: ctx.accounts.match_id.key(),
@@ -206,3 +368,252 @@ pub fn close_match_betting(ctx: Context<CloseMatchBetting>) -> Result<()> {
     msg!("Betting closed for match: {}", ctx.accounts.match_id.key());
     Ok(())
 }
+
+// Set Betting Window
+#[derive(Accounts)]
+pub struct SetBettingWindow<'info> {
+    #[account(
+        mut,
+        seeds = [MATCH_SEED, match_id.key().as_ref()],
+        bump = match_state.bump,
+        constraint = match_state.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
+    )]
+    pub match_state: Account<'info, MatchState>,
+    
+    #[account(
+        mut,
+        seeds = [PLATFORM_SEED],
+        bump = global_state.bump,
+        has_one = admin @ TrendXBetError::Unauthorized,
+        constraint = global_state.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    /// CHECK: Match identifier
+    pub match_id: UncheckedAccount<'info>,
+    
+    pub admin: Signer<'info>,
+    
+    /// Current audit log page, required once the audit log is enabled
+    #[account(mut, constraint = audit_log.is_current_version() @ TrendXBetError::UnsupportedAccountVersion)]
+    pub audit_log: Option<Account<'info, AuditLogPage>>,
+}
+
+pub fn set_betting_window(
+    ctx: Context<SetBettingWindow>,
+    opens_at: i64,
+    closes_at: i64,
+    early_bet_bonus: u16,
+) -> Result<()> {
+    let match_state = &mut ctx.accounts.match_state;
+    let current_time = TimeUtils::get_current_timestamp();
+    
+    // Bets already placed were weighed against the current window
+    require!(match_state.status == MatchStatus::Scheduled, TrendXBetError::InvalidMatchStatus);
+    require!(match_state.total_bets == 0, TrendXBetError::BettingWindowLocked);
+    
+    match_state.configure_betting(opens_at, closes_at, early_bet_bonus)?;
+    
+    let match_id = ctx.accounts.match_id.key();
+    AuditUtils::record(
+        &mut ctx.accounts.global_state,
+        ctx.accounts.audit_log.as_deref_mut(),
+        AuditAction::BettingWindowUpdate,
+        ctx.accounts.admin.key(),
+        Some(match_id),
+        AuditLogPage::params_hash(&(opens_at, closes_at, early_bet_bonus))?,
+        current_time,
+    )?;
+    
+    emit!(BettingWindowUpdated {
+        match_id: ctx.accounts.match_id.key(),
+        betting_opens_at: opens_at,
+        betting_closes_at: closes_at,
+        early_bet_bonus,
+        admin: ctx.accounts.admin.key(),
+        timestamp: current_time,
+    });
+    
+    msg!("Betting window set for: {}", ctx.accounts.match_id.key());
+    Ok(())
+}
+
+// Crank Match
+#[derive(Accounts)]
+pub struct CrankMatch<'info> {
+    #[account(
+        mut,
+        seeds = [MATCH_SEED, match_id.key().as_ref()],
+        bump = match_state.bump,
+        constraint = match_state.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
+    )]
+    pub match_state: Account<'info, MatchState>,
+    
+    #[account(
+        seeds = [PLATFORM_SEED],
+        bump = global_state.bump,
+        constraint = global_state.is_operational() @ TrendXBetError::PlatformPaused,
+        constraint = global_state.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED],
+        bump = treasury.bump,
+        constraint = treasury.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
+    )]
+    pub treasury: Account<'info, TreasuryState>,
+    
+    /// CHECK: Treasury PDA that holds deposited lamports
+    #[account(mut, address = treasury.key() @ TrendXBetError::InvalidAccount)]
+    pub treasury_account: UncheckedAccount<'info>,
+    
+    /// CHECK: Match identifier
+    pub match_id: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+    
+    /// Status index page listing the match, required for indexed matches whose status changes
+    #[account(mut, constraint = old_status_index.is_current_version() @ TrendXBetError::UnsupportedAccountVersion)]
+    pub old_status_index: Option<Account<'info, MatchIndexPage>>,
+    
+    /// Page of the new status's index with room for the match
+    #[account(mut, constraint = new_status_index.is_current_version() @ TrendXBetError::UnsupportedAccountVersion)]
+    pub new_status_index: Option<Account<'info, MatchIndexPage>>,
+}
+
+pub fn crank_match(ctx: Context<CrankMatch>) -> Result<()> {
+    let match_state = &mut ctx.accounts.match_state;
+    let treasury = &mut ctx.accounts.treasury;
+    let current_time = TimeUtils::get_current_timestamp();
+    let old_status = match_state.status.clone();
+    
+    // Anyone may advance the match, but only to the status the clock implies
+    let new_status = match_state
+        .crank_status(current_time)
+        .ok_or(TrendXBetError::NothingToCrank)?;
+    
+    match_state.update_status(new_status.clone());
+    
+    // Keep the status index in step
+    IndexUtils::move_status(
+        match_state,
+        &old_status,
+        ctx.accounts.old_status_index.as_deref_mut(),
+        ctx.accounts.new_status_index.as_deref_mut(),
+    )?;
+    
+    if !match_state.is_betting_closed {
+        match_state.close_betting();
+        
+        emit!(MatchBettingClosed {
+            match_id: ctx.accounts.match_id.key(),
+            total_pool: match_state.total_pool,
+            team1_pool: match_state.team1_pool,
+            team2_pool: match_state.team2_pool,
+            total_bets: match_state.total_bets,
+            timestamp: current_time,
+        });
+    }
+    
+    // Pay the bounty once per match, out of no more than the fees its own bets were charged
+    let bounty = if match_state.is_crank_bounty_paid {
+        0
+    } else {
+        ctx.accounts.global_state.crank_bounty
+            .min(match_state.platform_fees)
+            .min(treasury.available_platform_fees())
+    };
+    if bounty > 0 {
+        match_state.is_crank_bounty_paid = true;
+        ctx.accounts.treasury_account.sub_lamports(bounty)?;
+        ctx.accounts.cranker.add_lamports(bounty)?;
+        treasury.withdraw_platform_fees(bounty)?;
+    }
+    
+    emit!(MatchStatusUpdated {
+        match_id: ctx.accounts.match_id.key(),
+        old_status: old_status.code(),
+        new_status: new_status.code(),
+        timestamp: current_time,
+    });
+    
+    emit!(MatchCranked {
+        match_id: ctx.accounts.match_id.key(),
+        cranker: ctx.accounts.cranker.key(),
+        bounty,
+        timestamp: current_time,
+    });
+    
+    msg!("Match {} cranked to status {}", ctx.accounts.match_id.key(), new_status.code());
+    Ok(())
+}
+
+// Void Match
+#[derive(Accounts)]
+pub struct VoidMatch<'info> {
+    #[account(
+        mut,
+        seeds = [MATCH_SEED, match_id.key().as_ref()],
+        bump = match_state.bump,
+        constraint = match_state.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
+    )]
+    pub match_state: Account<'info, MatchState>,
+    
+    /// CHECK: Match identifier
+    pub match_id: UncheckedAccount<'info>,
+    
+    pub authority: Signer<'info>,
+    
+    /// Status index page listing the match, required for indexed matches whose status changes
+    #[account(mut, constraint = old_status_index.is_current_version() @ TrendXBetError::UnsupportedAccountVersion)]
+    pub old_status_index: Option<Account<'info, MatchIndexPage>>,
+    
+    /// Page of the new status's index with room for the match
+    #[account(mut, constraint = new_status_index.is_current_version() @ TrendXBetError::UnsupportedAccountVersion)]
+    pub new_status_index: Option<Account<'info, MatchIndexPage>>,
+}
+
+pub fn void_match(ctx: Context<VoidMatch>) -> Result<()> {
+    let match_state = &mut ctx.accounts.match_state;
+    let current_time = TimeUtils::get_current_timestamp();
+    let old_status = match_state.status.clone();
+    
+    // Anyone may void a match the oracles failed to resolve in time
+    require!(match_state.is_awaiting_result(), TrendXBetError::InvalidMatchStatus);
+    require!(
+        match_state.has_missed_oracle_deadline(current_time),
+        TrendXBetError::VoidDeadlineNotReached
+    );
+    
+    match_state.void();
+    
+    // Keep the status index in step
+    IndexUtils::move_status(
+        match_state,
+        &old_status,
+        ctx.accounts.old_status_index.as_deref_mut(),
+        ctx.accounts.new_status_index.as_deref_mut(),
+    )?;
+    
+    emit!(MatchStatusUpdated {
+        match_id: ctx.accounts.match_id.key(),
+        old_status: old_status.code(),
+        new_status: MatchStatus::Voided.code(),
+        timestamp: current_time,
+    });
+    
+    emit!(MatchVoided {
+        match_id: ctx.accounts.match_id.key(),
+        voided_by: ctx.accounts.authority.key(),
+        oracle_reports: match_state.oracle_reports,
+        total_pool: match_state.total_pool,
+        total_bets: match_state.total_bets,
+        timestamp: current_time,
+    });
+    
+    msg!("Match voided: {}", ctx.accounts.match_id.key());
+    Ok(())
+}
@@ -0,0 +1,123 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_lang::Discriminator;
+use crate::state::*;
+use crate::constants::*;
+use crate::error::TrendXBetError;
+use crate::utils::{TimeUtils, AuditUtils};
+use crate::events::*;
+
+// Migrate Account
+#[derive(Accounts)]
+pub struct MigrateAccount<'info> {
+    /// CHECK: Program account to upgrade; its type is read from the discriminator
+    #[account(mut, owner = crate::ID)]
+    pub account: UncheckedAccount<'info>,
+
+    /// CHECK: Platform state, read at any layout version to authorize the admin
+    #[account(
+        mut,
+        seeds = [PLATFORM_SEED],
+        bump
+    )]
+    pub global_state: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Current audit log page, required once the audit log is enabled
+    #[account(mut, constraint = audit_log.is_current_version() @ TrendXBetError::UnsupportedAccountVersion)]
+    pub audit_log: Option<Account<'info, AuditLogPage>>,
+}
+
+pub fn migrate_account(ctx: Context<MigrateAccount>) -> Result<()> {
+    let global_state = VersionUtils::load::<GlobalState>(&ctx.accounts.global_state)?;
+    require_keys_eq!(global_state.admin, ctx.accounts.admin.key(), TrendXBetError::Unauthorized);
+
+    let current_time = TimeUtils::get_current_timestamp();
+    let discriminator = {
+        let data = ctx.accounts.account.try_borrow_data()?;
+        require!(data.len() >= 8, ErrorCode::AccountDiscriminatorNotFound);
+        data[..8].to_vec()
+    };
+
+    // Dispatch on the account type; each upgrade runs every step up to the current version
+    let (account_type, (from_version, to_version)) = match discriminator.as_slice() {
+        d if d == GlobalState::DISCRIMINATOR => ("global", upgrade_in_place::<GlobalState>(&ctx)?),
+        d if d == UserState::DISCRIMINATOR => ("user", upgrade_in_place::<UserState>(&ctx)?),
+        d if d == MatchState::DISCRIMINATOR => ("match", upgrade_in_place::<MatchState>(&ctx)?),
+        d if d == BetState::DISCRIMINATOR => ("bet", upgrade_in_place::<BetState>(&ctx)?),
+        d if d == TreasuryState::DISCRIMINATOR => ("treasury", upgrade_in_place::<TreasuryState>(&ctx)?),
+        d if d == OracleState::DISCRIMINATOR => ("oracle", upgrade_in_place::<OracleState>(&ctx)?),
+        d if d == OracleProfile::DISCRIMINATOR => ("oracle_profile", upgrade_in_place::<OracleProfile>(&ctx)?),
+        d if d == PriceMarket::DISCRIMINATOR => ("price_market", upgrade_in_place::<PriceMarket>(&ctx)?),
+        d if d == ArbiterSet::DISCRIMINATOR => ("arbiter_set", upgrade_in_place::<ArbiterSet>(&ctx)?),
+        d if d == DisputeState::DISCRIMINATOR => ("dispute", upgrade_in_place::<DisputeState>(&ctx)?),
+        d if d == MatchRegistry::DISCRIMINATOR => ("match_registry", upgrade_in_place::<MatchRegistry>(&ctx)?),
+        d if d == MatchIndexPage::DISCRIMINATOR => ("match_index", upgrade_in_place::<MatchIndexPage>(&ctx)?),
+        d if d == UsernameRecord::DISCRIMINATOR => ("username", upgrade_in_place::<UsernameRecord>(&ctx)?),
+        d if d == SessionKey::DISCRIMINATOR => ("session_key", upgrade_in_place::<SessionKey>(&ctx)?),
+        d if d == Promoter::DISCRIMINATOR => ("promoter", upgrade_in_place::<Promoter>(&ctx)?),
+        d if d == PrivatePool::DISCRIMINATOR => ("private_pool", upgrade_in_place::<PrivatePool>(&ctx)?),
+        d if d == OddsHistory::DISCRIMINATOR => ("odds_history", upgrade_in_place::<OddsHistory>(&ctx)?),
+        d if d == AuditLogPage::DISCRIMINATOR => ("audit_log", upgrade_in_place::<AuditLogPage>(&ctx)?),
+        _ => return err!(ErrorCode::AccountDiscriminatorMismatch),
+    };
+
+    // Reload the platform state, which may be the account just migrated; an
+    // outdated one has no audit log yet
+    let mut global_state = VersionUtils::load::<GlobalState>(&ctx.accounts.global_state)?;
+    if global_state.is_current_version() && global_state.has_audit_log {
+        AuditUtils::record(
+            &mut global_state,
+            ctx.accounts.audit_log.as_deref_mut(),
+            AuditAction::AccountMigration,
+            ctx.accounts.admin.key(),
+            Some(ctx.accounts.account.key()),
+            AuditLogPage::params_hash(&(account_type, from_version, to_version))?,
+            current_time,
+        )?;
+        VersionUtils::store(&ctx.accounts.global_state.to_account_info(), &global_state)?;
+    }
+
+    emit!(AccountMigrated {
+        account: ctx.accounts.account.key(),
+        account_type: account_type.to_string(),
+        from_version,
+        to_version,
+        admin: ctx.accounts.admin.key(),
+        timestamp: current_time,
+    });
+
+    msg!("Migrated {} account from version {} to {}", account_type, from_version, to_version);
+    Ok(())
+}
+
+/// Upgrade `ctx.accounts.account` to `T`'s current layout, growing it and
+/// topping up rent from the admin when the layout needs more space
+fn upgrade_in_place<T: Versioned>(ctx: &Context<MigrateAccount>) -> Result<(u8, u8)> {
+    let account = ctx.accounts.account.to_account_info();
+    let migration = VersionUtils::migrate::<T>(&account)?;
+
+    if account.data_len() < T::SPACE {
+        let shortfall = Rent::get()?
+            .minimum_balance(T::SPACE)
+            .saturating_sub(account.lamports());
+        if shortfall > 0 {
+            let transfer_ctx = CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.admin.to_account_info(),
+                    to: account.clone(),
+                },
+            );
+            transfer(transfer_ctx, shortfall)?;
+        }
+        account.resize(T::SPACE)?;
+    }
+
+    VersionUtils::store(&account, &migration.state)?;
+    Ok((migration.from_version, migration.to_version))
+}
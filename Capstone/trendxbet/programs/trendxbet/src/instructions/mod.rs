@@ -3,11 +3,33 @@ pub mod user_instructions;
 pub mod match_instructions;
 pub mod bet_instructions;
 pub mod oracle_instructions;
+pub mod oracle_stake_instructions;
 pub mod admin_instructions;
+pub mod migration_instructions;
+pub mod price_market_instructions;
+pub mod dispute_instructions;
+pub mod registry_instructions;
+pub mod session_instructions;
+pub mod promotion_instructions;
+pub mod creator_instructions;
+pub mod private_pool_instructions;
+pub mod odds_history_instructions;
+pub mod audit_log_instructions;
 
 pub use initialize::*;
 pub use user_instructions::*;
 pub use match_instructions::*;
 pub use bet_instructions::*;
 pub use oracle_instructions::*;
+pub use oracle_stake_instructions::*;
 pub use admin_instructions::*;
+pub use migration_instructions::*;
+pub use price_market_instructions::*;
+pub use dispute_instructions::*;
+pub use registry_instructions::*;
+pub use session_instructions::*;
+pub use promotion_instructions::*;
+pub use creator_instructions::*;
+pub use private_pool_instructions::*;
+pub use odds_history_instructions::*;
+pub use audit_log_instructions::*;
@@ -0,0 +1,79 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::error::TrendXBetError;
+use crate::utils::TimeUtils;
+use crate::events::*;
+
+// Create Odds History
+#[derive(Accounts)]
+pub struct CreateOddsHistory<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = OddsHistory::LEN,
+        seeds = [ODDS_HISTORY_SEED, match_id.key().as_ref()],
+        bump
+    )]
+    pub odds_history: Box<Account<'info, OddsHistory>>,
+    
+    #[account(
+        mut,
+        seeds = [MATCH_SEED, match_id.key().as_ref()],
+        bump = match_state.bump,
+        constraint = match_state.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
+    )]
+    pub match_state: Account<'info, MatchState>,
+    
+    /// CHECK: Match identifier
+    pub match_id: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn create_odds_history(ctx: Context<CreateOddsHistory>) -> Result<()> {
+    let match_state = &mut ctx.accounts.match_state;
+    let current_time = TimeUtils::get_current_timestamp();
+    
+    // Anyone may start charting a match while its pools can still move
+    require!(match_state.is_awaiting_result(), TrendXBetError::InvalidMatchStatus);
+    
+    ctx.accounts.odds_history.initialize(
+        ctx.accounts.match_id.key(),
+        match_state,
+        ctx.bumps.odds_history,
+        current_time,
+    );
+    match_state.enable_odds_history();
+    
+    emit!(OddsHistoryCreated {
+        match_id: ctx.accounts.match_id.key(),
+        payer: ctx.accounts.payer.key(),
+        timestamp: current_time,
+    });
+    
+    msg!("Odds history created for match: {}", ctx.accounts.match_id.key());
+    Ok(())
+}
+
+// Get Odds History
+#[derive(Accounts)]
+pub struct GetOddsHistory<'info> {
+    #[account(
+        seeds = [ODDS_HISTORY_SEED, match_id.key().as_ref()],
+        bump = odds_history.bump,
+        constraint = odds_history.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
+    )]
+    pub odds_history: Box<Account<'info, OddsHistory>>,
+    
+    /// CHECK: Match identifier
+    pub match_id: UncheckedAccount<'info>,
+}
+
+pub fn get_odds_history(ctx: Context<GetOddsHistory>, offset: u16) -> Result<Vec<OddsPoint>> {
+    // Oldest first; longer series are read a page at a time
+    Ok(ctx.accounts.odds_history.page(offset))
+}
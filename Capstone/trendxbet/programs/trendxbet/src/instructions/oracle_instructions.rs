@@ -2,7 +2,7 @@ use anchor_lang::prelude::*;
 use crate::state::*;
 use crate::constants::*;
 use crate::error::TrendXBetError;
-use crate::utils::{ValidationUtils, TimeUtils};
+use crate::utils::{ValidationUtils, TimeUtils, IndexUtils, AuditUtils};
 use crate::events::*;
 
 // Register Oracle
@@ -18,18 +18,31 @@ pub struct RegisterOracle<'info> {
     pub oracle_state: Account<'info, OracleState>,
     
     #[account(
+        mut,
         seeds = [PLATFORM_SEED],
         bump = global_state.bump,
-        has_one = admin @ TrendXBetError::Unauthorized
+        has_one = admin @ TrendXBetError::Unauthorized,
+        constraint = global_state.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
     )]
     pub global_state: Account<'info, GlobalState>,
     
     #[account(
         seeds = [MATCH_SEED, match_id.key().as_ref()],
-        bump = match_state.bump
+        bump = match_state.bump,
+        constraint = match_state.is_oracle_resolved() @ TrendXBetError::InvalidMarketType,
+        constraint = match_state.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
     )]
     pub match_state: Account<'info, MatchState>,
     
+    #[account(
+        seeds = [ORACLE_PROFILE_SEED, oracle_authority.key().as_ref()],
+        bump = oracle_profile.bump,
+        constraint = oracle_profile.stake >= MIN_ORACLE_STAKE @ TrendXBetError::InsufficientOracleStake,
+        constraint = oracle_profile.reputation >= MIN_ORACLE_REPUTATION @ TrendXBetError::OracleReputationTooLow,
+        constraint = oracle_profile.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
+    )]
+    pub oracle_profile: Account<'info, OracleProfile>,
+    
     /// CHECK: Oracle authority
     pub oracle_authority: UncheckedAccount<'info>,
     
@@ -42,6 +55,10 @@ pub struct RegisterOracle<'info> {
     pub authority: Signer<'info>,
     
     pub system_program: Program<'info, System>,
+    
+    /// Current audit log page, required once the audit log is enabled
+    #[account(mut, constraint = audit_log.is_current_version() @ TrendXBetError::UnsupportedAccountVersion)]
+    pub audit_log: Option<Account<'info, AuditLogPage>>,
 }
 
 pub fn register_oracle(ctx: Context<RegisterOracle>, oracle_authority: Pubkey) -> Result<()> {
@@ -55,6 +72,17 @@ pub fn register_oracle(ctx: Context<RegisterOracle>, oracle_authority: Pubkey) -
         ctx.bumps.oracle_state,
     );
     
+    let match_id = ctx.accounts.match_id.key();
+    AuditUtils::record(
+        &mut ctx.accounts.global_state,
+        ctx.accounts.audit_log.as_deref_mut(),
+        AuditAction::OracleRegistration,
+        ctx.accounts.admin.key(),
+        Some(oracle_authority),
+        AuditLogPage::params_hash(&(oracle_authority, match_id))?,
+        current_time,
+    )?;
+    
     // Emit event
     emit!(OracleRegistered {
         oracle_authority,
@@ -66,14 +94,76 @@ pub fn register_oracle(ctx: Context<RegisterOracle>, oracle_authority: Pubkey) -
     Ok(())
 }
 
-// Update Match Result
+// Commit Match Result
+#[derive(Accounts)]
+pub struct CommitMatchResult<'info> {
+    #[account(
+        mut,
+        seeds = [ORACLE_SEED, oracle_authority.key().as_ref(), match_id.key().as_ref()],
+        bump = oracle_state.bump,
+        constraint = oracle_state.is_authorized_oracle(&authority.key()) @ TrendXBetError::InvalidOracleAuthority,
+        constraint = oracle_state.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
+    )]
+    pub oracle_state: Account<'info, OracleState>,
+    
+    #[account(
+        seeds = [MATCH_SEED, match_id.key().as_ref()],
+        bump = match_state.bump,
+        constraint = match_state.status == MatchStatus::Ended @ TrendXBetError::InvalidMatchStatus,
+        constraint = match_state.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
+    )]
+    pub match_state: Account<'info, MatchState>,
+    
+    #[account(
+        seeds = [PLATFORM_SEED],
+        bump = global_state.bump,
+        constraint = global_state.is_operational() @ TrendXBetError::PlatformPaused,
+        constraint = global_state.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    /// CHECK: Oracle authority
+    pub oracle_authority: UncheckedAccount<'info>,
+    
+    /// CHECK: Match identifier
+    pub match_id: UncheckedAccount<'info>,
+    
+    pub authority: Signer<'info>,
+}
+
+pub fn commit_match_result(ctx: Context<CommitMatchResult>, commitment: [u8; 32]) -> Result<()> {
+    let oracle_state = &mut ctx.accounts.oracle_state;
+    let current_time = TimeUtils::get_current_timestamp();
+    
+    // Commitments are only taken before anyone can reveal
+    require!(
+        TimeUtils::is_oracle_commit_open(ctx.accounts.match_state.end_time, current_time),
+        TrendXBetError::CommitWindowClosed
+    );
+    
+    oracle_state.commit(commitment, current_time)?;
+    
+    // Emit event
+    emit!(OracleResultCommitted {
+        match_id: ctx.accounts.match_id.key(),
+        oracle_authority: ctx.accounts.oracle_authority.key(),
+        commitment,
+        timestamp: current_time,
+    });
+    
+    msg!("Oracle result committed for match: {}", ctx.accounts.match_id.key());
+    Ok(())
+}
+
+// Reveal Match Result
 #[derive(Accounts)]
-pub struct UpdateMatchResult<'info> {
+pub struct RevealMatchResult<'info> {
     #[account(
         mut,
         seeds = [ORACLE_SEED, oracle_authority.key().as_ref(), match_id.key().as_ref()],
         bump = oracle_state.bump,
-        constraint = oracle_state.is_authorized_oracle(&oracle_authority.key()) @ TrendXBetError::InvalidOracleAuthority
+        constraint = oracle_state.is_authorized_oracle(&authority.key()) @ TrendXBetError::InvalidOracleAuthority,
+        constraint = oracle_state.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
     )]
     pub oracle_state: Account<'info, OracleState>,
     
@@ -81,14 +171,16 @@ pub struct UpdateMatchResult<'info> {
         mut,
         seeds = [MATCH_SEED, match_id.key().as_ref()],
         bump = match_state.bump,
-        constraint = match_state.status == MatchStatus::Ended @ TrendXBetError::InvalidMatchStatus
+        constraint = match_state.status == MatchStatus::Ended @ TrendXBetError::InvalidMatchStatus,
+        constraint = match_state.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
     )]
     pub match_state: Account<'info, MatchState>,
     
     #[account(
         seeds = [PLATFORM_SEED],
         bump = global_state.bump,
-        constraint = global_state.is_operational() @ TrendXBetError::PlatformPaused
+        constraint = global_state.is_operational() @ TrendXBetError::PlatformPaused,
+        constraint = global_state.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
     )]
     pub global_state: Account<'info, GlobalState>,
     
@@ -101,36 +193,49 @@ pub struct UpdateMatchResult<'info> {
     pub authority: Signer<'info>,
 }
 
-pub fn update_match_result(
-    ctx: Context<UpdateMatchResult>,
+pub fn reveal_match_result(
+    ctx: Context<RevealMatchResult>,
     winning_team: u8,
     final_score: String,
+    match_score: Option<MatchScore>,
+    salt: [u8; 32],
+    evidence: Option<ReportEvidence>,
 ) -> Result<()> {
     let oracle_state = &mut ctx.accounts.oracle_state;
     let match_state = &mut ctx.accounts.match_state;
     let current_time = TimeUtils::get_current_timestamp();
     
-    // Validate inputs
-    ValidationUtils::validate_team_selection(winning_team)?;
-    require!(final_score.len() <= MAX_SCORE_LENGTH, TrendXBetError::DescriptionTooLong);
-    
-    // Check if oracle update is within valid time window
+    // Reveals open once commitments close; late reveals never count
+    require_gt!(
+        current_time,
+        match_state.end_time + ORACLE_COMMIT_WINDOW,
+        TrendXBetError::RevealWindowNotOpen
+    );
     require!(
-        oracle_state.is_update_valid(match_state.end_time, current_time),
+        TimeUtils::is_oracle_reveal_open(match_state.end_time, current_time),
         TrendXBetError::OracleUpdateExpired
     );
     
-    // Submit or update result
-    if oracle_state.reported_result.is_none() {
-        oracle_state.submit_result(winning_team, final_score.clone(), current_time)?;
-    } else {
-        oracle_state.update_result(winning_team, final_score.clone(), current_time)?;
+    // Validate inputs
+    require_gte!(MAX_SCORE_LENGTH, final_score.len(), TrendXBetError::FinalScoreTooLong);
+    if let Some(score) = &match_score {
+        ValidationUtils::validate_match_score(score)?;
     }
-    
-    // If this oracle has minimum confirmations, update match result
-    if oracle_state.has_minimum_confirmations() {
-        match_state.set_result(winning_team, final_score.clone(), current_time);
+    if let Some(evidence) = &evidence {
+        ValidationUtils::validate_evidence(evidence)?;
     }
+    match_state.validate_report(winning_team, match_score.as_ref())?;
+    
+    // The report must be the one committed to
+    oracle_state.reveal(
+        winning_team,
+        final_score.clone(),
+        match_score.clone(),
+        evidence.clone(),
+        &salt,
+        current_time,
+    )?;
+    match_state.record_oracle_report();
     
     // Emit event
     emit!(MatchResultUpdated {
@@ -138,10 +243,12 @@ pub fn update_match_result(
         oracle_authority: ctx.accounts.oracle_authority.key(),
         winning_team,
         final_score,
+        match_score,
+        evidence,
         timestamp: current_time,
     });
     
-    msg!("Match result updated by oracle: team {} wins", winning_team);
+    msg!("Match result revealed by oracle: team {} wins", winning_team);
     Ok(())
 }
 
@@ -151,21 +258,25 @@ pub struct ValidateOracleUpdate<'info> {
     #[account(
         mut,
         seeds = [ORACLE_SEED, oracle_authority.key().as_ref(), match_id.key().as_ref()],
-        bump = oracle_state.bump
+        bump = oracle_state.bump,
+        constraint = oracle_state.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
     )]
     pub oracle_state: Account<'info, OracleState>,
     
     #[account(
         mut,
         seeds = [MATCH_SEED, match_id.key().as_ref()],
-        bump = match_state.bump
+        bump = match_state.bump,
+        constraint = match_state.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
     )]
     pub match_state: Account<'info, MatchState>,
     
     #[account(
+        mut,
         seeds = [PLATFORM_SEED],
         bump = global_state.bump,
-        has_one = admin @ TrendXBetError::Unauthorized
+        has_one = admin @ TrendXBetError::Unauthorized,
+        constraint = global_state.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
     )]
     pub global_state: Account<'info, GlobalState>,
     
@@ -176,27 +287,114 @@ pub struct ValidateOracleUpdate<'info> {
     pub match_id: UncheckedAccount<'info>,
     
     pub admin: Signer<'info>,
+    
+    /// Status index page listing the match, required for indexed matches whose status changes
+    #[account(mut, constraint = old_status_index.is_current_version() @ TrendXBetError::UnsupportedAccountVersion)]
+    pub old_status_index: Option<Account<'info, MatchIndexPage>>,
+    
+    /// Page of the new status's index with room for the match
+    #[account(mut, constraint = new_status_index.is_current_version() @ TrendXBetError::UnsupportedAccountVersion)]
+    pub new_status_index: Option<Account<'info, MatchIndexPage>>,
+    
+    /// Current audit log page, required once the audit log is enabled
+    #[account(mut, constraint = audit_log.is_current_version() @ TrendXBetError::UnsupportedAccountVersion)]
+    pub audit_log: Option<Account<'info, AuditLogPage>>,
 }
 
-pub fn validate_oracle_update(ctx: Context<ValidateOracleUpdate>) -> Result<()> {
+pub fn validate_oracle_update<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ValidateOracleUpdate<'info>>,
+) -> Result<()> {
     let oracle_state = &mut ctx.accounts.oracle_state;
     let match_state = &mut ctx.accounts.match_state;
     let current_time = TimeUtils::get_current_timestamp();
     
+    require!(!oracle_state.is_validated, TrendXBetError::OracleResultExists);
+    require!(
+        match_state.status == MatchStatus::Ended,
+        TrendXBetError::InvalidMatchStatus
+    );
+    let reported_result = oracle_state
+        .reported_result
+        .ok_or(TrendXBetError::ResultNotRevealed)?;
+    let reported_match_score = oracle_state.reported_match_score.clone();
+    
+    // Peer oracle states for the same match are passed as remaining accounts;
+    // each one that revealed the same result and score counts as a confirmation
+    let mut peers: Vec<Pubkey> = Vec::with_capacity(ctx.remaining_accounts.len());
+    for peer_info in ctx.remaining_accounts.iter() {
+        require!(
+            peer_info.key() != oracle_state.key() && !peers.contains(&peer_info.key()),
+            TrendXBetError::DuplicatePeerOracle
+        );
+        
+        let peer = Account::<OracleState>::try_from(peer_info)?;
+        require!(
+            peer.is_current_version(),
+            TrendXBetError::UnsupportedAccountVersion
+        );
+        require_keys_eq!(
+            peer.match_id,
+            ctx.accounts.match_id.key(),
+            TrendXBetError::PeerOracleMatchMismatch
+        );
+        
+        if peer.matches_report(reported_result, &reported_match_score) {
+            oracle_state.add_confirmation()?;
+        }
+        peers.push(peer_info.key());
+    }
+    
     // Validate the oracle result
     oracle_state.validate(current_time)?;
     
-    // If not already settled and oracle is ready, settle the match
-    if !match_state.is_settled && oracle_state.is_ready() {
+    // If not already settled and oracle is ready, settle the match;
+    // a line market that pushed is voided instead so its bets are refunded
+    if match_state.is_awaiting_result() && oracle_state.is_ready() {
         if let (Some(winning_team), Some(final_score)) = (
             oracle_state.get_validated_result(),
             oracle_state.get_validated_score(),
         ) {
-            match_state.set_result(winning_team, final_score, current_time);
-            match_state.settle();
+            let match_score = oracle_state.get_validated_match_score();
+            if winning_team == PUSH_RESULT {
+                match_state.push(final_score, match_score, current_time);
+                
+                emit!(MatchVoided {
+                    match_id: ctx.accounts.match_id.key(),
+                    voided_by: ctx.accounts.admin.key(),
+                    oracle_reports: match_state.oracle_reports,
+                    total_pool: match_state.total_pool,
+                    total_bets: match_state.total_bets,
+                    timestamp: current_time,
+                });
+            } else {
+                match_state.set_result(winning_team, final_score, current_time);
+                match_state.match_score = match_score;
+                match_state.settle();
+                match_state.open_dispute_window(current_time, ctx.accounts.global_state.dispute_window);
+            }
         }
     }
     
+    // Results arrive only on ended matches
+    IndexUtils::move_status(
+        match_state,
+        &MatchStatus::Ended,
+        ctx.accounts.old_status_index.as_deref_mut(),
+        ctx.accounts.new_status_index.as_deref_mut(),
+    )?;
+    
+    let oracle_authority = ctx.accounts.oracle_authority.key();
+    let match_id = ctx.accounts.match_id.key();
+    AuditUtils::record(
+        &mut ctx.accounts.global_state,
+        ctx.accounts.audit_log.as_deref_mut(),
+        AuditAction::OracleValidation,
+        ctx.accounts.admin.key(),
+        Some(oracle_authority),
+        AuditLogPage::params_hash(&(oracle_authority, match_id, &peers))?,
+        current_time,
+    )?;
+    
     // Emit event
     emit!(OracleUpdateValidated {
         match_id: ctx.accounts.match_id.key(),
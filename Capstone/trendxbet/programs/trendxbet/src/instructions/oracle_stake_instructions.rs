@@ -0,0 +1,251 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use crate::state::*;
+use crate::constants::*;
+use crate::error::TrendXBetError;
+use crate::utils::{TimeUtils, MathUtils};
+use crate::events::*;
+
+// Create Oracle Profile
+#[derive(Accounts)]
+pub struct CreateOracleProfile<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = OracleProfile::LEN,
+        seeds = [ORACLE_PROFILE_SEED, authority.key().as_ref()],
+        bump
+    )]
+    pub oracle_profile: Account<'info, OracleProfile>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn create_oracle_profile(ctx: Context<CreateOracleProfile>) -> Result<()> {
+    let current_time = TimeUtils::get_current_timestamp();
+    
+    ctx.accounts.oracle_profile.initialize(
+        ctx.accounts.authority.key(),
+        ctx.bumps.oracle_profile,
+        current_time,
+    );
+    
+    msg!("Oracle profile created for: {}", ctx.accounts.authority.key());
+    Ok(())
+}
+
+// Bond Oracle Stake
+#[derive(Accounts)]
+pub struct BondOracleStake<'info> {
+    #[account(
+        mut,
+        seeds = [ORACLE_PROFILE_SEED, authority.key().as_ref()],
+        bump = oracle_profile.bump,
+        has_one = authority @ TrendXBetError::Unauthorized,
+        constraint = oracle_profile.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
+    )]
+    pub oracle_profile: Account<'info, OracleProfile>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn bond_oracle_stake(ctx: Context<BondOracleStake>, amount: u64) -> Result<()> {
+    let current_time = TimeUtils::get_current_timestamp();
+    
+    require!(amount > 0, TrendXBetError::ZeroAmount);
+    
+    // Bonded lamports are held by the profile account itself
+    let transfer_ctx = CpiContext::new(
+        ctx.accounts.system_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.authority.to_account_info(),
+            to: ctx.accounts.oracle_profile.to_account_info(),
+        },
+    );
+    transfer(transfer_ctx, amount)?;
+    
+    let oracle_profile = &mut ctx.accounts.oracle_profile;
+    oracle_profile.bond(amount)?;
+    
+    emit!(OracleStakeBonded {
+        oracle_authority: oracle_profile.authority,
+        amount,
+        total_stake: oracle_profile.stake,
+        timestamp: current_time,
+    });
+    
+    msg!("Oracle bonded {} lamports", amount);
+    Ok(())
+}
+
+// Unbond Oracle Stake
+#[derive(Accounts)]
+pub struct UnbondOracleStake<'info> {
+    #[account(
+        mut,
+        seeds = [ORACLE_PROFILE_SEED, authority.key().as_ref()],
+        bump = oracle_profile.bump,
+        has_one = authority @ TrendXBetError::Unauthorized,
+        constraint = oracle_profile.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
+    )]
+    pub oracle_profile: Account<'info, OracleProfile>,
+    
+    pub authority: Signer<'info>,
+}
+
+pub fn unbond_oracle_stake(ctx: Context<UnbondOracleStake>, amount: u64) -> Result<()> {
+    let oracle_profile = &mut ctx.accounts.oracle_profile;
+    let current_time = TimeUtils::get_current_timestamp();
+    
+    require!(amount > 0, TrendXBetError::ZeroAmount);
+    
+    // Unbonding stake stays slashable until it is withdrawn
+    let available_at = oracle_profile.begin_unbonding(amount, current_time)?;
+    
+    emit!(OracleStakeUnbonding {
+        oracle_authority: oracle_profile.authority,
+        amount,
+        available_at,
+        timestamp: current_time,
+    });
+    
+    msg!("Oracle unbonding {} lamports until {}", amount, available_at);
+    Ok(())
+}
+
+// Withdraw Oracle Stake
+#[derive(Accounts)]
+pub struct WithdrawOracleStake<'info> {
+    #[account(
+        mut,
+        seeds = [ORACLE_PROFILE_SEED, authority.key().as_ref()],
+        bump = oracle_profile.bump,
+        has_one = authority @ TrendXBetError::Unauthorized,
+        constraint = oracle_profile.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
+    )]
+    pub oracle_profile: Account<'info, OracleProfile>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+pub fn withdraw_oracle_stake(ctx: Context<WithdrawOracleStake>) -> Result<()> {
+    let current_time = TimeUtils::get_current_timestamp();
+    let amount = ctx.accounts.oracle_profile.withdraw_unbonded(current_time)?;
+    
+    ctx.accounts.oracle_profile.sub_lamports(amount)?;
+    ctx.accounts.authority.add_lamports(amount)?;
+    
+    emit!(OracleStakeWithdrawn {
+        oracle_authority: ctx.accounts.authority.key(),
+        amount,
+        timestamp: current_time,
+    });
+    
+    msg!("Oracle withdrew {} lamports of stake", amount);
+    Ok(())
+}
+
+// Resolve Oracle Report
+#[derive(Accounts)]
+pub struct ResolveOracleReport<'info> {
+    #[account(
+        mut,
+        seeds = [ORACLE_SEED, oracle_authority.key().as_ref(), match_id.key().as_ref()],
+        bump = oracle_state.bump,
+        constraint = oracle_state.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
+    )]
+    pub oracle_state: Account<'info, OracleState>,
+    
+    #[account(
+        mut,
+        seeds = [ORACLE_PROFILE_SEED, oracle_authority.key().as_ref()],
+        bump = oracle_profile.bump,
+        constraint = oracle_profile.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
+    )]
+    pub oracle_profile: Account<'info, OracleProfile>,
+    
+    #[account(
+        seeds = [MATCH_SEED, match_id.key().as_ref()],
+        bump = match_state.bump,
+        constraint = match_state.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
+    )]
+    pub match_state: Account<'info, MatchState>,
+    
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED],
+        bump = treasury.bump,
+        constraint = treasury.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
+    )]
+    pub treasury: Account<'info, TreasuryState>,
+    
+    /// CHECK: Treasury PDA that holds deposited lamports
+    #[account(mut, address = treasury.key() @ TrendXBetError::InvalidAccount)]
+    pub treasury_account: UncheckedAccount<'info>,
+    
+    /// CHECK: Oracle authority, paid its fee share
+    #[account(mut)]
+    pub oracle_authority: UncheckedAccount<'info>,
+    
+    /// CHECK: Match identifier
+    pub match_id: UncheckedAccount<'info>,
+    
+    pub authority: Signer<'info>,
+}
+
+pub fn resolve_oracle_report(ctx: Context<ResolveOracleReport>) -> Result<()> {
+    let oracle_state = &mut ctx.accounts.oracle_state;
+    let oracle_profile = &mut ctx.accounts.oracle_profile;
+    let match_state = &ctx.accounts.match_state;
+    let treasury = &mut ctx.accounts.treasury;
+    let current_time = TimeUtils::get_current_timestamp();
+    
+    // Anyone may resolve a report once the match settled or pushed and the result is final
+    match_state.require_final_result(current_time)?;
+    let (consensus_result, consensus_score) = match_state
+        .consensus_report()
+        .ok_or(TrendXBetError::NoOracleConsensus)?;
+    let agreed = oracle_state.resolve(consensus_result, &consensus_score, match_state.is_score_final())?;
+    
+    let mut reward = 0;
+    let mut slashed = 0;
+    if agreed {
+        // Agreeing oracles share the match's fees, as much as is available
+        reward = MathUtils::calculate_oracle_reward(match_state.earned_platform_fees(), match_state.oracle_reports)?
+            .min(treasury.available_platform_fees());
+        if reward > 0 {
+            ctx.accounts.treasury_account.sub_lamports(reward)?;
+            ctx.accounts.oracle_authority.add_lamports(reward)?;
+            treasury.withdraw_platform_fees(reward)?;
+        }
+        oracle_profile.record_agreement(reward)?;
+    } else {
+        // Reports against consensus, or withheld after committing, lose stake to the treasury
+        slashed = oracle_profile.slash()?;
+        if slashed > 0 {
+            oracle_profile.sub_lamports(slashed)?;
+            ctx.accounts.treasury_account.add_lamports(slashed)?;
+            treasury.add_forfeit(slashed)?;
+        }
+    }
+    
+    emit!(OracleReportResolved {
+        match_id: ctx.accounts.match_id.key(),
+        oracle_authority: ctx.accounts.oracle_authority.key(),
+        agreed,
+        reward,
+        slashed,
+        reputation: oracle_profile.reputation,
+        timestamp: current_time,
+    });
+    
+    msg!("Oracle report resolved: agreed {}, reward {}, slashed {}", agreed, reward, slashed);
+    Ok(())
+}
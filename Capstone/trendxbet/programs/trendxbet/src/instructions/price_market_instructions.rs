@@ -0,0 +1,296 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use crate::state::*;
+use crate::constants::*;
+use crate::error::TrendXBetError;
+use crate::price_feed::{PriceFeedUtils, PriceStatus};
+use crate::utils::{ValidationUtils, TimeUtils, IndexUtils};
+use crate::events::*;
+
+// Create Price Market
+#[derive(Accounts)]
+pub struct CreatePriceMarket<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = MatchState::LEN,
+        seeds = [MATCH_SEED, match_id.key().as_ref()],
+        bump
+    )]
+    pub match_state: Account<'info, MatchState>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = PriceMarket::LEN,
+        seeds = [PRICE_MARKET_SEED, match_id.key().as_ref()],
+        bump
+    )]
+    pub price_market: Account<'info, PriceMarket>,
+    
+    #[account(
+        seeds = [PLATFORM_SEED],
+        bump = global_state.bump,
+        constraint = global_state.is_operational() @ TrendXBetError::PlatformPaused,
+        constraint = global_state.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    /// CHECK: Price feed account, parsed and checked against the configured feed program
+    #[account(owner = global_state.price_feed_program @ TrendXBetError::InvalidPriceFeed)]
+    pub price_feed: UncheckedAccount<'info>,
+    
+    /// CHECK: Unique identifier for the market
+    pub match_id: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+    
+    /// Registry the market is listed in; without it the market is not indexed
+    #[account(
+        mut,
+        seeds = [MATCH_REGISTRY_SEED],
+        bump = registry.bump,
+        constraint = registry.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
+    )]
+    pub registry: Option<Account<'info, MatchRegistry>>,
+    
+    /// Page of the Scheduled status index with room for the market
+    #[account(mut, constraint = status_index.is_current_version() @ TrendXBetError::UnsupportedAccountVersion)]
+    pub status_index: Option<Account<'info, MatchIndexPage>>,
+    
+    /// Page of the index for the market's start day with room for it
+    #[account(mut, constraint = start_day_index.is_current_version() @ TrendXBetError::UnsupportedAccountVersion)]
+    pub start_day_index: Option<Account<'info, MatchIndexPage>>,
+    
+    /// Page of the index of the league the market belongs to, if any
+    #[account(mut, constraint = league_index.is_current_version() @ TrendXBetError::UnsupportedAccountVersion)]
+    pub league_index: Option<Account<'info, MatchIndexPage>>,
+}
+
+pub fn create_price_market(
+    ctx: Context<CreatePriceMarket>,
+    asset: String,
+    strike_price: i64,
+    start_time: i64,
+    end_time: i64,
+    description: String,
+    creator_fee: u16,
+) -> Result<()> {
+    let current_time = TimeUtils::get_current_timestamp();
+    
+    // Anyone but the admin creates a community market, bonding against abuse
+    let creator = ctx.accounts.authority.key();
+    let is_community = creator != ctx.accounts.global_state.admin;
+    require!(
+        creator_fee <= ctx.accounts.global_state.max_creator_fee && (is_community || creator_fee == 0),
+        TrendXBetError::InvalidCreatorFee
+    );
+    let creator_bond = if is_community { ctx.accounts.global_state.creator_bond } else { 0 };
+    if creator_bond > 0 {
+        let transfer_ctx = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.authority.to_account_info(),
+                to: ctx.accounts.match_state.to_account_info(),
+            },
+        );
+        transfer(transfer_ctx, creator_bond)?;
+    }
+    
+    let match_state = &mut ctx.accounts.match_state;
+    let price_market = &mut ctx.accounts.price_market;
+    
+    // Validate inputs
+    ValidationUtils::validate_asset(&asset)?;
+    ValidationUtils::validate_description(&description)?;
+    ValidationUtils::validate_match_times(start_time, end_time, current_time)?;
+    
+    // The strike is quoted in the feed's own exponent
+    let feed = PriceFeedUtils::load(&ctx.accounts.price_feed)?;
+    
+    // Pools reuse the match layout: team 1 wins above the strike, team 2 at or below
+    match_state.initialize(
+        ctx.accounts.match_id.key(),
+        "Above strike".to_string(),
+        "At or below strike".to_string(),
+        description.clone(),
+        start_time,
+        end_time,
+        ctx.bumps.match_state,
+        current_time,
+    );
+    match_state.market_kind = MarketKind::PriceTrend;
+    
+    if is_community {
+        match_state.set_creator(creator, creator_bond, creator_fee);
+    }
+    
+    match_state.configure_betting(
+        start_time,
+        end_time.saturating_sub(ctx.accounts.global_state.betting_cutoff as i64),
+        ctx.accounts.global_state.early_bet_bonus,
+    )?;
+    
+    // List the market in the registry's indexes when it is enabled
+    if let Some(registry) = ctx.accounts.registry.as_deref_mut() {
+        IndexUtils::list_match(
+            registry,
+            match_state,
+            ctx.accounts.status_index.as_deref_mut(),
+            ctx.accounts.start_day_index.as_deref_mut(),
+            ctx.accounts.league_index.as_deref_mut(),
+        )?;
+    }
+    
+    price_market.initialize(
+        ctx.accounts.match_id.key(),
+        ctx.accounts.price_feed.key(),
+        ctx.accounts.global_state.price_feed_program,
+        asset.clone(),
+        strike_price,
+        feed.expo,
+        ctx.bumps.price_market,
+    );
+    
+    // Emit events
+    emit!(MatchCreated {
+        match_id: ctx.accounts.match_id.key(),
+        team1: match_state.team1.clone(),
+        team2: match_state.team2.clone(),
+        start_time,
+        end_time,
+        description,
+        creator: ctx.accounts.authority.key(),
+        timestamp: current_time,
+    });
+    
+    emit!(PriceMarketCreated {
+        match_id: ctx.accounts.match_id.key(),
+        price_feed: ctx.accounts.price_feed.key(),
+        asset: asset.clone(),
+        strike_price,
+        expo: feed.expo,
+        resolution_time: end_time,
+        timestamp: current_time,
+    });
+    
+    if is_community {
+        emit!(CommunityMarketCreated {
+            match_id: ctx.accounts.match_id.key(),
+            creator,
+            creator_bond,
+            creator_fee,
+            timestamp: current_time,
+        });
+    }
+    
+    msg!("Price market created: {} above {}e{}", asset, strike_price, feed.expo);
+    Ok(())
+}
+
+// Resolve Price Market
+#[derive(Accounts)]
+pub struct ResolvePriceMarket<'info> {
+    #[account(
+        mut,
+        seeds = [MATCH_SEED, match_id.key().as_ref()],
+        bump = match_state.bump,
+        constraint = match_state.market_kind == MarketKind::PriceTrend @ TrendXBetError::InvalidMarketType,
+        constraint = match_state.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
+    )]
+    pub match_state: Account<'info, MatchState>,
+    
+    #[account(
+        mut,
+        seeds = [PRICE_MARKET_SEED, match_id.key().as_ref()],
+        bump = price_market.bump,
+        has_one = price_feed @ TrendXBetError::InvalidPriceFeed,
+        constraint = price_market.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
+    )]
+    pub price_market: Account<'info, PriceMarket>,
+    
+    #[account(
+        seeds = [PLATFORM_SEED],
+        bump = global_state.bump,
+        constraint = global_state.is_operational() @ TrendXBetError::PlatformPaused,
+        constraint = global_state.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    /// CHECK: Price feed recorded on the market, still owned by the same feed program
+    #[account(owner = price_market.feed_program @ TrendXBetError::InvalidPriceFeed)]
+    pub price_feed: UncheckedAccount<'info>,
+    
+    /// CHECK: Match identifier
+    pub match_id: UncheckedAccount<'info>,
+    
+    pub resolver: Signer<'info>,
+    
+    /// Status index page listing the match, required for indexed matches whose status changes
+    #[account(mut, constraint = old_status_index.is_current_version() @ TrendXBetError::UnsupportedAccountVersion)]
+    pub old_status_index: Option<Account<'info, MatchIndexPage>>,
+    
+    /// Page of the new status's index with room for the match
+    #[account(mut, constraint = new_status_index.is_current_version() @ TrendXBetError::UnsupportedAccountVersion)]
+    pub new_status_index: Option<Account<'info, MatchIndexPage>>,
+}
+
+pub fn resolve_price_market(ctx: Context<ResolvePriceMarket>) -> Result<()> {
+    let match_state = &mut ctx.accounts.match_state;
+    let price_market = &mut ctx.accounts.price_market;
+    let current_time = TimeUtils::get_current_timestamp();
+    let old_status = match_state.status.clone();
+    
+    require!(match_state.is_awaiting_result(), TrendXBetError::InvalidMatchStatus);
+    require!(match_state.has_ended(current_time), TrendXBetError::SettlementPeriodNotReached);
+    
+    // Anyone may resolve, but only on the first prices published after the end time
+    let feed = PriceFeedUtils::load(&ctx.accounts.price_feed)?;
+    require!(feed.status == PriceStatus::Trading, TrendXBetError::PriceFeedUnavailable);
+    require!(feed.expo == price_market.expo, TrendXBetError::InvalidPriceFeed);
+    require!(
+        PriceMarket::is_resolution_price(match_state.end_time, feed.publish_time),
+        TrendXBetError::PriceNotAtResolutionTime
+    );
+    require!(feed.is_confident(MAX_PRICE_CONFIDENCE), TrendXBetError::PriceConfidenceTooWide);
+    
+    let winning_team = price_market.resolve(feed.price, feed.publish_time);
+    
+    match_state.close_betting();
+    match_state.set_result(winning_team, format!("{}e{}", feed.price, feed.expo), current_time);
+    match_state.settle();
+    
+    // Keep the status index in step
+    IndexUtils::move_status(
+        match_state,
+        &old_status,
+        ctx.accounts.old_status_index.as_deref_mut(),
+        ctx.accounts.new_status_index.as_deref_mut(),
+    )?;
+    
+    // Emit events
+    emit!(MatchStatusUpdated {
+        match_id: ctx.accounts.match_id.key(),
+        old_status: old_status.code(),
+        new_status: MatchStatus::Settled.code(),
+        timestamp: current_time,
+    });
+    
+    emit!(PriceMarketResolved {
+        match_id: ctx.accounts.match_id.key(),
+        price: feed.price,
+        conf: feed.conf,
+        expo: feed.expo,
+        publish_time: feed.publish_time,
+        strike_price: price_market.strike_price,
+        winning_team,
+        resolver: ctx.accounts.resolver.key(),
+        timestamp: current_time,
+    });
+    
+    msg!("Price market resolved at {}e{}: team {} wins", feed.price, feed.expo, winning_team);
+    Ok(())
+}
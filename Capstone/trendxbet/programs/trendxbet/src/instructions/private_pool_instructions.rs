@@ -0,0 +1,283 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::error::TrendXBetError;
+use crate::utils::{TimeUtils, IndexUtils};
+use crate::events::*;
+
+// Create Private Pool
+#[derive(Accounts)]
+pub struct CreatePrivatePool<'info> {
+    #[account(
+        init,
+        payer = organiser,
+        space = PrivatePool::LEN,
+        seeds = [PRIVATE_POOL_SEED, match_id.key().as_ref()],
+        bump
+    )]
+    pub private_pool: Account<'info, PrivatePool>,
+    
+    #[account(
+        mut,
+        seeds = [MATCH_SEED, match_id.key().as_ref()],
+        bump = match_state.bump,
+        constraint = match_state.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
+    )]
+    pub match_state: Account<'info, MatchState>,
+    
+    #[account(
+        seeds = [PLATFORM_SEED],
+        bump = global_state.bump,
+        constraint = global_state.is_operational() @ TrendXBetError::PlatformPaused,
+        constraint = global_state.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    /// CHECK: Match identifier
+    pub match_id: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub organiser: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn create_private_pool(
+    ctx: Context<CreatePrivatePool>,
+    invite_code_hash: Option<[u8; 32]>,
+    members: Vec<Pubkey>,
+    waives_house_edge: bool,
+) -> Result<()> {
+    let match_state = &mut ctx.accounts.match_state;
+    let organiser = ctx.accounts.organiser.key();
+    let current_time = TimeUtils::get_current_timestamp();
+    
+    // The market's creator, or the admin for its own markets, organises the pool
+    let creator = if match_state.has_creator() { match_state.creator } else { ctx.accounts.global_state.admin };
+    require!(organiser == creator, TrendXBetError::Unauthorized);
+    
+    // Bets already placed were open to anyone and charged the house edge
+    require!(
+        match_state.status == MatchStatus::Scheduled && match_state.total_bets == 0,
+        TrendXBetError::InvalidMatchStatus
+    );
+    
+    let member_count = members.len() as u32;
+    ctx.accounts.private_pool.initialize(
+        ctx.accounts.match_id.key(),
+        organiser,
+        invite_code_hash,
+        members,
+        ctx.bumps.private_pool,
+        current_time,
+    )?;
+    match_state.make_private(waives_house_edge);
+    
+    emit!(PrivatePoolCreated {
+        match_id: ctx.accounts.match_id.key(),
+        organiser,
+        members: member_count,
+        has_invite_code: invite_code_hash.is_some(),
+        waives_house_edge,
+        timestamp: current_time,
+    });
+    
+    msg!("Private pool created for match: {}", ctx.accounts.match_id.key());
+    Ok(())
+}
+
+// Update Private Pool
+#[derive(Accounts)]
+pub struct UpdatePrivatePool<'info> {
+    #[account(
+        mut,
+        seeds = [PRIVATE_POOL_SEED, match_id.key().as_ref()],
+        bump = private_pool.bump,
+        has_one = organiser @ TrendXBetError::Unauthorized,
+        constraint = private_pool.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
+    )]
+    pub private_pool: Account<'info, PrivatePool>,
+    
+    /// CHECK: Match identifier
+    pub match_id: UncheckedAccount<'info>,
+    
+    pub organiser: Signer<'info>,
+}
+
+pub fn update_private_pool(
+    ctx: Context<UpdatePrivatePool>,
+    invite_code_hash: Option<[u8; 32]>,
+    members: Vec<Pubkey>,
+) -> Result<()> {
+    let private_pool = &mut ctx.accounts.private_pool;
+    let current_time = TimeUtils::get_current_timestamp();
+    
+    // Removed members keep the bets they already placed
+    private_pool.update_invites(invite_code_hash, members)?;
+    
+    emit!(PrivatePoolUpdated {
+        match_id: ctx.accounts.match_id.key(),
+        organiser: ctx.accounts.organiser.key(),
+        members: private_pool.members.len() as u32,
+        has_invite_code: invite_code_hash.is_some(),
+        timestamp: current_time,
+    });
+    
+    msg!("Private pool invites updated for match: {}", ctx.accounts.match_id.key());
+    Ok(())
+}
+
+// Join Private Pool
+#[derive(Accounts)]
+pub struct JoinPrivatePool<'info> {
+    #[account(
+        mut,
+        seeds = [PRIVATE_POOL_SEED, match_id.key().as_ref()],
+        bump = private_pool.bump,
+        constraint = private_pool.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
+    )]
+    pub private_pool: Account<'info, PrivatePool>,
+    
+    /// CHECK: Match identifier
+    pub match_id: UncheckedAccount<'info>,
+    
+    pub bettor: Signer<'info>,
+}
+
+pub fn join_private_pool(ctx: Context<JoinPrivatePool>, invite_code: String) -> Result<()> {
+    let current_time = TimeUtils::get_current_timestamp();
+    
+    ctx.accounts.private_pool.join(ctx.accounts.bettor.key(), &invite_code)?;
+    
+    emit!(PrivatePoolJoined {
+        match_id: ctx.accounts.match_id.key(),
+        member: ctx.accounts.bettor.key(),
+        timestamp: current_time,
+    });
+    
+    msg!("{} joined the private pool of {}", ctx.accounts.bettor.key(), ctx.accounts.match_id.key());
+    Ok(())
+}
+
+// Close Private Pool Betting
+#[derive(Accounts)]
+pub struct ClosePrivatePoolBetting<'info> {
+    #[account(
+        seeds = [PRIVATE_POOL_SEED, match_id.key().as_ref()],
+        bump = private_pool.bump,
+        has_one = organiser @ TrendXBetError::Unauthorized,
+        constraint = private_pool.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
+    )]
+    pub private_pool: Account<'info, PrivatePool>,
+    
+    #[account(
+        mut,
+        seeds = [MATCH_SEED, match_id.key().as_ref()],
+        bump = match_state.bump,
+        constraint = match_state.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
+    )]
+    pub match_state: Account<'info, MatchState>,
+    
+    /// CHECK: Match identifier
+    pub match_id: UncheckedAccount<'info>,
+    
+    pub organiser: Signer<'info>,
+}
+
+pub fn close_private_pool_betting(ctx: Context<ClosePrivatePoolBetting>) -> Result<()> {
+    let match_state = &mut ctx.accounts.match_state;
+    let current_time = TimeUtils::get_current_timestamp();
+    
+    require!(
+        match_state.is_awaiting_result() && !match_state.is_betting_closed,
+        TrendXBetError::InvalidMatchStatus
+    );
+    
+    match_state.close_betting();
+    
+    emit!(MatchBettingClosed {
+        match_id: ctx.accounts.match_id.key(),
+        total_pool: match_state.total_pool,
+        team1_pool: match_state.team1_pool,
+        team2_pool: match_state.team2_pool,
+        total_bets: match_state.total_bets,
+        timestamp: current_time,
+    });
+    
+    msg!("Private pool betting closed for match: {}", ctx.accounts.match_id.key());
+    Ok(())
+}
+
+// Cancel Private Pool
+#[derive(Accounts)]
+pub struct CancelPrivatePool<'info> {
+    #[account(
+        seeds = [PRIVATE_POOL_SEED, match_id.key().as_ref()],
+        bump = private_pool.bump,
+        has_one = organiser @ TrendXBetError::Unauthorized,
+        constraint = private_pool.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
+    )]
+    pub private_pool: Account<'info, PrivatePool>,
+    
+    #[account(
+        mut,
+        seeds = [MATCH_SEED, match_id.key().as_ref()],
+        bump = match_state.bump,
+        constraint = match_state.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
+    )]
+    pub match_state: Account<'info, MatchState>,
+    
+    /// CHECK: Match identifier
+    pub match_id: UncheckedAccount<'info>,
+    
+    pub organiser: Signer<'info>,
+    
+    /// Status index page listing the match, required for indexed matches whose status changes
+    #[account(mut, constraint = old_status_index.is_current_version() @ TrendXBetError::UnsupportedAccountVersion)]
+    pub old_status_index: Option<Account<'info, MatchIndexPage>>,
+    
+    /// Page of the new status's index with room for the match
+    #[account(mut, constraint = new_status_index.is_current_version() @ TrendXBetError::UnsupportedAccountVersion)]
+    pub new_status_index: Option<Account<'info, MatchIndexPage>>,
+}
+
+pub fn cancel_private_pool(ctx: Context<CancelPrivatePool>) -> Result<()> {
+    let match_state = &mut ctx.accounts.match_state;
+    let current_time = TimeUtils::get_current_timestamp();
+    let old_status = match_state.status.clone();
+    
+    // Once an oracle has reported, the organiser could cancel a pool they are losing
+    require!(
+        match_state.is_awaiting_result() && match_state.oracle_reports == 0,
+        TrendXBetError::InvalidMatchStatus
+    );
+    
+    match_state.cancel();
+    match_state.close_betting();
+    
+    // Keep the status index in step
+    IndexUtils::move_status(
+        match_state,
+        &old_status,
+        ctx.accounts.old_status_index.as_deref_mut(),
+        ctx.accounts.new_status_index.as_deref_mut(),
+    )?;
+    
+    emit!(MatchStatusUpdated {
+        match_id: ctx.accounts.match_id.key(),
+        old_status: old_status.code(),
+        new_status: MatchStatus::Cancelled.code(),
+        timestamp: current_time,
+    });
+    
+    emit!(PrivatePoolCancelled {
+        match_id: ctx.accounts.match_id.key(),
+        organiser: ctx.accounts.organiser.key(),
+        total_pool: match_state.total_pool,
+        total_bets: match_state.total_bets,
+        timestamp: current_time,
+    });
+    
+    msg!("Private pool cancelled for match: {}", ctx.accounts.match_id.key());
+    Ok(())
+}
@@ -0,0 +1,195 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use crate::state::*;
+use crate::constants::*;
+use crate::error::TrendXBetError;
+use crate::utils::{TimeUtils, AuditUtils};
+use crate::events::*;
+
+// Add Promoter
+#[derive(Accounts)]
+#[instruction(promoter: Pubkey)]
+pub struct AddPromoter<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = Promoter::LEN,
+        seeds = [PROMOTER_SEED, promoter.as_ref()],
+        bump
+    )]
+    pub promoter_account: Account<'info, Promoter>,
+    
+    #[account(
+        mut,
+        seeds = [PLATFORM_SEED],
+        bump = global_state.bump,
+        has_one = admin @ TrendXBetError::Unauthorized,
+        constraint = global_state.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+    
+    /// Current audit log page, required once the audit log is enabled
+    #[account(mut, constraint = audit_log.is_current_version() @ TrendXBetError::UnsupportedAccountVersion)]
+    pub audit_log: Option<Account<'info, AuditLogPage>>,
+}
+
+pub fn add_promoter(ctx: Context<AddPromoter>, promoter: Pubkey) -> Result<()> {
+    let current_time = TimeUtils::get_current_timestamp();
+    
+    ctx.accounts.promoter_account.initialize(promoter, ctx.bumps.promoter_account, current_time);
+    
+    AuditUtils::record(
+        &mut ctx.accounts.global_state,
+        ctx.accounts.audit_log.as_deref_mut(),
+        AuditAction::PromoterAddition,
+        ctx.accounts.admin.key(),
+        Some(promoter),
+        AuditLogPage::params_hash(&promoter)?,
+        current_time,
+    )?;
+    
+    emit!(PromoterAdded {
+        promoter,
+        admin: ctx.accounts.admin.key(),
+        timestamp: current_time,
+    });
+    
+    msg!("Promoter {} added", promoter);
+    Ok(())
+}
+
+// Remove Promoter
+#[derive(Accounts)]
+pub struct RemovePromoter<'info> {
+    #[account(
+        mut,
+        seeds = [PROMOTER_SEED, promoter_account.authority.as_ref()],
+        bump = promoter_account.bump,
+        close = admin
+    )]
+    pub promoter_account: Account<'info, Promoter>,
+    
+    #[account(
+        mut,
+        seeds = [PLATFORM_SEED],
+        bump = global_state.bump,
+        has_one = admin @ TrendXBetError::Unauthorized,
+        constraint = global_state.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    /// Current audit log page, required once the audit log is enabled
+    #[account(mut, constraint = audit_log.is_current_version() @ TrendXBetError::UnsupportedAccountVersion)]
+    pub audit_log: Option<Account<'info, AuditLogPage>>,
+}
+
+pub fn remove_promoter(ctx: Context<RemovePromoter>) -> Result<()> {
+    let promoter_account = &ctx.accounts.promoter_account;
+    let current_time = TimeUtils::get_current_timestamp();
+    
+    AuditUtils::record(
+        &mut ctx.accounts.global_state,
+        ctx.accounts.audit_log.as_deref_mut(),
+        AuditAction::PromoterRemoval,
+        ctx.accounts.admin.key(),
+        Some(promoter_account.authority),
+        AuditLogPage::params_hash(&promoter_account.authority)?,
+        current_time,
+    )?;
+    
+    // Bonuses already issued stay with their users
+    emit!(PromoterRemoved {
+        promoter: promoter_account.authority,
+        admin: ctx.accounts.admin.key(),
+        total_issued: promoter_account.total_issued,
+        timestamp: current_time,
+    });
+    
+    msg!("Promoter {} removed", promoter_account.authority);
+    Ok(())
+}
+
+// Issue Bonus
+#[derive(Accounts)]
+pub struct IssueBonus<'info> {
+    #[account(
+        mut,
+        seeds = [PROMOTER_SEED, authority.key().as_ref()],
+        bump = promoter_account.bump,
+        has_one = authority @ TrendXBetError::Unauthorized,
+        constraint = promoter_account.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
+    )]
+    pub promoter_account: Account<'info, Promoter>,
+    
+    #[account(
+        mut,
+        seeds = [USER_SEED, user.key().as_ref()],
+        bump = user_state.bump,
+        constraint = user_state.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
+    )]
+    pub user_state: Account<'info, UserState>,
+    
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED],
+        bump = treasury.bump,
+        constraint = treasury.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
+    )]
+    pub treasury: Account<'info, TreasuryState>,
+    
+    /// CHECK: Wallet of the user receiving the bonus
+    pub user: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    /// CHECK: Treasury PDA that holds deposited lamports
+    #[account(mut, address = treasury.key() @ TrendXBetError::InvalidAccount)]
+    pub treasury_account: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn issue_bonus(ctx: Context<IssueBonus>, amount: u64, wagering_multiplier: u8) -> Result<()> {
+    let promoter_account = &mut ctx.accounts.promoter_account;
+    let user_state = &mut ctx.accounts.user_state;
+    let treasury = &mut ctx.accounts.treasury;
+    let current_time = TimeUtils::get_current_timestamp();
+    
+    require!(amount > 0, TrendXBetError::ZeroAmount);
+    let wagering_requirement = Promoter::wagering_requirement(amount, wagering_multiplier)?;
+    
+    // The promoter funds the bonus, so bets staked from it are backed like any deposit
+    let transfer_ctx = CpiContext::new(
+        ctx.accounts.system_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.authority.to_account_info(),
+            to: ctx.accounts.treasury_account.to_account_info(),
+        },
+    );
+    transfer(transfer_ctx, amount)?;
+    treasury.record_deposit(amount)?;
+    
+    user_state.add_bonus(amount, wagering_requirement)?;
+    promoter_account.record_issue(amount)?;
+    
+    emit!(BonusIssued {
+        user: ctx.accounts.user.key(),
+        promoter: ctx.accounts.authority.key(),
+        amount,
+        wagering_requirement,
+        bonus_balance: user_state.bonus_balance,
+        timestamp: current_time,
+    });
+    
+    msg!("Bonus of {} lamports issued to {}", amount, ctx.accounts.user.key());
+    Ok(())
+}
@@ -0,0 +1,107 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::error::TrendXBetError;
+use crate::utils::TimeUtils;
+use crate::events::*;
+
+// Create Match Registry
+#[derive(Accounts)]
+pub struct CreateMatchRegistry<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = MatchRegistry::LEN,
+        seeds = [MATCH_REGISTRY_SEED],
+        bump
+    )]
+    pub registry: Account<'info, MatchRegistry>,
+    
+    #[account(
+        seeds = [PLATFORM_SEED],
+        bump = global_state.bump,
+        has_one = admin @ TrendXBetError::Unauthorized,
+        constraint = global_state.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn create_match_registry(ctx: Context<CreateMatchRegistry>) -> Result<()> {
+    let current_time = TimeUtils::get_current_timestamp();
+    
+    ctx.accounts.registry.initialize(ctx.bumps.registry);
+    
+    emit!(MatchRegistryCreated {
+        admin: ctx.accounts.admin.key(),
+        timestamp: current_time,
+    });
+    
+    msg!("Match registry created");
+    Ok(())
+}
+
+// Create Match Index Page
+#[derive(Accounts)]
+#[instruction(key: MatchIndexKey, page: u32)]
+pub struct CreateMatchIndexPage<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = MatchIndexPage::LEN,
+        seeds = [MATCH_INDEX_SEED, key.seed().as_ref(), page.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub index_page: Account<'info, MatchIndexPage>,
+    
+    /// Page before the new one, required past the first page
+    #[account(constraint = previous_page.is_current_version() @ TrendXBetError::UnsupportedAccountVersion)]
+    pub previous_page: Option<Account<'info, MatchIndexPage>>,
+    
+    #[account(
+        seeds = [MATCH_REGISTRY_SEED],
+        bump = registry.bump,
+        constraint = registry.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
+    )]
+    pub registry: Account<'info, MatchRegistry>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn create_match_index_page(ctx: Context<CreateMatchIndexPage>, key: MatchIndexKey, page: u32) -> Result<()> {
+    let current_time = TimeUtils::get_current_timestamp();
+    
+    if let MatchIndexKey::Status(code) = key {
+        require!(code <= MatchStatus::Voided.code(), TrendXBetError::InvalidMatchStatus);
+    }
+    
+    // Anyone may add a page, but only once the previous one has filled up
+    if page > 0 {
+        let previous_page = ctx.accounts.previous_page.as_ref().ok_or(TrendXBetError::MatchIndexRequired)?;
+        require!(
+            previous_page.key == key && previous_page.page + 1 == page,
+            TrendXBetError::MatchIndexMismatch
+        );
+        require!(previous_page.is_full(), TrendXBetError::IndexPageNotFull);
+    }
+    
+    ctx.accounts.index_page.initialize(key, page, ctx.bumps.index_page);
+    
+    emit!(MatchIndexPageCreated {
+        index_page: ctx.accounts.index_page.key(),
+        key,
+        page,
+        payer: ctx.accounts.payer.key(),
+        timestamp: current_time,
+    });
+    
+    msg!("Match index page {} created", page);
+    Ok(())
+}
@@ -0,0 +1,105 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::error::TrendXBetError;
+use crate::utils::TimeUtils;
+use crate::events::*;
+
+// Create Session Key
+#[derive(Accounts)]
+#[instruction(session_key: Pubkey)]
+pub struct CreateSessionKey<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = SessionKey::LEN,
+        seeds = [SESSION_SEED, authority.key().as_ref(), session_key.as_ref()],
+        bump
+    )]
+    pub session: Account<'info, SessionKey>,
+    
+    #[account(
+        seeds = [USER_SEED, authority.key().as_ref()],
+        bump = user_state.bump,
+        has_one = authority @ TrendXBetError::Unauthorized,
+        constraint = user_state.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
+    )]
+    pub user_state: Account<'info, UserState>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn create_session_key(
+    ctx: Context<CreateSessionKey>,
+    session_key: Pubkey,
+    expires_at: i64,
+    max_bet_amount: u64,
+    spend_limit: u64,
+    permissions: u8,
+) -> Result<()> {
+    let current_time = TimeUtils::get_current_timestamp();
+    
+    ctx.accounts.session.initialize(
+        ctx.accounts.authority.key(),
+        session_key,
+        expires_at,
+        max_bet_amount,
+        spend_limit,
+        permissions,
+        ctx.bumps.session,
+        current_time,
+    )?;
+    
+    emit!(SessionKeyCreated {
+        user: ctx.accounts.authority.key(),
+        session_key,
+        expires_at,
+        max_bet_amount,
+        spend_limit,
+        permissions,
+        timestamp: current_time,
+    });
+    
+    msg!("Session key {} created until {}", session_key, expires_at);
+    Ok(())
+}
+
+// Revoke Session Key
+#[derive(Accounts)]
+pub struct RevokeSessionKey<'info> {
+    #[account(
+        mut,
+        seeds = [SESSION_SEED, authority.key().as_ref(), session.session_key.as_ref()],
+        bump = session.bump,
+        has_one = authority @ TrendXBetError::Unauthorized,
+        constraint = signer.key() == session.authority || signer.key() == session.session_key @ TrendXBetError::Unauthorized,
+        close = authority
+    )]
+    pub session: Account<'info, SessionKey>,
+    
+    /// CHECK: Profile owner the session rent is returned to
+    #[account(mut)]
+    pub authority: UncheckedAccount<'info>,
+    
+    /// Either the profile owner or the session key itself
+    pub signer: Signer<'info>,
+}
+
+pub fn revoke_session_key(ctx: Context<RevokeSessionKey>) -> Result<()> {
+    let session = &ctx.accounts.session;
+    let current_time = TimeUtils::get_current_timestamp();
+    
+    emit!(SessionKeyRevoked {
+        user: session.authority,
+        session_key: session.session_key,
+        revoked_by: ctx.accounts.signer.key(),
+        spent: session.spent,
+        timestamp: current_time,
+    });
+    
+    msg!("Session key {} revoked", session.session_key);
+    Ok(())
+}
@@ -23,6 +23,16 @@ pub struct CreateUserProfile<'info> {
     pub user: Signer<'info>,
 
     pub system_program: Program<'info, System>,
+
+    /// Claim on the username; creation fails if another profile holds it in any casing
+    #[account(
+        init,
+        payer = user,
+        space = UsernameRecord::LEN,
+        seeds = [USERNAME_SEED, UsernameRecord::seed(&username).as_ref()],
+        bump
+    )]
+    pub username_record: Account<'info, UsernameRecord>,
 }
 
 pub fn create_user_profile(ctx: Context<CreateUserProfile>, username: String) -> Result<()> {
@@ -39,10 +49,26 @@ pub fn create_user_profile(ctx: Context<CreateUserProfile>, username: String) ->
         ctx.bumps.user_state,
         current_time,
     );
+    user_state.has_username_record = true;
 
-    // Emit event
+    ctx.accounts.username_record.initialize(
+        ctx.accounts.user.key(),
+        user_state.key(),
+        username.clone(),
+        ctx.bumps.username_record,
+        current_time,
+    );
+
+    // Emit events
     emit!(UserProfileCreated {
         user: ctx.accounts.user.key(),
+        username: username.clone(),
+        timestamp: current_time,
+    });
+
+    emit!(UsernameClaimed {
+        user: ctx.accounts.user.key(),
+        user_state: user_state.key(),
         username,
         timestamp: current_time,
     });
@@ -53,20 +79,48 @@ pub fn create_user_profile(ctx: Context<CreateUserProfile>, username: String) ->
 
 // Update User Profile
 #[derive(Accounts)]
+#[instruction(username: Option<String>)]
 pub struct UpdateUserProfile<'info> {
     #[account(
         mut,
         seeds = [USER_SEED, user.key().as_ref()],
         bump = user_state.bump,
-        has_one = authority @ TrendXBetError::Unauthorized
+        has_one = authority @ TrendXBetError::Unauthorized,
+        constraint = user_state.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
     )]
     pub user_state: Account<'info, UserState>,
 
     /// CHECK: This is the user's authority
+    #[account(mut)]
     pub authority: Signer<'info>,
 
     /// CHECK: This is the user account that owns the profile
     pub user: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Record of the current username, released on rename; required once the profile holds one
+    #[account(
+        mut,
+        seeds = [USERNAME_SEED, UsernameRecord::seed(&user_state.username).as_ref()],
+        bump = current_username_record.bump,
+        constraint = current_username_record.user_state == user_state.key() @ TrendXBetError::UsernameRecordMismatch,
+        constraint = current_username_record.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
+    )]
+    pub current_username_record: Option<Account<'info, UsernameRecord>>,
+
+    /// Claim on the new username, unless it only changes the casing of a held one
+    #[account(
+        init,
+        payer = authority,
+        space = UsernameRecord::LEN,
+        seeds = [
+            USERNAME_SEED,
+            UsernameRecord::seed(username.as_deref().unwrap_or_default()).as_ref()
+        ],
+        bump
+    )]
+    pub new_username_record: Option<Account<'info, UsernameRecord>>,
 }
 
 pub fn update_user_profile(
@@ -78,6 +132,55 @@ pub fn update_user_profile(
 
     if let Some(new_username) = &username {
         ValidationUtils::validate_username(new_username)?;
+
+        let old_username = user_state.username.clone();
+        if user_state.has_username_record {
+            let current_record = ctx
+                .accounts
+                .current_username_record
+                .as_mut()
+                .ok_or(TrendXBetError::UsernameRecordRequired)?;
+
+            if UsernameRecord::is_same_username(&old_username, new_username) {
+                // Only the casing changes, so the record stays claimed
+                current_record.username = new_username.clone();
+            } else {
+                current_record.close(ctx.accounts.authority.to_account_info())?;
+
+                emit!(UsernameReleased {
+                    user: ctx.accounts.user.key(),
+                    user_state: user_state.key(),
+                    username: old_username,
+                    timestamp: current_time,
+                });
+            }
+        }
+
+        // Profiles created before usernames were unique claim theirs on the first rename
+        if let Some(new_record) = ctx.accounts.new_username_record.as_mut() {
+            new_record.initialize(
+                ctx.accounts.authority.key(),
+                user_state.key(),
+                new_username.clone(),
+                ctx.bumps.new_username_record.unwrap_or_default(),
+                current_time,
+            );
+            user_state.has_username_record = true;
+
+            emit!(UsernameClaimed {
+                user: ctx.accounts.user.key(),
+                user_state: user_state.key(),
+                username: new_username.clone(),
+                timestamp: current_time,
+            });
+        } else {
+            require!(
+                user_state.has_username_record
+                    && UsernameRecord::is_same_username(&user_state.username, new_username),
+                TrendXBetError::UsernameRecordRequired
+            );
+        }
+
         user_state.update_username(new_username.clone());
     }
 
@@ -99,14 +202,16 @@ pub struct DepositFunds<'info> {
         mut,
         seeds = [USER_SEED, user.key().as_ref()],
         bump = user_state.bump,
-        has_one = authority @ TrendXBetError::Unauthorized
+        has_one = authority @ TrendXBetError::Unauthorized,
+        constraint = user_state.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
     )]
     pub user_state: Account<'info, UserState>,
 
     #[account(
         mut,
         seeds = [TREASURY_SEED],
-        bump = treasury.bump
+        bump = treasury.bump,
+        constraint = treasury.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
     )]
     pub treasury: Account<'info, TreasuryState>,
 
@@ -116,8 +221,8 @@ pub struct DepositFunds<'info> {
     /// CHECK: This is the user account
     pub user: UncheckedAccount<'info>,
 
-    /// CHECK: Treasury account to receive funds
-    #[account(mut)]
+    /// CHECK: Treasury PDA that holds deposited lamports
+    #[account(mut, address = treasury.key() @ TrendXBetError::InvalidAccount)]
     pub treasury_account: UncheckedAccount<'info>,
 
     pub system_program: Program<'info, System>,
@@ -128,7 +233,7 @@ pub fn deposit_funds(ctx: Context<DepositFunds>, amount: u64) -> Result<()> {
     let treasury = &mut ctx.accounts.treasury;
     let current_time = TimeUtils::get_current_timestamp();
 
-    require!(amount > 0, TrendXBetError::BetAmountTooLow);
+    require!(amount > 0, TrendXBetError::ZeroAmount);
 
     // Transfer SOL from user to treasury
     let transfer_ctx = CpiContext::new(
@@ -169,14 +274,16 @@ pub struct WithdrawFunds<'info> {
         mut,
         seeds = [USER_SEED, user.key().as_ref()],
         bump = user_state.bump,
-        has_one = authority @ TrendXBetError::Unauthorized
+        has_one = authority @ TrendXBetError::Unauthorized,
+        constraint = user_state.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
     )]
     pub user_state: Account<'info, UserState>,
 
     #[account(
         mut,
         seeds = [TREASURY_SEED],
-        bump = treasury.bump
+        bump = treasury.bump,
+        constraint = treasury.is_current_version() @ TrendXBetError::UnsupportedAccountVersion
     )]
     pub treasury: Account<'info, TreasuryState>,
 
@@ -186,8 +293,8 @@ pub struct WithdrawFunds<'info> {
     /// CHECK: This is the user account
     pub user: UncheckedAccount<'info>,
 
-    /// CHECK: Treasury account to send funds from
-    #[account(mut)]
+    /// CHECK: Treasury PDA that holds deposited lamports
+    #[account(mut, address = treasury.key() @ TrendXBetError::InvalidAccount)]
     pub treasury_account: UncheckedAccount<'info>,
 
     pub system_program: Program<'info, System>,
@@ -198,29 +305,23 @@ pub fn withdraw_funds(ctx: Context<WithdrawFunds>, amount: u64) -> Result<()> {
     let treasury = &mut ctx.accounts.treasury;
     let current_time = TimeUtils::get_current_timestamp();
 
-    require!(amount > 0, TrendXBetError::BetAmountTooLow);
-    require!(
-        user_state.has_sufficient_balance(amount),
-        TrendXBetError::InsufficientBalance
+    require!(amount > 0, TrendXBetError::ZeroAmount);
+    require_gte!(user_state.balance, amount, TrendXBetError::InsufficientBalance);
+    require_gte!(
+        user_state.withdrawable_balance(),
+        amount,
+        TrendXBetError::BonusFundsLocked
     );
-    require!(
-        treasury.has_sufficient_funds(amount),
+    require_gte!(
+        u64::try_from(treasury.available_balance()).unwrap_or(0),
+        amount,
         TrendXBetError::TreasuryInsufficientFunds
     );
 
-    // Transfer SOL from treasury to user
-    let treasury_seeds = &[TREASURY_SEED, &[treasury.bump]];
-    let signer_seeds = &[&treasury_seeds[..]];
-
-    let transfer_ctx = CpiContext::new_with_signer(
-        ctx.accounts.system_program.to_account_info(),
-        Transfer {
-            from: ctx.accounts.treasury_account.to_account_info(),
-            to: ctx.accounts.authority.to_account_info(),
-        },
-        signer_seeds,
-    );
-    transfer(transfer_ctx, amount)?;
+    // Transfer SOL from treasury to user. The treasury PDA is program-owned
+    // and carries data, so the system program cannot debit it
+    ctx.accounts.treasury_account.sub_lamports(amount)?;
+    ctx.accounts.authority.add_lamports(amount)?;
 
     // Update user balance
     user_state.subtract_balance(amount)?;
@@ -2,19 +2,23 @@
 #![allow(unexpected_cfgs)]
 #![allow(deprecated)]
 
+pub mod analytics;
 pub mod constants;
 pub mod error;
 pub mod events;
 pub mod instructions;
+pub mod price_feed;
 pub mod state;
 pub mod utils;
 
 use anchor_lang::prelude::*;
 
+pub use analytics::*;
 pub use constants::*;
 pub use error::*;
 pub use events::*;
 pub use instructions::*;
+pub use price_feed::*;
 pub use state::*;
 pub use utils::*;
 
@@ -49,6 +53,42 @@ pub mod trendxbet {
         instructions::user_instructions::withdraw_funds(ctx, amount)
     }
 
+    // Session Key Instructions
+    pub fn create_session_key(
+        ctx: Context<CreateSessionKey>,
+        session_key: Pubkey,
+        expires_at: i64,
+        max_bet_amount: u64,
+        spend_limit: u64,
+        permissions: u8,
+    ) -> Result<()> {
+        instructions::session_instructions::create_session_key(
+            ctx,
+            session_key,
+            expires_at,
+            max_bet_amount,
+            spend_limit,
+            permissions,
+        )
+    }
+
+    pub fn revoke_session_key(ctx: Context<RevokeSessionKey>) -> Result<()> {
+        instructions::session_instructions::revoke_session_key(ctx)
+    }
+
+    // Promotion Instructions
+    pub fn add_promoter(ctx: Context<AddPromoter>, promoter: Pubkey) -> Result<()> {
+        instructions::promotion_instructions::add_promoter(ctx, promoter)
+    }
+
+    pub fn remove_promoter(ctx: Context<RemovePromoter>) -> Result<()> {
+        instructions::promotion_instructions::remove_promoter(ctx)
+    }
+
+    pub fn issue_bonus(ctx: Context<IssueBonus>, amount: u64, wagering_multiplier: u8) -> Result<()> {
+        instructions::promotion_instructions::issue_bonus(ctx, amount, wagering_multiplier)
+    }
+
     // Match Management Instructions
     pub fn create_match(
         ctx: Context<CreateMatch>,
@@ -57,6 +97,7 @@ pub mod trendxbet {
         start_time: i64,
         end_time: i64,
         description: String,
+        creator_fee: u16,
     ) -> Result<()> {
         instructions::match_instructions::create_match(
             ctx,
@@ -65,6 +106,32 @@ pub mod trendxbet {
             start_time,
             end_time,
             description,
+            creator_fee,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_line_market(
+        ctx: Context<CreateMatch>,
+        team1: String,
+        team2: String,
+        market_kind: MarketKind,
+        line: i32,
+        start_time: i64,
+        end_time: i64,
+        description: String,
+        creator_fee: u16,
+    ) -> Result<()> {
+        instructions::match_instructions::create_line_market(
+            ctx,
+            team1,
+            team2,
+            market_kind,
+            line,
+            start_time,
+            end_time,
+            description,
+            creator_fee,
         )
     }
 
@@ -76,24 +143,134 @@ pub mod trendxbet {
         instructions::match_instructions::close_match_betting(ctx)
     }
 
+    pub fn set_betting_window(
+        ctx: Context<SetBettingWindow>,
+        opens_at: i64,
+        closes_at: i64,
+        early_bet_bonus: u16,
+    ) -> Result<()> {
+        instructions::match_instructions::set_betting_window(ctx, opens_at, closes_at, early_bet_bonus)
+    }
+
+    pub fn crank_match(ctx: Context<CrankMatch>) -> Result<()> {
+        instructions::match_instructions::crank_match(ctx)
+    }
+
+    pub fn void_match(ctx: Context<VoidMatch>) -> Result<()> {
+        instructions::match_instructions::void_match(ctx)
+    }
+
+    // Community Market Instructions
+    pub fn settle_creator(ctx: Context<SettleCreator>) -> Result<()> {
+        instructions::creator_instructions::settle_creator(ctx)
+    }
+
+    pub fn void_market_for_abuse(ctx: Context<VoidMarketForAbuse>) -> Result<()> {
+        instructions::creator_instructions::void_market_for_abuse(ctx)
+    }
+
+    // Private Pool Instructions
+    pub fn create_private_pool(
+        ctx: Context<CreatePrivatePool>,
+        invite_code_hash: Option<[u8; 32]>,
+        members: Vec<Pubkey>,
+        waives_house_edge: bool,
+    ) -> Result<()> {
+        instructions::private_pool_instructions::create_private_pool(
+            ctx,
+            invite_code_hash,
+            members,
+            waives_house_edge,
+        )
+    }
+
+    pub fn update_private_pool(
+        ctx: Context<UpdatePrivatePool>,
+        invite_code_hash: Option<[u8; 32]>,
+        members: Vec<Pubkey>,
+    ) -> Result<()> {
+        instructions::private_pool_instructions::update_private_pool(ctx, invite_code_hash, members)
+    }
+
+    pub fn join_private_pool(ctx: Context<JoinPrivatePool>, invite_code: String) -> Result<()> {
+        instructions::private_pool_instructions::join_private_pool(ctx, invite_code)
+    }
+
+    pub fn close_private_pool_betting(ctx: Context<ClosePrivatePoolBetting>) -> Result<()> {
+        instructions::private_pool_instructions::close_private_pool_betting(ctx)
+    }
+
+    pub fn cancel_private_pool(ctx: Context<CancelPrivatePool>) -> Result<()> {
+        instructions::private_pool_instructions::cancel_private_pool(ctx)
+    }
+
+    // Odds History Instructions
+    pub fn create_odds_history(ctx: Context<CreateOddsHistory>) -> Result<()> {
+        instructions::odds_history_instructions::create_odds_history(ctx)
+    }
+
+    pub fn get_odds_history(ctx: Context<GetOddsHistory>, offset: u16) -> Result<Vec<OddsPoint>> {
+        instructions::odds_history_instructions::get_odds_history(ctx, offset)
+    }
+
+    // Price Market Instructions
+    pub fn create_price_market(
+        ctx: Context<CreatePriceMarket>,
+        asset: String,
+        strike_price: i64,
+        start_time: i64,
+        end_time: i64,
+        description: String,
+        creator_fee: u16,
+    ) -> Result<()> {
+        instructions::price_market_instructions::create_price_market(
+            ctx,
+            asset,
+            strike_price,
+            start_time,
+            end_time,
+            description,
+            creator_fee,
+        )
+    }
+
+    pub fn resolve_price_market(ctx: Context<ResolvePriceMarket>) -> Result<()> {
+        instructions::price_market_instructions::resolve_price_market(ctx)
+    }
+
     // Betting Instructions
     pub fn place_bet(
         ctx: Context<PlaceBet>,
         amount: u64,
         predicted_team: u8,
         odds_accepted: u64,
+        use_bonus: bool,
     ) -> Result<()> {
-        instructions::bet_instructions::place_bet(ctx, amount, predicted_team, odds_accepted)
+        instructions::bet_instructions::place_bet(ctx, amount, predicted_team, odds_accepted, use_bonus)
     }
 
     pub fn cancel_bet(ctx: Context<CancelBet>) -> Result<()> {
         instructions::bet_instructions::cancel_bet(ctx)
     }
 
+    pub fn modify_bet(
+        ctx: Context<ModifyBet>,
+        amount: u64,
+        predicted_team: u8,
+        odds_accepted: u64,
+        use_bonus: bool,
+    ) -> Result<()> {
+        instructions::bet_instructions::modify_bet(ctx, amount, predicted_team, odds_accepted, use_bonus)
+    }
+
     pub fn settle_bet(ctx: Context<SettleBet>) -> Result<()> {
         instructions::bet_instructions::settle_bet(ctx)
     }
 
+    pub fn refund_bet(ctx: Context<RefundBet>) -> Result<()> {
+        instructions::bet_instructions::refund_bet(ctx)
+    }
+
     pub fn claim_winnings(ctx: Context<ClaimWinnings>) -> Result<()> {
         instructions::bet_instructions::claim_winnings(ctx)
     }
@@ -103,33 +280,141 @@ pub mod trendxbet {
         instructions::oracle_instructions::register_oracle(ctx, oracle_authority)
     }
 
-    pub fn update_match_result(
-        ctx: Context<UpdateMatchResult>,
+    pub fn commit_match_result(
+        ctx: Context<CommitMatchResult>,
+        commitment: [u8; 32],
+    ) -> Result<()> {
+        instructions::oracle_instructions::commit_match_result(ctx, commitment)
+    }
+
+    pub fn reveal_match_result(
+        ctx: Context<RevealMatchResult>,
         winning_team: u8,
         final_score: String,
+        match_score: Option<MatchScore>,
+        salt: [u8; 32],
+        evidence: Option<ReportEvidence>,
     ) -> Result<()> {
-        instructions::oracle_instructions::update_match_result(ctx, winning_team, final_score)
+        instructions::oracle_instructions::reveal_match_result(
+            ctx,
+            winning_team,
+            final_score,
+            match_score,
+            salt,
+            evidence,
+        )
     }
 
-    pub fn validate_oracle_update(ctx: Context<ValidateOracleUpdate>) -> Result<()> {
+    pub fn validate_oracle_update<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ValidateOracleUpdate<'info>>,
+    ) -> Result<()> {
         instructions::oracle_instructions::validate_oracle_update(ctx)
     }
 
+    // Oracle Staking Instructions
+    pub fn create_oracle_profile(ctx: Context<CreateOracleProfile>) -> Result<()> {
+        instructions::oracle_stake_instructions::create_oracle_profile(ctx)
+    }
+
+    pub fn bond_oracle_stake(ctx: Context<BondOracleStake>, amount: u64) -> Result<()> {
+        instructions::oracle_stake_instructions::bond_oracle_stake(ctx, amount)
+    }
+
+    pub fn unbond_oracle_stake(ctx: Context<UnbondOracleStake>, amount: u64) -> Result<()> {
+        instructions::oracle_stake_instructions::unbond_oracle_stake(ctx, amount)
+    }
+
+    pub fn withdraw_oracle_stake(ctx: Context<WithdrawOracleStake>) -> Result<()> {
+        instructions::oracle_stake_instructions::withdraw_oracle_stake(ctx)
+    }
+
+    pub fn resolve_oracle_report(ctx: Context<ResolveOracleReport>) -> Result<()> {
+        instructions::oracle_stake_instructions::resolve_oracle_report(ctx)
+    }
+
+    // Dispute Instructions
+    pub fn create_arbiter_set(
+        ctx: Context<CreateArbiterSet>,
+        arbiters: Vec<Pubkey>,
+        quorum: u8,
+    ) -> Result<()> {
+        instructions::dispute_instructions::create_arbiter_set(ctx, arbiters, quorum)
+    }
+
+    pub fn update_arbiter_set(
+        ctx: Context<UpdateArbiterSet>,
+        arbiters: Vec<Pubkey>,
+        quorum: u8,
+    ) -> Result<()> {
+        instructions::dispute_instructions::update_arbiter_set(ctx, arbiters, quorum)
+    }
+
+    pub fn open_dispute(ctx: Context<OpenDispute>, proposed_team: u8) -> Result<()> {
+        instructions::dispute_instructions::open_dispute(ctx, proposed_team)
+    }
+
+    pub fn vote_dispute(ctx: Context<VoteDispute>, uphold: bool) -> Result<()> {
+        instructions::dispute_instructions::vote_dispute(ctx, uphold)
+    }
+
+    pub fn expire_dispute(ctx: Context<ExpireDispute>) -> Result<()> {
+        instructions::dispute_instructions::expire_dispute(ctx)
+    }
+
+    // Match Registry Instructions
+    pub fn create_match_registry(ctx: Context<CreateMatchRegistry>) -> Result<()> {
+        instructions::registry_instructions::create_match_registry(ctx)
+    }
+
+    pub fn create_match_index_page(
+        ctx: Context<CreateMatchIndexPage>,
+        key: MatchIndexKey,
+        page: u32,
+    ) -> Result<()> {
+        instructions::registry_instructions::create_match_index_page(ctx, key, page)
+    }
+
     // Admin Instructions
+    #[allow(clippy::too_many_arguments)]
     pub fn update_platform_config(
         ctx: Context<UpdatePlatformConfig>,
         house_edge: Option<u16>,
         min_bet_amount: Option<u64>,
         max_bet_amount: Option<u64>,
+        crank_bounty: Option<u64>,
+        price_feed_program: Option<Pubkey>,
+        dispute_window: Option<i64>,
+        dispute_bond: Option<u64>,
+        betting_cutoff: Option<u32>,
+        early_bet_bonus: Option<u16>,
+        creator_bond: Option<u64>,
+        max_creator_fee: Option<u16>,
+        cancellation_fee: Option<u16>,
     ) -> Result<()> {
         instructions::admin_instructions::update_platform_config(
             ctx,
             house_edge,
             min_bet_amount,
             max_bet_amount,
+            crank_bounty,
+            price_feed_program,
+            dispute_window,
+            dispute_bond,
+            betting_cutoff,
+            early_bet_bonus,
+            creator_bond,
+            max_creator_fee,
+            cancellation_fee,
         )
     }
 
+    pub fn update_exposure_limits(
+        ctx: Context<UpdateExposureLimits>,
+        limits: ExposureLimits,
+    ) -> Result<()> {
+        instructions::admin_instructions::update_exposure_limits(ctx, limits)
+    }
+
     pub fn withdraw_platform_fees(ctx: Context<WithdrawPlatformFees>, amount: u64) -> Result<()> {
         instructions::admin_instructions::withdraw_platform_fees(ctx, amount)
     }
@@ -145,4 +430,18 @@ pub mod trendxbet {
     pub fn emergency_withdraw(ctx: Context<EmergencyWithdraw>, amount: u64) -> Result<()> {
         instructions::admin_instructions::emergency_withdraw(ctx, amount)
     }
+
+    // Audit Log Instructions
+    pub fn enable_audit_log(ctx: Context<EnableAuditLog>) -> Result<()> {
+        instructions::audit_log_instructions::enable_audit_log(ctx)
+    }
+
+    pub fn create_audit_log_page(ctx: Context<CreateAuditLogPage>, page: u32) -> Result<()> {
+        instructions::audit_log_instructions::create_audit_log_page(ctx, page)
+    }
+
+    // Account Migration
+    pub fn migrate_account(ctx: Context<MigrateAccount>) -> Result<()> {
+        instructions::migration_instructions::migrate_account(ctx)
+    }
 }
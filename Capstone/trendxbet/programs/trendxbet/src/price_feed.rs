@@ -0,0 +1,112 @@
+use crate::error::TrendXBetError;
+use anchor_lang::prelude::*;
+
+/// Magic number at the start of every Pyth price account
+pub const PRICE_ACCOUNT_MAGIC: u32 = 0xa1b2_c3d4;
+
+/// Pyth account layout version understood by the reader
+pub const PRICE_ACCOUNT_VERSION: u32 = 2;
+
+/// Pyth account type tag for price accounts
+pub const PRICE_ACCOUNT_TYPE: u32 = 3;
+
+/// Bytes of a price account read by `PriceFeedUtils`, up to the end of the
+/// aggregate price; real Pyth accounts carry publisher components after it
+pub const PRICE_ACCOUNT_MIN_SIZE: usize = 240;
+
+/// Byte offsets of the fields read from a Pyth price account
+pub mod offsets {
+    pub const MAGIC: usize = 0;
+    pub const VERSION: usize = 4;
+    pub const ACCOUNT_TYPE: usize = 8;
+    pub const EXPONENT: usize = 20;
+    pub const TIMESTAMP: usize = 96;
+    pub const AGG_PRICE: usize = 208;
+    pub const AGG_CONF: usize = 216;
+    pub const AGG_STATUS: usize = 224;
+}
+
+/// Aggregate price status as published by Pyth
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PriceStatus {
+    Unknown,
+    Trading,
+    Halted,
+    Auction,
+    Ignored,
+}
+
+impl PriceStatus {
+    fn from_u32(status: u32) -> Self {
+        match status {
+            1 => PriceStatus::Trading,
+            2 => PriceStatus::Halted,
+            3 => PriceStatus::Auction,
+            4 => PriceStatus::Ignored,
+            _ => PriceStatus::Unknown,
+        }
+    }
+}
+
+/// Aggregate price read from a price feed account; the value is `price * 10^expo`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PriceFeed {
+    pub price: i64,
+    pub conf: u64,
+    pub expo: i32,
+    pub publish_time: i64,
+    pub status: PriceStatus,
+}
+
+impl PriceFeed {
+    /// Check if the confidence interval is within `max_conf_bps` of the price
+    pub fn is_confident(&self, max_conf_bps: u64) -> bool {
+        let limit = (self.price.unsigned_abs() as u128) * (max_conf_bps as u128)
+            / (crate::constants::BASIS_POINTS as u128);
+        (self.conf as u128) <= limit
+    }
+}
+
+pub struct PriceFeedUtils;
+
+impl PriceFeedUtils {
+    /// Read the aggregate price from a Pyth-compatible price account
+    pub fn load(account: &AccountInfo) -> Result<PriceFeed> {
+        let data = account.try_borrow_data()?;
+        Self::parse(&data)
+    }
+
+    /// Parse the aggregate price out of raw price account data
+    pub fn parse(data: &[u8]) -> Result<PriceFeed> {
+        require!(
+            data.len() >= PRICE_ACCOUNT_MIN_SIZE,
+            TrendXBetError::InvalidPriceFeed
+        );
+        require!(
+            read_u32(data, offsets::MAGIC) == PRICE_ACCOUNT_MAGIC
+                && read_u32(data, offsets::VERSION) == PRICE_ACCOUNT_VERSION
+                && read_u32(data, offsets::ACCOUNT_TYPE) == PRICE_ACCOUNT_TYPE,
+            TrendXBetError::InvalidPriceFeed
+        );
+
+        Ok(PriceFeed {
+            price: read_u64(data, offsets::AGG_PRICE) as i64,
+            conf: read_u64(data, offsets::AGG_CONF),
+            expo: read_u32(data, offsets::EXPONENT) as i32,
+            publish_time: read_u64(data, offsets::TIMESTAMP) as i64,
+            status: PriceStatus::from_u32(read_u32(data, offsets::AGG_STATUS)),
+        })
+    }
+}
+
+fn read_u32(data: &[u8], offset: usize) -> u32 {
+    let mut bytes = [0u8; 4];
+    bytes.copy_from_slice(&data[offset..offset + 4]);
+    u32::from_le_bytes(bytes)
+}
+
+fn read_u64(data: &[u8], offset: usize) -> u64 {
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&data[offset..offset + 8]);
+    u64::from_le_bytes(bytes)
+}
@@ -0,0 +1,74 @@
+use crate::constants::*;
+use crate::state::Versioned;
+use anchor_lang::prelude::*;
+
+/// Arbiters that decide disputed match results
+#[account]
+pub struct ArbiterSet {
+    /// Arbiters allowed to vote on disputes
+    pub arbiters: Vec<Pubkey>,
+
+    /// Votes needed on one side to resolve a dispute
+    pub quorum: u8,
+
+    /// Bump seed for PDA
+    pub bump: u8,
+
+    /// Account layout version, see `Versioned`
+    pub version: u8,
+
+    /// Reserved space for future fields
+    pub reserved: [u8; 32],
+}
+
+impl ArbiterSet {
+    pub const LEN: usize = ARBITER_SET_SPACE;
+
+    /// Initialize the arbiter set
+    pub fn initialize(&mut self, arbiters: Vec<Pubkey>, quorum: u8, bump: u8) -> Result<()> {
+        self.bump = bump;
+        self.version = Self::VERSION;
+        self.reserved = [0; 32];
+        self.update(arbiters, quorum)
+    }
+
+    /// Replace the arbiters and quorum
+    pub fn update(&mut self, arbiters: Vec<Pubkey>, quorum: u8) -> Result<()> {
+        require!(
+            !arbiters.is_empty() && arbiters.len() <= MAX_ARBITERS,
+            crate::error::TrendXBetError::InvalidArbiterSet
+        );
+        require!(
+            quorum >= 1 && quorum as usize <= arbiters.len(),
+            crate::error::TrendXBetError::InvalidArbiterSet
+        );
+        for (i, arbiter) in arbiters.iter().enumerate() {
+            require!(
+                !arbiters[..i].contains(arbiter),
+                crate::error::TrendXBetError::InvalidArbiterSet
+            );
+        }
+
+        self.arbiters = arbiters;
+        self.quorum = quorum;
+        Ok(())
+    }
+
+    /// Check if `key` is an arbiter
+    pub fn is_arbiter(&self, key: &Pubkey) -> bool {
+        self.arbiters.contains(key)
+    }
+}
+
+impl Versioned for ArbiterSet {
+    const VERSION: u8 = 1;
+    const SPACE: usize = ARBITER_SET_SPACE;
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+
+    fn set_version(&mut self, version: u8) {
+        self.version = version;
+    }
+}
@@ -0,0 +1,133 @@
+use crate::constants::*;
+use crate::state::Versioned;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+
+/// Kind of privileged action recorded in the audit log
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AuditAction {
+    /// Platform configuration updated
+    ConfigUpdate,
+    /// Exposure limits updated
+    ExposureLimitsUpdate,
+    /// Platform paused
+    Pause,
+    /// Platform unpaused
+    Unpause,
+    /// Platform fees withdrawn from the treasury
+    FeeWithdrawal,
+    /// Treasury funds withdrawn in an emergency
+    EmergencyWithdrawal,
+    /// Oracle registered for a match
+    OracleRegistration,
+    /// Arbiter set created or updated
+    ArbiterSetUpdate,
+    /// Promoter authorised to issue bonuses
+    PromoterAddition,
+    /// Promoter's authorisation removed
+    PromoterRemoval,
+    /// Community market voided for abuse
+    MarketVoid,
+    /// Match status changed by the admin
+    MatchStatusUpdate,
+    /// Match betting closed by the admin
+    BettingClosure,
+    /// Match betting window set
+    BettingWindowUpdate,
+    /// Account migrated to the current layout
+    AccountMigration,
+    /// Oracle report validated
+    OracleValidation,
+}
+
+/// One privileged action in the audit log
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AuditEntry {
+    /// Position of the entry across every page, from 0
+    pub sequence: u64,
+
+    /// Action taken
+    pub action: AuditAction,
+
+    /// Key that signed the action
+    pub actor: Pubkey,
+
+    /// Account the action applied to, if any
+    pub target: Option<Pubkey>,
+
+    /// Hash of the action's parameters, see `AuditLogPage::params_hash`
+    pub params_hash: [u8; 32],
+
+    /// Timestamp of the action
+    pub timestamp: i64,
+}
+
+/// One page of up to `AUDIT_LOG_PAGE_SIZE` audit log entries, oldest first.
+///
+/// Page `n` holds entries `n * AUDIT_LOG_PAGE_SIZE` onwards and can only be
+/// created once the entries before it have filled the previous page.
+#[account]
+pub struct AuditLogPage {
+    /// Position of the page within the log
+    pub page: u32,
+
+    /// Entries recorded on this page
+    pub entries: Vec<AuditEntry>,
+
+    /// Bump seed for PDA
+    pub bump: u8,
+
+    /// Account layout version, see `Versioned`
+    pub version: u8,
+
+    /// Reserved space for future fields
+    pub reserved: [u8; 16],
+}
+
+impl AuditLogPage {
+    pub const LEN: usize = AUDIT_LOG_PAGE_SPACE;
+
+    /// Hash an action's Borsh-serialized parameters are recorded as
+    pub fn params_hash<T: AnchorSerialize>(params: &T) -> Result<[u8; 32]> {
+        let mut data = Vec::new();
+        params.serialize(&mut data)?;
+        Ok(hash(&data).to_bytes())
+    }
+
+    /// Initialize an empty page
+    pub fn initialize(&mut self, page: u32, bump: u8) {
+        self.page = page;
+        self.entries = Vec::new();
+        self.bump = bump;
+        self.version = Self::VERSION;
+        self.reserved = [0; 16];
+    }
+
+    /// Check if the page has no room left
+    pub fn is_full(&self) -> bool {
+        self.entries.len() >= AUDIT_LOG_PAGE_SIZE
+    }
+
+    /// Append `entry` to the page
+    pub fn record(&mut self, entry: AuditEntry) -> Result<()> {
+        require!(
+            !self.is_full(),
+            crate::error::TrendXBetError::AuditLogPageFull
+        );
+        self.entries.push(entry);
+        Ok(())
+    }
+}
+
+impl Versioned for AuditLogPage {
+    const VERSION: u8 = 1;
+    const SPACE: usize = AUDIT_LOG_PAGE_SPACE;
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+
+    fn set_version(&mut self, version: u8) {
+        self.version = version;
+    }
+}
@@ -1,10 +1,12 @@
 use crate::constants::*;
+use crate::state::Versioned;
 use anchor_lang::prelude::*;
 
 /// Bet status enumeration
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Default)]
 pub enum BetStatus {
     /// Bet is active and waiting for match result
+    #[default]
     Active,
     /// Bet won and payout available
     Won,
@@ -14,12 +16,8 @@ pub enum BetStatus {
     Cancelled,
     /// Bet payout has been claimed
     Claimed,
-}
-
-impl Default for BetStatus {
-    fn default() -> Self {
-        BetStatus::Active
-    }
+    /// Match was cancelled or voided and the stake returned
+    Refunded,
 }
 
 /// Individual bet state account
@@ -58,14 +56,30 @@ pub struct BetState {
     /// Bump seed for PDA
     pub bump: u8,
 
+    /// Platform fee charged when the bet was placed, returned on cancellation
+    pub platform_fee: u64,
+
+    /// Account layout version, see `Versioned`
+    pub version: u8,
+
+    /// Pool weight earned on top of the stake for betting early
+    pub bonus_weight: u64,
+
+    /// Part of the stake paid from the bettor's bonus balance, which is not paid back on a win
+    pub bonus_stake: u64,
+
+    /// Part of the stake paid from locked bonus winnings, locked again if it is returned
+    pub locked_stake: u64,
+
     /// Reserved space for future fields
-    pub reserved: [u8; 32],
+    pub reserved: [u8; 7],
 }
 
 impl BetState {
     pub const LEN: usize = BET_STATE_SPACE;
 
     /// Initialize new bet
+    #[allow(clippy::too_many_arguments)]
     pub fn initialize(
         &mut self,
         bettor: Pubkey,
@@ -74,6 +88,10 @@ impl BetState {
         predicted_team: u8,
         odds_at_time: u64,
         potential_payout: u64,
+        platform_fee: u64,
+        bonus_weight: u64,
+        bonus_stake: u64,
+        locked_stake: u64,
         bump: u8,
         current_time: i64,
     ) {
@@ -88,7 +106,35 @@ impl BetState {
         self.settled_at = None;
         self.payout_amount = None;
         self.bump = bump;
-        self.reserved = [0; 32];
+        self.platform_fee = platform_fee;
+        self.version = Self::VERSION;
+        self.bonus_weight = bonus_weight;
+        self.bonus_stake = bonus_stake;
+        self.locked_stake = locked_stake;
+        self.reserved = [0; 7];
+    }
+
+    /// Replace the stake and side of an active bet, along with everything derived from them
+    #[allow(clippy::too_many_arguments)]
+    pub fn modify(
+        &mut self,
+        amount: u64,
+        predicted_team: u8,
+        odds_at_time: u64,
+        potential_payout: u64,
+        platform_fee: u64,
+        bonus_weight: u64,
+        bonus_stake: u64,
+        locked_stake: u64,
+    ) {
+        self.amount = amount;
+        self.predicted_team = predicted_team;
+        self.odds_at_time = odds_at_time;
+        self.potential_payout = potential_payout;
+        self.platform_fee = platform_fee;
+        self.bonus_weight = bonus_weight;
+        self.bonus_stake = bonus_stake;
+        self.locked_stake = locked_stake;
     }
 
     /// Cancel the bet and mark for refund
@@ -98,6 +144,13 @@ impl BetState {
         self.payout_amount = Some(self.amount); // Full refund
     }
 
+    /// Refund the stake of a bet on a cancelled or voided match
+    pub fn refund(&mut self, current_time: i64) {
+        self.status = BetStatus::Refunded;
+        self.settled_at = Some(current_time);
+        self.payout_amount = Some(self.amount);
+    }
+
     /// Settle bet as won
     pub fn settle_as_won(&mut self, payout_amount: u64, current_time: i64) {
         self.status = BetStatus::Won;
@@ -114,23 +167,22 @@ impl BetState {
 
     /// Mark payout as claimed
     pub fn claim_payout(&mut self) -> Result<()> {
-        if self.status != BetStatus::Won && self.status != BetStatus::Cancelled {
-            return Err(error!(crate::error::TrendXBetError::BetNotFound));
+        if self.status != BetStatus::Won {
+            return Err(error!(crate::error::TrendXBetError::NoWinningsToClaim));
         }
 
         self.status = BetStatus::Claimed;
         Ok(())
     }
 
-    /// Check if bet can be cancelled
-    pub fn can_be_cancelled(&self, match_start_time: i64, current_time: i64) -> bool {
-        self.status == BetStatus::Active && current_time < match_start_time
+    /// Check if bet can be cancelled (only while the match is still taking bets)
+    pub fn can_be_cancelled(&self, betting_open: bool) -> bool {
+        self.status == BetStatus::Active && betting_open
     }
 
-    /// Check if bet has winnings to claim
+    /// Check if bet has winnings to claim (cancelled bets are refunded immediately)
     pub fn has_claimable_winnings(&self) -> bool {
-        matches!(self.status, BetStatus::Won | BetStatus::Cancelled)
-            && self.payout_amount.unwrap_or(0) > 0
+        self.status == BetStatus::Won && self.payout_amount.unwrap_or(0) > 0
     }
 
     /// Get claimable amount
@@ -147,16 +199,47 @@ impl BetState {
         self.predicted_team == winning_team
     }
 
-    /// Calculate actual payout using parimutuel system
+    /// Split a winning payout into the part paid to the bettor and the part kept
+    /// by the platform, which repays the stake taken from their bonus balance
+    pub fn split_bonus_payout(&self, payout: u64) -> (u64, u64) {
+        let retained = payout.min(self.bonus_stake);
+        (payout - retained, retained)
+    }
+
+    /// Part of a claimed `payout` won on the bonus stake: the bonus stake's share of
+    /// the full payout, less the stake the platform kept
+    pub fn bonus_winnings(&self, payout: u64) -> Result<u64> {
+        use crate::utils::MathUtils;
+
+        if self.bonus_stake == 0 {
+            return Ok(0);
+        }
+        let full_payout = payout.saturating_add(self.bonus_stake);
+        let bonus_payout = MathUtils::calculate_share(full_payout, self.bonus_stake, self.amount)?;
+        Ok(bonus_payout.saturating_sub(self.bonus_stake).min(payout))
+    }
+
+    /// Share of the winning pool this bet is paid on: its stake plus any early-bet bonus
+    pub fn weight(&self) -> u64 {
+        self.amount.saturating_add(self.bonus_weight)
+    }
+
+    /// Calculate actual payout using parimutuel system, `winning_weight` being the
+    /// combined weight of the winning bets
     pub fn calculate_parimutuel_payout(
         &self,
-        winning_pool: u64,
+        winning_weight: u64,
         total_pool: u64,
         house_edge: u16,
     ) -> Result<u64> {
         use crate::utils::MathUtils;
 
-        MathUtils::calculate_parimutuel_payout(self.amount, winning_pool, total_pool, house_edge)
+        MathUtils::calculate_parimutuel_payout(
+            self.weight(),
+            winning_weight,
+            total_pool,
+            house_edge,
+        )
     }
 
     /// Get bet profit/loss
@@ -166,8 +249,8 @@ impl BetState {
                 (self.payout_amount.unwrap_or(0) as i64) - (self.amount as i64)
             }
             BetStatus::Lost => -(self.amount as i64),
-            BetStatus::Cancelled => 0, // Refunded
-            BetStatus::Active => 0,    // Not yet determined
+            BetStatus::Cancelled | BetStatus::Refunded => 0, // Refunded
+            BetStatus::Active => 0,                          // Not yet determined
         }
     }
 
@@ -185,3 +268,16 @@ impl BetState {
         }
     }
 }
+
+impl Versioned for BetState {
+    const VERSION: u8 = 2;
+    const SPACE: usize = BET_STATE_SPACE;
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+
+    fn set_version(&mut self, version: u8) {
+        self.version = version;
+    }
+}
@@ -0,0 +1,175 @@
+use crate::constants::*;
+use crate::state::Versioned;
+use anchor_lang::prelude::*;
+
+/// Dispute status enumeration
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Default)]
+pub enum DisputeStatus {
+    /// Waiting for arbiter votes
+    #[default]
+    Open,
+    /// Arbiters agreed with the challenger and the result was overturned
+    Upheld,
+    /// Arbiters kept the oracle result and the bond was forfeited
+    Rejected,
+    /// Arbiters missed the voting deadline, so the result stands and the bond was refunded
+    Expired,
+}
+
+/// Bonded challenge of a match result.
+///
+/// The challenger's bond is kept in the dispute account itself, on top of its rent.
+#[account]
+pub struct DisputeState {
+    /// Match whose result is disputed
+    pub match_id: Pubkey,
+
+    /// User who opened the dispute and posted the bond
+    pub challenger: Pubkey,
+
+    /// Lamports bonded by the challenger
+    pub bond: u64,
+
+    /// Winning team reported by the oracles
+    pub original_team: u8,
+
+    /// Winning team proposed by the challenger
+    pub proposed_team: u8,
+
+    /// Current dispute status
+    pub status: DisputeStatus,
+
+    /// Arbiter votes for the challenger
+    pub votes_uphold: u8,
+
+    /// Arbiter votes for the oracle result
+    pub votes_reject: u8,
+
+    /// Arbiters that have voted
+    pub voters: Vec<Pubkey>,
+
+    /// Timestamp when the dispute was opened
+    pub opened_at: i64,
+
+    /// Timestamp when the arbiters reached quorum or the dispute expired
+    pub resolved_at: Option<i64>,
+
+    /// Bump seed for PDA
+    pub bump: u8,
+
+    /// Account layout version, see `Versioned`
+    pub version: u8,
+
+    /// Reserved space for future fields
+    pub reserved: [u8; 32],
+}
+
+impl DisputeState {
+    pub const LEN: usize = DISPUTE_STATE_SPACE;
+
+    /// Initialize a new dispute
+    #[allow(clippy::too_many_arguments)]
+    pub fn initialize(
+        &mut self,
+        match_id: Pubkey,
+        challenger: Pubkey,
+        bond: u64,
+        original_team: u8,
+        proposed_team: u8,
+        bump: u8,
+        current_time: i64,
+    ) {
+        self.match_id = match_id;
+        self.challenger = challenger;
+        self.bond = bond;
+        self.original_team = original_team;
+        self.proposed_team = proposed_team;
+        self.status = DisputeStatus::Open;
+        self.votes_uphold = 0;
+        self.votes_reject = 0;
+        self.voters = Vec::new();
+        self.opened_at = current_time;
+        self.resolved_at = None;
+        self.bump = bump;
+        self.version = Self::VERSION;
+        self.reserved = [0; 32];
+    }
+
+    /// Time after which votes are refused and the dispute can be expired
+    pub fn voting_deadline(&self) -> i64 {
+        self.opened_at.saturating_add(DISPUTE_VOTING_PERIOD)
+    }
+
+    /// Record an arbiter vote, resolving the dispute once either side reaches `quorum`
+    pub fn record_vote(
+        &mut self,
+        arbiter: Pubkey,
+        uphold: bool,
+        quorum: u8,
+        current_time: i64,
+    ) -> Result<()> {
+        require!(
+            self.status == DisputeStatus::Open,
+            crate::error::TrendXBetError::DisputeNotOpen
+        );
+        require_gt!(
+            self.voting_deadline(),
+            current_time,
+            crate::error::TrendXBetError::DisputeVotingClosed
+        );
+        require!(
+            !self.voters.contains(&arbiter),
+            crate::error::TrendXBetError::AlreadyVoted
+        );
+        require!(
+            self.voters.len() < MAX_ARBITERS,
+            crate::error::TrendXBetError::InvalidArbiterSet
+        );
+
+        self.voters.push(arbiter);
+        if uphold {
+            self.votes_uphold += 1;
+        } else {
+            self.votes_reject += 1;
+        }
+
+        if self.votes_uphold >= quorum {
+            self.status = DisputeStatus::Upheld;
+            self.resolved_at = Some(current_time);
+        } else if self.votes_reject >= quorum {
+            self.status = DisputeStatus::Rejected;
+            self.resolved_at = Some(current_time);
+        }
+        Ok(())
+    }
+
+    /// Close a dispute still open at its voting deadline, keeping the result
+    pub fn expire(&mut self, current_time: i64) -> Result<()> {
+        require!(
+            self.status == DisputeStatus::Open,
+            crate::error::TrendXBetError::DisputeNotOpen
+        );
+        require_gte!(
+            current_time,
+            self.voting_deadline(),
+            crate::error::TrendXBetError::DisputeVotingOpen
+        );
+
+        self.status = DisputeStatus::Expired;
+        self.resolved_at = Some(current_time);
+        Ok(())
+    }
+}
+
+impl Versioned for DisputeState {
+    const VERSION: u8 = 1;
+    const SPACE: usize = DISPUTE_STATE_SPACE;
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+
+    fn set_version(&mut self, version: u8) {
+        self.version = version;
+    }
+}
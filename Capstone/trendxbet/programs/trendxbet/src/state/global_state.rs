@@ -1,6 +1,71 @@
 use crate::constants::*;
+use crate::state::{MatchState, Versioned};
 use anchor_lang::prelude::*;
 
+/// Caps on how much a single match may take on; zero disables a cap
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub struct ExposureLimits {
+    /// Largest total pool a match may reach
+    pub max_match_pool: u64,
+
+    /// Largest lamport difference between the two outcome pools a bet may leave
+    pub max_pool_imbalance: u64,
+
+    /// Largest total a single user may stake on one match
+    pub max_user_match_stake: u64,
+}
+
+impl ExposureLimits {
+    /// Check that a bet of `amount` on `team` keeps `match_state` within the caps,
+    /// `user_stake` being the bettor's total stake on the match including the bet
+    pub fn check(
+        &self,
+        match_state: &MatchState,
+        team: u8,
+        amount: u64,
+        user_stake: u64,
+    ) -> Result<()> {
+        let total_pool = match_state.total_pool.saturating_add(amount);
+        if self.max_match_pool > 0 {
+            require_gte!(
+                self.max_match_pool,
+                total_pool,
+                crate::error::TrendXBetError::MatchPoolCapExceeded
+            );
+        }
+
+        // Bets that even the pools out are always accepted
+        let (team1_pool, team2_pool) = match team {
+            0 => (
+                match_state.team1_pool.saturating_add(amount),
+                match_state.team2_pool,
+            ),
+            _ => (
+                match_state.team1_pool,
+                match_state.team2_pool.saturating_add(amount),
+            ),
+        };
+        let imbalance = team1_pool.abs_diff(team2_pool);
+        let previous_imbalance = match_state.team1_pool.abs_diff(match_state.team2_pool);
+        if self.max_pool_imbalance > 0 && imbalance > previous_imbalance {
+            require_gte!(
+                self.max_pool_imbalance,
+                imbalance,
+                crate::error::TrendXBetError::PoolImbalanceCapExceeded
+            );
+        }
+
+        if self.max_user_match_stake > 0 {
+            require_gte!(
+                self.max_user_match_stake,
+                user_stake,
+                crate::error::TrendXBetError::UserMatchStakeCapExceeded
+            );
+        }
+        Ok(())
+    }
+}
+
 /// Global platform state account
 #[account]
 pub struct GlobalState {
@@ -30,6 +95,45 @@ pub struct GlobalState {
 
     /// Bump seed for PDA
     pub bump: u8,
+
+    /// Account layout version, see `Versioned`
+    pub version: u8,
+
+    /// Lamports paid from platform fees to whoever cranks a match status
+    pub crank_bounty: u64,
+
+    /// Program that owns the price feed accounts price markets may resolve from
+    pub price_feed_program: Pubkey,
+
+    /// Seconds after an oracle result settles a match during which it can be disputed
+    pub dispute_window: i64,
+
+    /// Lamports a challenger bonds to dispute a result
+    pub dispute_bond: u64,
+
+    /// Caps on per-match exposure enforced when bets are placed
+    pub exposure_limits: ExposureLimits,
+
+    /// Seconds before a match ends at which betting closes on new matches
+    pub betting_cutoff: u32,
+
+    /// Extra pool weight, in basis points, given to the earliest bets on new matches
+    pub early_bet_bonus: u16,
+
+    /// Lamports anyone other than the admin bonds to create a market
+    pub creator_bond: u64,
+
+    /// Highest creator fee, in basis points, a market creator may set
+    pub max_creator_fee: u16,
+
+    /// Share, in basis points, of a stake withdrawn before betting closes that the platform keeps
+    pub cancellation_fee: u16,
+
+    /// Whether privileged actions must be recorded in the audit log
+    pub has_audit_log: bool,
+
+    /// Privileged actions recorded in the audit log
+    pub audit_entries: u64,
 }
 
 impl GlobalState {
@@ -54,14 +158,37 @@ impl GlobalState {
         self.platform_created_at = current_time;
         self.is_paused = false;
         self.bump = bump;
+        self.version = Self::VERSION;
+        self.crank_bounty = DEFAULT_CRANK_BOUNTY;
+        self.price_feed_program = PYTH_ORACLE_PROGRAM_ID;
+        self.dispute_window = DEFAULT_DISPUTE_WINDOW;
+        self.dispute_bond = DEFAULT_DISPUTE_BOND;
+        self.exposure_limits = ExposureLimits::default();
+        self.betting_cutoff = 0;
+        self.early_bet_bonus = 0;
+        self.creator_bond = DEFAULT_CREATOR_BOND;
+        self.max_creator_fee = DEFAULT_MAX_CREATOR_FEE;
+        self.cancellation_fee = 0;
+        self.has_audit_log = false;
+        self.audit_entries = 0;
     }
 
     /// Update platform configuration
+    #[allow(clippy::too_many_arguments)]
     pub fn update_config(
         &mut self,
         house_edge: Option<u16>,
         min_bet_amount: Option<u64>,
         max_bet_amount: Option<u64>,
+        crank_bounty: Option<u64>,
+        price_feed_program: Option<Pubkey>,
+        dispute_window: Option<i64>,
+        dispute_bond: Option<u64>,
+        betting_cutoff: Option<u32>,
+        early_bet_bonus: Option<u16>,
+        creator_bond: Option<u64>,
+        max_creator_fee: Option<u16>,
+        cancellation_fee: Option<u16>,
     ) {
         if let Some(edge) = house_edge {
             self.house_edge = edge;
@@ -72,6 +199,33 @@ impl GlobalState {
         if let Some(max_amount) = max_bet_amount {
             self.max_bet_amount = max_amount;
         }
+        if let Some(bounty) = crank_bounty {
+            self.crank_bounty = bounty;
+        }
+        if let Some(program) = price_feed_program {
+            self.price_feed_program = program;
+        }
+        if let Some(window) = dispute_window {
+            self.dispute_window = window;
+        }
+        if let Some(bond) = dispute_bond {
+            self.dispute_bond = bond;
+        }
+        if let Some(cutoff) = betting_cutoff {
+            self.betting_cutoff = cutoff;
+        }
+        if let Some(bonus) = early_bet_bonus {
+            self.early_bet_bonus = bonus;
+        }
+        if let Some(bond) = creator_bond {
+            self.creator_bond = bond;
+        }
+        if let Some(fee) = max_creator_fee {
+            self.max_creator_fee = fee;
+        }
+        if let Some(fee) = cancellation_fee {
+            self.cancellation_fee = fee;
+        }
     }
 
     /// Add volume to total
@@ -92,6 +246,27 @@ impl GlobalState {
         Ok(())
     }
 
+    /// Fee kept from `amount` of stake withdrawn before betting closes
+    pub fn cancellation_fee_on(&self, amount: u64) -> Result<u64> {
+        use crate::utils::MathUtils;
+
+        MathUtils::calculate_platform_fee(amount, self.cancellation_fee)
+    }
+
+    /// Audit log page the next privileged action is recorded on
+    pub fn audit_page(&self) -> u32 {
+        (self.audit_entries / AUDIT_LOG_PAGE_SIZE as u64) as u32
+    }
+
+    /// Count a privileged action recorded in the audit log, returning its sequence number
+    pub fn record_audit_entry(&mut self) -> Result<u64> {
+        let sequence = self.audit_entries;
+        self.audit_entries = sequence
+            .checked_add(1)
+            .ok_or(error!(crate::error::TrendXBetError::MathematicalOverflow))?;
+        Ok(sequence)
+    }
+
     /// Pause the platform
     pub fn pause(&mut self) {
         self.is_paused = true;
@@ -107,3 +282,41 @@ impl GlobalState {
         !self.is_paused
     }
 }
+
+impl Versioned for GlobalState {
+    const VERSION: u8 = 4;
+    const SPACE: usize = GLOBAL_STATE_SPACE;
+
+    fn upgrade(&mut self, from_version: u8) -> Result<()> {
+        // Version 1 accounts written before the crank bounty, price feeds and
+        // disputes existed read those settings back as zero
+        if from_version == 1 {
+            if self.crank_bounty == 0 {
+                self.crank_bounty = DEFAULT_CRANK_BOUNTY;
+            }
+            if self.price_feed_program == Pubkey::default() {
+                self.price_feed_program = PYTH_ORACLE_PROGRAM_ID;
+            }
+            if self.dispute_window == 0 {
+                self.dispute_window = DEFAULT_DISPUTE_WINDOW;
+            }
+            if self.dispute_bond == 0 {
+                self.dispute_bond = DEFAULT_DISPUTE_BOND;
+            }
+        }
+        // Version 2 had no community markets
+        if from_version == 2 {
+            self.creator_bond = DEFAULT_CREATOR_BOND;
+            self.max_creator_fee = DEFAULT_MAX_CREATOR_FEE;
+        }
+        Ok(())
+    }
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+
+    fn set_version(&mut self, version: u8) {
+        self.version = version;
+    }
+}
@@ -0,0 +1,176 @@
+use crate::constants::*;
+use crate::state::{MatchStatus, Versioned};
+use anchor_lang::prelude::*;
+
+/// Key of a paged match index
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MatchIndexKey {
+    /// Matches in a league, by league id
+    League(u32),
+    /// Matches in a status, by `MatchStatus::code`
+    Status(u8),
+    /// Matches starting on a UTC day, in days since the unix epoch
+    StartDay(i64),
+}
+
+impl MatchIndexKey {
+    /// Index of the matches in `status`
+    pub fn status(status: &MatchStatus) -> Self {
+        MatchIndexKey::Status(status.code())
+    }
+
+    /// Index of the matches starting on the same day as `start_time`
+    pub fn start_day(start_time: i64) -> Self {
+        MatchIndexKey::StartDay(start_time.div_euclid(SECONDS_PER_DAY))
+    }
+
+    /// PDA seed: the variant tag followed by the value in little-endian
+    pub fn seed(&self) -> [u8; MATCH_INDEX_KEY_SPACE] {
+        let (tag, value) = match *self {
+            MatchIndexKey::League(league) => (0, league as i64),
+            MatchIndexKey::Status(code) => (1, code as i64),
+            MatchIndexKey::StartDay(day) => (2, day),
+        };
+
+        let mut seed = [0; MATCH_INDEX_KEY_SPACE];
+        seed[0] = tag;
+        seed[1..].copy_from_slice(&value.to_le_bytes());
+        seed
+    }
+}
+
+/// Registry of every match listed in the paged indexes
+#[account]
+pub struct MatchRegistry {
+    /// Matches listed since the registry was created
+    pub total_matches: u64,
+
+    /// Bump seed for PDA
+    pub bump: u8,
+
+    /// Account layout version, see `Versioned`
+    pub version: u8,
+
+    /// Reserved space for future fields
+    pub reserved: [u8; 32],
+}
+
+impl MatchRegistry {
+    pub const LEN: usize = MATCH_REGISTRY_SPACE;
+
+    /// Initialize the registry
+    pub fn initialize(&mut self, bump: u8) {
+        self.total_matches = 0;
+        self.bump = bump;
+        self.version = Self::VERSION;
+        self.reserved = [0; 32];
+    }
+
+    /// Count a newly listed match
+    pub fn record_match(&mut self) -> Result<()> {
+        self.total_matches = self
+            .total_matches
+            .checked_add(1)
+            .ok_or(error!(crate::error::TrendXBetError::MathematicalOverflow))?;
+        Ok(())
+    }
+}
+
+/// One page of up to `MATCH_INDEX_PAGE_SIZE` matches under an index key.
+///
+/// Pages of a key are numbered from 0; a page is only created once the one before it is full.
+#[account]
+pub struct MatchIndexPage {
+    /// Index this page belongs to
+    pub key: MatchIndexKey,
+
+    /// Position of the page within its index
+    pub page: u32,
+
+    /// Matches listed on this page
+    pub matches: Vec<Pubkey>,
+
+    /// Bump seed for PDA
+    pub bump: u8,
+
+    /// Account layout version, see `Versioned`
+    pub version: u8,
+
+    /// Reserved space for future fields
+    pub reserved: [u8; 16],
+}
+
+impl MatchIndexPage {
+    pub const LEN: usize = MATCH_INDEX_PAGE_SPACE;
+
+    /// Initialize an empty page
+    pub fn initialize(&mut self, key: MatchIndexKey, page: u32, bump: u8) {
+        self.key = key;
+        self.page = page;
+        self.matches = Vec::new();
+        self.bump = bump;
+        self.version = Self::VERSION;
+        self.reserved = [0; 16];
+    }
+
+    /// Check if the page has no room left
+    pub fn is_full(&self) -> bool {
+        self.matches.len() >= MATCH_INDEX_PAGE_SIZE
+    }
+
+    /// List `match_id` on this page, which must belong to `key`
+    pub fn insert(&mut self, key: MatchIndexKey, match_id: Pubkey) -> Result<()> {
+        require!(
+            self.key == key,
+            crate::error::TrendXBetError::MatchIndexMismatch
+        );
+        require!(!self.is_full(), crate::error::TrendXBetError::IndexPageFull);
+
+        if !self.matches.contains(&match_id) {
+            self.matches.push(match_id);
+        }
+        Ok(())
+    }
+
+    /// Unlist `match_id` from this page, which must belong to `key` and list it
+    pub fn remove(&mut self, key: MatchIndexKey, match_id: &Pubkey) -> Result<()> {
+        require!(
+            self.key == key,
+            crate::error::TrendXBetError::MatchIndexMismatch
+        );
+        let position = self
+            .matches
+            .iter()
+            .position(|listed| listed == match_id)
+            .ok_or(error!(crate::error::TrendXBetError::MatchIndexMismatch))?;
+
+        self.matches.swap_remove(position);
+        Ok(())
+    }
+}
+
+impl Versioned for MatchRegistry {
+    const VERSION: u8 = 1;
+    const SPACE: usize = MATCH_REGISTRY_SPACE;
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+
+    fn set_version(&mut self, version: u8) {
+        self.version = version;
+    }
+}
+
+impl Versioned for MatchIndexPage {
+    const VERSION: u8 = 1;
+    const SPACE: usize = MATCH_INDEX_PAGE_SPACE;
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+
+    fn set_version(&mut self, version: u8) {
+        self.version = version;
+    }
+}
@@ -1,10 +1,12 @@
 use crate::constants::*;
+use crate::state::Versioned;
 use anchor_lang::prelude::*;
 
 /// Match status enumeration
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Default)]
 pub enum MatchStatus {
     /// Match is scheduled and betting is open
+    #[default]
     Scheduled,
     /// Match is live and betting is closed
     Live,
@@ -14,11 +16,76 @@ pub enum MatchStatus {
     Settled,
     /// Match was cancelled
     Cancelled,
+    /// No result arrived before the oracle deadline; bets are refundable
+    Voided,
 }
 
-impl Default for MatchStatus {
-    fn default() -> Self {
-        MatchStatus::Scheduled
+impl MatchStatus {
+    /// Numeric code used in events
+    pub fn code(&self) -> u8 {
+        match self {
+            MatchStatus::Scheduled => 0,
+            MatchStatus::Live => 1,
+            MatchStatus::Ended => 2,
+            MatchStatus::Settled => 3,
+            MatchStatus::Cancelled => 4,
+            MatchStatus::Voided => 5,
+        }
+    }
+}
+
+/// Kind of market a match account settles
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MarketKind {
+    /// Team vs team, resolved by oracles
+    #[default]
+    TeamMatch,
+    /// Asset above a strike price at end time, resolved from a price feed
+    PriceTrend,
+    /// Combined points over (team 1) or under (team 2) the line, resolved by oracles
+    Totals,
+    /// Team 1's points plus the line against team 2's; team 1 covers if ahead
+    Spread,
+}
+
+/// Points scored by each side in one period of a match
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub struct PeriodScore {
+    pub home: u16,
+    pub away: u16,
+}
+
+/// Structured final score; home is team 1 and away is team 2
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq, Default)]
+pub struct MatchScore {
+    /// Final points of team 1
+    pub home: u16,
+
+    /// Final points of team 2
+    pub away: u16,
+
+    /// Optional per-period breakdown, informational only
+    pub periods: Vec<PeriodScore>,
+}
+
+impl MatchScore {
+    /// Combined points of both sides
+    pub fn total(&self) -> i64 {
+        self.home as i64 + self.away as i64
+    }
+
+    /// Team 1's points minus team 2's
+    pub fn margin(&self) -> i64 {
+        self.home as i64 - self.away as i64
+    }
+
+    /// Team ahead on points, or None for a draw
+    pub fn leader(&self) -> Option<u8> {
+        match self.margin() {
+            m if m > 0 => Some(0),
+            m if m < 0 => Some(1),
+            _ => None,
+        }
     }
 }
 
@@ -79,14 +146,88 @@ pub struct MatchState {
     /// Bump seed for PDA
     pub bump: u8,
 
+    /// Account layout version, see `Versioned`
+    pub version: u8,
+
+    /// Number of oracles that have reported a result
+    pub oracle_reports: u8,
+
+    /// Kind of market, which decides how the result is resolved
+    pub market_kind: MarketKind,
+
+    /// Line of a totals or spread market, in `LINE_SCALE` units
+    pub line: i32,
+
+    /// Structured final score once the oracles agree on it
+    pub match_score: Option<MatchScore>,
+
+    /// Platform fees charged on the bets still standing on this match
+    pub platform_fees: u64,
+
+    /// Timestamp until which an oracle-settled result can be disputed
+    pub dispute_deadline: i64,
+
+    /// Whether a dispute of the result is awaiting the arbiters
+    pub is_disputed: bool,
+
+    /// Time betting opens, which may be before the match starts
+    pub betting_opens_at: i64,
+
+    /// Time betting closes, at the latest when the match ends
+    pub betting_closes_at: i64,
+
+    /// Extra pool weight, in basis points, of a bet placed as betting opens,
+    /// decaying linearly to none at close
+    pub early_bet_bonus: u16,
+
+    /// Early-bet bonus weight of the bets on team 1
+    pub team1_bonus_weight: u64,
+
+    /// Early-bet bonus weight of the bets on team 2
+    pub team2_bonus_weight: u64,
+
+    /// League the match is listed under in the registry, 0 if none
+    pub league: u32,
+
+    /// Whether the match is listed in the registry's indexes
+    pub is_indexed: bool,
+
+    /// Community member who created the market, the default key for admin markets
+    pub creator: Pubkey,
+
+    /// Lamports the creator bonded, held on this account until the creator is settled
+    pub creator_bond: u64,
+
+    /// Creator's cut of the pool, in basis points, paid at settlement
+    pub creator_fee: u16,
+
+    /// Whether the creator's fee and bond have been paid out or forfeited
+    pub is_creator_settled: bool,
+
+    /// Whether only the bettors invited to the match's private pool may bet
+    pub is_private: bool,
+
+    /// Whether the private pool's organiser waived the house edge
+    pub waives_house_edge: bool,
+
+    /// Whether bets must record the pools in the match's odds history
+    pub has_odds_history: bool,
+
+    /// Whether an upheld dispute replaced the winner the oracles reported
+    pub is_result_overturned: bool,
+
+    /// Whether a cranker was already paid the bounty for advancing the match
+    pub is_crank_bounty_paid: bool,
+
     /// Reserved space for future fields
-    pub reserved: [u8; 64],
+    pub reserved: [u8; 5],
 }
 
 impl MatchState {
     pub const LEN: usize = MATCH_STATE_SPACE;
 
     /// Initialize new match
+    #[allow(clippy::too_many_arguments)]
     pub fn initialize(
         &mut self,
         match_id: Pubkey,
@@ -116,7 +257,89 @@ impl MatchState {
         self.is_settled = false;
         self.match_created_at = current_time;
         self.bump = bump;
-        self.reserved = [0; 64];
+        self.version = Self::VERSION;
+        self.oracle_reports = 0;
+        self.market_kind = MarketKind::TeamMatch;
+        self.line = 0;
+        self.match_score = None;
+        self.platform_fees = 0;
+        self.dispute_deadline = 0;
+        self.is_disputed = false;
+        self.betting_opens_at = start_time;
+        self.betting_closes_at = end_time;
+        self.early_bet_bonus = 0;
+        self.team1_bonus_weight = 0;
+        self.team2_bonus_weight = 0;
+        self.league = 0;
+        self.is_indexed = false;
+        self.creator = Pubkey::default();
+        self.creator_bond = 0;
+        self.creator_fee = 0;
+        self.is_creator_settled = false;
+        self.is_private = false;
+        self.waives_house_edge = false;
+        self.has_odds_history = false;
+        self.is_result_overturned = false;
+        self.is_crank_bounty_paid = false;
+        self.reserved = [0; 5];
+    }
+
+    /// Record the community creator of the market and the terms they set
+    pub fn set_creator(&mut self, creator: Pubkey, creator_bond: u64, creator_fee: u16) {
+        self.creator = creator;
+        self.creator_bond = creator_bond;
+        self.creator_fee = creator_fee;
+    }
+
+    /// Check if the market was created by a community member rather than the admin
+    pub fn has_creator(&self) -> bool {
+        self.creator != Pubkey::default()
+    }
+
+    /// Restrict betting to a private pool's invitees, optionally without a house edge
+    pub fn make_private(&mut self, waives_house_edge: bool) {
+        self.is_private = true;
+        self.waives_house_edge = waives_house_edge;
+    }
+
+    /// Require every bet on the match to record its pools in the odds history
+    pub fn enable_odds_history(&mut self) {
+        self.has_odds_history = true;
+    }
+
+    /// House edge, in basis points, charged on this match given the platform's
+    pub fn house_edge(&self, platform_house_edge: u16) -> u16 {
+        if self.waives_house_edge {
+            0
+        } else {
+            platform_house_edge
+        }
+    }
+
+    /// Share of the pool, in basis points, taken before winners are paid
+    pub fn total_edge(&self, house_edge: u16) -> u16 {
+        self.house_edge(house_edge).saturating_add(self.creator_fee)
+    }
+
+    /// Creator fee earned on the pool of a settled market
+    pub fn creator_fee_amount(&self) -> Result<u64> {
+        use crate::utils::MathUtils;
+
+        MathUtils::calculate_platform_fee(self.total_pool, self.creator_fee)
+    }
+
+    /// Mark the creator's fee and bond as paid out or forfeited
+    pub fn settle_creator(&mut self) -> Result<()> {
+        require!(
+            self.has_creator(),
+            crate::error::TrendXBetError::NotCommunityMarket
+        );
+        require!(
+            !self.is_creator_settled,
+            crate::error::TrendXBetError::CreatorAlreadySettled
+        );
+        self.is_creator_settled = true;
+        Ok(())
     }
 
     /// Update match status
@@ -124,8 +347,50 @@ impl MatchState {
         self.status = new_status;
     }
 
+    /// Set when bets are taken and the bonus weight of the earliest ones
+    pub fn configure_betting(
+        &mut self,
+        opens_at: i64,
+        closes_at: i64,
+        early_bet_bonus: u16,
+    ) -> Result<()> {
+        require_gt!(
+            closes_at,
+            opens_at,
+            crate::error::TrendXBetError::InvalidBettingWindow
+        );
+        require_gte!(
+            self.end_time,
+            closes_at,
+            crate::error::TrendXBetError::InvalidBettingWindow
+        );
+        require_gte!(
+            MAX_EARLY_BET_BONUS,
+            early_bet_bonus,
+            crate::error::TrendXBetError::InvalidEarlyBetBonus
+        );
+
+        self.betting_opens_at = opens_at;
+        self.betting_closes_at = closes_at;
+        self.early_bet_bonus = early_bet_bonus;
+        Ok(())
+    }
+
+    /// Bonus weight a bet of `amount` placed now earns on top of its stake
+    pub fn early_bet_bonus_weight(&self, amount: u64, current_time: i64) -> Result<u64> {
+        use crate::utils::MathUtils;
+
+        MathUtils::calculate_early_bet_bonus(
+            amount,
+            self.early_bet_bonus,
+            self.betting_opens_at,
+            self.betting_closes_at,
+            current_time,
+        )
+    }
+
     /// Add bet to the pools
-    pub fn add_bet(&mut self, amount: u64, team: u8) -> Result<()> {
+    pub fn add_bet(&mut self, amount: u64, bonus_weight: u64, team: u8) -> Result<()> {
         // Add to total pool
         self.total_pool = self
             .total_pool
@@ -138,11 +403,19 @@ impl MatchState {
                 .team1_pool
                 .checked_add(amount)
                 .ok_or(error!(crate::error::TrendXBetError::MathematicalOverflow))?;
+            self.team1_bonus_weight = self
+                .team1_bonus_weight
+                .checked_add(bonus_weight)
+                .ok_or(error!(crate::error::TrendXBetError::MathematicalOverflow))?;
         } else {
             self.team2_pool = self
                 .team2_pool
                 .checked_add(amount)
                 .ok_or(error!(crate::error::TrendXBetError::MathematicalOverflow))?;
+            self.team2_bonus_weight = self
+                .team2_bonus_weight
+                .checked_add(bonus_weight)
+                .ok_or(error!(crate::error::TrendXBetError::MathematicalOverflow))?;
         }
 
         // Increment bet count
@@ -155,7 +428,7 @@ impl MatchState {
     }
 
     /// Remove bet from the pools (for cancellations)
-    pub fn remove_bet(&mut self, amount: u64, team: u8) -> Result<()> {
+    pub fn remove_bet(&mut self, amount: u64, bonus_weight: u64, team: u8) -> Result<()> {
         // Remove from total pool
         self.total_pool = self
             .total_pool
@@ -168,11 +441,19 @@ impl MatchState {
                 .team1_pool
                 .checked_sub(amount)
                 .ok_or(error!(crate::error::TrendXBetError::MathematicalOverflow))?;
+            self.team1_bonus_weight = self
+                .team1_bonus_weight
+                .checked_sub(bonus_weight)
+                .ok_or(error!(crate::error::TrendXBetError::MathematicalOverflow))?;
         } else {
             self.team2_pool = self
                 .team2_pool
                 .checked_sub(amount)
                 .ok_or(error!(crate::error::TrendXBetError::MathematicalOverflow))?;
+            self.team2_bonus_weight = self
+                .team2_bonus_weight
+                .checked_sub(bonus_weight)
+                .ok_or(error!(crate::error::TrendXBetError::MathematicalOverflow))?;
         }
 
         // Decrement bet count
@@ -184,6 +465,30 @@ impl MatchState {
         Ok(())
     }
 
+    /// Add the platform fee charged on a new bet
+    pub fn add_platform_fee(&mut self, amount: u64) -> Result<()> {
+        self.platform_fees = self
+            .platform_fees
+            .checked_add(amount)
+            .ok_or(error!(crate::error::TrendXBetError::MathematicalOverflow))?;
+        Ok(())
+    }
+
+    /// Remove the platform fee of a cancelled bet.
+    /// Saturates for bets placed before match fees were tracked.
+    pub fn remove_platform_fee(&mut self, amount: u64) {
+        self.platform_fees = self.platform_fees.saturating_sub(amount);
+    }
+
+    /// Platform fees the match earned; a voided match refunds its fees
+    pub fn earned_platform_fees(&self) -> u64 {
+        if self.is_settled {
+            self.platform_fees
+        } else {
+            0
+        }
+    }
+
     /// Close betting for this match
     pub fn close_betting(&mut self) {
         self.is_betting_closed = true;
@@ -203,17 +508,173 @@ impl MatchState {
         self.status = MatchStatus::Settled;
     }
 
+    /// Open the window in which the settled result can be disputed
+    pub fn open_dispute_window(&mut self, current_time: i64, dispute_window: i64) {
+        self.dispute_deadline = current_time.saturating_add(dispute_window);
+    }
+
+    /// Check if the result can still be disputed
+    pub fn is_dispute_window_open(&self, current_time: i64) -> bool {
+        self.is_settled && !self.is_disputed && current_time < self.dispute_deadline
+    }
+
+    /// Fail unless the result is final: no dispute is pending and the window is closed
+    pub fn require_final_result(&self, current_time: i64) -> Result<()> {
+        require!(
+            !self.is_disputed,
+            crate::error::TrendXBetError::ResultUnderDispute
+        );
+        require_gte!(
+            current_time,
+            self.dispute_deadline,
+            crate::error::TrendXBetError::DisputeWindowOpen
+        );
+        Ok(())
+    }
+
+    /// Close a dispute, replacing the winner when it was upheld
+    pub fn resolve_dispute(&mut self, winning_team: Option<u8>, current_time: i64) {
+        if let Some(team) = winning_team {
+            self.winning_team = Some(team);
+            // The reported score no longer backs the result
+            self.match_score = None;
+            self.is_result_overturned = true;
+        }
+        self.is_disputed = false;
+        self.dispute_deadline = self.dispute_deadline.min(current_time);
+    }
+
     /// Cancel the match
     pub fn cancel(&mut self) {
         self.status = MatchStatus::Cancelled;
     }
 
+    /// Check if the result is reported by oracles rather than read from a price feed
+    pub fn is_oracle_resolved(&self) -> bool {
+        self.market_kind != MarketKind::PriceTrend
+    }
+
+    /// Check if the market settles against a line rather than on the winner
+    pub fn is_line_market(&self) -> bool {
+        matches!(self.market_kind, MarketKind::Totals | MarketKind::Spread)
+    }
+
+    /// Side a final score settles the market on, or None for a draw or a push
+    pub fn score_outcome(&self, score: &MatchScore) -> Option<u8> {
+        let line = self.line as i64;
+        let edge = match self.market_kind {
+            MarketKind::TeamMatch => return score.leader(),
+            MarketKind::PriceTrend => return None,
+            MarketKind::Totals => score.total() * LINE_SCALE - line,
+            MarketKind::Spread => score.margin() * LINE_SCALE + line,
+        };
+
+        match edge {
+            e if e > 0 => Some(0),
+            e if e < 0 => Some(1),
+            _ => None,
+        }
+    }
+
+    /// Check an oracle's reported result against its structured score.
+    ///
+    /// Line markets require a score and settle only on what it implies, `PUSH_RESULT`
+    /// when it lands on the line. Team matches may omit it, and a drawn score leaves
+    /// the winner to the oracle, e.g. after a shootout.
+    pub fn validate_report(&self, winning_team: u8, score: Option<&MatchScore>) -> Result<()> {
+        if self.is_line_market() {
+            let score = score.ok_or(error!(crate::error::TrendXBetError::ScoreRequired))?;
+            let expected = self.score_outcome(score).unwrap_or(PUSH_RESULT);
+            require_eq!(
+                winning_team,
+                expected,
+                crate::error::TrendXBetError::ScoreMismatch
+            );
+            return Ok(());
+        }
+
+        require_gte!(1, winning_team, crate::error::TrendXBetError::InvalidTeam);
+        if let Some(leader) = score.and_then(|score| self.score_outcome(score)) {
+            require_eq!(
+                winning_team,
+                leader,
+                crate::error::TrendXBetError::ScoreMismatch
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Record a push: the line market is voided so every bet is refunded
+    pub fn push(&mut self, final_score: String, score: Option<MatchScore>, oracle_time: i64) {
+        self.final_score = Some(final_score);
+        self.match_score = score;
+        self.oracle_result_time = Some(oracle_time);
+        self.void();
+    }
+
+    /// Result and score the oracles agreed on, once the match settled or pushed
+    pub fn consensus_report(&self) -> Option<(u8, Option<MatchScore>)> {
+        match self.status {
+            MatchStatus::Settled => self
+                .winning_team
+                .map(|winning_team| (winning_team, self.match_score.clone())),
+            MatchStatus::Voided if self.final_score.is_some() => {
+                Some((PUSH_RESULT, self.match_score.clone()))
+            }
+            _ => None,
+        }
+    }
+
+    /// Whether oracle reports are judged on their structured score as well as the
+    /// result; an overturned result keeps only the winner the arbiters chose
+    pub fn is_score_final(&self) -> bool {
+        !self.is_result_overturned
+    }
+
+    /// Count an oracle's first report on this match
+    pub fn record_oracle_report(&mut self) {
+        self.oracle_reports = self.oracle_reports.saturating_add(1);
+    }
+
+    /// Check if the match is still waiting for a settled result
+    pub fn is_awaiting_result(&self) -> bool {
+        !self.is_settled
+            && matches!(
+                self.status,
+                MatchStatus::Scheduled | MatchStatus::Live | MatchStatus::Ended
+            )
+    }
+
+    /// Check if the oracles missed their deadline to settle the match.
+    ///
+    /// A match nobody reported on is past it once the oracle update window is over;
+    /// one with reports that never reached consensus, once the settlement window is.
+    pub fn has_missed_oracle_deadline(&self, current_time: i64) -> bool {
+        let unreported = self.oracle_reports == 0
+            && current_time > self.end_time.saturating_add(ORACLE_UPDATE_WINDOW);
+        let unsettled = current_time >= self.end_time.saturating_add(SETTLEMENT_WINDOW);
+
+        unreported || unsettled
+    }
+
+    /// Void the match so every bet on it can be refunded
+    pub fn void(&mut self) {
+        self.status = MatchStatus::Voided;
+        self.is_betting_closed = true;
+    }
+
+    /// Check if bets on this match are refunded rather than settled
+    pub fn is_refundable(&self) -> bool {
+        !self.is_settled && matches!(self.status, MatchStatus::Cancelled | MatchStatus::Voided)
+    }
+
     /// Check if betting is currently allowed
     pub fn is_betting_allowed(&self, current_time: i64) -> bool {
         !self.is_betting_closed
             && self.status == MatchStatus::Scheduled
-            && current_time >= self.start_time
-            && current_time <= self.end_time
+            && current_time >= self.betting_opens_at
+            && current_time <= self.betting_closes_at
     }
 
     /// Check if match has started
@@ -226,6 +687,24 @@ impl MatchState {
         current_time >= self.end_time
     }
 
+    /// Time the crank moves the match to Live, closing the live betting window
+    pub fn live_at(&self) -> i64 {
+        self.start_time
+            .saturating_add(LIVE_BETTING_WINDOW)
+            .min(self.end_time)
+    }
+
+    /// Status the clock says the match should be in, if it differs from the current one
+    pub fn crank_status(&self, current_time: i64) -> Option<MatchStatus> {
+        match self.status {
+            MatchStatus::Scheduled | MatchStatus::Live if self.has_ended(current_time) => {
+                Some(MatchStatus::Ended)
+            }
+            MatchStatus::Scheduled if current_time >= self.live_at() => Some(MatchStatus::Live),
+            _ => None,
+        }
+    }
+
     /// Get odds for a team (in basis points)
     pub fn get_team_odds(&self, team: u8) -> u64 {
         use crate::utils::MathUtils;
@@ -255,6 +734,17 @@ impl MatchState {
         })
     }
 
+    /// Get the combined weight of the winning bets, stakes plus early-bet bonuses
+    pub fn get_winning_weight(&self) -> Option<u64> {
+        self.winning_team.map(|team| {
+            if team == 0 {
+                self.team1_pool.saturating_add(self.team1_bonus_weight)
+            } else {
+                self.team2_pool.saturating_add(self.team2_bonus_weight)
+            }
+        })
+    }
+
     /// Get the losing pool amount
     pub fn get_losing_pool(&self) -> Option<u64> {
         self.winning_team.map(|team| {
@@ -266,3 +756,29 @@ impl MatchState {
         })
     }
 }
+
+impl Versioned for MatchState {
+    const VERSION: u8 = 4;
+    const SPACE: usize = MATCH_STATE_SPACE;
+
+    fn upgrade(&mut self, from_version: u8) -> Result<()> {
+        // Version 1 took bets for as long as the match ran
+        if from_version == 1 {
+            self.betting_opens_at = self.start_time;
+            self.betting_closes_at = self.end_time;
+        }
+        // Version 3 paid a bounty on every crank, so a match already under way has had its bounty
+        if from_version == 3 {
+            self.is_crank_bounty_paid = self.status != MatchStatus::Scheduled;
+        }
+        Ok(())
+    }
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+
+    fn set_version(&mut self, version: u8) {
+        self.version = version;
+    }
+}
@@ -1,13 +1,37 @@
+pub mod arbiter_set;
+pub mod audit_log;
 pub mod bet_state;
 pub mod global_state;
+pub mod dispute_state;
+pub mod match_registry;
 pub mod match_state;
+pub mod odds_history;
+pub mod oracle_profile;
 pub mod oracle_state;
+pub mod price_market;
+pub mod private_pool;
+pub mod promoter;
+pub mod session_key;
 pub mod treasury_state;
 pub mod user_state;
+pub mod username_record;
+pub mod version;
 
+pub use arbiter_set::*;
+pub use audit_log::*;
 pub use bet_state::*;
 pub use global_state::*;
+pub use dispute_state::*;
+pub use match_registry::*;
 pub use match_state::*;
+pub use odds_history::*;
+pub use oracle_profile::*;
 pub use oracle_state::*;
+pub use price_market::*;
+pub use private_pool::*;
+pub use promoter::*;
+pub use session_key::*;
 pub use treasury_state::*;
 pub use user_state::*;
+pub use username_record::*;
+pub use version::*;
@@ -0,0 +1,117 @@
+use crate::constants::*;
+use crate::state::{MatchState, Versioned};
+use anchor_lang::prelude::*;
+
+/// Pools and implied odds of a match at one moment
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct OddsPoint {
+    /// Timestamp of the change that produced these pools
+    pub timestamp: i64,
+
+    /// Lamports staked on team1
+    pub team1_pool: u64,
+
+    /// Lamports staked on team2
+    pub team2_pool: u64,
+
+    /// Implied odds of team1 (in basis points)
+    pub team1_odds: u64,
+
+    /// Implied odds of team2 (in basis points)
+    pub team2_odds: u64,
+}
+
+/// Ring buffer of a match's most recent odds, appended to whenever a bet changes its pools
+#[account]
+pub struct OddsHistory {
+    /// Match the history charts
+    pub match_id: Pubkey,
+
+    /// Recorded points, `head` being the slot the next one is written to
+    pub points: [OddsPoint; ODDS_HISTORY_CAPACITY],
+
+    /// Slot the next point is written to
+    pub head: u16,
+
+    /// Number of points recorded, up to `ODDS_HISTORY_CAPACITY`
+    pub len: u16,
+
+    /// Timestamp when the history was created
+    pub created_at: i64,
+
+    /// Bump seed for PDA
+    pub bump: u8,
+
+    /// Account layout version, see `Versioned`
+    pub version: u8,
+
+    /// Reserved space for future fields
+    pub reserved: [u8; 16],
+}
+
+impl OddsHistory {
+    pub const LEN: usize = ODDS_HISTORY_SPACE;
+
+    /// Initialize the history of `match_id`, starting from its current pools
+    pub fn initialize(
+        &mut self,
+        match_id: Pubkey,
+        match_state: &MatchState,
+        bump: u8,
+        current_time: i64,
+    ) {
+        self.match_id = match_id;
+        self.points = [OddsPoint::default(); ODDS_HISTORY_CAPACITY];
+        self.head = 0;
+        self.len = 0;
+        self.created_at = current_time;
+        self.bump = bump;
+        self.version = Self::VERSION;
+        self.reserved = [0; 16];
+        self.record(match_state, current_time);
+    }
+
+    /// Append the current pools and odds of `match_state`, overwriting the oldest point when full
+    pub fn record(&mut self, match_state: &MatchState, current_time: i64) {
+        self.points[self.head as usize] = OddsPoint {
+            timestamp: current_time,
+            team1_pool: match_state.team1_pool,
+            team2_pool: match_state.team2_pool,
+            team1_odds: match_state.get_team_odds(0),
+            team2_odds: match_state.get_team_odds(1),
+        };
+        self.head = ((self.head as usize + 1) % ODDS_HISTORY_CAPACITY) as u16;
+        self.len = (self.len as usize + 1).min(ODDS_HISTORY_CAPACITY) as u16;
+    }
+
+    /// Recorded points from oldest to newest
+    pub fn series(&self) -> Vec<OddsPoint> {
+        let len = self.len as usize;
+        let start = (self.head as usize + ODDS_HISTORY_CAPACITY - len) % ODDS_HISTORY_CAPACITY;
+        (0..len)
+            .map(|offset| self.points[(start + offset) % ODDS_HISTORY_CAPACITY])
+            .collect()
+    }
+
+    /// Up to `ODDS_HISTORY_PAGE_SIZE` points of the series, skipping the `offset` oldest
+    pub fn page(&self, offset: u16) -> Vec<OddsPoint> {
+        self.series()
+            .into_iter()
+            .skip(offset as usize)
+            .take(ODDS_HISTORY_PAGE_SIZE)
+            .collect()
+    }
+}
+
+impl Versioned for OddsHistory {
+    const VERSION: u8 = 1;
+    const SPACE: usize = ODDS_HISTORY_SPACE;
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+
+    fn set_version(&mut self, version: u8) {
+        self.version = version;
+    }
+}
@@ -0,0 +1,169 @@
+use crate::constants::*;
+use crate::state::Versioned;
+use anchor_lang::prelude::*;
+
+/// Oracle operator profile holding its bonded stake and reputation.
+///
+/// Bonded lamports are kept in the profile account itself, on top of its rent.
+#[account]
+pub struct OracleProfile {
+    /// Oracle authority that owns this profile
+    pub authority: Pubkey,
+
+    /// Lamports bonded and at stake for reports
+    pub stake: u64,
+
+    /// Lamports leaving the bond, still slashable until withdrawn
+    pub unbonding_amount: u64,
+
+    /// Timestamp after which the unbonding amount can be withdrawn
+    pub unbonding_at: i64,
+
+    /// Reputation in basis points, raised by agreeing reports and cut by slashes
+    pub reputation: u16,
+
+    /// Number of reports that matched consensus
+    pub reports_agreed: u32,
+
+    /// Number of reports that were slashed
+    pub reports_slashed: u32,
+
+    /// Total fee share earned
+    pub total_rewards: u64,
+
+    /// Total stake slashed
+    pub total_slashed: u64,
+
+    /// Timestamp when the profile was created
+    pub created_at: i64,
+
+    /// Bump seed for PDA
+    pub bump: u8,
+
+    /// Account layout version, see `Versioned`
+    pub version: u8,
+
+    /// Reserved space for future fields
+    pub reserved: [u8; 32],
+}
+
+impl OracleProfile {
+    pub const LEN: usize = ORACLE_PROFILE_SPACE;
+
+    /// Initialize an oracle profile with nothing bonded
+    pub fn initialize(&mut self, authority: Pubkey, bump: u8, current_time: i64) {
+        self.authority = authority;
+        self.stake = 0;
+        self.unbonding_amount = 0;
+        self.unbonding_at = 0;
+        self.reputation = ORACLE_INITIAL_REPUTATION;
+        self.reports_agreed = 0;
+        self.reports_slashed = 0;
+        self.total_rewards = 0;
+        self.total_slashed = 0;
+        self.created_at = current_time;
+        self.bump = bump;
+        self.version = Self::VERSION;
+        self.reserved = [0; 32];
+    }
+
+    /// Add bonded stake
+    pub fn bond(&mut self, amount: u64) -> Result<()> {
+        self.stake = self
+            .stake
+            .checked_add(amount)
+            .ok_or(error!(crate::error::TrendXBetError::MathematicalOverflow))?;
+        Ok(())
+    }
+
+    /// Move stake into unbonding; every call restarts the unbonding period
+    pub fn begin_unbonding(&mut self, amount: u64, current_time: i64) -> Result<i64> {
+        require_gte!(
+            self.stake,
+            amount,
+            crate::error::TrendXBetError::InsufficientOracleStake
+        );
+
+        self.stake -= amount;
+        self.unbonding_amount = self
+            .unbonding_amount
+            .checked_add(amount)
+            .ok_or(error!(crate::error::TrendXBetError::MathematicalOverflow))?;
+        self.unbonding_at = current_time
+            .checked_add(ORACLE_UNBONDING_PERIOD)
+            .ok_or(error!(crate::error::TrendXBetError::MathematicalOverflow))?;
+        Ok(self.unbonding_at)
+    }
+
+    /// Release the unbonded stake once the unbonding period is over
+    pub fn withdraw_unbonded(&mut self, current_time: i64) -> Result<u64> {
+        require!(
+            self.unbonding_amount > 0,
+            crate::error::TrendXBetError::NothingUnbonding
+        );
+        require_gte!(
+            current_time,
+            self.unbonding_at,
+            crate::error::TrendXBetError::StakeStillUnbonding
+        );
+
+        let amount = self.unbonding_amount;
+        self.unbonding_amount = 0;
+        self.unbonding_at = 0;
+        Ok(amount)
+    }
+
+    /// Lamports that can still be slashed
+    pub fn slashable_stake(&self) -> u64 {
+        self.stake.saturating_add(self.unbonding_amount)
+    }
+
+    /// Record a report that matched consensus
+    pub fn record_agreement(&mut self, reward: u64) -> Result<()> {
+        self.reports_agreed = self.reports_agreed.saturating_add(1);
+        self.total_rewards = self
+            .total_rewards
+            .checked_add(reward)
+            .ok_or(error!(crate::error::TrendXBetError::MathematicalOverflow))?;
+        self.reputation = self
+            .reputation
+            .saturating_add(ORACLE_REPUTATION_GAIN)
+            .min(BASIS_POINTS as u16);
+        Ok(())
+    }
+
+    /// Slash `ORACLE_SLASH_BPS` of the slashable stake, bonded stake first,
+    /// returning the lamports taken
+    pub fn slash(&mut self) -> Result<u64> {
+        let amount = self
+            .slashable_stake()
+            .checked_mul(ORACLE_SLASH_BPS)
+            .ok_or(error!(crate::error::TrendXBetError::MathematicalOverflow))?
+            / BASIS_POINTS;
+
+        let from_stake = amount.min(self.stake);
+        self.stake -= from_stake;
+        self.unbonding_amount -= amount - from_stake;
+
+        self.reports_slashed = self.reports_slashed.saturating_add(1);
+        self.total_slashed = self
+            .total_slashed
+            .checked_add(amount)
+            .ok_or(error!(crate::error::TrendXBetError::MathematicalOverflow))?;
+        self.reputation = self.reputation.saturating_sub(ORACLE_REPUTATION_PENALTY);
+        Ok(amount)
+    }
+}
+
+impl Versioned for OracleProfile {
+    const VERSION: u8 = 1;
+    const SPACE: usize = ORACLE_PROFILE_SPACE;
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+
+    fn set_version(&mut self, version: u8) {
+        self.version = version;
+    }
+}
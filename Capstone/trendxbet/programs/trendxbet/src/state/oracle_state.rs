@@ -1,5 +1,18 @@
+use crate::analytics::Analytics;
 use crate::constants::*;
+use crate::state::{MatchScore, Versioned};
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+
+/// Source an oracle relied on for its report, e.g. a stats-provider page snapshot
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ReportEvidence {
+    /// Where the source can be retrieved
+    pub uri: String,
+
+    /// SHA-256 of the source content the oracle saw
+    pub content_hash: [u8; 32],
+}
 
 /// Oracle state account for managing match result reporting
 #[account]
@@ -31,8 +44,26 @@ pub struct OracleState {
     /// Bump seed for PDA
     pub bump: u8,
 
+    /// Account layout version, see `Versioned`
+    pub version: u8,
+
+    /// Reported structured score
+    pub reported_match_score: Option<MatchScore>,
+
+    /// Hash the oracle committed to before revealing, see `OracleState::commitment`
+    pub commitment: Option<[u8; 32]>,
+
+    /// Timestamp when the commitment was made
+    pub commit_time: Option<i64>,
+
+    /// Whether the report was rewarded or slashed against consensus
+    pub is_resolved: bool,
+
+    /// Source attached to the revealed report
+    pub evidence: Option<ReportEvidence>,
+
     /// Reserved space for future fields
-    pub reserved: [u8; 32],
+    pub reserved: [u8; 31],
 }
 
 impl OracleState {
@@ -49,44 +80,94 @@ impl OracleState {
         self.validation_time = None;
         self.confirmations = 0;
         self.bump = bump;
-        self.reserved = [0; 32];
+        self.version = Self::VERSION;
+        self.reported_match_score = None;
+        self.commitment = None;
+        self.commit_time = None;
+        self.is_resolved = false;
+        self.evidence = None;
+        self.reserved = [0; 31];
     }
 
-    /// Submit oracle result
-    pub fn submit_result(
-        &mut self,
+    /// Hash an oracle commits to before revealing its report.
+    ///
+    /// Binding the oracle and match stops one oracle from reusing another's commitment.
+    pub fn commitment(
+        oracle_authority: &Pubkey,
+        match_id: &Pubkey,
         winning_team: u8,
-        final_score: String,
-        current_time: i64,
-    ) -> Result<()> {
-        // Check if result already exists
-        if self.reported_result.is_some() {
+        final_score: &str,
+        match_score: &Option<MatchScore>,
+        salt: &[u8; 32],
+    ) -> Result<[u8; 32]> {
+        let mut report = vec![winning_team];
+        final_score.serialize(&mut report)?;
+        match_score.serialize(&mut report)?;
+
+        Ok(hashv(&[oracle_authority.as_ref(), match_id.as_ref(), &report, salt]).to_bytes())
+    }
+
+    /// Commit to a result without publishing it
+    pub fn commit(&mut self, commitment: [u8; 32], current_time: i64) -> Result<()> {
+        if self.commitment.is_some() {
             return Err(error!(crate::error::TrendXBetError::OracleResultExists));
         }
 
-        self.reported_result = Some(winning_team);
-        self.reported_score = Some(final_score);
-        self.report_time = Some(current_time);
-        self.confirmations = 1; // First confirmation
+        self.commitment = Some(commitment);
+        self.commit_time = Some(current_time);
+        Ok(())
+    }
 
+    /// Reveal the committed result, which must hash to the commitment, with the
+    /// evidence it was based on
+    pub fn reveal(
+        &mut self,
+        winning_team: u8,
+        final_score: String,
+        match_score: Option<MatchScore>,
+        evidence: Option<ReportEvidence>,
+        salt: &[u8; 32],
+        current_time: i64,
+    ) -> Result<()> {
+        let commitment = self
+            .commitment
+            .ok_or(error!(crate::error::TrendXBetError::OracleNotCommitted))?;
+        let revealed = Self::commitment(
+            &self.oracle_authority,
+            &self.match_id,
+            winning_team,
+            &final_score,
+            &match_score,
+            salt,
+        )?;
+        require!(
+            revealed == commitment,
+            crate::error::TrendXBetError::CommitmentMismatch
+        );
+
+        self.submit_result(winning_team, final_score, match_score, current_time)?;
+        self.evidence = evidence;
         Ok(())
     }
 
-    /// Update oracle result (for corrections)
-    pub fn update_result(
+    /// Submit oracle result
+    pub fn submit_result(
         &mut self,
         winning_team: u8,
         final_score: String,
+        match_score: Option<MatchScore>,
         current_time: i64,
     ) -> Result<()> {
-        // Only allow updates if not yet validated
-        if self.is_validated {
+        // Check if result already exists
+        if self.reported_result.is_some() {
             return Err(error!(crate::error::TrendXBetError::OracleResultExists));
         }
 
         self.reported_result = Some(winning_team);
         self.reported_score = Some(final_score);
+        self.reported_match_score = match_score;
         self.report_time = Some(current_time);
+        self.confirmations = 1; // First confirmation
 
         Ok(())
     }
@@ -102,11 +183,11 @@ impl OracleState {
 
     /// Validate the oracle result
     pub fn validate(&mut self, current_time: i64) -> Result<()> {
-        if self.confirmations < MIN_ORACLE_CONFIRMATIONS {
-            return Err(error!(
-                crate::error::TrendXBetError::InsufficientOracleConfirmations
-            ));
-        }
+        require_gte!(
+            self.confirmations,
+            MIN_ORACLE_CONFIRMATIONS,
+            crate::error::TrendXBetError::InsufficientOracleConfirmations
+        );
 
         self.is_validated = true;
         self.validation_time = Some(current_time);
@@ -118,18 +199,6 @@ impl OracleState {
         self.is_validated && self.reported_result.is_some()
     }
 
-    /// Check if oracle update is within valid time window
-    pub fn is_update_valid(&self, match_end_time: i64, current_time: i64) -> bool {
-        use crate::utils::TimeUtils;
-
-        if let Some(report_time) = self.report_time {
-            TimeUtils::is_oracle_update_valid(match_end_time, report_time)
-        } else {
-            // For new reports, check current time
-            TimeUtils::is_oracle_update_valid(match_end_time, current_time)
-        }
-    }
-
     /// Get reported result with validation check
     pub fn get_validated_result(&self) -> Option<u8> {
         if self.is_ready() {
@@ -148,20 +217,23 @@ impl OracleState {
         }
     }
 
+    /// Get reported structured score with validation check
+    pub fn get_validated_match_score(&self) -> Option<MatchScore> {
+        if self.is_ready() {
+            self.reported_match_score.clone()
+        } else {
+            None
+        }
+    }
+
     /// Check if oracle has minimum confirmations
     pub fn has_minimum_confirmations(&self) -> bool {
         self.confirmations >= MIN_ORACLE_CONFIRMATIONS
     }
 
-    /// Calculate confidence score based on confirmations
-    pub fn confidence_score(&self) -> f64 {
-        if self.confirmations == 0 {
-            return 0.0;
-        }
-
-        // Simple confidence calculation: min(confirmations / min_required, 1.0)
-        let score = (self.confirmations as f64) / (MIN_ORACLE_CONFIRMATIONS as f64);
-        score.min(1.0)
+    /// Calculate confidence score based on confirmations (in basis points, capped at 100%)
+    pub fn confidence_score_bps(&self) -> u64 {
+        Analytics::confidence_bps(self.confirmations, MIN_ORACLE_CONFIRMATIONS)
     }
 
     /// Check if result matches another oracle's result
@@ -173,6 +245,37 @@ impl OracleState {
         }
     }
 
+    /// Check if result and structured score both match another oracle's report
+    pub fn matches_report(&self, other_result: u8, other_score: &Option<MatchScore>) -> bool {
+        self.matches_result(other_result) && self.reported_match_score == *other_score
+    }
+
+    /// Resolve the report against the consensus result, and its score when
+    /// `check_score`, returning whether it agreed. A commitment that was never
+    /// revealed counts as disagreeing.
+    pub fn resolve(
+        &mut self,
+        consensus_result: u8,
+        consensus_score: &Option<MatchScore>,
+        check_score: bool,
+    ) -> Result<bool> {
+        require!(
+            !self.is_resolved,
+            crate::error::TrendXBetError::OracleReportAlreadyResolved
+        );
+        require!(
+            self.commitment.is_some(),
+            crate::error::TrendXBetError::OracleNotCommitted
+        );
+
+        self.is_resolved = true;
+        if check_score {
+            Ok(self.matches_report(consensus_result, consensus_score))
+        } else {
+            Ok(self.matches_result(consensus_result))
+        }
+    }
+
     /// Get time since report submission
     pub fn time_since_report(&self, current_time: i64) -> Option<i64> {
         self.report_time
@@ -184,3 +287,16 @@ impl OracleState {
         self.oracle_authority == *authority
     }
 }
+
+impl Versioned for OracleState {
+    const VERSION: u8 = 3;
+    const SPACE: usize = ORACLE_STATE_SPACE;
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+
+    fn set_version(&mut self, version: u8) {
+        self.version = version;
+    }
+}
@@ -0,0 +1,106 @@
+use crate::constants::*;
+use crate::state::Versioned;
+use anchor_lang::prelude::*;
+
+/// Price-trend market: "will `asset` be above `strike_price` at the match end time".
+///
+/// The match it belongs to holds the pools; team 1 is "above", team 2 "at or below".
+#[account]
+pub struct PriceMarket {
+    /// Match the market settles
+    pub match_id: Pubkey,
+
+    /// Price feed account the market resolves from
+    pub price_feed: Pubkey,
+
+    /// Owner of `price_feed` when the market was created
+    pub feed_program: Pubkey,
+
+    /// Asset label, e.g. "SOL/USD"
+    pub asset: String,
+
+    /// Strike price in feed units, i.e. `strike_price * 10^expo`
+    pub strike_price: i64,
+
+    /// Feed exponent the strike is expressed in
+    pub expo: i32,
+
+    /// Feed price the market resolved on
+    pub resolved_price: Option<i64>,
+
+    /// Publish time of the price the market resolved on
+    pub resolved_publish_time: Option<i64>,
+
+    /// Bump seed for PDA
+    pub bump: u8,
+
+    /// Account layout version, see `Versioned`
+    pub version: u8,
+
+    /// Reserved space for future fields
+    pub reserved: [u8; 32],
+}
+
+impl PriceMarket {
+    pub const LEN: usize = PRICE_MARKET_SPACE;
+
+    /// Team that wins when the market resolves on `price`
+    pub fn winning_team(strike_price: i64, price: i64) -> u8 {
+        if price > strike_price {
+            0
+        } else {
+            1
+        }
+    }
+
+    /// Initialize a new price market
+    #[allow(clippy::too_many_arguments)]
+    pub fn initialize(
+        &mut self,
+        match_id: Pubkey,
+        price_feed: Pubkey,
+        feed_program: Pubkey,
+        asset: String,
+        strike_price: i64,
+        expo: i32,
+        bump: u8,
+    ) {
+        self.match_id = match_id;
+        self.price_feed = price_feed;
+        self.feed_program = feed_program;
+        self.asset = asset;
+        self.strike_price = strike_price;
+        self.expo = expo;
+        self.resolved_price = None;
+        self.resolved_publish_time = None;
+        self.bump = bump;
+        self.version = Self::VERSION;
+        self.reserved = [0; 32];
+    }
+
+    /// Record the price the market resolved on, returning the winning team
+    pub fn resolve(&mut self, price: i64, publish_time: i64) -> u8 {
+        self.resolved_price = Some(price);
+        self.resolved_publish_time = Some(publish_time);
+        Self::winning_team(self.strike_price, price)
+    }
+
+    /// Check if a price published at `publish_time` may resolve a market ending at `end_time`
+    pub fn is_resolution_price(end_time: i64, publish_time: i64) -> bool {
+        publish_time >= end_time
+            && publish_time <= end_time.saturating_add(PRICE_RESOLUTION_TOLERANCE)
+    }
+}
+
+impl Versioned for PriceMarket {
+    const VERSION: u8 = 1;
+    const SPACE: usize = PRICE_MARKET_SPACE;
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+
+    fn set_version(&mut self, version: u8) {
+        self.version = version;
+    }
+}
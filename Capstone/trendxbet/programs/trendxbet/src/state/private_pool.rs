@@ -0,0 +1,113 @@
+use crate::constants::*;
+use crate::state::Versioned;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+
+/// Invite list of a private match: only its organiser and members may bet on it
+#[account]
+pub struct PrivatePool {
+    /// Match the pool restricts
+    pub match_id: Pubkey,
+
+    /// Key that manages the invites and may close or cancel the pool
+    pub organiser: Pubkey,
+
+    /// Hash of the invite code letting anyone who knows it join, see `invite_code_hash`
+    pub invite_code_hash: Option<[u8; 32]>,
+
+    /// Bettors invited to the pool
+    pub members: Vec<Pubkey>,
+
+    /// Timestamp when the pool was created
+    pub created_at: i64,
+
+    /// Bump seed for PDA
+    pub bump: u8,
+
+    /// Account layout version, see `Versioned`
+    pub version: u8,
+
+    /// Reserved space for future fields
+    pub reserved: [u8; 16],
+}
+
+impl PrivatePool {
+    pub const LEN: usize = PRIVATE_POOL_SPACE;
+
+    /// Hash an invite code is stored as, bound to the match so codes cannot be replayed
+    pub fn invite_code_hash(match_id: &Pubkey, invite_code: &str) -> [u8; 32] {
+        hashv(&[match_id.as_ref(), invite_code.as_bytes()]).to_bytes()
+    }
+
+    /// Initialize the pool of `match_id` for `organiser`
+    pub fn initialize(
+        &mut self,
+        match_id: Pubkey,
+        organiser: Pubkey,
+        invite_code_hash: Option<[u8; 32]>,
+        members: Vec<Pubkey>,
+        bump: u8,
+        current_time: i64,
+    ) -> Result<()> {
+        self.match_id = match_id;
+        self.organiser = organiser;
+        self.created_at = current_time;
+        self.bump = bump;
+        self.version = Self::VERSION;
+        self.reserved = [0; 16];
+        self.update_invites(invite_code_hash, members)
+    }
+
+    /// Replace the invite code and the member list
+    pub fn update_invites(
+        &mut self,
+        invite_code_hash: Option<[u8; 32]>,
+        members: Vec<Pubkey>,
+    ) -> Result<()> {
+        require_gte!(
+            MAX_POOL_MEMBERS,
+            members.len(),
+            crate::error::TrendXBetError::PrivatePoolFull
+        );
+
+        self.invite_code_hash = invite_code_hash;
+        self.members = members;
+        Ok(())
+    }
+
+    /// Check if `bettor` may bet in the pool
+    pub fn is_member(&self, bettor: &Pubkey) -> bool {
+        *bettor == self.organiser || self.members.contains(bettor)
+    }
+
+    /// Add `bettor` as a member if `invite_code` is the pool's code
+    pub fn join(&mut self, bettor: Pubkey, invite_code: &str) -> Result<()> {
+        require!(
+            self.invite_code_hash == Some(Self::invite_code_hash(&self.match_id, invite_code)),
+            crate::error::TrendXBetError::InvalidInviteCode
+        );
+        if self.is_member(&bettor) {
+            return Ok(());
+        }
+        require!(
+            self.members.len() < MAX_POOL_MEMBERS,
+            crate::error::TrendXBetError::PrivatePoolFull
+        );
+
+        self.members.push(bettor);
+        Ok(())
+    }
+}
+
+impl Versioned for PrivatePool {
+    const VERSION: u8 = 1;
+    const SPACE: usize = PRIVATE_POOL_SPACE;
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+
+    fn set_version(&mut self, version: u8) {
+        self.version = version;
+    }
+}
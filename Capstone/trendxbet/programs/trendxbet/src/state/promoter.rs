@@ -0,0 +1,83 @@
+use crate::constants::*;
+use crate::state::Versioned;
+use anchor_lang::prelude::*;
+
+/// Key the admin has authorised to issue promotional bonuses
+#[account]
+pub struct Promoter {
+    /// Wallet that issues bonuses and funds them
+    pub authority: Pubkey,
+
+    /// Total bonus lamports issued
+    pub total_issued: u64,
+
+    /// Number of bonuses issued
+    pub bonuses_issued: u64,
+
+    /// Timestamp when the admin added the promoter
+    pub added_at: i64,
+
+    /// Bump seed for PDA
+    pub bump: u8,
+
+    /// Account layout version, see `Versioned`
+    pub version: u8,
+
+    /// Reserved space for future fields
+    pub reserved: [u8; 16],
+}
+
+impl Promoter {
+    pub const LEN: usize = PROMOTER_SPACE;
+
+    /// Initialize a promoter
+    pub fn initialize(&mut self, authority: Pubkey, bump: u8, current_time: i64) {
+        self.authority = authority;
+        self.total_issued = 0;
+        self.bonuses_issued = 0;
+        self.added_at = current_time;
+        self.bump = bump;
+        self.version = Self::VERSION;
+        self.reserved = [0; 16];
+    }
+
+    /// Check a bonus's wagering multiplier and return the stake it requires
+    pub fn wagering_requirement(amount: u64, wagering_multiplier: u8) -> Result<u64> {
+        require_gte!(
+            MAX_WAGERING_MULTIPLIER,
+            wagering_multiplier,
+            crate::error::TrendXBetError::InvalidWageringRequirement
+        );
+
+        amount
+            .checked_mul(wagering_multiplier as u64)
+            .ok_or(error!(crate::error::TrendXBetError::MathematicalOverflow))
+    }
+
+    /// Record a bonus issued
+    pub fn record_issue(&mut self, amount: u64) -> Result<()> {
+        self.total_issued = self
+            .total_issued
+            .checked_add(amount)
+            .ok_or(error!(crate::error::TrendXBetError::MathematicalOverflow))?;
+
+        self.bonuses_issued = self
+            .bonuses_issued
+            .checked_add(1)
+            .ok_or(error!(crate::error::TrendXBetError::MathematicalOverflow))?;
+        Ok(())
+    }
+}
+
+impl Versioned for Promoter {
+    const VERSION: u8 = 1;
+    const SPACE: usize = PROMOTER_SPACE;
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+
+    fn set_version(&mut self, version: u8) {
+        self.version = version;
+    }
+}
@@ -0,0 +1,146 @@
+use crate::constants::*;
+use crate::state::Versioned;
+use anchor_lang::prelude::*;
+
+/// Key a user lets sign bets on their profile, within an expiry, spend limits and permissions
+#[account]
+pub struct SessionKey {
+    /// Wallet authority of the profile the session acts for
+    pub authority: Pubkey,
+
+    /// Key allowed to sign for the profile
+    pub session_key: Pubkey,
+
+    /// Timestamp after which the session is no longer accepted
+    pub expires_at: i64,
+
+    /// Largest single bet the session may place
+    pub max_bet_amount: u64,
+
+    /// Total the session may stake over its lifetime
+    pub spend_limit: u64,
+
+    /// Total staked through the session so far
+    pub spent: u64,
+
+    /// Bitmask of `SESSION_*` instructions the session may sign
+    pub permissions: u8,
+
+    /// Timestamp when the session was created
+    pub created_at: i64,
+
+    /// Bump seed for PDA
+    pub bump: u8,
+
+    /// Account layout version, see `Versioned`
+    pub version: u8,
+
+    /// Reserved space for future fields
+    pub reserved: [u8; 16],
+}
+
+impl SessionKey {
+    pub const LEN: usize = SESSION_KEY_SPACE;
+
+    /// Initialize a session, checking its expiry, limits and permissions
+    #[allow(clippy::too_many_arguments)]
+    pub fn initialize(
+        &mut self,
+        authority: Pubkey,
+        session_key: Pubkey,
+        expires_at: i64,
+        max_bet_amount: u64,
+        spend_limit: u64,
+        permissions: u8,
+        bump: u8,
+        current_time: i64,
+    ) -> Result<()> {
+        require!(
+            expires_at > current_time
+                && expires_at <= current_time.saturating_add(MAX_SESSION_DURATION),
+            crate::error::TrendXBetError::InvalidSessionConfig
+        );
+        require!(
+            max_bet_amount > 0 && max_bet_amount <= spend_limit,
+            crate::error::TrendXBetError::InvalidSessionConfig
+        );
+        require!(
+            permissions != 0 && permissions & !SESSION_PERMISSIONS == 0,
+            crate::error::TrendXBetError::InvalidSessionConfig
+        );
+        require!(
+            session_key != authority,
+            crate::error::TrendXBetError::InvalidSessionConfig
+        );
+
+        self.authority = authority;
+        self.session_key = session_key;
+        self.expires_at = expires_at;
+        self.max_bet_amount = max_bet_amount;
+        self.spend_limit = spend_limit;
+        self.spent = 0;
+        self.permissions = permissions;
+        self.created_at = current_time;
+        self.bump = bump;
+        self.version = Self::VERSION;
+        self.reserved = [0; 16];
+        Ok(())
+    }
+
+    /// Check the session is live and allowed `permission`
+    pub fn authorize(&self, permission: u8, current_time: i64) -> Result<()> {
+        require_gt!(
+            self.expires_at,
+            current_time,
+            crate::error::TrendXBetError::SessionExpired
+        );
+        require!(
+            self.permissions & permission == permission,
+            crate::error::TrendXBetError::SessionNotPermitted
+        );
+        Ok(())
+    }
+
+    /// Authorize a bet of `stake` in total through the session, counting the `amount`
+    /// of new stake against its spend limit
+    pub fn spend(
+        &mut self,
+        permission: u8,
+        stake: u64,
+        amount: u64,
+        current_time: i64,
+    ) -> Result<()> {
+        self.authorize(permission, current_time)?;
+        require_gte!(
+            self.max_bet_amount,
+            stake,
+            crate::error::TrendXBetError::SessionLimitExceeded
+        );
+
+        let spent = self
+            .spent
+            .checked_add(amount)
+            .ok_or(error!(crate::error::TrendXBetError::MathematicalOverflow))?;
+        require_gte!(
+            self.spend_limit,
+            spent,
+            crate::error::TrendXBetError::SessionLimitExceeded
+        );
+
+        self.spent = spent;
+        Ok(())
+    }
+}
+
+impl Versioned for SessionKey {
+    const VERSION: u8 = 1;
+    const SPACE: usize = SESSION_KEY_SPACE;
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+
+    fn set_version(&mut self, version: u8) {
+        self.version = version;
+    }
+}
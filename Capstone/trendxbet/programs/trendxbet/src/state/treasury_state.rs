@@ -1,4 +1,6 @@
+use crate::analytics::Analytics;
 use crate::constants::*;
+use crate::state::Versioned;
 use anchor_lang::prelude::*;
 
 /// Treasury state account for managing platform funds
@@ -25,8 +27,15 @@ pub struct TreasuryState {
     /// Bump seed for PDA
     pub bump: u8,
 
+    /// Account layout version, see `Versioned`
+    pub version: u8,
+
+    /// Part of `platform_fees` charged on bets that have not settled yet, which
+    /// may still be refunded and so cannot be withdrawn
+    pub unearned_fees: u64,
+
     /// Reserved space for future fields
-    pub reserved: [u8; 64],
+    pub reserved: [u8; 55],
 }
 
 impl TreasuryState {
@@ -41,7 +50,9 @@ impl TreasuryState {
         self.pending_payouts = 0;
         self.last_fee_collection = current_time;
         self.bump = bump;
-        self.reserved = [0; 64];
+        self.version = Self::VERSION;
+        self.unearned_fees = 0;
+        self.reserved = [0; 55];
     }
 
     /// Record a deposit to treasury
@@ -62,8 +73,58 @@ impl TreasuryState {
         Ok(())
     }
 
-    /// Add platform fees
+    /// Add platform fees charged on a new bet; they stay unearned until it settles
     pub fn add_platform_fees(&mut self, amount: u64) -> Result<()> {
+        self.platform_fees = self
+            .platform_fees
+            .checked_add(amount)
+            .ok_or(error!(crate::error::TrendXBetError::MathematicalOverflow))?;
+
+        self.unearned_fees = self
+            .unearned_fees
+            .checked_add(amount)
+            .ok_or(error!(crate::error::TrendXBetError::MathematicalOverflow))?;
+        Ok(())
+    }
+
+    /// Mark the fee of a settled bet as earned, making it withdrawable.
+    /// Saturates for bets placed before unearned fees were tracked.
+    pub fn earn_platform_fees(&mut self, amount: u64) {
+        self.unearned_fees = self.unearned_fees.saturating_sub(amount);
+    }
+
+    /// Remove platform fees that were charged on a cancelled or refunded bet
+    pub fn remove_platform_fees(&mut self, amount: u64) -> Result<()> {
+        self.platform_fees = self
+            .platform_fees
+            .checked_sub(amount)
+            .ok_or(error!(crate::error::TrendXBetError::MathematicalOverflow))?;
+
+        self.unearned_fees = self.unearned_fees.saturating_sub(amount);
+        Ok(())
+    }
+
+    /// Take in slashed oracle stake or a forfeited dispute bond as earned platform fees
+    pub fn add_forfeit(&mut self, amount: u64) -> Result<()> {
+        self.platform_fees = self
+            .platform_fees
+            .checked_add(amount)
+            .ok_or(error!(crate::error::TrendXBetError::MathematicalOverflow))?;
+
+        self.record_deposit(amount)
+    }
+
+    /// Keep the bonus-funded part of a winning stake as earned platform fees
+    pub fn retain_bonus_stake(&mut self, amount: u64) -> Result<()> {
+        self.platform_fees = self
+            .platform_fees
+            .checked_add(amount)
+            .ok_or(error!(crate::error::TrendXBetError::MathematicalOverflow))?;
+        Ok(())
+    }
+
+    /// Keep the cancellation fee on a withdrawn stake as earned platform fees
+    pub fn collect_cancellation_fee(&mut self, amount: u64) -> Result<()> {
         self.platform_fees = self
             .platform_fees
             .checked_add(amount)
@@ -73,11 +134,11 @@ impl TreasuryState {
 
     /// Withdraw platform fees
     pub fn withdraw_platform_fees(&mut self, amount: u64) -> Result<()> {
-        if self.platform_fees < amount {
-            return Err(error!(
-                crate::error::TrendXBetError::TreasuryInsufficientFunds
-            ));
-        }
+        require_gte!(
+            self.platform_fees,
+            amount,
+            crate::error::TrendXBetError::TreasuryInsufficientFunds
+        );
 
         self.platform_fees = self
             .platform_fees
@@ -132,20 +193,23 @@ impl TreasuryState {
 
     /// Get total platform fees available for withdrawal
     pub fn available_platform_fees(&self) -> u64 {
-        self.platform_fees
+        self.platform_fees.saturating_sub(self.unearned_fees)
     }
 
-    /// Calculate treasury utilization ratio (pending payouts / total deposits)
-    pub fn utilization_ratio(&self) -> f64 {
-        if self.total_deposits == 0 {
-            return 0.0;
-        }
-        (self.pending_payouts as f64) / (self.total_deposits as f64)
+    /// Calculate treasury utilization ratio (pending payouts / total deposits, in basis points)
+    pub fn utilization_bps(&self) -> u64 {
+        Analytics::utilization_bps(self.pending_payouts, self.total_deposits)
+    }
+
+    /// Calculate how many times the net balance covers pending payouts (in basis points),
+    /// or `None` when nothing is pending
+    pub fn coverage_ratio_bps(&self) -> Option<u64> {
+        Analytics::coverage_ratio_bps(self.net_balance(), self.pending_payouts)
     }
 
     /// Check if treasury is healthy (low utilization, positive balance)
     pub fn is_healthy(&self) -> bool {
-        self.net_balance() > 0 && self.utilization_ratio() < 0.8 // Less than 80% utilization
+        self.net_balance() > 0 && self.utilization_bps() < MAX_HEALTHY_UTILIZATION
     }
 
     /// Calculate total fees collected over time
@@ -165,3 +229,24 @@ impl TreasuryState {
         }
     }
 }
+
+impl Versioned for TreasuryState {
+    const VERSION: u8 = 2;
+    const SPACE: usize = TREASURY_STATE_SPACE;
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+
+    fn set_version(&mut self, version: u8) {
+        self.version = version;
+    }
+
+    fn upgrade(&mut self, from_version: u8) -> Result<()> {
+        // Version 1 did not track unearned fees, so fees on bets still open count as earned
+        if from_version == 1 {
+            self.unearned_fees = 0;
+        }
+        Ok(())
+    }
+}
@@ -1,4 +1,6 @@
+use crate::analytics::Analytics;
 use crate::constants::*;
+use crate::state::Versioned;
 use anchor_lang::prelude::*;
 
 /// User profile state account
@@ -31,8 +33,26 @@ pub struct UserState {
     /// Bump seed for PDA
     pub bump: u8,
 
+    /// Sum of the odds accepted on every bet placed (in basis points)
+    pub total_odds_accepted: u64,
+
+    /// Account layout version, see `Versioned`
+    pub version: u8,
+
+    /// Whether the username is claimed by a `UsernameRecord` that renames must release
+    pub has_username_record: bool,
+
+    /// Promotional credit that can be staked but never withdrawn
+    pub bonus_balance: u64,
+
+    /// Stake still to be settled before winnings on bonus stakes unlock
+    pub wagering_remaining: u64,
+
+    /// Part of `balance` won on bonus stakes, withdrawable once wagering is complete
+    pub locked_bonus_winnings: u64,
+
     /// Reserved space for future fields
-    pub reserved: [u8; 32],
+    pub reserved: [u8; 22],
 }
 
 impl UserState {
@@ -49,7 +69,13 @@ impl UserState {
         self.total_winnings = 0;
         self.profile_created_at = current_time;
         self.bump = bump;
-        self.reserved = [0; 32];
+        self.total_odds_accepted = 0;
+        self.version = Self::VERSION;
+        self.has_username_record = false;
+        self.bonus_balance = 0;
+        self.wagering_remaining = 0;
+        self.locked_bonus_winnings = 0;
+        self.reserved = [0; 22];
     }
 
     /// Update username
@@ -68,9 +94,11 @@ impl UserState {
 
     /// Subtract funds from user balance
     pub fn subtract_balance(&mut self, amount: u64) -> Result<()> {
-        if self.balance < amount {
-            return Err(error!(crate::error::TrendXBetError::InsufficientBalance));
-        }
+        require_gte!(
+            self.balance,
+            amount,
+            crate::error::TrendXBetError::InsufficientBalance
+        );
 
         self.balance = self
             .balance
@@ -79,8 +107,90 @@ impl UserState {
         Ok(())
     }
 
+    /// Credit a promotional bonus and add its wagering requirement
+    pub fn add_bonus(&mut self, amount: u64, wagering_requirement: u64) -> Result<()> {
+        self.bonus_balance = self
+            .bonus_balance
+            .checked_add(amount)
+            .ok_or(error!(crate::error::TrendXBetError::MathematicalOverflow))?;
+
+        self.wagering_remaining = self
+            .wagering_remaining
+            .checked_add(wagering_requirement)
+            .ok_or(error!(crate::error::TrendXBetError::MathematicalOverflow))?;
+        Ok(())
+    }
+
+    /// Total a bet may stake, counting the bonus balance if it is to be used
+    pub fn stakeable_balance(&self, use_bonus: bool) -> u64 {
+        if use_bonus {
+            self.balance.saturating_add(self.bonus_balance)
+        } else {
+            self.balance
+        }
+    }
+
+    /// Take a stake of `amount`, spending the bonus balance first if requested.
+    /// Returns the parts paid from the bonus balance and from locked bonus winnings.
+    pub fn take_stake(&mut self, amount: u64, use_bonus: bool) -> Result<(u64, u64)> {
+        let bonus_stake = if use_bonus {
+            amount.min(self.bonus_balance)
+        } else {
+            0
+        };
+
+        self.subtract_balance(amount - bonus_stake)?;
+        self.bonus_balance -= bonus_stake;
+
+        // Locked winnings are staked last, once the unlocked balance is used up
+        let locked = self.locked_bonus_winnings;
+        self.locked_bonus_winnings = locked.min(self.balance);
+        Ok((bonus_stake, locked - self.locked_bonus_winnings))
+    }
+
+    /// Return a cancelled or refunded stake, `bonus_stake` of it to the bonus balance
+    /// and up to `locked_stake` of the rest locked again while wagering is outstanding
+    pub fn return_stake(&mut self, amount: u64, bonus_stake: u64, locked_stake: u64) -> Result<()> {
+        let bonus_stake = bonus_stake.min(amount);
+        let returned = amount - bonus_stake;
+        self.add_balance(returned)?;
+
+        self.bonus_balance = self
+            .bonus_balance
+            .checked_add(bonus_stake)
+            .ok_or(error!(crate::error::TrendXBetError::MathematicalOverflow))?;
+        self.lock_bonus_winnings(locked_stake.min(returned))
+    }
+
+    /// Count a settled stake toward the wagering requirement, unlocking bonus
+    /// winnings once it is met
+    pub fn record_wager(&mut self, amount: u64) {
+        self.wagering_remaining = self.wagering_remaining.saturating_sub(amount);
+        if self.wagering_remaining == 0 {
+            self.locked_bonus_winnings = 0;
+        }
+    }
+
+    /// Lock winnings paid on a bonus stake while wagering is outstanding
+    pub fn lock_bonus_winnings(&mut self, amount: u64) -> Result<()> {
+        if self.wagering_remaining == 0 {
+            return Ok(());
+        }
+
+        self.locked_bonus_winnings = self
+            .locked_bonus_winnings
+            .checked_add(amount)
+            .ok_or(error!(crate::error::TrendXBetError::MathematicalOverflow))?;
+        Ok(())
+    }
+
+    /// Balance that can be withdrawn, excluding locked bonus winnings
+    pub fn withdrawable_balance(&self) -> u64 {
+        self.balance.saturating_sub(self.locked_bonus_winnings)
+    }
+
     /// Record a new bet placed
-    pub fn record_bet_placed(&mut self, amount: u64) -> Result<()> {
+    pub fn record_bet_placed(&mut self, amount: u64, odds_accepted: u64) -> Result<()> {
         self.total_bets_placed = self
             .total_bets_placed
             .checked_add(1)
@@ -91,6 +201,35 @@ impl UserState {
             .checked_add(amount)
             .ok_or(error!(crate::error::TrendXBetError::MathematicalOverflow))?;
 
+        self.total_odds_accepted = self
+            .total_odds_accepted
+            .checked_add(odds_accepted)
+            .ok_or(error!(crate::error::TrendXBetError::MathematicalOverflow))?;
+
+        Ok(())
+    }
+
+    /// Record a modified bet, replacing its stake and odds in the volume and
+    /// odds totals
+    pub fn record_bet_modified(
+        &mut self,
+        old_amount: u64,
+        new_amount: u64,
+        old_odds: u64,
+        new_odds: u64,
+    ) -> Result<()> {
+        self.total_volume = self
+            .total_volume
+            .saturating_sub(old_amount)
+            .checked_add(new_amount)
+            .ok_or(error!(crate::error::TrendXBetError::MathematicalOverflow))?;
+
+        self.total_odds_accepted = self
+            .total_odds_accepted
+            .saturating_sub(old_odds)
+            .checked_add(new_odds)
+            .ok_or(error!(crate::error::TrendXBetError::MathematicalOverflow))?;
+
         Ok(())
     }
 
@@ -109,12 +248,19 @@ impl UserState {
         Ok(())
     }
 
-    /// Calculate user's win rate
-    pub fn win_rate(&self) -> f64 {
-        if self.total_bets_placed == 0 {
-            return 0.0;
-        }
-        (self.total_bets_won as f64) / (self.total_bets_placed as f64)
+    /// Calculate user's win rate (in basis points)
+    pub fn win_rate_bps(&self) -> u64 {
+        Analytics::win_rate_bps(self.total_bets_won, self.total_bets_placed)
+    }
+
+    /// Calculate user's return on volume (in basis points, negative for a net loss)
+    pub fn roi_bps(&self) -> i64 {
+        Analytics::roi_bps(self.total_winnings, self.total_volume)
+    }
+
+    /// Calculate the average odds accepted by the user (in basis points)
+    pub fn average_odds_bps(&self) -> u64 {
+        Analytics::average_odds_bps(self.total_odds_accepted, self.total_bets_placed)
     }
 
     /// Calculate user's profit/loss
@@ -127,3 +273,16 @@ impl UserState {
         self.balance >= amount
     }
 }
+
+impl Versioned for UserState {
+    const VERSION: u8 = 2;
+    const SPACE: usize = USER_STATE_SPACE;
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+
+    fn set_version(&mut self, version: u8) {
+        self.version = version;
+    }
+}
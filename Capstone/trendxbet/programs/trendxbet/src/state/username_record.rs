@@ -0,0 +1,79 @@
+use crate::constants::*;
+use crate::state::Versioned;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+
+/// Claim on a username, unique up to case, pointing back at the profile that holds it
+#[account]
+pub struct UsernameRecord {
+    /// Wallet authority of the profile holding the username
+    pub authority: Pubkey,
+
+    /// Profile holding the username
+    pub user_state: Pubkey,
+
+    /// Username as the holder spelled it
+    pub username: String,
+
+    /// Timestamp when the username was claimed
+    pub claimed_at: i64,
+
+    /// Bump seed for PDA
+    pub bump: u8,
+
+    /// Account layout version, see `Versioned`
+    pub version: u8,
+
+    /// Reserved space for future fields
+    pub reserved: [u8; 16],
+}
+
+impl UsernameRecord {
+    pub const LEN: usize = USERNAME_RECORD_SPACE;
+
+    /// Case-folded form two usernames are compared in
+    pub fn normalize(username: &str) -> String {
+        username.to_lowercase()
+    }
+
+    /// PDA seed: the hash of the normalized username, which may be longer than a seed allows
+    pub fn seed(username: &str) -> [u8; 32] {
+        hash(Self::normalize(username).as_bytes()).to_bytes()
+    }
+
+    /// Check if `a` and `b` claim the same record
+    pub fn is_same_username(a: &str, b: &str) -> bool {
+        Self::normalize(a) == Self::normalize(b)
+    }
+
+    /// Initialize a claim on `username` for `user_state`
+    pub fn initialize(
+        &mut self,
+        authority: Pubkey,
+        user_state: Pubkey,
+        username: String,
+        bump: u8,
+        current_time: i64,
+    ) {
+        self.authority = authority;
+        self.user_state = user_state;
+        self.username = username;
+        self.claimed_at = current_time;
+        self.bump = bump;
+        self.version = Self::VERSION;
+        self.reserved = [0; 16];
+    }
+}
+
+impl Versioned for UsernameRecord {
+    const VERSION: u8 = 1;
+    const SPACE: usize = USERNAME_RECORD_SPACE;
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+
+    fn set_version(&mut self, version: u8) {
+        self.version = version;
+    }
+}
@@ -0,0 +1,95 @@
+use crate::error::TrendXBetError;
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+
+/// Accounts written before layouts were versioned read back as version 0
+pub const LEGACY_ACCOUNT_VERSION: u8 = 0;
+
+/// A program account whose layout is versioned and upgradable in place.
+///
+/// The version byte sits in what used to be reserved space, so accounts written
+/// before versioning existed deserialize as `LEGACY_ACCOUNT_VERSION`. Layout
+/// changes bump `VERSION`, grow `SPACE` if needed and add a step to `upgrade`.
+pub trait Versioned:
+    AccountSerialize + AccountDeserialize + AnchorDeserialize + Discriminator
+{
+    /// Layout version written by this build of the program
+    const VERSION: u8;
+
+    /// Account size for the current layout, including the discriminator
+    const SPACE: usize;
+
+    fn version(&self) -> u8;
+
+    fn set_version(&mut self, version: u8);
+
+    /// Upgrade fields from `from_version` to `from_version + 1`
+    fn upgrade(&mut self, _from_version: u8) -> Result<()> {
+        Ok(())
+    }
+
+    /// Whether the account uses the layout this build reads and writes
+    fn is_current_version(&self) -> bool {
+        self.version() == Self::VERSION
+    }
+}
+
+/// Layout change applied by `migrate`
+pub struct Migration<T> {
+    pub state: T,
+    pub from_version: u8,
+    pub to_version: u8,
+}
+
+pub struct VersionUtils;
+
+impl VersionUtils {
+    /// Deserialize an account of any known version, zero-filling the bytes an
+    /// older, shorter layout does not have
+    pub fn load<T: Versioned>(account: &AccountInfo) -> Result<T> {
+        let data = account.try_borrow_data()?;
+        require!(
+            data.len() >= 8 && data[..8] == *T::DISCRIMINATOR,
+            ErrorCode::AccountDiscriminatorMismatch
+        );
+
+        let mut body = data[8..].to_vec();
+        body.resize(body.len().max(T::SPACE - 8), 0);
+        let state = T::deserialize(&mut body.as_slice())
+            .map_err(|_| error!(ErrorCode::AccountDidNotDeserialize))?;
+
+        require!(
+            state.version() <= T::VERSION,
+            TrendXBetError::UnsupportedAccountVersion
+        );
+        Ok(state)
+    }
+
+    /// Read an outdated account and upgrade it to the current layout in memory
+    pub fn migrate<T: Versioned>(account: &AccountInfo) -> Result<Migration<T>> {
+        let mut state = Self::load::<T>(account)?;
+        let from_version = state.version();
+        require!(
+            from_version < T::VERSION,
+            TrendXBetError::AccountAlreadyMigrated
+        );
+
+        for version in from_version..T::VERSION {
+            state.upgrade(version)?;
+        }
+        state.set_version(T::VERSION);
+
+        Ok(Migration {
+            state,
+            from_version,
+            to_version: T::VERSION,
+        })
+    }
+
+    /// Serialize `state`, discriminator included, over the start of `account`
+    pub fn store<T: Versioned>(account: &AccountInfo, state: &T) -> Result<()> {
+        let mut data = account.try_borrow_mut_data()?;
+        let mut writer: &mut [u8] = &mut data;
+        state.try_serialize(&mut writer)
+    }
+}
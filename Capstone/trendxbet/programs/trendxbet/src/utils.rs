@@ -1,5 +1,9 @@
 use crate::constants::*;
 use crate::error::TrendXBetError;
+use crate::state::{
+    AuditAction, AuditEntry, AuditLogPage, GlobalState, MarketKind, MatchIndexKey, MatchIndexPage,
+    MatchRegistry, MatchScore, MatchState, MatchStatus, ReportEvidence, UsernameRecord,
+};
 use anchor_lang::prelude::*;
 
 pub struct MathUtils;
@@ -95,6 +99,56 @@ impl MathUtils {
 
         Ok(payout)
     }
+
+    /// Calculate the bonus weight of a bet placed at `current_time`: `bonus_bps` of the
+    /// stake when betting opens, decaying linearly to nothing when it closes
+    pub fn calculate_early_bet_bonus(
+        bet_amount: u64,
+        bonus_bps: u16,
+        opens_at: i64,
+        closes_at: i64,
+        current_time: i64,
+    ) -> Result<u64> {
+        let window = closes_at.saturating_sub(opens_at);
+        if bonus_bps == 0 || window <= 0 {
+            return Ok(0);
+        }
+
+        let remaining = closes_at.saturating_sub(current_time).clamp(0, window);
+
+        // Bonus = bet_amount * bonus_bps / BASIS_POINTS * remaining / window
+        let bonus = (bet_amount as u128)
+            .checked_mul(bonus_bps as u128)
+            .and_then(|value| value.checked_mul(remaining as u128))
+            .ok_or(TrendXBetError::MathematicalOverflow)?
+            .checked_div(BASIS_POINTS as u128 * window as u128)
+            .ok_or(TrendXBetError::DivisionByZero)?;
+
+        u64::try_from(bonus).map_err(|_| TrendXBetError::MathematicalOverflow.into())
+    }
+
+    /// Calculate each agreeing oracle's share of a match's fees, split evenly
+    /// across every report revealed on it
+    pub fn calculate_oracle_reward(match_fees: u64, oracle_reports: u8) -> Result<u64> {
+        match_fees
+            .checked_mul(ORACLE_FEE_SHARE_BPS)
+            .ok_or(TrendXBetError::MathematicalOverflow)?
+            .checked_div(BASIS_POINTS)
+            .ok_or(TrendXBetError::DivisionByZero)?
+            .checked_div(oracle_reports.max(1) as u64)
+            .ok_or(TrendXBetError::DivisionByZero.into())
+    }
+
+    /// Calculate the share of `value` that `part` of `whole` accounts for, rounding down
+    pub fn calculate_share(value: u64, part: u64, whole: u64) -> Result<u64> {
+        let share = (value as u128)
+            .checked_mul(part as u128)
+            .ok_or(TrendXBetError::MathematicalOverflow)?
+            .checked_div(whole as u128)
+            .ok_or(TrendXBetError::DivisionByZero)?;
+
+        u64::try_from(share).map_err(|_| TrendXBetError::MathematicalOverflow.into())
+    }
 }
 
 pub struct ValidationUtils;
@@ -102,17 +156,15 @@ pub struct ValidationUtils;
 impl ValidationUtils {
     /// Validate username format and length
     pub fn validate_username(username: &str) -> Result<()> {
-        if username.len() > MAX_USERNAME_LENGTH {
-            return Err(TrendXBetError::UsernameTooLong.into());
-        }
+        require_gte!(MAX_USERNAME_LENGTH, username.len(), TrendXBetError::UsernameTooLong);
 
         if username.trim().is_empty() {
-            return Err(TrendXBetError::InvalidAccount.into());
+            return Err(TrendXBetError::InvalidUsername.into());
         }
 
         // Check for valid characters (alphanumeric and underscore only)
         if !username.chars().all(|c| c.is_alphanumeric() || c == '_') {
-            return Err(TrendXBetError::InvalidAccount.into());
+            return Err(TrendXBetError::InvalidUsername.into());
         }
 
         Ok(())
@@ -120,12 +172,10 @@ impl ValidationUtils {
 
     /// Validate team name format and length
     pub fn validate_team_name(team_name: &str) -> Result<()> {
-        if team_name.len() > MAX_TEAM_NAME_LENGTH {
-            return Err(TrendXBetError::TeamNameTooLong.into());
-        }
+        require_gte!(MAX_TEAM_NAME_LENGTH, team_name.len(), TrendXBetError::TeamNameTooLong);
 
         if team_name.trim().is_empty() {
-            return Err(TrendXBetError::InvalidAccount.into());
+            return Err(TrendXBetError::InvalidTeamName.into());
         }
 
         Ok(())
@@ -133,59 +183,81 @@ impl ValidationUtils {
 
     /// Validate description format and length
     pub fn validate_description(description: &str) -> Result<()> {
-        if description.len() > MAX_DESCRIPTION_LENGTH {
-            return Err(TrendXBetError::DescriptionTooLong.into());
+        require_gte!(MAX_DESCRIPTION_LENGTH, description.len(), TrendXBetError::DescriptionTooLong);
+
+        Ok(())
+    }
+
+    /// Validate price market asset label
+    pub fn validate_asset(asset: &str) -> Result<()> {
+        if asset.trim().is_empty() || asset.len() > MAX_ASSET_LENGTH {
+            return Err(TrendXBetError::AssetNameTooLong.into());
         }
 
         Ok(())
     }
 
-    /// Validate bet amount
-    pub fn validate_bet_amount(amount: u64, min_amount: u64, max_amount: u64) -> Result<()> {
-        if amount < min_amount {
-            return Err(TrendXBetError::BetAmountTooLow.into());
+    /// Validate structured score breakdown
+    pub fn validate_match_score(score: &MatchScore) -> Result<()> {
+        require_gte!(MAX_SCORE_PERIODS, score.periods.len(), TrendXBetError::InvalidScore);
+
+        Ok(())
+    }
+
+    /// Validate evidence attached to an oracle report
+    pub fn validate_evidence(evidence: &ReportEvidence) -> Result<()> {
+        if evidence.uri.is_empty() || evidence.uri.len() > MAX_EVIDENCE_URI_LENGTH {
+            return Err(TrendXBetError::InvalidEvidence.into());
         }
 
-        if amount > max_amount {
-            return Err(TrendXBetError::BetAmountTooHigh.into());
+        if evidence.content_hash == [0; 32] {
+            return Err(TrendXBetError::InvalidEvidence.into());
         }
 
         Ok(())
     }
 
+    /// Validate the line of a totals or spread market
+    pub fn validate_line(market_kind: MarketKind, line: i32) -> Result<()> {
+        match market_kind {
+            MarketKind::Totals if line > 0 => Ok(()),
+            MarketKind::Spread => Ok(()),
+            MarketKind::Totals => Err(TrendXBetError::InvalidLine.into()),
+            _ => Err(TrendXBetError::InvalidMarketType.into()),
+        }
+    }
+
+    /// Validate bet amount
+    pub fn validate_bet_amount(amount: u64, min_amount: u64, max_amount: u64) -> Result<()> {
+        require_gte!(amount, min_amount, TrendXBetError::BetAmountTooLow);
+        require_gte!(max_amount, amount, TrendXBetError::BetAmountTooHigh);
+
+        Ok(())
+    }
+
     /// Validate house edge (0-50%)
     pub fn validate_house_edge(house_edge: u16) -> Result<()> {
-        if house_edge > 5000 {
-            // 50% in basis points
-            return Err(TrendXBetError::InvalidHouseEdge.into());
-        }
+        // 50% in basis points
+        require_gte!(5000, house_edge, TrendXBetError::InvalidHouseEdge);
 
         Ok(())
     }
 
     /// Validate time configuration for matches
     pub fn validate_match_times(start_time: i64, end_time: i64, current_time: i64) -> Result<()> {
-        if start_time <= current_time {
-            return Err(TrendXBetError::StartTimeInPast.into());
-        }
-
-        if end_time <= start_time {
-            return Err(TrendXBetError::EndTimeBeforeStart.into());
-        }
+        require_gt!(start_time, current_time, TrendXBetError::StartTimeInPast);
+        require_gt!(end_time, start_time, TrendXBetError::EndTimeBeforeStart);
 
         let duration = end_time - start_time;
-        if duration < MIN_BETTING_DURATION || duration > MAX_BETTING_DURATION {
-            return Err(TrendXBetError::InvalidTimeConfiguration.into());
-        }
+        require_gte!(duration, MIN_BETTING_DURATION, TrendXBetError::InvalidMatchDuration);
+        require_gte!(MAX_BETTING_DURATION, duration, TrendXBetError::InvalidMatchDuration);
 
         Ok(())
     }
 
     /// Validate team selection (0 = team1, 1 = team2)
     pub fn validate_team_selection(team: u8) -> Result<()> {
-        if team > 1 {
-            return Err(TrendXBetError::InvalidTeam.into());
-        }
+        require_gte!(1, team, TrendXBetError::InvalidTeam);
 
         Ok(())
     }
@@ -222,6 +294,17 @@ impl TimeUtils {
         update_time >= match_end_time && update_time <= (match_end_time + ORACLE_UPDATE_WINDOW)
     }
 
+    /// Check if oracles may commit to a result: from the end time until the commit window closes
+    pub fn is_oracle_commit_open(match_end_time: i64, current_time: i64) -> bool {
+        current_time >= match_end_time && current_time <= (match_end_time + ORACLE_COMMIT_WINDOW)
+    }
+
+    /// Check if oracles may reveal a result: after the commit window, until the update window closes
+    pub fn is_oracle_reveal_open(match_end_time: i64, current_time: i64) -> bool {
+        current_time > (match_end_time + ORACLE_COMMIT_WINDOW)
+            && current_time <= (match_end_time + ORACLE_UPDATE_WINDOW)
+    }
+
     /// Check if settlement window has passed
     pub fn is_settlement_period_reached(match_end_time: i64) -> bool {
         let current_time = Self::get_current_timestamp();
@@ -264,8 +347,152 @@ impl SecurityUtils {
         )
     }
 
+    /// Generate PDA for oracle profile account
+    pub fn generate_oracle_profile_pda(oracle_authority: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[ORACLE_PROFILE_SEED, oracle_authority.as_ref()], program_id)
+    }
+
+    /// Generate PDA for price market account
+    pub fn generate_price_market_pda(match_id: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[PRICE_MARKET_SEED, match_id.as_ref()], program_id)
+    }
+
+    /// Generate PDA for the arbiter set account
+    pub fn generate_arbiter_set_pda(program_id: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[ARBITER_SET_SEED], program_id)
+    }
+
+    /// Generate PDA for a match's dispute account
+    pub fn generate_dispute_pda(match_id: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[DISPUTE_SEED, match_id.as_ref()], program_id)
+    }
+
+    /// Generate PDA for the match registry account
+    pub fn generate_match_registry_pda(program_id: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[MATCH_REGISTRY_SEED], program_id)
+    }
+
+    /// Generate PDA for a page of a match index
+    pub fn generate_match_index_pda(key: &MatchIndexKey, page: u32, program_id: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[MATCH_INDEX_SEED, key.seed().as_ref(), page.to_le_bytes().as_ref()],
+            program_id,
+        )
+    }
+
+    /// Generate PDA for the record claiming `username`, shared by every casing of it
+    pub fn generate_username_pda(username: &str, program_id: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[USERNAME_SEED, UsernameRecord::seed(username).as_ref()], program_id)
+    }
+
+    /// Generate PDA for a session key `session_key` acting for `authority`
+    pub fn generate_session_pda(authority: &Pubkey, session_key: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[SESSION_SEED, authority.as_ref(), session_key.as_ref()], program_id)
+    }
+
+    /// Generate PDA for a promoter authorised to issue bonuses
+    pub fn generate_promoter_pda(promoter: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[PROMOTER_SEED, promoter.as_ref()], program_id)
+    }
+
+    /// Generate PDA for the invite list of a private match
+    pub fn generate_private_pool_pda(match_id: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[PRIVATE_POOL_SEED, match_id.as_ref()], program_id)
+    }
+
+    /// Generate PDA for the odds history of a match
+    pub fn generate_odds_history_pda(match_id: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[ODDS_HISTORY_SEED, match_id.as_ref()], program_id)
+    }
+
+    /// Generate PDA for a page of the audit log
+    pub fn generate_audit_log_pda(page: u32, program_id: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[AUDIT_LOG_SEED, page.to_le_bytes().as_ref()], program_id)
+    }
+
     /// Generate PDA for global state account
     pub fn generate_platform_pda(program_id: &Pubkey) -> (Pubkey, u8) {
         Pubkey::find_program_address(&[PLATFORM_SEED], program_id)
     }
 }
+
+pub struct IndexUtils;
+
+impl IndexUtils {
+    /// List a new match in the registry under its status, its start day and,
+    /// when a league page is given, that page's league
+    pub fn list_match(
+        registry: &mut MatchRegistry,
+        match_state: &mut MatchState,
+        status_page: Option<&mut MatchIndexPage>,
+        start_day_page: Option<&mut MatchIndexPage>,
+        league_page: Option<&mut MatchIndexPage>,
+    ) -> Result<()> {
+        let match_id = match_state.match_id;
+        let status_page = status_page.ok_or(TrendXBetError::MatchIndexRequired)?;
+        let start_day_page = start_day_page.ok_or(TrendXBetError::MatchIndexRequired)?;
+
+        status_page.insert(MatchIndexKey::status(&match_state.status), match_id)?;
+        start_day_page.insert(MatchIndexKey::start_day(match_state.start_time), match_id)?;
+
+        if let Some(league_page) = league_page {
+            let MatchIndexKey::League(league) = league_page.key else {
+                return Err(TrendXBetError::MatchIndexMismatch.into());
+            };
+            league_page.insert(league_page.key, match_id)?;
+            match_state.league = league;
+        }
+
+        match_state.is_indexed = true;
+        registry.record_match()
+    }
+
+    /// Move an indexed match from the `old_status` index to that of its current status
+    pub fn move_status(
+        match_state: &MatchState,
+        old_status: &MatchStatus,
+        old_page: Option<&mut MatchIndexPage>,
+        new_page: Option<&mut MatchIndexPage>,
+    ) -> Result<()> {
+        if !match_state.is_indexed || match_state.status == *old_status {
+            return Ok(());
+        }
+
+        let old_page = old_page.ok_or(TrendXBetError::MatchIndexRequired)?;
+        let new_page = new_page.ok_or(TrendXBetError::MatchIndexRequired)?;
+        old_page.remove(MatchIndexKey::status(old_status), &match_state.match_id)?;
+        new_page.insert(MatchIndexKey::status(&match_state.status), match_state.match_id)
+    }
+}
+
+pub struct AuditUtils;
+
+impl AuditUtils {
+    /// Record a privileged action on the current audit log page once the log is enabled
+    pub fn record(
+        global_state: &mut GlobalState,
+        audit_log: Option<&mut AuditLogPage>,
+        action: AuditAction,
+        actor: Pubkey,
+        target: Option<Pubkey>,
+        params_hash: [u8; 32],
+        current_time: i64,
+    ) -> Result<()> {
+        if !global_state.has_audit_log {
+            return Ok(());
+        }
+
+        let audit_log = audit_log.ok_or(TrendXBetError::AuditLogRequired)?;
+        require!(audit_log.page == global_state.audit_page(), TrendXBetError::AuditLogMismatch);
+
+        let sequence = global_state.record_audit_entry()?;
+        audit_log.record(AuditEntry {
+            sequence,
+            action,
+            actor,
+            target,
+            params_hash,
+            timestamp: current_time,
+        })
+    }
+}
@@ -0,0 +1,84 @@
+//! Fixed-point analytics: the pure helpers, and the derived metrics the program
+//! keeps on `UserState` and `TreasuryState`.
+
+mod common;
+
+use common::*;
+use trendxbet::{Analytics, BetState, TreasuryState, UserState, BASIS_POINTS};
+
+const HOUR: i64 = 3600;
+
+#[test]
+fn ratios_round_down_in_basis_points() {
+    assert_eq!(Analytics::ratio_bps(1, 3), 3_333);
+    assert_eq!(Analytics::ratio_bps(2, 3), 6_666);
+    assert_eq!(Analytics::ratio_bps(5, 0), 0);
+    assert_eq!(Analytics::ratio_bps(3, 2), 15_000);
+    assert_eq!(Analytics::ratio_bps(3, 5), 6_000);
+    assert_eq!(Analytics::ratio_bps(u64::MAX, 1), u64::MAX);
+}
+
+#[test]
+fn derived_metrics_handle_losses_and_empty_books() {
+    assert_eq!(Analytics::roi_bps(15, 10), 5_000);
+    assert_eq!(Analytics::roi_bps(4, 10), -6_000);
+    assert_eq!(Analytics::roi_bps(0, 0), 0);
+
+    assert_eq!(Analytics::average_odds_bps(35_000, 2), 17_500);
+    assert_eq!(Analytics::average_odds_bps(0, 0), 0);
+
+    assert_eq!(Analytics::coverage_ratio_bps(300, 200), Some(15_000));
+    assert_eq!(Analytics::coverage_ratio_bps(-50, 200), Some(0));
+    assert_eq!(Analytics::coverage_ratio_bps(300, 0), None);
+
+    assert_eq!(Analytics::confidence_bps(1, 3), 3_333);
+    assert_eq!(Analytics::confidence_bps(5, 3), BASIS_POINTS);
+    assert_eq!(Analytics::confidence_bps(0, 0), BASIS_POINTS);
+}
+
+#[test]
+fn user_and_treasury_metrics_track_the_books() {
+    let mut platform = Platform::new();
+    let alice = platform.user("alice");
+    let bob = platform.user("bob");
+    platform.deposit(&alice, 10 * SOL).unwrap();
+    platform.deposit(&bob, 10 * SOL).unwrap();
+
+    let first = platform.create_match(HOUR, 3 * HOUR);
+    let second = platform.create_match(HOUR, 3 * HOUR);
+    platform.harness.warp_by(HOUR);
+    platform.place_bet(&bob, &first, 3 * SOL, 0).unwrap();
+    platform.place_bet(&alice, &first, SOL, 1).unwrap();
+    platform.place_bet(&alice, &second, 2 * SOL, 1).unwrap();
+
+    let odds: Vec<u64> = [first, second]
+        .iter()
+        .map(|match_id| {
+            platform
+                .harness
+                .state::<BetState>(&bet_pda(&alice, match_id))
+                .odds_at_time
+        })
+        .collect();
+
+    let profile: UserState = platform.harness.state(&user_pda(&alice));
+    assert_eq!(profile.total_bets_placed, 2);
+    assert_eq!(profile.total_odds_accepted, odds.iter().sum::<u64>());
+    assert_eq!(profile.average_odds_bps(), (odds[0] + odds[1]) / 2);
+    assert_eq!(profile.win_rate_bps(), 0);
+    assert_eq!(profile.roi_bps(), -(BASIS_POINTS as i64));
+
+    let treasury: TreasuryState = platform.harness.state(&treasury_pda());
+    assert_eq!(
+        treasury.utilization_bps(),
+        Analytics::ratio_bps(treasury.pending_payouts, treasury.total_deposits)
+    );
+    assert_eq!(
+        treasury.coverage_ratio_bps(),
+        Analytics::coverage_ratio_bps(treasury.net_balance(), treasury.pending_payouts)
+    );
+    assert_eq!(
+        treasury.is_healthy(),
+        treasury.utilization_bps() < trendxbet::MAX_HEALTHY_UTILIZATION
+    );
+}
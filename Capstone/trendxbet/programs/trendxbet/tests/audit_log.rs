@@ -0,0 +1,330 @@
+//! Audit log: once enabled, every privileged action is appended to paged
+//! accounts with its actor, a hash of its parameters and its timestamp.
+
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::system_program;
+use common::*;
+use trendxbet::{
+    accounts, instruction as ix, AuditAction, AuditLogEnabled, AuditLogPage, AuditLogPageCreated,
+    ExposureLimits, GlobalState, MatchStatus, TreasuryState, TrendXBetError, UserState, Versioned,
+    AUDIT_LOG_PAGE_SIZE,
+};
+
+const HOUR: i64 = 3600;
+
+/// Parameters of `update_platform_config`, in the order they are hashed
+type ConfigParams = (
+    Option<u16>,
+    Option<u64>,
+    Option<u64>,
+    Option<u64>,
+    Option<Pubkey>,
+    Option<i64>,
+    Option<u64>,
+    Option<u32>,
+    Option<u16>,
+    Option<u64>,
+    Option<u16>,
+    Option<u16>,
+);
+
+fn set_version<T: Versioned>(platform: &mut Platform, key: &Pubkey, version: u8) {
+    let mut state: T = platform.harness.state(key);
+    state.set_version(version);
+    platform.harness.set_state(key, &state);
+}
+
+/// Pause the platform passing `audit_log` as the page to record on
+fn pause_with(platform: &mut Platform, audit_log: Option<Pubkey>) -> TxResult {
+    platform.harness.send(
+        accounts::PausePlatform {
+            global_state: platform_pda(),
+            admin: platform.admin,
+            audit_log,
+        },
+        ix::PausePlatform {},
+        &[platform.admin],
+    )
+}
+
+#[test]
+fn privileged_actions_are_recorded_with_actor_and_parameters() {
+    let mut platform = Platform::new();
+    platform.enable_audit_log().unwrap();
+    let event = &platform.harness.events::<AuditLogEnabled>()[0];
+    assert_eq!(event.admin, platform.admin);
+    let enabled_at = platform.harness.now();
+
+    platform.pause_platform().unwrap();
+    platform.unpause_platform().unwrap();
+    platform.set_cancellation_fee(100).unwrap();
+    let limits = ExposureLimits {
+        max_match_pool: 100 * SOL,
+        ..ExposureLimits::default()
+    };
+    platform.set_exposure_limits(limits).unwrap();
+    let match_id = platform.create_match(HOUR, 2 * HOUR);
+    let oracle = platform.register_oracle(&match_id);
+
+    // A withdrawn stake leaves a cancellation fee to collect
+    let alice = platform.user("alice");
+    platform.deposit(&alice, 5 * SOL).unwrap();
+    platform.harness.warp_by(HOUR);
+    platform.place_bet(&alice, &match_id, 2 * SOL, 0).unwrap();
+    platform.modify_bet(&alice, &match_id, SOL, 0).unwrap();
+    let treasury: TreasuryState = platform.harness.state(&treasury_pda());
+    let fee = treasury.available_platform_fees();
+    platform.withdraw_platform_fees(fee).unwrap();
+    platform.emergency_withdraw(SOL).unwrap();
+
+    let log: AuditLogPage = platform.harness.state(&audit_log_pda(0));
+    let actions: Vec<AuditAction> = log.entries.iter().map(|entry| entry.action).collect();
+    assert_eq!(
+        actions,
+        vec![
+            AuditAction::Pause,
+            AuditAction::Unpause,
+            AuditAction::ConfigUpdate,
+            AuditAction::ExposureLimitsUpdate,
+            AuditAction::OracleRegistration,
+            AuditAction::FeeWithdrawal,
+            AuditAction::EmergencyWithdrawal,
+        ]
+    );
+    for (sequence, entry) in log.entries.iter().enumerate() {
+        assert_eq!(entry.sequence, sequence as u64);
+        assert_eq!(entry.actor, platform.admin);
+    }
+    assert_eq!(log.entries[0].timestamp, enabled_at);
+    assert_eq!(log.entries[6].timestamp, platform.harness.now());
+    let global_state: GlobalState = platform.harness.state(&platform_pda());
+    assert_eq!(global_state.audit_entries, 7);
+
+    // Parameters are recorded as the hash of their serialized form
+    let hash = |entry: usize| log.entries[entry].params_hash;
+    assert_eq!(hash(0), AuditLogPage::params_hash(&()).unwrap());
+    let config: ConfigParams = (
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(100),
+    );
+    assert_eq!(hash(2), AuditLogPage::params_hash(&config).unwrap());
+    assert_eq!(hash(3), AuditLogPage::params_hash(&limits).unwrap());
+    assert_eq!(
+        hash(4),
+        AuditLogPage::params_hash(&(oracle, match_id)).unwrap()
+    );
+    assert_eq!(log.entries[4].target, Some(oracle));
+    assert_eq!(hash(5), AuditLogPage::params_hash(&fee).unwrap());
+    assert_eq!(hash(6), AuditLogPage::params_hash(&SOL).unwrap());
+}
+
+#[test]
+fn admin_market_and_oracle_actions_are_recorded() {
+    let mut platform = Platform::new();
+    let admin = platform.admin;
+    platform.enable_audit_log().unwrap();
+
+    let arbiters = [
+        platform.harness.fund(SOL),
+        platform.harness.fund(SOL),
+        platform.harness.fund(SOL),
+    ];
+    platform.create_arbiter_set(&arbiters[..2], 2).unwrap();
+    platform.update_arbiter_set(&arbiters, 2).unwrap();
+    let promoter = platform.harness.fund(SOL);
+    platform.add_promoter(&promoter).unwrap();
+    platform.remove_promoter(&promoter).unwrap();
+
+    let scheduled = platform.create_match(HOUR, 2 * HOUR);
+    let now = platform.harness.now();
+    platform
+        .set_betting_window(&scheduled, now, now + HOUR, 1_000)
+        .unwrap();
+    platform.close_match_betting(&scheduled).unwrap();
+    platform
+        .update_match_status(&scheduled, MatchStatus::Cancelled)
+        .unwrap();
+
+    let creator = platform.harness.fund(5 * SOL);
+    let community = platform
+        .create_community_match(&creator, 100, HOUR, 2 * HOUR)
+        .unwrap();
+    platform.void_market_for_abuse(&community, &admin).unwrap();
+
+    let settled = platform.create_match(HOUR, 2 * HOUR);
+    platform.harness.warp_by(3 * HOUR);
+    let cranker = platform.harness.fund(SOL);
+    platform.crank_match(&settled, &cranker).unwrap();
+    platform.resolve_match_in_window(&settled, 0, None);
+
+    let alice = platform.user("alice");
+    set_version::<UserState>(&mut platform, &user_pda(&alice), 1);
+    platform.migrate_account(&user_pda(&alice), &admin).unwrap();
+
+    let log: AuditLogPage = platform.harness.state(&audit_log_pda(0));
+    let actions: Vec<AuditAction> = log.entries.iter().map(|entry| entry.action).collect();
+    assert_eq!(
+        actions,
+        vec![
+            AuditAction::ArbiterSetUpdate,
+            AuditAction::ArbiterSetUpdate,
+            AuditAction::PromoterAddition,
+            AuditAction::PromoterRemoval,
+            AuditAction::BettingWindowUpdate,
+            AuditAction::BettingClosure,
+            AuditAction::MatchStatusUpdate,
+            AuditAction::MarketVoid,
+            AuditAction::OracleRegistration,
+            AuditAction::OracleRegistration,
+            AuditAction::OracleValidation,
+            AuditAction::AccountMigration,
+        ]
+    );
+    assert!(log.entries.iter().all(|entry| entry.actor == admin));
+
+    // Each entry names what it acted on and hashes its parameters
+    let entry = |action: AuditAction| {
+        log.entries
+            .iter()
+            .rev()
+            .find(|entry| entry.action == action)
+            .unwrap()
+    };
+    let arbiter_entry = entry(AuditAction::ArbiterSetUpdate);
+    assert_eq!(arbiter_entry.target, None);
+    assert_eq!(
+        arbiter_entry.params_hash,
+        AuditLogPage::params_hash(&(arbiters.to_vec(), 2u8)).unwrap()
+    );
+    let promoter_entry = entry(AuditAction::PromoterRemoval);
+    assert_eq!(promoter_entry.target, Some(promoter));
+    assert_eq!(
+        promoter_entry.params_hash,
+        AuditLogPage::params_hash(&promoter).unwrap()
+    );
+    let window_entry = entry(AuditAction::BettingWindowUpdate);
+    assert_eq!(window_entry.target, Some(scheduled));
+    assert_eq!(
+        window_entry.params_hash,
+        AuditLogPage::params_hash(&(now, now + HOUR, 1_000u16)).unwrap()
+    );
+    assert_eq!(entry(AuditAction::BettingClosure).target, Some(scheduled));
+    assert_eq!(
+        entry(AuditAction::MatchStatusUpdate).params_hash,
+        AuditLogPage::params_hash(&MatchStatus::Cancelled).unwrap()
+    );
+    assert_eq!(entry(AuditAction::MarketVoid).target, Some(community));
+    // The validated report is the first oracle's, confirmed by the second
+    assert_eq!(
+        entry(AuditAction::OracleValidation).target,
+        log.entries[8].target
+    );
+    let migration_entry = entry(AuditAction::AccountMigration);
+    assert_eq!(migration_entry.target, Some(user_pda(&alice)));
+    assert_eq!(
+        migration_entry.params_hash,
+        AuditLogPage::params_hash(&("user", 1u8, UserState::VERSION)).unwrap()
+    );
+
+    // None of them may skip the log once it is enabled
+    let result = platform.harness.send(
+        accounts::AddPromoter {
+            promoter_account: promoter_pda(&promoter),
+            global_state: platform_pda(),
+            admin,
+            system_program: system_program::ID,
+            audit_log: None,
+        },
+        ix::AddPromoter { promoter },
+        &[admin],
+    );
+    assert_error(result, TrendXBetError::AuditLogRequired);
+}
+
+#[test]
+fn full_pages_roll_over_to_the_next_page() {
+    let mut platform = Platform::new();
+    platform.enable_audit_log().unwrap();
+    for _ in 0..AUDIT_LOG_PAGE_SIZE / 2 {
+        platform.pause_platform().unwrap();
+        platform.unpause_platform().unwrap();
+    }
+    let first_page: AuditLogPage = platform.harness.state(&audit_log_pda(0));
+    assert!(first_page.is_full());
+
+    // Privileged actions need the page the next entry goes on
+    assert_error(
+        pause_with(&mut platform, None),
+        TrendXBetError::AuditLogRequired,
+    );
+    assert_error(
+        pause_with(&mut platform, Some(audit_log_pda(0))),
+        TrendXBetError::AuditLogMismatch,
+    );
+
+    // Anyone may add that page, but no other
+    let payer = platform.harness.fund(SOL);
+    assert_error(
+        platform.create_audit_log_page(&payer, 2),
+        TrendXBetError::AuditLogMismatch,
+    );
+    platform.create_audit_log_page(&payer, 1).unwrap();
+    let event = &platform.harness.events::<AuditLogPageCreated>()[0];
+    assert_eq!(
+        (event.audit_log, event.page, event.payer),
+        (audit_log_pda(1), 1, payer)
+    );
+
+    pause_with(&mut platform, Some(audit_log_pda(1))).unwrap();
+    let second_page: AuditLogPage = platform.harness.state(&audit_log_pda(1));
+    assert_eq!(second_page.entries.len(), 1);
+    assert_eq!(second_page.entries[0].sequence, AUDIT_LOG_PAGE_SIZE as u64);
+    assert_eq!(second_page.entries[0].action, AuditAction::Pause);
+}
+
+#[test]
+fn only_the_admin_enables_the_audit_log() {
+    let mut platform = Platform::new();
+    let payer = platform.harness.fund(SOL);
+    assert_error(
+        platform.create_audit_log_page(&payer, 0),
+        TrendXBetError::AuditLogNotEnabled,
+    );
+
+    // Until the log is enabled, privileged actions record nothing
+    platform.pause_platform().unwrap();
+    let global_state: GlobalState = platform.harness.state(&platform_pda());
+    assert!(!global_state.has_audit_log);
+    assert_eq!(global_state.audit_entries, 0);
+
+    let stranger = platform.harness.fund(SOL);
+    let result = platform.harness.send(
+        accounts::EnableAuditLog {
+            audit_log: audit_log_pda(0),
+            global_state: platform_pda(),
+            admin: stranger,
+            system_program: system_program::ID,
+        },
+        ix::EnableAuditLog {},
+        &[stranger],
+    );
+    assert_error(result, TrendXBetError::Unauthorized);
+
+    platform.enable_audit_log().unwrap();
+    assert!(platform.enable_audit_log().is_err());
+    platform.unpause_platform().unwrap();
+    let log: AuditLogPage = platform.harness.state(&audit_log_pda(0));
+    assert_eq!(log.entries[0].action, AuditAction::Unpause);
+}
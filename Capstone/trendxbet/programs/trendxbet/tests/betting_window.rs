@@ -0,0 +1,149 @@
+//! Betting windows apart from the event times, the platform cutoff before the end
+//! of a match and the extra pool weight of early bets.
+
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use common::*;
+use trendxbet::{
+    BetPlaced, BetState, BettingWindowUpdated, MatchState, TrendXBetError, BASIS_POINTS,
+    DEFAULT_HOUSE_EDGE, MAX_EARLY_BET_BONUS, MIN_BETTING_DURATION,
+};
+
+const HOUR: i64 = 3600;
+
+fn match_state(platform: &Platform, match_id: &Pubkey) -> MatchState {
+    platform.harness.state(&match_pda(match_id))
+}
+
+#[test]
+fn platform_cutoff_closes_betting_before_the_end() {
+    let mut platform = Platform::new();
+    assert_error(
+        platform.set_betting_defaults(MIN_BETTING_DURATION as u32, 0),
+        TrendXBetError::InvalidBettingCutoff,
+    );
+    assert_error(
+        platform.set_betting_defaults(0, MAX_EARLY_BET_BONUS + 1),
+        TrendXBetError::InvalidEarlyBetBonus,
+    );
+    platform.set_betting_defaults(1800, 0).unwrap();
+
+    let alice = platform.user("alice");
+    let bob = platform.user("bob");
+    platform.deposit(&alice, 5 * SOL).unwrap();
+    platform.deposit(&bob, 5 * SOL).unwrap();
+    let match_id = platform.create_match(HOUR, HOUR);
+    let state = match_state(&platform, &match_id);
+    assert_eq!(state.betting_opens_at, state.start_time);
+    assert_eq!(state.betting_closes_at, state.end_time - 1800);
+
+    platform.harness.warp_to(state.betting_closes_at);
+    platform.place_bet(&alice, &match_id, SOL, 0).unwrap();
+    platform.harness.warp_by(1);
+    assert_error(
+        platform.place_bet(&bob, &match_id, SOL, 1),
+        TrendXBetError::BettingClosed,
+    );
+}
+
+#[test]
+fn betting_windows_are_set_before_the_first_bet() {
+    let mut platform = Platform::new();
+    let alice = platform.user("alice");
+    platform.deposit(&alice, 5 * SOL).unwrap();
+    let match_id = platform.create_match(2 * HOUR, 2 * HOUR);
+    let state = match_state(&platform, &match_id);
+    let now = platform.harness.now();
+
+    for (opens_at, closes_at) in [
+        (state.start_time, state.start_time),
+        (now, state.end_time + 1),
+    ] {
+        assert_error(
+            platform.set_betting_window(&match_id, opens_at, closes_at, 0),
+            TrendXBetError::InvalidBettingWindow,
+        );
+    }
+    assert_error(
+        platform.set_betting_window(&match_id, now, state.start_time, MAX_EARLY_BET_BONUS + 1),
+        TrendXBetError::InvalidEarlyBetBonus,
+    );
+
+    // Bets can be taken before the match starts
+    platform
+        .set_betting_window(&match_id, now, state.start_time, 1_000)
+        .unwrap();
+    let event = &platform.harness.events::<BettingWindowUpdated>()[0];
+    assert_eq!(
+        (event.betting_opens_at, event.betting_closes_at),
+        (now, state.start_time)
+    );
+    assert_eq!(event.early_bet_bonus, 1_000);
+
+    platform.place_bet(&alice, &match_id, SOL, 0).unwrap();
+    assert_error(
+        platform.set_betting_window(&match_id, now, state.end_time, 0),
+        TrendXBetError::BettingWindowLocked,
+    );
+
+    platform.harness.warp_to(state.start_time + 1);
+    assert_error(
+        platform.cancel_bet(&alice, &match_id, &alice),
+        TrendXBetError::CannotCancelBet,
+    );
+}
+
+#[test]
+fn early_bets_earn_a_larger_share_of_the_winning_pool() {
+    let mut platform = Platform::new();
+    let [alice, bob, carol, dave] = ["alice", "bob", "carol", "dave"].map(|name| {
+        let user = platform.user(name);
+        platform.deposit(&user, 5 * SOL).unwrap();
+        user
+    });
+    let match_id = platform.create_match(HOUR, 2 * HOUR);
+    let opens_at = platform.harness.now();
+    platform
+        .set_betting_window(&match_id, opens_at, opens_at + 1_000, MAX_EARLY_BET_BONUS)
+        .unwrap();
+
+    // The bonus decays from half the stake at opening to nothing at close
+    platform.place_bet(&alice, &match_id, SOL, 0).unwrap();
+    assert_eq!(
+        platform.harness.events::<BetPlaced>()[0].bonus_weight,
+        SOL / 2
+    );
+    platform.place_bet(&dave, &match_id, SOL, 0).unwrap();
+    platform.harness.warp_by(500);
+    platform.place_bet(&bob, &match_id, SOL, 0).unwrap();
+    platform.cancel_bet(&dave, &match_id, &dave).unwrap();
+    platform.harness.warp_by(500);
+    platform.place_bet(&carol, &match_id, SOL, 1).unwrap();
+
+    let state = match_state(&platform, &match_id);
+    assert_eq!(state.team1_bonus_weight, SOL / 2 + SOL / 4);
+    assert_eq!(state.team2_bonus_weight, 0);
+
+    platform.harness.warp_to(state.end_time);
+    let cranker = platform.harness.fund(SOL);
+    platform.crank_match(&match_id, &cranker).unwrap();
+    platform.resolve_match(&match_id, 0);
+    platform.settle_bet(&alice, &match_id, &alice).unwrap();
+    platform.settle_bet(&bob, &match_id, &bob).unwrap();
+
+    // Winners split the net pool by weight rather than stake
+    let net_pool = 3 * SOL - 3 * SOL * DEFAULT_HOUSE_EDGE as u64 / BASIS_POINTS;
+    let winning_weight = 2 * SOL + SOL / 2 + SOL / 4;
+    let alice_bet: BetState = platform.harness.state(&bet_pda(&alice, &match_id));
+    let bob_bet: BetState = platform.harness.state(&bet_pda(&bob, &match_id));
+    assert_eq!(
+        alice_bet.payout_amount,
+        Some(((SOL + SOL / 2) as u128 * net_pool as u128 / winning_weight as u128) as u64)
+    );
+    assert_eq!(
+        bob_bet.payout_amount,
+        Some(((SOL + SOL / 4) as u128 * net_pool as u128 / winning_weight as u128) as u64)
+    );
+    assert!(alice_bet.payout_amount.unwrap() + bob_bet.payout_amount.unwrap() <= net_pool);
+}
@@ -0,0 +1,282 @@
+//! Promotional bonus balances: promoter-funded, stake-only credit whose winnings
+//! pay only the profit and stay locked until the wagering requirement is met.
+
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use common::*;
+use trendxbet::{
+    BetState, BonusIssued, MatchState, PromoterRemoved, TreasuryState, TrendXBetError, UserState,
+    BASIS_POINTS, DEFAULT_HOUSE_EDGE, MAX_WAGERING_MULTIPLIER, ORACLE_UPDATE_WINDOW,
+};
+
+const HOUR: i64 = 3600;
+
+/// Add a funded promoter
+fn promoter(platform: &mut Platform) -> Pubkey {
+    let promoter = platform.harness.fund(20 * SOL);
+    platform.add_promoter(&promoter).unwrap();
+    promoter
+}
+
+/// Run `match_id` to a settled result for `winning_team`
+fn finish(platform: &mut Platform, match_id: &Pubkey, winning_team: u8) {
+    let end_time = platform
+        .harness
+        .state::<MatchState>(&match_pda(match_id))
+        .end_time;
+    platform.harness.warp_to(end_time);
+    let cranker = platform.harness.fund(SOL);
+    platform.crank_match(match_id, &cranker).unwrap();
+    platform.resolve_match(match_id, winning_team);
+}
+
+fn user_state(platform: &Platform, user: &Pubkey) -> UserState {
+    platform.harness.state(&user_pda(user))
+}
+
+#[test]
+fn bonus_winnings_pay_profit_and_unlock_after_wagering() {
+    let mut platform = Platform::new();
+    let promoter = promoter(&mut platform);
+    let alice = platform.user("alice");
+    let bob = platform.user("bob");
+    platform.deposit(&bob, 5 * SOL).unwrap();
+
+    let treasury_before = platform.harness.lamports(&treasury_pda());
+    platform.issue_bonus(&promoter, &alice, SOL, 2).unwrap();
+    let event = &platform.harness.events::<BonusIssued>()[0];
+    assert_eq!((event.amount, event.wagering_requirement), (SOL, 2 * SOL));
+    assert_eq!(
+        platform.harness.lamports(&treasury_pda()),
+        treasury_before + SOL
+    );
+
+    let match_id = platform.create_match(HOUR, 2 * HOUR);
+    platform.harness.warp_by(HOUR);
+    platform.place_bonus_bet(&alice, &match_id, SOL, 0).unwrap();
+    platform.place_bet(&bob, &match_id, SOL, 1).unwrap();
+    let alice_state = user_state(&platform, &alice);
+    assert_eq!((alice_state.balance, alice_state.bonus_balance), (0, 0));
+
+    finish(&mut platform, &match_id, 0);
+    let fees_before = platform
+        .harness
+        .state::<TreasuryState>(&treasury_pda())
+        .platform_fees;
+    platform.settle_bet(&alice, &match_id, &alice).unwrap();
+
+    // The winning bonus stake goes back to the platform, not the bettor
+    let payout = 2 * SOL - 2 * SOL * DEFAULT_HOUSE_EDGE as u64 / BASIS_POINTS;
+    let bet: BetState = platform.harness.state(&bet_pda(&alice, &match_id));
+    assert_eq!(bet.payout_amount, Some(payout - SOL));
+    let treasury: TreasuryState = platform.harness.state(&treasury_pda());
+    assert_eq!(treasury.platform_fees, fees_before + SOL);
+
+    platform.claim_winnings(&alice, &match_id).unwrap();
+    let alice_state = user_state(&platform, &alice);
+    assert_eq!(alice_state.balance, payout - SOL);
+    assert_eq!(alice_state.locked_bonus_winnings, payout - SOL);
+    assert_eq!(alice_state.wagering_remaining, SOL);
+    assert_error(
+        platform.withdraw(&alice, payout - SOL),
+        TrendXBetError::BonusFundsLocked,
+    );
+
+    // Deposits stay withdrawable while the winnings are locked
+    platform.deposit(&alice, 2 * SOL).unwrap();
+    platform.withdraw(&alice, SOL).unwrap();
+
+    // Settling another stake completes the wagering and unlocks the winnings
+    let match_id = platform.create_match(HOUR, 2 * HOUR);
+    platform.harness.warp_by(HOUR);
+    platform.place_bet(&alice, &match_id, SOL, 0).unwrap();
+    platform.place_bet(&bob, &match_id, SOL, 1).unwrap();
+    finish(&mut platform, &match_id, 1);
+    platform.settle_bet(&alice, &match_id, &alice).unwrap();
+
+    let alice_state = user_state(&platform, &alice);
+    assert_eq!(alice_state.wagering_remaining, 0);
+    assert_eq!(alice_state.locked_bonus_winnings, 0);
+    platform.withdraw(&alice, alice_state.balance).unwrap();
+}
+
+#[test]
+fn only_the_bonus_share_of_mixed_winnings_is_locked() {
+    let mut platform = Platform::new();
+    let promoter = promoter(&mut platform);
+    let alice = platform.user("alice");
+    let bob = platform.user("bob");
+    platform.deposit(&alice, SOL).unwrap();
+    platform.deposit(&bob, 5 * SOL).unwrap();
+    platform.issue_bonus(&promoter, &alice, SOL, 3).unwrap();
+
+    // Half the stake is bonus and half is cash
+    let match_id = platform.create_match(HOUR, 2 * HOUR);
+    platform.harness.warp_by(HOUR);
+    platform
+        .place_bonus_bet(&alice, &match_id, 2 * SOL, 0)
+        .unwrap();
+    platform.place_bet(&bob, &match_id, 2 * SOL, 1).unwrap();
+    finish(&mut platform, &match_id, 0);
+    platform.settle_bet(&alice, &match_id, &alice).unwrap();
+
+    // The bonus stake is kept from its half of the payout, and only the rest of that half is locked
+    let bet: BetState = platform.harness.state(&bet_pda(&alice, &match_id));
+    let payout = bet.payout_amount.unwrap() + SOL;
+    platform.claim_winnings(&alice, &match_id).unwrap();
+    let alice_state = user_state(&platform, &alice);
+    assert_eq!(alice_state.balance, payout - SOL);
+    assert_eq!(alice_state.locked_bonus_winnings, payout / 2 - SOL);
+    assert_eq!(alice_state.wagering_remaining, SOL);
+
+    // The cash half of the winnings can be withdrawn straight away
+    assert_error(
+        platform.withdraw(&alice, payout / 2 + 1),
+        TrendXBetError::BonusFundsLocked,
+    );
+    platform.withdraw(&alice, payout / 2).unwrap();
+}
+
+#[test]
+fn bonus_is_staked_only_when_requested_and_never_withdrawn() {
+    let mut platform = Platform::new();
+    let promoter = promoter(&mut platform);
+    let alice = platform.user("alice");
+    platform.issue_bonus(&promoter, &alice, SOL, 0).unwrap();
+    let match_id = platform.create_match(HOUR, 2 * HOUR);
+    platform.harness.warp_by(HOUR);
+
+    assert_error(
+        platform.place_bet(&alice, &match_id, SOL, 0),
+        TrendXBetError::InsufficientBalance,
+    );
+    assert_error(
+        platform.place_bonus_bet(&alice, &match_id, 2 * SOL, 0),
+        TrendXBetError::InsufficientBalance,
+    );
+    assert_error(
+        platform.withdraw(&alice, SOL),
+        TrendXBetError::InsufficientBalance,
+    );
+
+    // Bonus covers the stake first and the rest comes from the balance
+    platform.deposit(&alice, SOL).unwrap();
+    platform
+        .place_bonus_bet(&alice, &match_id, 2 * SOL, 0)
+        .unwrap();
+    let bet: BetState = platform.harness.state(&bet_pda(&alice, &match_id));
+    assert_eq!(bet.bonus_stake, SOL);
+
+    // Cancelling returns each part of the stake where it came from
+    platform.cancel_bet(&alice, &match_id, &alice).unwrap();
+    let alice_state = user_state(&platform, &alice);
+    assert_eq!((alice_state.balance, alice_state.bonus_balance), (SOL, SOL));
+    platform.withdraw(&alice, SOL).unwrap();
+}
+
+#[test]
+fn returned_stakes_lock_bonus_winnings_again() {
+    let mut platform = Platform::new();
+    let promoter = promoter(&mut platform);
+    let alice = platform.user("alice");
+    let bob = platform.user("bob");
+    platform.deposit(&bob, 5 * SOL).unwrap();
+    platform.issue_bonus(&promoter, &alice, SOL, 2).unwrap();
+
+    let match_id = platform.create_match(HOUR, 2 * HOUR);
+    platform.harness.warp_by(HOUR);
+    platform.place_bonus_bet(&alice, &match_id, SOL, 0).unwrap();
+    platform.place_bet(&bob, &match_id, SOL, 1).unwrap();
+    finish(&mut platform, &match_id, 0);
+    platform.settle_bet(&alice, &match_id, &alice).unwrap();
+    platform.claim_winnings(&alice, &match_id).unwrap();
+    let locked = user_state(&platform, &alice).locked_bonus_winnings;
+    assert!(locked > 0);
+
+    // Staking the locked winnings uses them up until the stake is returned
+    let match_id = platform.create_match(HOUR, 2 * HOUR);
+    platform.harness.warp_by(HOUR);
+    platform.place_bet(&alice, &match_id, locked, 0).unwrap();
+    let bet: BetState = platform.harness.state(&bet_pda(&alice, &match_id));
+    assert_eq!(bet.locked_stake, locked);
+    assert_eq!(user_state(&platform, &alice).locked_bonus_winnings, 0);
+
+    platform.cancel_bet(&alice, &match_id, &alice).unwrap();
+    assert_eq!(user_state(&platform, &alice).locked_bonus_winnings, locked);
+    assert_error(
+        platform.withdraw(&alice, locked),
+        TrendXBetError::BonusFundsLocked,
+    );
+
+    // Reducing a stake locks the released part again
+    let match_id = platform.create_match(HOUR, 2 * HOUR);
+    platform.harness.warp_by(HOUR);
+    platform.place_bet(&alice, &match_id, locked, 0).unwrap();
+    platform
+        .modify_bet(&alice, &match_id, locked / 2, 0)
+        .unwrap();
+    let bet: BetState = platform.harness.state(&bet_pda(&alice, &match_id));
+    assert_eq!(bet.locked_stake, locked / 2);
+    let alice_state = user_state(&platform, &alice);
+    assert_eq!(alice_state.locked_bonus_winnings, locked - locked / 2);
+    assert_error(
+        platform.withdraw(&alice, alice_state.balance),
+        TrendXBetError::BonusFundsLocked,
+    );
+
+    // So does refunding it from a voided market
+    let end_time = platform
+        .harness
+        .state::<MatchState>(&match_pda(&match_id))
+        .end_time;
+    platform.harness.warp_to(end_time);
+    let stranger = platform.harness.fund(SOL);
+    platform.crank_match(&match_id, &stranger).unwrap();
+    platform
+        .harness
+        .warp_to(end_time + ORACLE_UPDATE_WINDOW + 1);
+    platform.void_match(&match_id, &stranger).unwrap();
+    platform.refund_bet(&alice, &match_id, &stranger).unwrap();
+    assert_eq!(user_state(&platform, &alice).locked_bonus_winnings, locked);
+    assert_error(
+        platform.withdraw(&alice, locked),
+        TrendXBetError::BonusFundsLocked,
+    );
+}
+
+#[test]
+fn only_promoters_issue_bonuses() {
+    let mut platform = Platform::new();
+    let promoter = promoter(&mut platform);
+    let alice = platform.user("alice");
+    let mallory = platform.user("mallory");
+
+    assert!(platform.issue_bonus(&mallory, &alice, SOL, 1).is_err());
+    assert_error(
+        platform.issue_bonus(&promoter, &alice, 0, 1),
+        TrendXBetError::ZeroAmount,
+    );
+    assert_error(
+        platform.issue_bonus(&promoter, &alice, SOL, MAX_WAGERING_MULTIPLIER + 1),
+        TrendXBetError::InvalidWageringRequirement,
+    );
+
+    platform
+        .issue_bonus(&promoter, &alice, SOL, MAX_WAGERING_MULTIPLIER)
+        .unwrap();
+    platform.issue_bonus(&promoter, &alice, SOL, 1).unwrap();
+    let alice_state = user_state(&platform, &alice);
+    assert_eq!(alice_state.bonus_balance, 2 * SOL);
+    assert_eq!(
+        alice_state.wagering_remaining,
+        (MAX_WAGERING_MULTIPLIER as u64 + 1) * SOL
+    );
+
+    // Removed promoters can no longer issue, and issued bonuses stay
+    platform.remove_promoter(&promoter).unwrap();
+    let event = &platform.harness.events::<PromoterRemoved>()[0];
+    assert_eq!(event.total_issued, 2 * SOL);
+    assert!(platform.issue_bonus(&promoter, &alice, SOL, 1).is_err());
+    assert_eq!(user_state(&platform, &alice).bonus_balance, 2 * SOL);
+}
@@ -0,0 +1,230 @@
+//! Commit-reveal oracle reporting: oracles commit to a hash of their report
+//! while commitments are open and reveal it afterwards, so nobody can copy a
+//! published answer or change theirs.
+
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use common::*;
+use trendxbet::{
+    accounts, instruction as ix, MatchResultUpdated, MatchState, MatchStatus,
+    OracleResultCommitted, OracleState, ReportEvidence, TrendXBetError, MAX_EVIDENCE_URI_LENGTH,
+    ORACLE_COMMIT_WINDOW,
+};
+
+const HOUR: i64 = 3600;
+
+/// Create a match and crank it to Ended at its end time
+fn ended_match(platform: &mut Platform) -> Pubkey {
+    let match_id = platform.create_match(HOUR, 2 * HOUR);
+    platform.harness.warp_by(3 * HOUR);
+    let cranker = platform.harness.fund(SOL);
+    platform.crank_match(&match_id, &cranker).unwrap();
+    match_id
+}
+
+fn oracle_state(platform: &Platform, oracle: &Pubkey, match_id: &Pubkey) -> OracleState {
+    platform.harness.state(&oracle_pda(oracle, match_id))
+}
+
+#[test]
+fn reveals_must_match_the_commitment() {
+    let mut platform = Platform::new();
+    let match_id = ended_match(&mut platform);
+    let oracle_a = platform.register_oracle(&match_id);
+    let oracle_b = platform.register_oracle(&match_id);
+    let copycat = platform.register_oracle(&match_id);
+    let silent = platform.register_oracle(&match_id);
+
+    platform
+        .commit_result(&oracle_a, &match_id, 0, "2-1", None)
+        .unwrap();
+    let committed = &platform.harness.events::<OracleResultCommitted>()[0];
+    assert_eq!(committed.oracle_authority, oracle_a);
+
+    // Only the hash is public until reveals open, and it cannot be replaced
+    let state = oracle_state(&platform, &oracle_a, &match_id);
+    assert_eq!(state.commitment, Some(committed.commitment));
+    assert_eq!(state.reported_result, None);
+    assert_error(
+        platform.commit_result(&oracle_a, &match_id, 1, "1-2", None),
+        TrendXBetError::OracleResultExists,
+    );
+    assert_error(
+        platform.reveal_result(&oracle_a, &match_id, 0, "2-1", None),
+        TrendXBetError::RevealWindowNotOpen,
+    );
+
+    // Commitments are bound to the oracle that made them
+    platform
+        .commit(&copycat, &match_id, committed.commitment)
+        .unwrap();
+    platform
+        .commit_result(&oracle_b, &match_id, 0, "2-1", None)
+        .unwrap();
+
+    // Only the oracle itself may commit on its behalf
+    let stranger = platform.harness.fund(SOL);
+    assert_error(
+        platform.harness.send(
+            accounts::CommitMatchResult {
+                oracle_state: oracle_pda(&silent, &match_id),
+                match_state: match_pda(&match_id),
+                global_state: platform_pda(),
+                oracle_authority: silent,
+                match_id,
+                authority: stranger,
+            },
+            ix::CommitMatchResult {
+                commitment: [7; 32],
+            },
+            &[stranger],
+        ),
+        TrendXBetError::InvalidOracleAuthority,
+    );
+
+    platform.open_reveals(&match_id);
+    assert_error(
+        platform.commit_result(&silent, &match_id, 0, "2-1", None),
+        TrendXBetError::CommitWindowClosed,
+    );
+    assert_error(
+        platform.reveal_result(&silent, &match_id, 0, "2-1", None),
+        TrendXBetError::OracleNotCommitted,
+    );
+
+    platform
+        .reveal_result(&oracle_a, &match_id, 0, "2-1", None)
+        .unwrap();
+    assert_error(
+        platform.reveal(
+            &copycat,
+            &match_id,
+            0,
+            "2-1",
+            None,
+            oracle_salt(&oracle_a),
+            None,
+        ),
+        TrendXBetError::CommitmentMismatch,
+    );
+
+    // A different answer or salt than the one committed is refused
+    assert_error(
+        platform.reveal_result(&oracle_b, &match_id, 1, "1-2", None),
+        TrendXBetError::CommitmentMismatch,
+    );
+    assert_error(
+        platform.reveal(&oracle_b, &match_id, 0, "2-1", None, [0; 32], None),
+        TrendXBetError::CommitmentMismatch,
+    );
+    platform
+        .reveal_result(&oracle_b, &match_id, 0, "2-1", None)
+        .unwrap();
+    assert_error(
+        platform.reveal_result(&oracle_b, &match_id, 0, "2-1", None),
+        TrendXBetError::OracleResultExists,
+    );
+
+    let state: MatchState = platform.harness.state(&match_pda(&match_id));
+    assert_eq!(state.oracle_reports, 2);
+
+    // The copycat never revealed, so it confirms nothing
+    platform
+        .validate_oracle(&oracle_a, &match_id, &[copycat, oracle_b])
+        .unwrap();
+    assert_eq!(
+        oracle_state(&platform, &oracle_a, &match_id).confirmations,
+        2
+    );
+    let state: MatchState = platform.harness.state(&match_pda(&match_id));
+    assert!(state.status == MatchStatus::Settled);
+}
+
+#[test]
+fn late_reveals_do_not_count() {
+    let mut platform = Platform::new();
+    let match_id = ended_match(&mut platform);
+    let end_time = platform
+        .harness
+        .state::<MatchState>(&match_pda(&match_id))
+        .end_time;
+    let oracle_a = platform.register_oracle(&match_id);
+    let oracle_b = platform.register_oracle(&match_id);
+
+    platform.harness.warp_to(end_time + ORACLE_COMMIT_WINDOW);
+    for oracle in [oracle_a, oracle_b] {
+        platform
+            .commit_result(&oracle, &match_id, 1, "0-3", None)
+            .unwrap();
+    }
+
+    platform.open_reveals(&match_id);
+    platform
+        .reveal_result(&oracle_a, &match_id, 1, "0-3", None)
+        .unwrap();
+
+    platform
+        .harness
+        .warp_to(end_time + trendxbet::ORACLE_UPDATE_WINDOW + 1);
+    assert_error(
+        platform.reveal_result(&oracle_b, &match_id, 1, "0-3", None),
+        TrendXBetError::OracleUpdateExpired,
+    );
+    assert_error(
+        platform.validate_oracle(&oracle_a, &match_id, &[oracle_b]),
+        TrendXBetError::InsufficientOracleConfirmations,
+    );
+}
+
+#[test]
+fn reveals_carry_evidence() {
+    let mut platform = Platform::new();
+    let match_id = ended_match(&mut platform);
+    let oracle = platform.register_oracle(&match_id);
+    platform
+        .commit_result(&oracle, &match_id, 0, "2-1", None)
+        .unwrap();
+    platform.open_reveals(&match_id);
+
+    let evidence = |uri: String, content_hash: [u8; 32]| ReportEvidence { uri, content_hash };
+    let invalid = [
+        evidence(String::new(), [1; 32]),
+        evidence("x".repeat(MAX_EVIDENCE_URI_LENGTH + 1), [1; 32]),
+        evidence("https://stats.example/m/1".to_string(), [0; 32]),
+    ];
+    for evidence in invalid {
+        assert_error(
+            platform.reveal(
+                &oracle,
+                &match_id,
+                0,
+                "2-1",
+                None,
+                oracle_salt(&oracle),
+                Some(evidence),
+            ),
+            TrendXBetError::InvalidEvidence,
+        );
+    }
+
+    // Evidence is not part of the commitment, so any valid source can be cited
+    let cited = evidence("https://stats.example/m/1".to_string(), [9; 32]);
+    platform
+        .reveal(
+            &oracle,
+            &match_id,
+            0,
+            "2-1",
+            None,
+            oracle_salt(&oracle),
+            Some(cited.clone()),
+        )
+        .unwrap();
+    let revealed = &platform.harness.events::<MatchResultUpdated>()[0];
+    assert_eq!(revealed.evidence, Some(cited.clone()));
+    assert_eq!(
+        oracle_state(&platform, &oracle, &match_id).evidence,
+        Some(cited)
+    );
+}
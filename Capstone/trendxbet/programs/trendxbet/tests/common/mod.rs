@@ -0,0 +1,709 @@
+//! In-process harness that runs the program entrypoint against an in-memory
+//! account store, without a validator or network.
+//!
+//! Accounts are serialized into the same aligned input buffer the BPF loader
+//! builds, so `realloc`, `assign` and Anchor's `init`/`close` behave as they do
+//! on chain. Syscalls are stubbed for the clock, rent, event logs, return data
+//! and the system program instructions Anchor issues over CPI. After every instruction
+//! the runtime's own invariants (lamport conservation, ownership, writability
+//! and rent exemption) are re-checked, and the store is only updated when the
+//! instruction succeeds.
+
+#![allow(dead_code)]
+
+mod platform;
+
+pub use platform::*;
+
+use anchor_lang::error::ComparedValues;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    clock::Clock,
+    entrypoint::{deserialize, MAX_PERMITTED_DATA_INCREASE, SUCCESS},
+    instruction::Instruction,
+    program::MAX_RETURN_DATA,
+    program_error::ProgramError,
+    program_stubs::{set_syscall_stubs, SyscallStubs},
+    rent::Rent,
+};
+use anchor_lang::system_program;
+use anchor_lang::{AccountDeserialize, AccountSerialize, Event, InstructionData, ToAccountMetas};
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::sync::Once;
+use trendxbet::{MatchIndexKey, SecurityUtils, TrendXBetError};
+
+/// Unix time every harness starts at
+pub const GENESIS_TIME: i64 = 1_700_000_000;
+
+/// Lamports in one SOL
+pub const SOL: u64 = 1_000_000_000;
+
+const NATIVE_LOADER_ID: Pubkey = pubkey!("NativeLoader1111111111111111111111111111111");
+const NON_DUP_MARKER: u8 = u8::MAX;
+
+/// An account held by the harness
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TestAccount {
+    pub lamports: u64,
+    pub data: Vec<u8>,
+    pub owner: Pubkey,
+    pub executable: bool,
+}
+
+impl TestAccount {
+    fn system(lamports: u64) -> Self {
+        Self {
+            lamports,
+            data: Vec::new(),
+            owner: system_program::ID,
+            executable: false,
+        }
+    }
+}
+
+/// Per-thread state read by the syscall stubs, so tests can run in parallel
+#[derive(Default)]
+struct Runtime {
+    clock: Clock,
+    /// Payloads passed to `sol_log_data` by the current instruction
+    events: Vec<Vec<u8>>,
+    /// Accounts debited, resized or reassigned by system program CPIs
+    system_touched: HashSet<Pubkey>,
+    /// Data passed to `sol_set_return_data` by the current instruction
+    return_data: Vec<u8>,
+}
+
+thread_local! {
+    static RUNTIME: RefCell<Runtime> = RefCell::new(Runtime::default());
+}
+
+struct Stubs;
+
+impl SyscallStubs for Stubs {
+    fn sol_get_clock_sysvar(&self, var_addr: *mut u8) -> u64 {
+        let clock = RUNTIME.with(|runtime| runtime.borrow().clock.clone());
+        unsafe { std::ptr::write(var_addr as *mut Clock, clock) };
+        SUCCESS
+    }
+
+    fn sol_get_rent_sysvar(&self, var_addr: *mut u8) -> u64 {
+        unsafe { std::ptr::write(var_addr as *mut Rent, Rent::default()) };
+        SUCCESS
+    }
+
+    fn sol_log_data(&self, fields: &[&[u8]]) {
+        RUNTIME.with(|runtime| runtime.borrow_mut().events.push(fields.concat()));
+    }
+
+    fn sol_set_return_data(&self, data: &[u8]) {
+        assert!(
+            data.len() <= MAX_RETURN_DATA,
+            "return data of {} bytes exceeds the runtime limit",
+            data.len()
+        );
+        RUNTIME.with(|runtime| runtime.borrow_mut().return_data = data.to_vec());
+    }
+
+    fn sol_invoke_signed(
+        &self,
+        instruction: &Instruction,
+        account_infos: &[AccountInfo],
+        signers_seeds: &[&[&[u8]]],
+    ) -> std::result::Result<(), ProgramError> {
+        assert_eq!(
+            instruction.program_id,
+            system_program::ID,
+            "harness only supports CPIs into the system program"
+        );
+        invoke_system(instruction, account_infos, signers_seeds)
+    }
+}
+
+fn install_stubs() {
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| {
+        set_syscall_stubs(Box::new(Stubs));
+    });
+}
+
+/// Execute a system program instruction with the runtime's ownership rules
+fn invoke_system(
+    instruction: &Instruction,
+    account_infos: &[AccountInfo],
+    signers_seeds: &[&[&[u8]]],
+) -> std::result::Result<(), ProgramError> {
+    let info = |index: usize| -> std::result::Result<&AccountInfo, ProgramError> {
+        let meta = instruction
+            .accounts
+            .get(index)
+            .ok_or(ProgramError::NotEnoughAccountKeys)?;
+        account_infos
+            .iter()
+            .find(|info| *info.key == meta.pubkey)
+            .ok_or(ProgramError::NotEnoughAccountKeys)
+    };
+
+    // A signer is either a transaction signer or a PDA of the calling program
+    for meta in instruction.accounts.iter().filter(|meta| meta.is_signer) {
+        let signed = account_infos
+            .iter()
+            .any(|info| *info.key == meta.pubkey && info.is_signer)
+            || signers_seeds.iter().any(|seeds| {
+                Pubkey::create_program_address(seeds, &trendxbet::ID).ok() == Some(meta.pubkey)
+            });
+        if !signed {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+    }
+
+    let data = &instruction.data;
+    let tag = u32::from_le_bytes(data[0..4].try_into().unwrap());
+    let u64_at = |offset: usize| u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap());
+    let pubkey_at = |offset: usize| Pubkey::try_from(&data[offset..offset + 32]).unwrap();
+
+    match tag {
+        // CreateAccount { lamports, space, owner }
+        0 => {
+            let (from, to) = (info(0)?, info(1)?);
+            if to.lamports() > 0 || !to.data_is_empty() || *to.owner != system_program::ID {
+                // SystemError::AccountAlreadyInUse
+                return Err(ProgramError::Custom(0));
+            }
+            debit_system_account(from, u64_at(4))?;
+            **to.try_borrow_mut_lamports()? += u64_at(4);
+            to.resize(u64_at(12) as usize)?;
+            to.assign(&pubkey_at(20));
+            touch(&[from, to]);
+        }
+        // Assign { owner }
+        1 => {
+            let account = info(0)?;
+            require_system_owned(account)?;
+            account.assign(&pubkey_at(4));
+            touch(&[account]);
+        }
+        // Transfer { lamports }
+        2 => {
+            let (from, to) = (info(0)?, info(1)?);
+            debit_system_account(from, u64_at(4))?;
+            **to.try_borrow_mut_lamports()? += u64_at(4);
+            touch(&[from, to]);
+        }
+        // Allocate { space }
+        8 => {
+            let account = info(0)?;
+            require_system_owned(account)?;
+            if !account.data_is_empty() {
+                return Err(ProgramError::Custom(0));
+            }
+            account.resize(u64_at(4) as usize)?;
+            touch(&[account]);
+        }
+        other => panic!("harness does not support system instruction {}", other),
+    }
+
+    Ok(())
+}
+
+fn require_system_owned(account: &AccountInfo) -> std::result::Result<(), ProgramError> {
+    if *account.owner != system_program::ID {
+        return Err(ProgramError::InvalidAccountOwner);
+    }
+    Ok(())
+}
+
+/// Debit `amount` from a system account, which must not carry data
+fn debit_system_account(
+    account: &AccountInfo,
+    amount: u64,
+) -> std::result::Result<(), ProgramError> {
+    require_system_owned(account)?;
+    if !account.data_is_empty() {
+        return Err(ProgramError::InvalidArgument);
+    }
+    let mut lamports = account.try_borrow_mut_lamports()?;
+    // SystemError::ResultWithNegativeLamports
+    **lamports = lamports
+        .checked_sub(amount)
+        .ok_or(ProgramError::Custom(1))?;
+    Ok(())
+}
+
+fn touch(accounts: &[&AccountInfo]) {
+    RUNTIME.with(|runtime| {
+        runtime
+            .borrow_mut()
+            .system_touched
+            .extend(accounts.iter().map(|account| *account.key))
+    });
+}
+
+/// Offsets of one serialized account inside the input buffer
+struct Layout {
+    owner: usize,
+    lamports: usize,
+    data_len: usize,
+    data: usize,
+}
+
+/// In-memory ledger with a controllable clock
+pub struct Harness {
+    accounts: HashMap<Pubkey, TestAccount>,
+    events: Vec<Vec<u8>>,
+    return_data: Vec<u8>,
+}
+
+impl Harness {
+    /// Create an empty ledger at `GENESIS_TIME` with the system program loaded
+    pub fn new() -> Self {
+        install_stubs();
+        RUNTIME.with(|runtime| {
+            *runtime.borrow_mut() = Runtime {
+                clock: Clock {
+                    unix_timestamp: GENESIS_TIME,
+                    ..Clock::default()
+                },
+                ..Runtime::default()
+            }
+        });
+
+        let mut accounts = HashMap::new();
+        accounts.insert(
+            system_program::ID,
+            TestAccount {
+                lamports: 1,
+                data: b"system_program".to_vec(),
+                owner: NATIVE_LOADER_ID,
+                executable: true,
+            },
+        );
+
+        Self {
+            accounts,
+            events: Vec::new(),
+            return_data: Vec::new(),
+        }
+    }
+
+    /// Current cluster time
+    pub fn now(&self) -> i64 {
+        RUNTIME.with(|runtime| runtime.borrow().clock.unix_timestamp)
+    }
+
+    /// Move the clock to `unix_timestamp`
+    pub fn warp_to(&mut self, unix_timestamp: i64) {
+        RUNTIME.with(|runtime| {
+            let clock = &mut runtime.borrow_mut().clock;
+            assert!(
+                unix_timestamp >= clock.unix_timestamp,
+                "clock cannot go backwards"
+            );
+            clock.slot += ((unix_timestamp - clock.unix_timestamp) as u64).max(1);
+            clock.unix_timestamp = unix_timestamp;
+        });
+    }
+
+    /// Advance the clock by `seconds`
+    pub fn warp_by(&mut self, seconds: i64) {
+        self.warp_to(self.now() + seconds);
+    }
+
+    /// Create a funded system account
+    pub fn fund(&mut self, lamports: u64) -> Pubkey {
+        let key = Pubkey::new_unique();
+        self.accounts.insert(key, TestAccount::system(lamports));
+        key
+    }
+
+    /// Get an account, if it exists
+    pub fn account(&self, key: &Pubkey) -> Option<&TestAccount> {
+        self.accounts.get(key)
+    }
+
+    /// Get an account's lamports, zero if it does not exist
+    pub fn lamports(&self, key: &Pubkey) -> u64 {
+        self.accounts.get(key).map_or(0, |account| account.lamports)
+    }
+
+    /// Deserialize a program account
+    pub fn state<T: AccountDeserialize>(&self, key: &Pubkey) -> T {
+        let account = self
+            .accounts
+            .get(key)
+            .unwrap_or_else(|| panic!("account {} does not exist", key));
+        assert_eq!(
+            account.owner,
+            trendxbet::ID,
+            "account {} is not owned by the program",
+            key
+        );
+        T::try_deserialize(&mut account.data.as_slice())
+            .unwrap_or_else(|err| panic!("failed to deserialize {}: {}", key, err))
+    }
+
+    /// Mutate an account outside of any instruction, e.g. to plant a legacy layout
+    pub fn account_mut(&mut self, key: &Pubkey) -> &mut TestAccount {
+        self.accounts
+            .get_mut(key)
+            .unwrap_or_else(|| panic!("account {} does not exist", key))
+    }
+
+    /// Plant an account owned by another program, e.g. a price feed
+    pub fn set_account(&mut self, key: Pubkey, account: TestAccount) {
+        self.accounts.insert(key, account);
+    }
+
+    /// Overwrite a program account's data with `state`, keeping its size
+    pub fn set_state<T: AccountSerialize>(&mut self, key: &Pubkey, state: &T) {
+        let mut data: &mut [u8] = &mut self.account_mut(key).data;
+        state
+            .try_serialize(&mut data)
+            .unwrap_or_else(|err| panic!("failed to serialize {}: {}", key, err));
+    }
+
+    /// Events of type `E` emitted by the last successful instruction
+    pub fn events<E: Event>(&self) -> Vec<E> {
+        self.events
+            .iter()
+            .filter_map(|data| data.strip_prefix(E::DISCRIMINATOR))
+            .map(|mut payload| E::deserialize(&mut payload).expect("event decodes"))
+            .collect()
+    }
+
+    /// Decode the value returned by the last successful instruction
+    pub fn return_data<T: AnchorDeserialize>(&self) -> T {
+        T::deserialize(&mut self.return_data.as_slice()).expect("return data decodes")
+    }
+
+    /// Build and run a program instruction signed by `signers`
+    pub fn send(
+        &mut self,
+        accounts: impl ToAccountMetas,
+        data: impl InstructionData,
+        signers: &[Pubkey],
+    ) -> std::result::Result<(), ProgramError> {
+        self.process(
+            Instruction {
+                program_id: trendxbet::ID,
+                accounts: accounts.to_account_metas(None),
+                data: data.data(),
+            },
+            signers,
+        )
+    }
+
+    /// Run `instruction`, committing its account changes only if it succeeds
+    pub fn process(
+        &mut self,
+        instruction: Instruction,
+        signers: &[Pubkey],
+    ) -> std::result::Result<(), ProgramError> {
+        assert_eq!(instruction.program_id, trendxbet::ID);
+
+        // Merge duplicate keys the way the runtime does
+        let mut keys: Vec<Pubkey> = Vec::new();
+        let mut writable: HashSet<Pubkey> = HashSet::new();
+        for meta in &instruction.accounts {
+            if meta.is_signer && !signers.contains(&meta.pubkey) {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+            if meta.is_writable {
+                writable.insert(meta.pubkey);
+            }
+            if !keys.contains(&meta.pubkey) {
+                keys.push(meta.pubkey);
+            }
+        }
+        let signed: HashSet<Pubkey> = instruction
+            .accounts
+            .iter()
+            .filter(|meta| meta.is_signer)
+            .map(|meta| meta.pubkey)
+            .collect();
+
+        let before: HashMap<Pubkey, TestAccount> = keys
+            .iter()
+            .map(|key| {
+                let account = self
+                    .accounts
+                    .get(key)
+                    .cloned()
+                    .unwrap_or_else(|| TestAccount::system(0));
+                (*key, account)
+            })
+            .collect();
+
+        let (mut buffer, layouts) = serialize(&instruction, &before, &signed, &writable);
+        RUNTIME.with(|runtime| {
+            let mut runtime = runtime.borrow_mut();
+            runtime.events.clear();
+            runtime.system_touched.clear();
+            runtime.return_data.clear();
+        });
+
+        let input = buffer.as_mut_ptr() as *mut u8;
+        let result = {
+            let (program_id, infos, data) = unsafe { deserialize(input) };
+            trendxbet::entry(program_id, &infos, data)
+        };
+        result?;
+
+        let after: HashMap<Pubkey, TestAccount> = layouts
+            .iter()
+            .map(|(key, layout)| (*key, unsafe { read_account(input, layout, &before[key]) }))
+            .collect();
+
+        let system_touched = RUNTIME.with(|runtime| runtime.borrow().system_touched.clone());
+        check_invariants(&before, &after, &writable, &system_touched);
+
+        for (key, account) in after {
+            if account.lamports == 0 {
+                self.accounts.remove(&key);
+            } else {
+                self.accounts.insert(key, account);
+            }
+        }
+        self.events = RUNTIME.with(|runtime| std::mem::take(&mut runtime.borrow_mut().events));
+        self.return_data =
+            RUNTIME.with(|runtime| std::mem::take(&mut runtime.borrow_mut().return_data));
+        Ok(())
+    }
+}
+
+/// Lay accounts out exactly as the BPF loader's aligned serializer does
+fn serialize(
+    instruction: &Instruction,
+    accounts: &HashMap<Pubkey, TestAccount>,
+    signed: &HashSet<Pubkey>,
+    writable: &HashSet<Pubkey>,
+) -> (Vec<u64>, Vec<(Pubkey, Layout)>) {
+    let mut bytes: Vec<u8> = Vec::new();
+    let mut layouts: Vec<(Pubkey, Layout)> = Vec::new();
+    let mut positions: HashMap<Pubkey, usize> = HashMap::new();
+
+    bytes.extend_from_slice(&(instruction.accounts.len() as u64).to_le_bytes());
+    for (position, meta) in instruction.accounts.iter().enumerate() {
+        if let Some(first) = positions.get(&meta.pubkey) {
+            bytes.push(*first as u8);
+            bytes.extend_from_slice(&[0; 7]);
+            continue;
+        }
+        positions.insert(meta.pubkey, position);
+
+        let account = &accounts[&meta.pubkey];
+        bytes.push(NON_DUP_MARKER);
+        bytes.push(signed.contains(&meta.pubkey) as u8);
+        bytes.push(writable.contains(&meta.pubkey) as u8);
+        bytes.push(account.executable as u8);
+        bytes.extend_from_slice(&[0; 4]); // original data length, filled in by `deserialize`
+        bytes.extend_from_slice(meta.pubkey.as_ref());
+        let owner = bytes.len();
+        bytes.extend_from_slice(account.owner.as_ref());
+        let lamports = bytes.len();
+        bytes.extend_from_slice(&account.lamports.to_le_bytes());
+        let data_len = bytes.len();
+        bytes.extend_from_slice(&(account.data.len() as u64).to_le_bytes());
+        let data = bytes.len();
+        bytes.extend_from_slice(&account.data);
+        bytes.resize(bytes.len() + MAX_PERMITTED_DATA_INCREASE, 0);
+        bytes.resize(bytes.len().next_multiple_of(8), 0);
+        bytes.extend_from_slice(&u64::MAX.to_le_bytes()); // rent epoch
+
+        layouts.push((
+            meta.pubkey,
+            Layout {
+                owner,
+                lamports,
+                data_len,
+                data,
+            },
+        ));
+    }
+
+    bytes.extend_from_slice(&(instruction.data.len() as u64).to_le_bytes());
+    bytes.extend_from_slice(&instruction.data);
+    bytes.extend_from_slice(instruction.program_id.as_ref());
+
+    // `deserialize` reads u64s in place, so the buffer must be 8-byte aligned
+    let mut buffer = vec![0u64; bytes.len().div_ceil(8)];
+    unsafe {
+        std::ptr::copy_nonoverlapping(bytes.as_ptr(), buffer.as_mut_ptr() as *mut u8, bytes.len());
+    }
+    (buffer, layouts)
+}
+
+/// Read an account back out of the input buffer after execution
+unsafe fn read_account(input: *const u8, layout: &Layout, before: &TestAccount) -> TestAccount {
+    let read_u64 = |offset: usize| u64::from_le_bytes(*(input.add(offset) as *const [u8; 8]));
+    let data_len = read_u64(layout.data_len) as usize;
+    TestAccount {
+        lamports: read_u64(layout.lamports),
+        data: std::slice::from_raw_parts(input.add(layout.data), data_len).to_vec(),
+        owner: Pubkey::new_from_array(*(input.add(layout.owner) as *const [u8; 32])),
+        executable: before.executable,
+    }
+}
+
+/// Re-check the rules the runtime enforces after every instruction
+fn check_invariants(
+    before: &HashMap<Pubkey, TestAccount>,
+    after: &HashMap<Pubkey, TestAccount>,
+    writable: &HashSet<Pubkey>,
+    system_touched: &HashSet<Pubkey>,
+) {
+    let total = |accounts: &HashMap<Pubkey, TestAccount>| -> u128 {
+        accounts
+            .values()
+            .map(|account| account.lamports as u128)
+            .sum()
+    };
+    assert_eq!(
+        total(before),
+        total(after),
+        "instruction did not conserve lamports"
+    );
+
+    let rent = Rent::default();
+    for (key, new) in after {
+        let old = &before[key];
+        if new == old {
+            continue;
+        }
+        assert!(
+            writable.contains(key),
+            "read-only account {} was modified",
+            key
+        );
+
+        let program_owned = old.owner == trendxbet::ID;
+        if !program_owned && !system_touched.contains(key) {
+            assert!(
+                new.lamports >= old.lamports,
+                "program debited account {} it does not own",
+                key
+            );
+            assert_eq!(
+                new.data, old.data,
+                "program modified data of account {} it does not own",
+                key
+            );
+            assert_eq!(
+                new.owner, old.owner,
+                "program reassigned account {} it does not own",
+                key
+            );
+        }
+
+        assert!(
+            new.lamports == 0 || new.lamports >= rent.minimum_balance(new.data.len()),
+            "account {} is left below rent exemption with {} lamports",
+            key,
+            new.lamports
+        );
+    }
+}
+
+/// Assert that `result` failed with `expected`
+#[track_caller]
+pub fn assert_error(result: std::result::Result<(), ProgramError>, expected: TrendXBetError) {
+    assert_eq!(result, Err(ProgramError::Custom(u32::from(expected))));
+}
+
+/// Values a failed comparison logged with its error, as `(left, right)`
+pub fn compared_values<T>(result: anchor_lang::Result<T>) -> Option<(String, String)> {
+    match result {
+        Err(anchor_lang::error::Error::AnchorError(error)) => match error.compared_values? {
+            ComparedValues::Values(values) => Some(values),
+            ComparedValues::Pubkeys((left, right)) => Some((left.to_string(), right.to_string())),
+        },
+        _ => None,
+    }
+}
+
+/// Platform PDA
+pub fn platform_pda() -> Pubkey {
+    SecurityUtils::generate_platform_pda(&trendxbet::ID).0
+}
+
+/// Treasury PDA
+pub fn treasury_pda() -> Pubkey {
+    SecurityUtils::generate_treasury_pda(&trendxbet::ID).0
+}
+
+/// User profile PDA for `user`
+pub fn user_pda(user: &Pubkey) -> Pubkey {
+    SecurityUtils::generate_user_pda(user, &trendxbet::ID).0
+}
+
+/// Match PDA for `match_id`
+pub fn match_pda(match_id: &Pubkey) -> Pubkey {
+    SecurityUtils::generate_match_pda(match_id, &trendxbet::ID).0
+}
+
+/// Bet PDA for `bettor` on `match_id`
+pub fn bet_pda(bettor: &Pubkey, match_id: &Pubkey) -> Pubkey {
+    SecurityUtils::generate_bet_pda(bettor, match_id, &trendxbet::ID).0
+}
+
+pub fn price_market_pda(match_id: &Pubkey) -> Pubkey {
+    SecurityUtils::generate_price_market_pda(match_id, &trendxbet::ID).0
+}
+
+/// Oracle PDA for `oracle_authority` on `match_id`
+pub fn oracle_pda(oracle_authority: &Pubkey, match_id: &Pubkey) -> Pubkey {
+    SecurityUtils::generate_oracle_pda(oracle_authority, match_id, &trendxbet::ID).0
+}
+
+pub fn oracle_profile_pda(oracle_authority: &Pubkey) -> Pubkey {
+    SecurityUtils::generate_oracle_profile_pda(oracle_authority, &trendxbet::ID).0
+}
+
+pub fn arbiter_set_pda() -> Pubkey {
+    SecurityUtils::generate_arbiter_set_pda(&trendxbet::ID).0
+}
+
+/// Dispute PDA for `match_id`
+pub fn dispute_pda(match_id: &Pubkey) -> Pubkey {
+    SecurityUtils::generate_dispute_pda(match_id, &trendxbet::ID).0
+}
+
+/// Match registry PDA
+pub fn match_registry_pda() -> Pubkey {
+    SecurityUtils::generate_match_registry_pda(&trendxbet::ID).0
+}
+
+/// PDA of page `page` of the match index `key`
+pub fn match_index_pda(key: &MatchIndexKey, page: u32) -> Pubkey {
+    SecurityUtils::generate_match_index_pda(key, page, &trendxbet::ID).0
+}
+
+/// PDA of the record claiming `username`
+pub fn username_pda(username: &str) -> Pubkey {
+    SecurityUtils::generate_username_pda(username, &trendxbet::ID).0
+}
+
+/// Session PDA letting `session_key` act for `authority`
+pub fn session_pda(authority: &Pubkey, session_key: &Pubkey) -> Pubkey {
+    SecurityUtils::generate_session_pda(authority, session_key, &trendxbet::ID).0
+}
+
+/// Promoter PDA authorising `promoter` to issue bonuses
+pub fn promoter_pda(promoter: &Pubkey) -> Pubkey {
+    SecurityUtils::generate_promoter_pda(promoter, &trendxbet::ID).0
+}
+
+/// Private pool PDA restricting who may bet on `match_id`
+pub fn private_pool_pda(match_id: &Pubkey) -> Pubkey {
+    SecurityUtils::generate_private_pool_pda(match_id, &trendxbet::ID).0
+}
+
+/// Odds history PDA charting the pools of `match_id`
+pub fn odds_history_pda(match_id: &Pubkey) -> Pubkey {
+    SecurityUtils::generate_odds_history_pda(match_id, &trendxbet::ID).0
+}
+
+/// PDA of page `page` of the audit log
+pub fn audit_log_pda(page: u32) -> Pubkey {
+    SecurityUtils::generate_audit_log_pda(page, &trendxbet::ID).0
+}
@@ -0,0 +1,1837 @@
+//! Typed wrappers around each TrendXBet instruction for use with `Harness`.
+
+use super::*;
+use mock_price_feed::{price_account_data, PriceUpdate};
+use trendxbet::{
+    accounts, instruction as ix, ExposureLimits, GlobalState, MarketKind, MatchIndexKey,
+    MatchIndexPage, MatchScore, MatchState, MatchStatus, OddsPoint, OracleState, PrivatePool,
+    ReportEvidence, UserState, MIN_ORACLE_STAKE, ORACLE_COMMIT_WINDOW, PUSH_RESULT,
+};
+
+/// Outcome of running one instruction
+pub type TxResult = std::result::Result<(), ProgramError>;
+
+/// Registry accounts a new match is listed with; all `None` leaves it unlisted
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Listing {
+    pub registry: Option<Pubkey>,
+    pub status_index: Option<Pubkey>,
+    pub start_day_index: Option<Pubkey>,
+    pub league_index: Option<Pubkey>,
+}
+
+/// A harness with an initialized platform and a funded admin
+pub struct Platform {
+    pub harness: Harness,
+    pub admin: Pubkey,
+}
+
+impl Platform {
+    /// Create a ledger and initialize the platform with a fresh admin
+    pub fn new() -> Self {
+        let mut harness = Harness::new();
+        let admin = harness.fund(100 * SOL);
+        let mut platform = Self { harness, admin };
+        platform.initialize().expect("initialize succeeds");
+        platform
+    }
+
+    fn initialize(&mut self) -> TxResult {
+        self.harness.send(
+            accounts::Initialize {
+                global_state: platform_pda(),
+                treasury: treasury_pda(),
+                admin: self.admin,
+                system_program: system_program::ID,
+            },
+            ix::Initialize { admin: self.admin },
+            &[self.admin],
+        )
+    }
+
+    /// Fund a new wallet and create its profile
+    pub fn user(&mut self, username: &str) -> Pubkey {
+        let user = self.harness.fund(100 * SOL);
+        self.create_user_profile(&user, username)
+            .expect("profile creation succeeds");
+        user
+    }
+
+    pub fn create_user_profile(&mut self, user: &Pubkey, username: &str) -> TxResult {
+        self.harness.send(
+            accounts::CreateUserProfile {
+                user_state: user_pda(user),
+                user: *user,
+                system_program: system_program::ID,
+                username_record: username_pda(username),
+            },
+            ix::CreateUserProfile {
+                username: username.to_string(),
+            },
+            &[*user],
+        )
+    }
+
+    /// Rename `user`, releasing its current username record and claiming the new one
+    pub fn rename_user(&mut self, user: &Pubkey, username: &str) -> TxResult {
+        let current = self.harness.state::<UserState>(&user_pda(user));
+        let current_record = current
+            .has_username_record
+            .then(|| username_pda(&current.username));
+        let new_record = username_pda(username);
+        self.update_username(
+            user,
+            username,
+            current_record,
+            (current_record != Some(new_record)).then_some(new_record),
+        )
+    }
+
+    /// Rename `user` passing exactly the given username records
+    pub fn update_username(
+        &mut self,
+        user: &Pubkey,
+        username: &str,
+        current_username_record: Option<Pubkey>,
+        new_username_record: Option<Pubkey>,
+    ) -> TxResult {
+        self.harness.send(
+            accounts::UpdateUserProfile {
+                user_state: user_pda(user),
+                authority: *user,
+                user: *user,
+                system_program: system_program::ID,
+                current_username_record,
+                new_username_record,
+            },
+            ix::UpdateUserProfile {
+                username: Some(username.to_string()),
+            },
+            &[*user],
+        )
+    }
+
+    /// Let `session_key` sign for `user` until `expires_at`
+    pub fn create_session_key(
+        &mut self,
+        user: &Pubkey,
+        session_key: &Pubkey,
+        expires_at: i64,
+        max_bet_amount: u64,
+        spend_limit: u64,
+        permissions: u8,
+    ) -> TxResult {
+        self.harness.send(
+            accounts::CreateSessionKey {
+                session: session_pda(user, session_key),
+                user_state: user_pda(user),
+                authority: *user,
+                system_program: system_program::ID,
+            },
+            ix::CreateSessionKey {
+                session_key: *session_key,
+                expires_at,
+                max_bet_amount,
+                spend_limit,
+                permissions,
+            },
+            &[*user],
+        )
+    }
+
+    /// Revoke `user`'s session `session_key`, signed by `signer`
+    pub fn revoke_session_key(
+        &mut self,
+        user: &Pubkey,
+        session_key: &Pubkey,
+        signer: &Pubkey,
+    ) -> TxResult {
+        self.harness.send(
+            accounts::RevokeSessionKey {
+                session: session_pda(user, session_key),
+                authority: *user,
+                signer: *signer,
+            },
+            ix::RevokeSessionKey {},
+            &[*signer],
+        )
+    }
+
+    pub fn deposit(&mut self, user: &Pubkey, amount: u64) -> TxResult {
+        self.harness.send(
+            accounts::DepositFunds {
+                user_state: user_pda(user),
+                treasury: treasury_pda(),
+                authority: *user,
+                user: *user,
+                treasury_account: treasury_pda(),
+                system_program: system_program::ID,
+            },
+            ix::DepositFunds { amount },
+            &[*user],
+        )
+    }
+
+    pub fn withdraw(&mut self, user: &Pubkey, amount: u64) -> TxResult {
+        self.harness.send(
+            accounts::WithdrawFunds {
+                user_state: user_pda(user),
+                treasury: treasury_pda(),
+                authority: *user,
+                user: *user,
+                treasury_account: treasury_pda(),
+                system_program: system_program::ID,
+            },
+            ix::WithdrawFunds { amount },
+            &[*user],
+        )
+    }
+
+    /// Create a match starting `starts_in` seconds from now and lasting `duration`
+    pub fn create_match(&mut self, starts_in: i64, duration: i64) -> Pubkey {
+        let start_time = self.harness.now() + starts_in;
+        self.try_create_match(start_time, start_time + duration)
+            .expect("match creation succeeds")
+    }
+
+    pub fn try_create_match(
+        &mut self,
+        start_time: i64,
+        end_time: i64,
+    ) -> std::result::Result<Pubkey, ProgramError> {
+        let listing = self.listing(start_time, None);
+        self.try_create_listed_match(start_time, end_time, listing)
+    }
+
+    /// Create a match with a fresh id, listed on the pages of `listing`
+    pub fn try_create_listed_match(
+        &mut self,
+        start_time: i64,
+        end_time: i64,
+        listing: Listing,
+    ) -> std::result::Result<Pubkey, ProgramError> {
+        let admin = self.admin;
+        self.send_create_match(&admin, start_time, end_time, listing, 0)
+    }
+
+    /// Create a community market with a fresh id as `creator`, starting in
+    /// `starts_in` seconds and lasting `duration`
+    pub fn create_community_match(
+        &mut self,
+        creator: &Pubkey,
+        creator_fee: u16,
+        starts_in: i64,
+        duration: i64,
+    ) -> std::result::Result<Pubkey, ProgramError> {
+        let start_time = self.harness.now() + starts_in;
+        let listing = self.listing(start_time, None);
+        self.send_create_match(
+            creator,
+            start_time,
+            start_time + duration,
+            listing,
+            creator_fee,
+        )
+    }
+
+    fn send_create_match(
+        &mut self,
+        authority: &Pubkey,
+        start_time: i64,
+        end_time: i64,
+        listing: Listing,
+        creator_fee: u16,
+    ) -> std::result::Result<Pubkey, ProgramError> {
+        let match_id = Pubkey::new_unique();
+        self.harness.send(
+            accounts::CreateMatch {
+                match_state: match_pda(&match_id),
+                global_state: platform_pda(),
+                match_id,
+                authority: *authority,
+                system_program: system_program::ID,
+                registry: listing.registry,
+                status_index: listing.status_index,
+                start_day_index: listing.start_day_index,
+                league_index: listing.league_index,
+            },
+            ix::CreateMatch {
+                team1: "India".to_string(),
+                team2: "Australia".to_string(),
+                start_time,
+                end_time,
+                description: "Test match".to_string(),
+                creator_fee,
+            },
+            &[*authority],
+        )?;
+        Ok(match_id)
+    }
+
+    /// Create a totals or spread market with a fresh id, starting in `starts_in`
+    /// seconds and lasting `duration`
+    pub fn create_line_market(
+        &mut self,
+        market_kind: MarketKind,
+        line: i32,
+        starts_in: i64,
+        duration: i64,
+    ) -> std::result::Result<Pubkey, ProgramError> {
+        let match_id = Pubkey::new_unique();
+        let start_time = self.harness.now() + starts_in;
+        let listing = self.listing(start_time, None);
+        self.harness.send(
+            accounts::CreateMatch {
+                match_state: match_pda(&match_id),
+                global_state: platform_pda(),
+                match_id,
+                authority: self.admin,
+                system_program: system_program::ID,
+                registry: listing.registry,
+                status_index: listing.status_index,
+                start_day_index: listing.start_day_index,
+                league_index: listing.league_index,
+            },
+            ix::CreateLineMarket {
+                team1: "India".to_string(),
+                team2: "Australia".to_string(),
+                market_kind,
+                line,
+                start_time,
+                end_time: start_time + duration,
+                description: "Test line market".to_string(),
+                creator_fee: 0,
+            },
+            &[self.admin],
+        )?;
+        Ok(match_id)
+    }
+
+    /// Create a price market on `price_feed` with a fresh id, starting in
+    /// `starts_in` seconds and resolving `duration` seconds later
+    pub fn create_price_market(
+        &mut self,
+        price_feed: &Pubkey,
+        strike_price: i64,
+        starts_in: i64,
+        duration: i64,
+    ) -> std::result::Result<Pubkey, ProgramError> {
+        let admin = self.admin;
+        self.create_community_price_market(&admin, 0, price_feed, strike_price, starts_in, duration)
+    }
+
+    /// Create a price market on `price_feed` with a fresh id as `creator`, starting
+    /// in `starts_in` seconds and resolving `duration` seconds later
+    pub fn create_community_price_market(
+        &mut self,
+        creator: &Pubkey,
+        creator_fee: u16,
+        price_feed: &Pubkey,
+        strike_price: i64,
+        starts_in: i64,
+        duration: i64,
+    ) -> std::result::Result<Pubkey, ProgramError> {
+        let match_id = Pubkey::new_unique();
+        let start_time = self.harness.now() + starts_in;
+        let listing = self.listing(start_time, None);
+        self.harness.send(
+            accounts::CreatePriceMarket {
+                match_state: match_pda(&match_id),
+                price_market: price_market_pda(&match_id),
+                global_state: platform_pda(),
+                price_feed: *price_feed,
+                match_id,
+                authority: *creator,
+                system_program: system_program::ID,
+                registry: listing.registry,
+                status_index: listing.status_index,
+                start_day_index: listing.start_day_index,
+                league_index: listing.league_index,
+            },
+            ix::CreatePriceMarket {
+                asset: "SOL/USD".to_string(),
+                strike_price,
+                start_time,
+                end_time: start_time + duration,
+                description: "SOL above strike".to_string(),
+                creator_fee,
+            },
+            &[*creator],
+        )?;
+        Ok(match_id)
+    }
+
+    /// Resolve a price market from `price_feed`, signed by `resolver`
+    pub fn resolve_price_market(
+        &mut self,
+        match_id: &Pubkey,
+        price_feed: &Pubkey,
+        resolver: &Pubkey,
+    ) -> TxResult {
+        let (old_status_index, new_status_index) = self.status_move(match_id, MatchStatus::Settled);
+        self.harness.send(
+            accounts::ResolvePriceMarket {
+                match_state: match_pda(match_id),
+                price_market: price_market_pda(match_id),
+                global_state: platform_pda(),
+                price_feed: *price_feed,
+                match_id: *match_id,
+                resolver: *resolver,
+                old_status_index,
+                new_status_index,
+            },
+            ix::ResolvePriceMarket {},
+            &[*resolver],
+        )
+    }
+
+    pub fn update_match_status(&mut self, match_id: &Pubkey, status: MatchStatus) -> TxResult {
+        let (old_status_index, new_status_index) = self.status_move(match_id, status.clone());
+        let audit_log = self.audit_log();
+        self.harness.send(
+            accounts::UpdateMatchStatus {
+                match_state: match_pda(match_id),
+                global_state: platform_pda(),
+                match_id: *match_id,
+                admin: self.admin,
+                old_status_index,
+                new_status_index,
+                audit_log,
+            },
+            ix::UpdateMatchStatus { status },
+            &[self.admin],
+        )
+    }
+
+    pub fn close_match_betting(&mut self, match_id: &Pubkey) -> TxResult {
+        let audit_log = self.audit_log();
+        self.harness.send(
+            accounts::CloseMatchBetting {
+                match_state: match_pda(match_id),
+                global_state: platform_pda(),
+                match_id: *match_id,
+                admin: self.admin,
+                audit_log,
+            },
+            ix::CloseMatchBetting {},
+            &[self.admin],
+        )
+    }
+
+    /// Advance `match_id` to the status implied by the clock, signed by `cranker`
+    pub fn crank_match(&mut self, match_id: &Pubkey, cranker: &Pubkey) -> TxResult {
+        let match_state = self.harness.state::<MatchState>(&match_pda(match_id));
+        let new_status = match_state
+            .crank_status(self.harness.now())
+            .unwrap_or(match_state.status);
+        let (old_status_index, new_status_index) = self.status_move(match_id, new_status);
+        self.harness.send(
+            accounts::CrankMatch {
+                match_state: match_pda(match_id),
+                global_state: platform_pda(),
+                treasury: treasury_pda(),
+                treasury_account: treasury_pda(),
+                match_id: *match_id,
+                cranker: *cranker,
+                old_status_index,
+                new_status_index,
+            },
+            ix::CrankMatch {},
+            &[*cranker],
+        )
+    }
+
+    /// Void `match_id` after its oracle deadline, signed by `caller`
+    pub fn void_match(&mut self, match_id: &Pubkey, caller: &Pubkey) -> TxResult {
+        let (old_status_index, new_status_index) = self.status_move(match_id, MatchStatus::Voided);
+        self.harness.send(
+            accounts::VoidMatch {
+                match_state: match_pda(match_id),
+                match_id: *match_id,
+                authority: *caller,
+                old_status_index,
+                new_status_index,
+            },
+            ix::VoidMatch {},
+            &[*caller],
+        )
+    }
+
+    pub fn settle_creator(&mut self, match_id: &Pubkey, caller: &Pubkey) -> TxResult {
+        let creator = self
+            .harness
+            .state::<MatchState>(&match_pda(match_id))
+            .creator;
+        self.harness.send(
+            accounts::SettleCreator {
+                match_state: match_pda(match_id),
+                treasury: treasury_pda(),
+                treasury_account: treasury_pda(),
+                creator,
+                match_id: *match_id,
+                authority: *caller,
+            },
+            ix::SettleCreator {},
+            &[*caller],
+        )
+    }
+
+    pub fn void_market_for_abuse(&mut self, match_id: &Pubkey, caller: &Pubkey) -> TxResult {
+        let (old_status_index, new_status_index) = self.status_move(match_id, MatchStatus::Voided);
+        let audit_log = self.audit_log();
+        self.harness.send(
+            accounts::VoidMarketForAbuse {
+                match_state: match_pda(match_id),
+                global_state: platform_pda(),
+                treasury: treasury_pda(),
+                treasury_account: treasury_pda(),
+                match_id: *match_id,
+                admin: *caller,
+                old_status_index,
+                new_status_index,
+                audit_log,
+            },
+            ix::VoidMarketForAbuse {},
+            &[*caller],
+        )
+    }
+
+    /// Make `match_id` private as `organiser`, inviting `members` and holders of `invite_code`
+    pub fn create_private_pool(
+        &mut self,
+        organiser: &Pubkey,
+        match_id: &Pubkey,
+        members: &[Pubkey],
+        invite_code: Option<&str>,
+        waives_house_edge: bool,
+    ) -> TxResult {
+        self.harness.send(
+            accounts::CreatePrivatePool {
+                private_pool: private_pool_pda(match_id),
+                match_state: match_pda(match_id),
+                global_state: platform_pda(),
+                match_id: *match_id,
+                organiser: *organiser,
+                system_program: system_program::ID,
+            },
+            ix::CreatePrivatePool {
+                invite_code_hash: invite_code
+                    .map(|code| PrivatePool::invite_code_hash(match_id, code)),
+                members: members.to_vec(),
+                waives_house_edge,
+            },
+            &[*organiser],
+        )
+    }
+
+    pub fn update_private_pool(
+        &mut self,
+        organiser: &Pubkey,
+        match_id: &Pubkey,
+        members: &[Pubkey],
+        invite_code: Option<&str>,
+    ) -> TxResult {
+        self.harness.send(
+            accounts::UpdatePrivatePool {
+                private_pool: private_pool_pda(match_id),
+                match_id: *match_id,
+                organiser: *organiser,
+            },
+            ix::UpdatePrivatePool {
+                invite_code_hash: invite_code
+                    .map(|code| PrivatePool::invite_code_hash(match_id, code)),
+                members: members.to_vec(),
+            },
+            &[*organiser],
+        )
+    }
+
+    pub fn join_private_pool(
+        &mut self,
+        bettor: &Pubkey,
+        match_id: &Pubkey,
+        invite_code: &str,
+    ) -> TxResult {
+        self.harness.send(
+            accounts::JoinPrivatePool {
+                private_pool: private_pool_pda(match_id),
+                match_id: *match_id,
+                bettor: *bettor,
+            },
+            ix::JoinPrivatePool {
+                invite_code: invite_code.to_string(),
+            },
+            &[*bettor],
+        )
+    }
+
+    pub fn close_private_pool_betting(
+        &mut self,
+        organiser: &Pubkey,
+        match_id: &Pubkey,
+    ) -> TxResult {
+        self.harness.send(
+            accounts::ClosePrivatePoolBetting {
+                private_pool: private_pool_pda(match_id),
+                match_state: match_pda(match_id),
+                match_id: *match_id,
+                organiser: *organiser,
+            },
+            ix::ClosePrivatePoolBetting {},
+            &[*organiser],
+        )
+    }
+
+    pub fn cancel_private_pool(&mut self, organiser: &Pubkey, match_id: &Pubkey) -> TxResult {
+        let (old_status_index, new_status_index) =
+            self.status_move(match_id, MatchStatus::Cancelled);
+        self.harness.send(
+            accounts::CancelPrivatePool {
+                private_pool: private_pool_pda(match_id),
+                match_state: match_pda(match_id),
+                match_id: *match_id,
+                organiser: *organiser,
+                old_status_index,
+                new_status_index,
+            },
+            ix::CancelPrivatePool {},
+            &[*organiser],
+        )
+    }
+
+    pub fn place_bet(
+        &mut self,
+        bettor: &Pubkey,
+        match_id: &Pubkey,
+        amount: u64,
+        predicted_team: u8,
+    ) -> TxResult {
+        self.place_bet_as(bettor, match_id, amount, predicted_team, bettor, None)
+    }
+
+    /// Place `bettor`'s bet signed by `authority`, through `session` if given
+    pub fn place_bet_as(
+        &mut self,
+        bettor: &Pubkey,
+        match_id: &Pubkey,
+        amount: u64,
+        predicted_team: u8,
+        authority: &Pubkey,
+        session: Option<Pubkey>,
+    ) -> TxResult {
+        self.send_place_bet(
+            bettor,
+            match_id,
+            amount,
+            predicted_team,
+            authority,
+            session,
+            false,
+        )
+    }
+
+    /// Place a bet staked from the bettor's bonus balance first
+    pub fn place_bonus_bet(
+        &mut self,
+        bettor: &Pubkey,
+        match_id: &Pubkey,
+        amount: u64,
+        predicted_team: u8,
+    ) -> TxResult {
+        self.send_place_bet(bettor, match_id, amount, predicted_team, bettor, None, true)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn send_place_bet(
+        &mut self,
+        bettor: &Pubkey,
+        match_id: &Pubkey,
+        amount: u64,
+        predicted_team: u8,
+        authority: &Pubkey,
+        session: Option<Pubkey>,
+        use_bonus: bool,
+    ) -> TxResult {
+        self.harness.send(
+            accounts::PlaceBet {
+                bet_state: bet_pda(bettor, match_id),
+                user_state: user_pda(bettor),
+                match_state: match_pda(match_id),
+                global_state: platform_pda(),
+                treasury: treasury_pda(),
+                match_id: *match_id,
+                bettor: *bettor,
+                authority: *authority,
+                system_program: system_program::ID,
+                session,
+                private_pool: self.existing(private_pool_pda(match_id)),
+                odds_history: self.existing(odds_history_pda(match_id)),
+            },
+            ix::PlaceBet {
+                amount,
+                predicted_team,
+                odds_accepted: 0,
+                use_bonus,
+            },
+            &[*authority],
+        )
+    }
+
+    pub fn cancel_bet(
+        &mut self,
+        bettor: &Pubkey,
+        match_id: &Pubkey,
+        authority: &Pubkey,
+    ) -> TxResult {
+        self.cancel_bet_as(bettor, match_id, authority, None)
+    }
+
+    /// Cancel `bettor`'s bet signed by `authority`, through `session` if given
+    pub fn cancel_bet_as(
+        &mut self,
+        bettor: &Pubkey,
+        match_id: &Pubkey,
+        authority: &Pubkey,
+        session: Option<Pubkey>,
+    ) -> TxResult {
+        self.harness.send(
+            accounts::CancelBet {
+                bet_state: bet_pda(bettor, match_id),
+                user_state: user_pda(bettor),
+                match_state: match_pda(match_id),
+                treasury: treasury_pda(),
+                match_id: *match_id,
+                bettor: *bettor,
+                authority: *authority,
+                session,
+                odds_history: self.existing(odds_history_pda(match_id)),
+            },
+            ix::CancelBet {},
+            &[*authority],
+        )
+    }
+
+    /// Change `bettor`'s bet to `amount` on `predicted_team`, signed by the bettor
+    pub fn modify_bet(
+        &mut self,
+        bettor: &Pubkey,
+        match_id: &Pubkey,
+        amount: u64,
+        predicted_team: u8,
+    ) -> TxResult {
+        self.modify_bet_as(bettor, match_id, amount, predicted_team, bettor, None)
+    }
+
+    /// Change `bettor`'s bet signed by `authority`, through `session` if given
+    pub fn modify_bet_as(
+        &mut self,
+        bettor: &Pubkey,
+        match_id: &Pubkey,
+        amount: u64,
+        predicted_team: u8,
+        authority: &Pubkey,
+        session: Option<Pubkey>,
+    ) -> TxResult {
+        self.harness.send(
+            accounts::ModifyBet {
+                bet_state: bet_pda(bettor, match_id),
+                user_state: user_pda(bettor),
+                match_state: match_pda(match_id),
+                global_state: platform_pda(),
+                treasury: treasury_pda(),
+                match_id: *match_id,
+                bettor: *bettor,
+                authority: *authority,
+                session,
+                private_pool: self.existing(private_pool_pda(match_id)),
+                odds_history: self.existing(odds_history_pda(match_id)),
+            },
+            ix::ModifyBet {
+                amount,
+                predicted_team,
+                odds_accepted: 0,
+                use_bonus: false,
+            },
+            &[*authority],
+        )
+    }
+
+    /// Settle `bettor`'s bet, signed by an arbitrary `caller`
+    pub fn settle_bet(&mut self, bettor: &Pubkey, match_id: &Pubkey, caller: &Pubkey) -> TxResult {
+        self.harness.send(
+            accounts::SettleBet {
+                bet_state: bet_pda(bettor, match_id),
+                user_state: user_pda(bettor),
+                match_state: match_pda(match_id),
+                global_state: platform_pda(),
+                treasury: treasury_pda(),
+                match_id: *match_id,
+                bettor: *bettor,
+                authority: *caller,
+            },
+            ix::SettleBet {},
+            &[*caller],
+        )
+    }
+
+    pub fn refund_bet(&mut self, bettor: &Pubkey, match_id: &Pubkey, caller: &Pubkey) -> TxResult {
+        self.harness.send(
+            accounts::RefundBet {
+                bet_state: bet_pda(bettor, match_id),
+                user_state: user_pda(bettor),
+                match_state: match_pda(match_id),
+                treasury: treasury_pda(),
+                match_id: *match_id,
+                bettor: *bettor,
+                authority: *caller,
+            },
+            ix::RefundBet {},
+            &[*caller],
+        )
+    }
+
+    pub fn claim_winnings(&mut self, bettor: &Pubkey, match_id: &Pubkey) -> TxResult {
+        self.harness.send(
+            accounts::ClaimWinnings {
+                bet_state: bet_pda(bettor, match_id),
+                user_state: user_pda(bettor),
+                treasury: treasury_pda(),
+                match_id: *match_id,
+                bettor: *bettor,
+                authority: *bettor,
+            },
+            ix::ClaimWinnings {},
+            &[*bettor],
+        )
+    }
+
+    /// Fund a fresh oracle, bond the minimum stake and register it for `match_id`
+    pub fn register_oracle(&mut self, match_id: &Pubkey) -> Pubkey {
+        let oracle_authority = self.oracle(MIN_ORACLE_STAKE);
+        self.register_oracle_authority(&oracle_authority, match_id)
+            .expect("oracle registration succeeds");
+        oracle_authority
+    }
+
+    /// Fund a fresh oracle with a profile holding `stake`
+    pub fn oracle(&mut self, stake: u64) -> Pubkey {
+        let oracle_authority = self.harness.fund(10 * SOL);
+        self.create_oracle_profile(&oracle_authority)
+            .expect("oracle profile creation succeeds");
+        if stake > 0 {
+            self.bond_oracle_stake(&oracle_authority, stake)
+                .expect("oracle bond succeeds");
+        }
+        oracle_authority
+    }
+
+    pub fn register_oracle_authority(
+        &mut self,
+        oracle_authority: &Pubkey,
+        match_id: &Pubkey,
+    ) -> TxResult {
+        let audit_log = self.audit_log();
+        self.harness.send(
+            accounts::RegisterOracle {
+                oracle_state: oracle_pda(oracle_authority, match_id),
+                global_state: platform_pda(),
+                match_state: match_pda(match_id),
+                oracle_profile: oracle_profile_pda(oracle_authority),
+                oracle_authority: *oracle_authority,
+                match_id: *match_id,
+                admin: self.admin,
+                authority: self.admin,
+                system_program: system_program::ID,
+                audit_log,
+            },
+            ix::RegisterOracle {
+                oracle_authority: *oracle_authority,
+            },
+            &[self.admin],
+        )
+    }
+
+    pub fn create_oracle_profile(&mut self, oracle_authority: &Pubkey) -> TxResult {
+        self.harness.send(
+            accounts::CreateOracleProfile {
+                oracle_profile: oracle_profile_pda(oracle_authority),
+                authority: *oracle_authority,
+                system_program: system_program::ID,
+            },
+            ix::CreateOracleProfile {},
+            &[*oracle_authority],
+        )
+    }
+
+    pub fn bond_oracle_stake(&mut self, oracle_authority: &Pubkey, amount: u64) -> TxResult {
+        self.harness.send(
+            accounts::BondOracleStake {
+                oracle_profile: oracle_profile_pda(oracle_authority),
+                authority: *oracle_authority,
+                system_program: system_program::ID,
+            },
+            ix::BondOracleStake { amount },
+            &[*oracle_authority],
+        )
+    }
+
+    pub fn unbond_oracle_stake(&mut self, oracle_authority: &Pubkey, amount: u64) -> TxResult {
+        self.harness.send(
+            accounts::UnbondOracleStake {
+                oracle_profile: oracle_profile_pda(oracle_authority),
+                authority: *oracle_authority,
+            },
+            ix::UnbondOracleStake { amount },
+            &[*oracle_authority],
+        )
+    }
+
+    pub fn withdraw_oracle_stake(&mut self, oracle_authority: &Pubkey) -> TxResult {
+        self.harness.send(
+            accounts::WithdrawOracleStake {
+                oracle_profile: oracle_profile_pda(oracle_authority),
+                authority: *oracle_authority,
+            },
+            ix::WithdrawOracleStake {},
+            &[*oracle_authority],
+        )
+    }
+
+    /// Reward or slash an oracle's report on a resolved match, signed by `caller`
+    pub fn resolve_oracle_report(
+        &mut self,
+        oracle_authority: &Pubkey,
+        match_id: &Pubkey,
+        caller: &Pubkey,
+    ) -> TxResult {
+        self.harness.send(
+            accounts::ResolveOracleReport {
+                oracle_state: oracle_pda(oracle_authority, match_id),
+                oracle_profile: oracle_profile_pda(oracle_authority),
+                match_state: match_pda(match_id),
+                treasury: treasury_pda(),
+                treasury_account: treasury_pda(),
+                oracle_authority: *oracle_authority,
+                match_id: *match_id,
+                authority: *caller,
+            },
+            ix::ResolveOracleReport {},
+            &[*caller],
+        )
+    }
+
+    /// Commit and reveal a report from a single oracle, moving the clock into
+    /// the reveal window in between
+    pub fn report_result(
+        &mut self,
+        oracle_authority: &Pubkey,
+        match_id: &Pubkey,
+        winning_team: u8,
+        final_score: &str,
+    ) -> TxResult {
+        self.report_score(oracle_authority, match_id, winning_team, final_score, None)
+    }
+
+    /// Commit and reveal a result together with its structured score
+    pub fn report_score(
+        &mut self,
+        oracle_authority: &Pubkey,
+        match_id: &Pubkey,
+        winning_team: u8,
+        final_score: &str,
+        match_score: Option<MatchScore>,
+    ) -> TxResult {
+        self.commit_result(
+            oracle_authority,
+            match_id,
+            winning_team,
+            final_score,
+            match_score.clone(),
+        )?;
+        self.open_reveals(match_id);
+        self.reveal_result(
+            oracle_authority,
+            match_id,
+            winning_team,
+            final_score,
+            match_score,
+        )
+    }
+
+    /// Commit to a report salted with `oracle_salt`
+    pub fn commit_result(
+        &mut self,
+        oracle_authority: &Pubkey,
+        match_id: &Pubkey,
+        winning_team: u8,
+        final_score: &str,
+        match_score: Option<MatchScore>,
+    ) -> TxResult {
+        let commitment = OracleState::commitment(
+            oracle_authority,
+            match_id,
+            winning_team,
+            final_score,
+            &match_score,
+            &oracle_salt(oracle_authority),
+        )
+        .expect("commitment hashes");
+        self.commit(oracle_authority, match_id, commitment)
+    }
+
+    pub fn commit(
+        &mut self,
+        oracle_authority: &Pubkey,
+        match_id: &Pubkey,
+        commitment: [u8; 32],
+    ) -> TxResult {
+        self.harness.send(
+            accounts::CommitMatchResult {
+                oracle_state: oracle_pda(oracle_authority, match_id),
+                match_state: match_pda(match_id),
+                global_state: platform_pda(),
+                oracle_authority: *oracle_authority,
+                match_id: *match_id,
+                authority: *oracle_authority,
+            },
+            ix::CommitMatchResult { commitment },
+            &[*oracle_authority],
+        )
+    }
+
+    /// Reveal a report committed with `commit_result`
+    pub fn reveal_result(
+        &mut self,
+        oracle_authority: &Pubkey,
+        match_id: &Pubkey,
+        winning_team: u8,
+        final_score: &str,
+        match_score: Option<MatchScore>,
+    ) -> TxResult {
+        let salt = oracle_salt(oracle_authority);
+        self.reveal(
+            oracle_authority,
+            match_id,
+            winning_team,
+            final_score,
+            match_score,
+            salt,
+            None,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn reveal(
+        &mut self,
+        oracle_authority: &Pubkey,
+        match_id: &Pubkey,
+        winning_team: u8,
+        final_score: &str,
+        match_score: Option<MatchScore>,
+        salt: [u8; 32],
+        evidence: Option<ReportEvidence>,
+    ) -> TxResult {
+        self.harness.send(
+            accounts::RevealMatchResult {
+                oracle_state: oracle_pda(oracle_authority, match_id),
+                match_state: match_pda(match_id),
+                global_state: platform_pda(),
+                oracle_authority: *oracle_authority,
+                match_id: *match_id,
+                authority: *oracle_authority,
+            },
+            ix::RevealMatchResult {
+                winning_team,
+                final_score: final_score.to_string(),
+                match_score,
+                salt,
+                evidence,
+            },
+            &[*oracle_authority],
+        )
+    }
+
+    /// Move the clock past the commit window of `match_id`, if it is not already
+    pub fn open_reveals(&mut self, match_id: &Pubkey) {
+        let end_time = self
+            .harness
+            .state::<MatchState>(&match_pda(match_id))
+            .end_time;
+        let reveals_open = end_time + ORACLE_COMMIT_WINDOW + 1;
+        if self.harness.now() < reveals_open {
+            self.harness.warp_to(reveals_open);
+        }
+    }
+
+    /// Validate `oracle_authority`'s report, counting `peers` as confirmations
+    pub fn validate_oracle(
+        &mut self,
+        oracle_authority: &Pubkey,
+        match_id: &Pubkey,
+        peers: &[Pubkey],
+    ) -> TxResult {
+        // A pushed line market is voided, any other result settles the match
+        let new_status = match self
+            .harness
+            .state::<OracleState>(&oracle_pda(oracle_authority, match_id))
+            .reported_result
+        {
+            Some(PUSH_RESULT) => MatchStatus::Voided,
+            _ => MatchStatus::Settled,
+        };
+        let (old_status_index, new_status_index) = self.status_move(match_id, new_status);
+        let audit_log = self.audit_log();
+        let mut metas = accounts::ValidateOracleUpdate {
+            oracle_state: oracle_pda(oracle_authority, match_id),
+            match_state: match_pda(match_id),
+            global_state: platform_pda(),
+            oracle_authority: *oracle_authority,
+            match_id: *match_id,
+            admin: self.admin,
+            old_status_index,
+            new_status_index,
+            audit_log,
+        }
+        .to_account_metas(None);
+        metas.extend(
+            peers
+                .iter()
+                .map(|peer| AccountMeta::new_readonly(oracle_pda(peer, match_id), false)),
+        );
+        self.harness.process(
+            Instruction {
+                program_id: trendxbet::ID,
+                accounts: metas,
+                data: ix::ValidateOracleUpdate {}.data(),
+            },
+            &[self.admin],
+        )
+    }
+
+    /// Settle an ended match on `winning_team`, reported by two fresh oracles
+    pub fn resolve_match(&mut self, match_id: &Pubkey, winning_team: u8) {
+        self.resolve_match_with_score(match_id, winning_team, None);
+    }
+
+    /// Settle an ended match on `winning_team` and `match_score`, reported by two fresh oracles,
+    /// and wait out the dispute window
+    pub fn resolve_match_with_score(
+        &mut self,
+        match_id: &Pubkey,
+        winning_team: u8,
+        match_score: Option<MatchScore>,
+    ) {
+        self.resolve_match_in_window(match_id, winning_team, match_score);
+        self.close_dispute_window(match_id);
+    }
+
+    /// Settle an ended match like `resolve_match_with_score`, leaving the dispute window open
+    pub fn resolve_match_in_window(
+        &mut self,
+        match_id: &Pubkey,
+        winning_team: u8,
+        match_score: Option<MatchScore>,
+    ) {
+        let oracle_a = self.register_oracle(match_id);
+        let oracle_b = self.register_oracle(match_id);
+        for oracle in [oracle_a, oracle_b] {
+            self.commit_result(
+                &oracle,
+                match_id,
+                winning_team,
+                "final",
+                match_score.clone(),
+            )
+            .expect("oracle commit succeeds");
+        }
+        self.open_reveals(match_id);
+        for oracle in [oracle_a, oracle_b] {
+            self.reveal_result(
+                &oracle,
+                match_id,
+                winning_team,
+                "final",
+                match_score.clone(),
+            )
+            .expect("oracle reveal succeeds");
+        }
+        self.validate_oracle(&oracle_a, match_id, &[oracle_b])
+            .expect("oracle validation succeeds");
+    }
+
+    /// Move the clock to the end of `match_id`'s dispute window if it is still open
+    pub fn close_dispute_window(&mut self, match_id: &Pubkey) {
+        let deadline = self
+            .harness
+            .state::<MatchState>(&match_pda(match_id))
+            .dispute_deadline;
+        if self.harness.now() < deadline {
+            self.harness.warp_to(deadline);
+        }
+    }
+
+    pub fn set_dispute_terms(&mut self, dispute_window: i64, dispute_bond: u64) -> TxResult {
+        let audit_log = self.audit_log();
+        self.harness.send(
+            accounts::UpdatePlatformConfig {
+                global_state: platform_pda(),
+                admin: self.admin,
+                audit_log,
+            },
+            ix::UpdatePlatformConfig {
+                house_edge: None,
+                min_bet_amount: None,
+                max_bet_amount: None,
+                crank_bounty: None,
+                price_feed_program: None,
+                dispute_window: Some(dispute_window),
+                dispute_bond: Some(dispute_bond),
+                betting_cutoff: None,
+                early_bet_bonus: None,
+                creator_bond: None,
+                max_creator_fee: None,
+                cancellation_fee: None,
+            },
+            &[self.admin],
+        )
+    }
+
+    pub fn set_betting_defaults(&mut self, betting_cutoff: u32, early_bet_bonus: u16) -> TxResult {
+        let audit_log = self.audit_log();
+        self.harness.send(
+            accounts::UpdatePlatformConfig {
+                global_state: platform_pda(),
+                admin: self.admin,
+                audit_log,
+            },
+            ix::UpdatePlatformConfig {
+                house_edge: None,
+                min_bet_amount: None,
+                max_bet_amount: None,
+                crank_bounty: None,
+                price_feed_program: None,
+                dispute_window: None,
+                dispute_bond: None,
+                betting_cutoff: Some(betting_cutoff),
+                early_bet_bonus: Some(early_bet_bonus),
+                creator_bond: None,
+                max_creator_fee: None,
+                cancellation_fee: None,
+            },
+            &[self.admin],
+        )
+    }
+
+    pub fn set_betting_window(
+        &mut self,
+        match_id: &Pubkey,
+        opens_at: i64,
+        closes_at: i64,
+        early_bet_bonus: u16,
+    ) -> TxResult {
+        let audit_log = self.audit_log();
+        self.harness.send(
+            accounts::SetBettingWindow {
+                match_state: match_pda(match_id),
+                global_state: platform_pda(),
+                match_id: *match_id,
+                admin: self.admin,
+                audit_log,
+            },
+            ix::SetBettingWindow {
+                opens_at,
+                closes_at,
+                early_bet_bonus,
+            },
+            &[self.admin],
+        )
+    }
+
+    pub fn set_exposure_limits(&mut self, limits: ExposureLimits) -> TxResult {
+        let audit_log = self.audit_log();
+        self.harness.send(
+            accounts::UpdateExposureLimits {
+                global_state: platform_pda(),
+                admin: self.admin,
+                audit_log,
+            },
+            ix::UpdateExposureLimits { limits },
+            &[self.admin],
+        )
+    }
+
+    pub fn create_arbiter_set(&mut self, arbiters: &[Pubkey], quorum: u8) -> TxResult {
+        let audit_log = self.audit_log();
+        self.harness.send(
+            accounts::CreateArbiterSet {
+                arbiter_set: arbiter_set_pda(),
+                global_state: platform_pda(),
+                admin: self.admin,
+                system_program: system_program::ID,
+                audit_log,
+            },
+            ix::CreateArbiterSet {
+                arbiters: arbiters.to_vec(),
+                quorum,
+            },
+            &[self.admin],
+        )
+    }
+
+    pub fn update_arbiter_set(&mut self, arbiters: &[Pubkey], quorum: u8) -> TxResult {
+        let audit_log = self.audit_log();
+        self.harness.send(
+            accounts::UpdateArbiterSet {
+                arbiter_set: arbiter_set_pda(),
+                global_state: platform_pda(),
+                admin: self.admin,
+                audit_log,
+            },
+            ix::UpdateArbiterSet {
+                arbiters: arbiters.to_vec(),
+                quorum,
+            },
+            &[self.admin],
+        )
+    }
+
+    pub fn open_dispute(
+        &mut self,
+        challenger: &Pubkey,
+        match_id: &Pubkey,
+        proposed_team: u8,
+    ) -> TxResult {
+        self.harness.send(
+            accounts::OpenDispute {
+                dispute_state: dispute_pda(match_id),
+                match_state: match_pda(match_id),
+                global_state: platform_pda(),
+                arbiter_set: arbiter_set_pda(),
+                match_id: *match_id,
+                challenger: *challenger,
+                system_program: system_program::ID,
+            },
+            ix::OpenDispute { proposed_team },
+            &[*challenger],
+        )
+    }
+
+    /// Vote on `match_id`'s dispute as `arbiter`, refunding `challenger` if it is upheld
+    pub fn vote_dispute(
+        &mut self,
+        arbiter: &Pubkey,
+        match_id: &Pubkey,
+        challenger: &Pubkey,
+        uphold: bool,
+    ) -> TxResult {
+        self.harness.send(
+            accounts::VoteDispute {
+                dispute_state: dispute_pda(match_id),
+                arbiter_set: arbiter_set_pda(),
+                match_state: match_pda(match_id),
+                treasury: treasury_pda(),
+                treasury_account: treasury_pda(),
+                challenger: *challenger,
+                match_id: *match_id,
+                arbiter: *arbiter,
+            },
+            ix::VoteDispute { uphold },
+            &[*arbiter],
+        )
+    }
+
+    /// Expire `match_id`'s undecided dispute as `caller`, refunding `challenger`
+    pub fn expire_dispute(
+        &mut self,
+        match_id: &Pubkey,
+        challenger: &Pubkey,
+        caller: &Pubkey,
+    ) -> TxResult {
+        self.harness.send(
+            accounts::ExpireDispute {
+                dispute_state: dispute_pda(match_id),
+                match_state: match_pda(match_id),
+                challenger: *challenger,
+                match_id: *match_id,
+                authority: *caller,
+            },
+            ix::ExpireDispute {},
+            &[*caller],
+        )
+    }
+
+    pub fn set_creator_terms(&mut self, creator_bond: u64, max_creator_fee: u16) -> TxResult {
+        let audit_log = self.audit_log();
+        self.harness.send(
+            accounts::UpdatePlatformConfig {
+                global_state: platform_pda(),
+                admin: self.admin,
+                audit_log,
+            },
+            ix::UpdatePlatformConfig {
+                house_edge: None,
+                min_bet_amount: None,
+                max_bet_amount: None,
+                crank_bounty: None,
+                price_feed_program: None,
+                dispute_window: None,
+                dispute_bond: None,
+                betting_cutoff: None,
+                early_bet_bonus: None,
+                creator_bond: Some(creator_bond),
+                max_creator_fee: Some(max_creator_fee),
+                cancellation_fee: None,
+            },
+            &[self.admin],
+        )
+    }
+
+    pub fn set_bet_limits(
+        &mut self,
+        min_bet_amount: Option<u64>,
+        max_bet_amount: Option<u64>,
+    ) -> TxResult {
+        let audit_log = self.audit_log();
+        self.harness.send(
+            accounts::UpdatePlatformConfig {
+                global_state: platform_pda(),
+                admin: self.admin,
+                audit_log,
+            },
+            ix::UpdatePlatformConfig {
+                house_edge: None,
+                min_bet_amount,
+                max_bet_amount,
+                crank_bounty: None,
+                price_feed_program: None,
+                dispute_window: None,
+                dispute_bond: None,
+                betting_cutoff: None,
+                early_bet_bonus: None,
+                creator_bond: None,
+                max_creator_fee: None,
+                cancellation_fee: None,
+            },
+            &[self.admin],
+        )
+    }
+
+    pub fn set_cancellation_fee(&mut self, cancellation_fee: u16) -> TxResult {
+        let audit_log = self.audit_log();
+        self.harness.send(
+            accounts::UpdatePlatformConfig {
+                global_state: platform_pda(),
+                admin: self.admin,
+                audit_log,
+            },
+            ix::UpdatePlatformConfig {
+                house_edge: None,
+                min_bet_amount: None,
+                max_bet_amount: None,
+                crank_bounty: None,
+                price_feed_program: None,
+                dispute_window: None,
+                dispute_bond: None,
+                betting_cutoff: None,
+                early_bet_bonus: None,
+                creator_bond: None,
+                max_creator_fee: None,
+                cancellation_fee: Some(cancellation_fee),
+            },
+            &[self.admin],
+        )
+    }
+
+    pub fn set_crank_bounty(&mut self, crank_bounty: u64) -> TxResult {
+        let audit_log = self.audit_log();
+        self.harness.send(
+            accounts::UpdatePlatformConfig {
+                global_state: platform_pda(),
+                admin: self.admin,
+                audit_log,
+            },
+            ix::UpdatePlatformConfig {
+                house_edge: None,
+                min_bet_amount: None,
+                max_bet_amount: None,
+                crank_bounty: Some(crank_bounty),
+                price_feed_program: None,
+                dispute_window: None,
+                dispute_bond: None,
+                betting_cutoff: None,
+                early_bet_bonus: None,
+                creator_bond: None,
+                max_creator_fee: None,
+                cancellation_fee: None,
+            },
+            &[self.admin],
+        )
+    }
+
+    pub fn set_price_feed_program(&mut self, program: Pubkey) -> TxResult {
+        let audit_log = self.audit_log();
+        self.harness.send(
+            accounts::UpdatePlatformConfig {
+                global_state: platform_pda(),
+                admin: self.admin,
+                audit_log,
+            },
+            ix::UpdatePlatformConfig {
+                house_edge: None,
+                min_bet_amount: None,
+                max_bet_amount: None,
+                crank_bounty: None,
+                price_feed_program: Some(program),
+                dispute_window: None,
+                dispute_bond: None,
+                betting_cutoff: None,
+                early_bet_bonus: None,
+                creator_bond: None,
+                max_creator_fee: None,
+                cancellation_fee: None,
+            },
+            &[self.admin],
+        )
+    }
+
+    /// Plant a mock feed account holding `update`
+    pub fn price_feed(&mut self, update: PriceUpdate) -> Pubkey {
+        let key = Pubkey::new_unique();
+        self.set_price(&key, update);
+        key
+    }
+
+    /// Publish `update` to a mock feed account
+    pub fn set_price(&mut self, price_feed: &Pubkey, update: PriceUpdate) {
+        self.harness.set_account(
+            *price_feed,
+            TestAccount {
+                lamports: Rent::default().minimum_balance(mock_price_feed::PRICE_ACCOUNT_SIZE),
+                data: price_account_data(&update),
+                owner: mock_price_feed::ID,
+                executable: false,
+            },
+        );
+    }
+
+    pub fn pause_platform(&mut self) -> TxResult {
+        let audit_log = self.audit_log();
+        self.harness.send(
+            accounts::PausePlatform {
+                global_state: platform_pda(),
+                admin: self.admin,
+                audit_log,
+            },
+            ix::PausePlatform {},
+            &[self.admin],
+        )
+    }
+
+    pub fn unpause_platform(&mut self) -> TxResult {
+        let audit_log = self.audit_log();
+        self.harness.send(
+            accounts::UnpausePlatform {
+                global_state: platform_pda(),
+                admin: self.admin,
+                audit_log,
+            },
+            ix::UnpausePlatform {},
+            &[self.admin],
+        )
+    }
+
+    pub fn withdraw_platform_fees(&mut self, amount: u64) -> TxResult {
+        let audit_log = self.audit_log();
+        self.harness.send(
+            accounts::WithdrawPlatformFees {
+                global_state: platform_pda(),
+                treasury: treasury_pda(),
+                admin: self.admin,
+                treasury_account: treasury_pda(),
+                system_program: system_program::ID,
+                audit_log,
+            },
+            ix::WithdrawPlatformFees { amount },
+            &[self.admin],
+        )
+    }
+
+    pub fn emergency_withdraw(&mut self, amount: u64) -> TxResult {
+        let audit_log = self.audit_log();
+        self.harness.send(
+            accounts::EmergencyWithdraw {
+                global_state: platform_pda(),
+                treasury: treasury_pda(),
+                admin: self.admin,
+                treasury_account: treasury_pda(),
+                system_program: system_program::ID,
+                audit_log,
+            },
+            ix::EmergencyWithdraw { amount },
+            &[self.admin],
+        )
+    }
+
+    pub fn add_promoter(&mut self, promoter: &Pubkey) -> TxResult {
+        let audit_log = self.audit_log();
+        self.harness.send(
+            accounts::AddPromoter {
+                promoter_account: promoter_pda(promoter),
+                global_state: platform_pda(),
+                admin: self.admin,
+                system_program: system_program::ID,
+                audit_log,
+            },
+            ix::AddPromoter {
+                promoter: *promoter,
+            },
+            &[self.admin],
+        )
+    }
+
+    pub fn remove_promoter(&mut self, promoter: &Pubkey) -> TxResult {
+        let audit_log = self.audit_log();
+        self.harness.send(
+            accounts::RemovePromoter {
+                promoter_account: promoter_pda(promoter),
+                global_state: platform_pda(),
+                admin: self.admin,
+                audit_log,
+            },
+            ix::RemovePromoter {},
+            &[self.admin],
+        )
+    }
+
+    /// Credit `user` a bonus of `amount` funded by `promoter`
+    pub fn issue_bonus(
+        &mut self,
+        promoter: &Pubkey,
+        user: &Pubkey,
+        amount: u64,
+        wagering_multiplier: u8,
+    ) -> TxResult {
+        self.harness.send(
+            accounts::IssueBonus {
+                promoter_account: promoter_pda(promoter),
+                user_state: user_pda(user),
+                treasury: treasury_pda(),
+                user: *user,
+                authority: *promoter,
+                treasury_account: treasury_pda(),
+                system_program: system_program::ID,
+            },
+            ix::IssueBonus {
+                amount,
+                wagering_multiplier,
+            },
+            &[*promoter],
+        )
+    }
+
+    /// Upgrade `account` to the current layout, signed by `authority`
+    pub fn migrate_account(&mut self, account: &Pubkey, authority: &Pubkey) -> TxResult {
+        // A legacy platform layout predates the audit log, so there is no page to pass
+        let legacy_platform = self
+            .harness
+            .account(&platform_pda())
+            .is_some_and(|account| {
+                GlobalState::try_deserialize(&mut account.data.as_slice()).is_err()
+            });
+        let audit_log = if legacy_platform {
+            None
+        } else {
+            self.audit_log()
+        };
+        self.harness.send(
+            accounts::MigrateAccount {
+                account: *account,
+                global_state: platform_pda(),
+                admin: *authority,
+                system_program: system_program::ID,
+                audit_log,
+            },
+            ix::MigrateAccount {},
+            &[*authority],
+        )
+    }
+
+    pub fn create_match_registry(&mut self) -> TxResult {
+        self.harness.send(
+            accounts::CreateMatchRegistry {
+                registry: match_registry_pda(),
+                global_state: platform_pda(),
+                admin: self.admin,
+                system_program: system_program::ID,
+            },
+            ix::CreateMatchRegistry {},
+            &[self.admin],
+        )
+    }
+
+    /// Create page `page` of the index `key`, paid by `payer`
+    pub fn create_index_page(&mut self, payer: &Pubkey, key: MatchIndexKey, page: u32) -> TxResult {
+        self.harness.send(
+            accounts::CreateMatchIndexPage {
+                index_page: match_index_pda(&key, page),
+                previous_page: page
+                    .checked_sub(1)
+                    .map(|previous| match_index_pda(&key, previous)),
+                registry: match_registry_pda(),
+                payer: *payer,
+                system_program: system_program::ID,
+            },
+            ix::CreateMatchIndexPage { key, page },
+            &[*payer],
+        )
+    }
+
+    /// Every match listed under `key`, page by page
+    pub fn index(&self, key: MatchIndexKey) -> Vec<Pubkey> {
+        (0..)
+            .map(|page| match_index_pda(&key, page))
+            .take_while(|address| self.harness.account(address).is_some())
+            .flat_map(|address| self.harness.state::<MatchIndexPage>(&address).matches)
+            .collect()
+    }
+
+    /// First page of `key` with room for a match, created by the admin when every page is full
+    pub fn index_page_with_room(&mut self, key: MatchIndexKey) -> Pubkey {
+        for page in 0.. {
+            let address = match_index_pda(&key, page);
+            if self.harness.account(&address).is_none() {
+                self.create_index_page(&self.admin.clone(), key, page)
+                    .expect("index page creation succeeds");
+                return address;
+            }
+            if !self.harness.state::<MatchIndexPage>(&address).is_full() {
+                return address;
+            }
+        }
+        unreachable!("index pages are numbered by u32")
+    }
+
+    /// Pages listing a match starting at `start_time`, or no listing when the registry is missing
+    pub fn listing(&mut self, start_time: i64, league: Option<u32>) -> Listing {
+        if self.harness.account(&match_registry_pda()).is_none() {
+            return Listing::default();
+        }
+        Listing {
+            registry: Some(match_registry_pda()),
+            status_index: Some(
+                self.index_page_with_room(MatchIndexKey::status(&MatchStatus::Scheduled)),
+            ),
+            start_day_index: Some(self.index_page_with_room(MatchIndexKey::start_day(start_time))),
+            league_index: league
+                .map(|league| self.index_page_with_room(MatchIndexKey::League(league))),
+        }
+    }
+
+    /// `address` if the account exists, for optional accounts that must be passed once created
+    fn existing(&self, address: Pubkey) -> Option<Pubkey> {
+        self.harness.account(&address).map(|_| address)
+    }
+
+    pub fn enable_audit_log(&mut self) -> TxResult {
+        self.harness.send(
+            accounts::EnableAuditLog {
+                audit_log: audit_log_pda(0),
+                global_state: platform_pda(),
+                admin: self.admin,
+                system_program: system_program::ID,
+            },
+            ix::EnableAuditLog {},
+            &[self.admin],
+        )
+    }
+
+    /// Create page `page` of the audit log, paid by `payer`
+    pub fn create_audit_log_page(&mut self, payer: &Pubkey, page: u32) -> TxResult {
+        self.harness.send(
+            accounts::CreateAuditLogPage {
+                audit_log: audit_log_pda(page),
+                global_state: platform_pda(),
+                payer: *payer,
+                system_program: system_program::ID,
+            },
+            ix::CreateAuditLogPage { page },
+            &[*payer],
+        )
+    }
+
+    /// Page the next privileged action is recorded on once the audit log is enabled,
+    /// created by the admin when the previous page is full
+    pub fn audit_log(&mut self) -> Option<Pubkey> {
+        let global_state: GlobalState = self.harness.state(&platform_pda());
+        if !global_state.has_audit_log {
+            return None;
+        }
+        let page = global_state.audit_page();
+        let address = audit_log_pda(page);
+        if self.harness.account(&address).is_none() {
+            self.create_audit_log_page(&self.admin.clone(), page)
+                .expect("audit log page creation succeeds");
+        }
+        Some(address)
+    }
+
+    /// Start recording the odds history of `match_id`, paid by `payer`
+    pub fn create_odds_history(&mut self, match_id: &Pubkey, payer: &Pubkey) -> TxResult {
+        self.harness.send(
+            accounts::CreateOddsHistory {
+                odds_history: odds_history_pda(match_id),
+                match_state: match_pda(match_id),
+                match_id: *match_id,
+                payer: *payer,
+                system_program: system_program::ID,
+            },
+            ix::CreateOddsHistory {},
+            &[*payer],
+        )
+    }
+
+    /// Read a page of the odds history of `match_id`, skipping the `offset` oldest points
+    pub fn odds_history(&mut self, match_id: &Pubkey, offset: u16) -> Vec<OddsPoint> {
+        self.harness
+            .send(
+                accounts::GetOddsHistory {
+                    odds_history: odds_history_pda(match_id),
+                    match_id: *match_id,
+                },
+                ix::GetOddsHistory { offset },
+                &[],
+            )
+            .unwrap();
+        self.harness.return_data()
+    }
+
+    /// Status pages moving an indexed `match_id` to `new_status`, or `None` when it stays put
+    pub fn status_move(
+        &mut self,
+        match_id: &Pubkey,
+        new_status: MatchStatus,
+    ) -> (Option<Pubkey>, Option<Pubkey>) {
+        let match_state = self.harness.state::<MatchState>(&match_pda(match_id));
+        if !match_state.is_indexed || match_state.status == new_status {
+            return (None, None);
+        }
+
+        let old_key = MatchIndexKey::status(&match_state.status);
+        let old_page = (0..)
+            .map(|page| match_index_pda(&old_key, page))
+            .take_while(|address| self.harness.account(address).is_some())
+            .find(|address| {
+                self.harness
+                    .state::<MatchIndexPage>(address)
+                    .matches
+                    .contains(match_id)
+            });
+        let new_page = self.index_page_with_room(MatchIndexKey::status(&new_status));
+        (old_page, Some(new_page))
+    }
+}
+
+/// Salt the fixture commits every report of `oracle_authority` with
+pub fn oracle_salt(oracle_authority: &Pubkey) -> [u8; 32] {
+    oracle_authority.to_bytes()
+}
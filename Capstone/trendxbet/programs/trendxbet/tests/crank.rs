@@ -0,0 +1,196 @@
+//! Permissionless crank: match status follows the clock, and whoever first advances
+//! it is paid a bounty out of earned platform fees, capped by the match's own fees.
+
+mod common;
+
+use common::*;
+use trendxbet::{
+    GlobalState, MatchBettingClosed, MatchCranked, MatchState, MatchStatus, MatchStatusUpdated,
+    TreasuryState, TrendXBetError, DEFAULT_CRANK_BOUNTY, LIVE_BETTING_WINDOW, MAX_CRANK_BOUNTY,
+};
+
+const HOUR: i64 = 3600;
+
+fn match_state(platform: &Platform, match_id: &anchor_lang::prelude::Pubkey) -> MatchState {
+    platform.harness.state(&match_pda(match_id))
+}
+
+#[test]
+fn crank_follows_the_clock_and_pays_the_bounty() {
+    let mut platform = Platform::new();
+    let alice = platform.user("alice");
+    let bob = platform.user("bob");
+    platform.deposit(&alice, 5 * SOL).unwrap();
+    platform.deposit(&bob, 5 * SOL).unwrap();
+    let cranker = platform.harness.fund(SOL);
+
+    let earlier = platform.create_match(HOUR, HOUR);
+    let match_id = platform.create_match(4 * HOUR, 2 * HOUR);
+    let start_time = match_state(&platform, &match_id).start_time;
+
+    // Nothing to do before the live betting window has run its course
+    assert_error(
+        platform.crank_match(&match_id, &cranker),
+        TrendXBetError::NothingToCrank,
+    );
+
+    // Bounties come out of earned fees, so settle a bet on an earlier match first
+    platform.harness.warp_by(HOUR);
+    platform.place_bet(&bob, &earlier, SOL, 0).unwrap();
+    platform
+        .update_match_status(&earlier, MatchStatus::Live)
+        .unwrap();
+    platform.harness.warp_by(HOUR);
+    platform
+        .update_match_status(&earlier, MatchStatus::Ended)
+        .unwrap();
+    platform.resolve_match(&earlier, 0);
+    platform.settle_bet(&bob, &earlier, &bob).unwrap();
+
+    platform.harness.warp_to(start_time);
+    platform.place_bet(&alice, &match_id, SOL, 0).unwrap();
+    platform
+        .harness
+        .warp_to(start_time + LIVE_BETTING_WINDOW - 1);
+    assert_error(
+        platform.crank_match(&match_id, &cranker),
+        TrendXBetError::NothingToCrank,
+    );
+
+    // Scheduled → Live closes betting and pays the cranker from fees
+    platform.harness.warp_to(start_time + LIVE_BETTING_WINDOW);
+    let fees_before: TreasuryState = platform.harness.state(&treasury_pda());
+    platform.crank_match(&match_id, &cranker).unwrap();
+
+    let state = match_state(&platform, &match_id);
+    assert!(state.status == MatchStatus::Live);
+    assert!(state.is_betting_closed);
+    assert_eq!(
+        platform.harness.lamports(&cranker),
+        SOL + DEFAULT_CRANK_BOUNTY
+    );
+
+    let treasury: TreasuryState = platform.harness.state(&treasury_pda());
+    assert_eq!(
+        treasury.platform_fees,
+        fees_before.platform_fees - DEFAULT_CRANK_BOUNTY
+    );
+    assert_eq!(treasury.total_withdrawals, DEFAULT_CRANK_BOUNTY);
+
+    assert_eq!(platform.harness.events::<MatchBettingClosed>().len(), 1);
+    let updated = &platform.harness.events::<MatchStatusUpdated>()[0];
+    assert_eq!((updated.old_status, updated.new_status), (0, 1));
+    let cranked = &platform.harness.events::<MatchCranked>()[0];
+    assert_eq!(cranked.cranker, cranker);
+    assert_eq!(cranked.bounty, DEFAULT_CRANK_BOUNTY);
+
+    assert_error(
+        platform.place_bet(&bob, &match_id, SOL, 1),
+        TrendXBetError::BettingClosed,
+    );
+    assert_error(
+        platform.crank_match(&match_id, &cranker),
+        TrendXBetError::NothingToCrank,
+    );
+
+    // Live → Ended at end_time, the match having already paid its bounty
+    platform.harness.warp_to(state.end_time);
+    platform.crank_match(&match_id, &cranker).unwrap();
+    assert!(match_state(&platform, &match_id).status == MatchStatus::Ended);
+    assert_eq!(platform.harness.events::<MatchCranked>()[0].bounty, 0);
+    assert_eq!(
+        platform.harness.lamports(&cranker),
+        SOL + DEFAULT_CRANK_BOUNTY
+    );
+    assert_error(
+        platform.crank_match(&match_id, &cranker),
+        TrendXBetError::NothingToCrank,
+    );
+}
+
+#[test]
+fn bounty_is_limited_to_available_fees() {
+    let mut platform = Platform::new();
+    let alice = platform.user("alice");
+    platform.deposit(&alice, 5 * SOL).unwrap();
+    let cranker = platform.harness.fund(SOL);
+    let match_id = platform.create_match(HOUR, 2 * HOUR);
+
+    // Fees on unsettled bets may still be refunded, so they pay no bounty
+    platform.harness.warp_by(HOUR);
+    platform.place_bet(&alice, &match_id, SOL, 0).unwrap();
+    let treasury: TreasuryState = platform.harness.state(&treasury_pda());
+    assert!(treasury.platform_fees > 0);
+    assert_eq!(treasury.available_platform_fees(), 0);
+
+    // A match nobody cranked while live goes straight to Ended, unpaid without fees
+    platform.harness.warp_by(2 * HOUR);
+    platform.crank_match(&match_id, &cranker).unwrap();
+    assert!(match_state(&platform, &match_id).status == MatchStatus::Ended);
+    assert_eq!(platform.harness.events::<MatchCranked>()[0].bounty, 0);
+    assert_eq!(platform.harness.lamports(&cranker), SOL);
+}
+
+#[test]
+fn empty_community_markets_pay_no_bounty() {
+    let mut platform = Platform::new();
+    let bob = platform.user("bob");
+    platform.deposit(&bob, 5 * SOL).unwrap();
+    let cranker = platform.harness.fund(SOL);
+
+    // The platform holds earned fees from a settled bet
+    let earlier = platform.create_match(HOUR, HOUR);
+    platform.harness.warp_by(HOUR);
+    platform.place_bet(&bob, &earlier, SOL, 0).unwrap();
+    platform.harness.warp_by(HOUR);
+    platform.crank_match(&earlier, &cranker).unwrap();
+    platform.resolve_match(&earlier, 0);
+    platform.settle_bet(&bob, &earlier, &bob).unwrap();
+    let treasury: TreasuryState = platform.harness.state(&treasury_pda());
+    assert!(treasury.available_platform_fees() > DEFAULT_CRANK_BOUNTY);
+    let cranker_before = platform.harness.lamports(&cranker);
+
+    // A market nobody bet on was charged no fees, so cranking it pays nothing
+    let creator = platform.harness.fund(5 * SOL);
+    let match_id = platform
+        .create_community_match(&creator, 0, HOUR, HOUR)
+        .unwrap();
+    let start_time = match_state(&platform, &match_id).start_time;
+    platform.harness.warp_to(start_time + LIVE_BETTING_WINDOW);
+    platform.crank_match(&match_id, &cranker).unwrap();
+    assert_eq!(platform.harness.events::<MatchCranked>()[0].bounty, 0);
+    platform
+        .harness
+        .warp_to(match_state(&platform, &match_id).end_time);
+    platform.crank_match(&match_id, &cranker).unwrap();
+    assert_eq!(platform.harness.events::<MatchCranked>()[0].bounty, 0);
+
+    assert_eq!(platform.harness.lamports(&cranker), cranker_before);
+    let after: TreasuryState = platform.harness.state(&treasury_pda());
+    assert_eq!(after.platform_fees, treasury.platform_fees);
+}
+
+#[test]
+fn crank_bounty_is_configurable_up_to_the_cap() {
+    let mut platform = Platform::new();
+
+    assert_error(
+        platform.set_crank_bounty(MAX_CRANK_BOUNTY + 1),
+        TrendXBetError::CrankBountyTooHigh,
+    );
+    platform.set_crank_bounty(MAX_CRANK_BOUNTY).unwrap();
+    let global: GlobalState = platform.harness.state(&platform_pda());
+    assert_eq!(global.crank_bounty, MAX_CRANK_BOUNTY);
+
+    // The admin-driven path keeps working alongside the crank
+    let match_id = platform.create_match(HOUR, 2 * HOUR);
+    platform.harness.warp_by(HOUR);
+    platform
+        .update_match_status(&match_id, MatchStatus::Live)
+        .unwrap();
+    let cranker = platform.harness.fund(SOL);
+    assert_error(
+        platform.crank_match(&match_id, &cranker),
+        TrendXBetError::NothingToCrank,
+    );
+}
@@ -0,0 +1,184 @@
+//! Community markets: a creator bonds to open a market, earns a capped share of
+//! its pool at settlement and forfeits the bond if it is voided for abuse.
+
+mod common;
+
+use anchor_lang::prelude::{Pubkey, Rent};
+use common::*;
+use trendxbet::{
+    BetState, CommunityMarketCreated, CreatorSettled, MarketVoidedForAbuse, MatchState,
+    MatchStatus, TreasuryState, TrendXBetError, BASIS_POINTS, DEFAULT_CREATOR_BOND,
+    DEFAULT_HOUSE_EDGE, DEFAULT_MAX_CREATOR_FEE, MATCH_STATE_SPACE, MAX_CREATOR_FEE,
+};
+
+const HOUR: i64 = 3600;
+
+/// Bet `SOL` from a fresh user on each side of `match_id`, returning the bettors
+fn back_both_sides(platform: &mut Platform, match_id: &Pubkey) -> (Pubkey, Pubkey) {
+    let alice = platform.user("alice");
+    let bob = platform.user("bob");
+    platform.deposit(&alice, 5 * SOL).unwrap();
+    platform.deposit(&bob, 5 * SOL).unwrap();
+    platform.place_bet(&alice, match_id, SOL, 0).unwrap();
+    platform.place_bet(&bob, match_id, SOL, 1).unwrap();
+    (alice, bob)
+}
+
+#[test]
+fn creator_is_paid_the_fee_and_bond_once_the_result_is_final() {
+    let mut platform = Platform::new();
+    let creator = platform.harness.fund(5 * SOL);
+    let creator_fee = 100;
+    let match_id = platform
+        .create_community_match(&creator, creator_fee, HOUR, 2 * HOUR)
+        .unwrap();
+    let event = &platform.harness.events::<CommunityMarketCreated>()[0];
+    assert_eq!(
+        (event.creator, event.creator_bond, event.creator_fee),
+        (creator, DEFAULT_CREATOR_BOND, creator_fee)
+    );
+    assert_eq!(
+        platform.harness.lamports(&match_pda(&match_id)),
+        Rent::default().minimum_balance(MATCH_STATE_SPACE) + DEFAULT_CREATOR_BOND
+    );
+
+    platform.harness.warp_by(HOUR);
+    let (alice, _) = back_both_sides(&mut platform, &match_id);
+    let cranker = platform.harness.fund(SOL);
+    assert_error(
+        platform.settle_creator(&match_id, &cranker),
+        TrendXBetError::InvalidMatchStatus,
+    );
+
+    platform.harness.warp_by(2 * HOUR);
+    platform.crank_match(&match_id, &cranker).unwrap();
+    platform.resolve_match(&match_id, 0);
+
+    // Winners are paid the pool net of both the house edge and the creator fee
+    platform.settle_bet(&alice, &match_id, &alice).unwrap();
+    let edge = (DEFAULT_HOUSE_EDGE + creator_fee) as u64;
+    let bet: BetState = platform.harness.state(&bet_pda(&alice, &match_id));
+    assert_eq!(
+        bet.payout_amount,
+        Some(2 * SOL - 2 * SOL * edge / BASIS_POINTS)
+    );
+
+    let creator_before = platform.harness.lamports(&creator);
+    let treasury_before = platform.harness.lamports(&treasury_pda());
+    platform.settle_creator(&match_id, &cranker).unwrap();
+    let fee = 2 * SOL * creator_fee as u64 / BASIS_POINTS;
+    let event = &platform.harness.events::<CreatorSettled>()[0];
+    assert_eq!(
+        (event.fee_amount, event.bond_refunded),
+        (fee, DEFAULT_CREATOR_BOND)
+    );
+    assert_eq!(
+        platform.harness.lamports(&creator),
+        creator_before + fee + DEFAULT_CREATOR_BOND
+    );
+    assert_eq!(
+        platform.harness.lamports(&treasury_pda()),
+        treasury_before - fee
+    );
+
+    assert_error(
+        platform.settle_creator(&match_id, &cranker),
+        TrendXBetError::CreatorAlreadySettled,
+    );
+}
+
+#[test]
+fn creator_fee_is_capped_by_the_platform() {
+    let mut platform = Platform::new();
+    let admin = platform.admin;
+    let creator = platform.harness.fund(5 * SOL);
+
+    assert_error(
+        platform
+            .create_community_match(&creator, DEFAULT_MAX_CREATOR_FEE + 1, HOUR, 2 * HOUR)
+            .map(|_| ()),
+        TrendXBetError::InvalidCreatorFee,
+    );
+    // Admin markets take no bond and pay no creator
+    assert_error(
+        platform
+            .create_community_match(&admin, 1, HOUR, 2 * HOUR)
+            .map(|_| ()),
+        TrendXBetError::InvalidCreatorFee,
+    );
+    let match_id = platform.create_match(HOUR, 2 * HOUR);
+    let state: MatchState = platform.harness.state(&match_pda(&match_id));
+    assert!(!state.has_creator());
+    assert_eq!(state.creator_bond, 0);
+
+    assert_error(
+        platform.set_creator_terms(SOL, MAX_CREATOR_FEE + 1),
+        TrendXBetError::InvalidCreatorFee,
+    );
+    platform
+        .set_creator_terms(2 * SOL, MAX_CREATOR_FEE)
+        .unwrap();
+    let match_id = platform
+        .create_community_match(&creator, MAX_CREATOR_FEE, HOUR, 2 * HOUR)
+        .unwrap();
+    let state: MatchState = platform.harness.state(&match_pda(&match_id));
+    assert_eq!(
+        (state.creator, state.creator_bond, state.creator_fee),
+        (creator, 2 * SOL, MAX_CREATOR_FEE)
+    );
+}
+
+#[test]
+fn abusive_markets_forfeit_the_bond_and_refund_bettors() {
+    let mut platform = Platform::new();
+    let admin = platform.admin;
+    let creator = platform.harness.fund(5 * SOL);
+    let match_id = platform
+        .create_community_match(&creator, 100, HOUR, 2 * HOUR)
+        .unwrap();
+    platform.harness.warp_by(HOUR);
+    let (alice, bob) = back_both_sides(&mut platform, &match_id);
+
+    assert_error(
+        platform.void_market_for_abuse(&match_id, &creator),
+        TrendXBetError::Unauthorized,
+    );
+    let fees_before = platform
+        .harness
+        .state::<TreasuryState>(&treasury_pda())
+        .platform_fees;
+    platform.void_market_for_abuse(&match_id, &admin).unwrap();
+    let event = &platform.harness.events::<MarketVoidedForAbuse>()[0];
+    assert_eq!(
+        (event.creator, event.bond_forfeited),
+        (creator, DEFAULT_CREATOR_BOND)
+    );
+    let treasury: TreasuryState = platform.harness.state(&treasury_pda());
+    assert_eq!(treasury.platform_fees, fees_before + DEFAULT_CREATOR_BOND);
+    assert_error(
+        platform.settle_creator(&match_id, &creator),
+        TrendXBetError::CreatorAlreadySettled,
+    );
+
+    platform.refund_bet(&alice, &match_id, &alice).unwrap();
+    platform.refund_bet(&bob, &match_id, &bob).unwrap();
+
+    // A market cancelled in good faith returns the bond without a fee
+    let match_id = platform
+        .create_community_match(&creator, 100, HOUR, 2 * HOUR)
+        .unwrap();
+    platform
+        .update_match_status(&match_id, MatchStatus::Cancelled)
+        .unwrap();
+    let creator_before = platform.harness.lamports(&creator);
+    platform.settle_creator(&match_id, &creator).unwrap();
+    let event = &platform.harness.events::<CreatorSettled>()[0];
+    assert_eq!(
+        (event.fee_amount, event.bond_refunded),
+        (0, DEFAULT_CREATOR_BOND)
+    );
+    assert_eq!(
+        platform.harness.lamports(&creator),
+        creator_before + DEFAULT_CREATOR_BOND
+    );
+}
@@ -0,0 +1,360 @@
+//! Bonded disputes of oracle results, decided by the arbiter set before bets settle.
+
+mod common;
+
+use anchor_lang::error::ErrorCode;
+use anchor_lang::prelude::{ProgramError, Pubkey};
+use common::*;
+use trendxbet::{
+    BetState, BetStatus, DisputeExpired, DisputeOpened, DisputeResolved, DisputeState,
+    DisputeStatus, MatchScore, MatchState, OracleReportResolved, TreasuryState, TrendXBetError,
+    DEFAULT_DISPUTE_BOND, DISPUTE_VOTING_PERIOD, MAX_ARBITERS, MAX_DISPUTE_WINDOW,
+};
+
+const HOUR: i64 = 3600;
+
+fn match_state(platform: &Platform, match_id: &Pubkey) -> MatchState {
+    platform.harness.state(&match_pda(match_id))
+}
+
+/// Alice backs team 1 and Bob team 2 on a match the oracles settle on team 1,
+/// leaving the dispute window open
+fn settled_match(platform: &mut Platform) -> (Pubkey, Pubkey, Pubkey) {
+    let alice = platform.user("alice");
+    let bob = platform.user("bob");
+    platform.deposit(&alice, 5 * SOL).unwrap();
+    platform.deposit(&bob, 5 * SOL).unwrap();
+
+    let match_id = platform.create_match(HOUR, 2 * HOUR);
+    platform.harness.warp_by(HOUR);
+    platform.place_bet(&alice, &match_id, SOL, 0).unwrap();
+    platform.place_bet(&bob, &match_id, SOL, 1).unwrap();
+
+    let end_time = match_state(platform, &match_id).end_time;
+    platform.harness.warp_to(end_time);
+    let cranker = platform.harness.fund(SOL);
+    platform.crank_match(&match_id, &cranker).unwrap();
+    platform.resolve_match_in_window(&match_id, 0, None);
+    (match_id, alice, bob)
+}
+
+/// Fund three arbiters and install them with a quorum of two
+fn arbiters(platform: &mut Platform) -> [Pubkey; 3] {
+    let arbiters = [(); 3].map(|_| platform.harness.fund(SOL));
+    platform.create_arbiter_set(&arbiters, 2).unwrap();
+    arbiters
+}
+
+#[test]
+fn upheld_dispute_overturns_the_result_and_returns_the_bond() {
+    let mut platform = Platform::new();
+    let [arbiter_a, arbiter_b, _] = arbiters(&mut platform);
+    let (match_id, alice, bob) = settled_match(&mut platform);
+    let carol = platform.harness.fund(2 * SOL);
+
+    assert_error(
+        platform.settle_bet(&alice, &match_id, &alice),
+        TrendXBetError::DisputeWindowOpen,
+    );
+
+    platform.open_dispute(&carol, &match_id, 1).unwrap();
+    let opened = &platform.harness.events::<DisputeOpened>()[0];
+    assert_eq!(opened.challenger, carol);
+    assert_eq!(opened.bond, DEFAULT_DISPUTE_BOND);
+    assert_eq!((opened.original_team, opened.proposed_team), (0, 1));
+    let carol_after_bond = platform.harness.lamports(&carol);
+    assert!(match_state(&platform, &match_id).is_disputed);
+
+    // A pending dispute blocks settlement even after the window closes
+    platform.close_dispute_window(&match_id);
+    assert_error(
+        platform.settle_bet(&alice, &match_id, &alice),
+        TrendXBetError::ResultUnderDispute,
+    );
+
+    // Only arbiters vote, once each
+    assert_error(
+        platform.vote_dispute(&carol, &match_id, &carol, true),
+        TrendXBetError::NotAnArbiter,
+    );
+    platform
+        .vote_dispute(&arbiter_a, &match_id, &carol, true)
+        .unwrap();
+    assert_error(
+        platform.vote_dispute(&arbiter_a, &match_id, &carol, true),
+        TrendXBetError::AlreadyVoted,
+    );
+    assert!(match_state(&platform, &match_id).is_disputed);
+
+    platform
+        .vote_dispute(&arbiter_b, &match_id, &carol, true)
+        .unwrap();
+    let resolved = &platform.harness.events::<DisputeResolved>()[0];
+    assert!(resolved.upheld);
+    assert_eq!(resolved.winning_team, 1);
+    assert_eq!(
+        platform.harness.lamports(&carol),
+        carol_after_bond + DEFAULT_DISPUTE_BOND
+    );
+    let dispute: DisputeState = platform.harness.state(&dispute_pda(&match_id));
+    assert!(dispute.status == DisputeStatus::Upheld);
+    assert_eq!((dispute.votes_uphold, dispute.votes_reject), (2, 0));
+
+    let state = match_state(&platform, &match_id);
+    assert!(!state.is_disputed);
+    assert_eq!(state.winning_team, Some(1));
+
+    // Bets settle on the overturned result
+    platform.settle_bet(&alice, &match_id, &alice).unwrap();
+    platform.settle_bet(&bob, &match_id, &bob).unwrap();
+    let alice_bet: BetState = platform.harness.state(&bet_pda(&alice, &match_id));
+    assert!(alice_bet.status == BetStatus::Lost);
+    let bob_bet: BetState = platform.harness.state(&bet_pda(&bob, &match_id));
+    assert!(bob_bet.status == BetStatus::Won);
+}
+
+#[test]
+fn oracle_reports_are_resolved_against_the_overturned_result() {
+    let mut platform = Platform::new();
+    let [arbiter_a, arbiter_b, _] = arbiters(&mut platform);
+    let match_id = platform.create_match(HOUR, 2 * HOUR);
+    let [honest_a, honest_b, dissenter] = [(); 3].map(|_| platform.register_oracle(&match_id));
+    platform.harness.warp_by(3 * HOUR);
+    let caller = platform.harness.fund(SOL);
+    platform.crank_match(&match_id, &caller).unwrap();
+
+    // Two oracles agree on a team 1 win, the dissenter reports the team 2 win
+    // the arbiters later uphold
+    let score = |home, away| {
+        Some(MatchScore {
+            home,
+            away,
+            periods: Vec::new(),
+        })
+    };
+    for oracle in [honest_a, honest_b] {
+        platform
+            .commit_result(&oracle, &match_id, 0, "2-1", score(2, 1))
+            .unwrap();
+    }
+    platform
+        .commit_result(&dissenter, &match_id, 1, "1-2", score(1, 2))
+        .unwrap();
+    platform.open_reveals(&match_id);
+    for oracle in [honest_a, honest_b] {
+        platform
+            .reveal_result(&oracle, &match_id, 0, "2-1", score(2, 1))
+            .unwrap();
+    }
+    platform
+        .reveal_result(&dissenter, &match_id, 1, "1-2", score(1, 2))
+        .unwrap();
+    platform
+        .validate_oracle(&honest_a, &match_id, &[honest_b, dissenter])
+        .unwrap();
+
+    let carol = platform.harness.fund(2 * SOL);
+    platform.open_dispute(&carol, &match_id, 1).unwrap();
+    for arbiter in [arbiter_a, arbiter_b] {
+        platform
+            .vote_dispute(&arbiter, &match_id, &carol, true)
+            .unwrap();
+    }
+    let state = match_state(&platform, &match_id);
+    assert!(state.is_result_overturned);
+    assert_eq!(state.match_score, None);
+
+    // Reports are judged on the upheld winner alone, the cleared score aside
+    platform
+        .resolve_oracle_report(&dissenter, &match_id, &caller)
+        .unwrap();
+    assert!(platform.harness.events::<OracleReportResolved>()[0].agreed);
+    platform
+        .resolve_oracle_report(&honest_a, &match_id, &caller)
+        .unwrap();
+    assert!(!platform.harness.events::<OracleReportResolved>()[0].agreed);
+}
+
+#[test]
+fn rejected_dispute_forfeits_the_bond_to_the_treasury() {
+    let mut platform = Platform::new();
+    let [arbiter_a, arbiter_b, arbiter_c] = arbiters(&mut platform);
+    let (match_id, alice, _) = settled_match(&mut platform);
+    let carol = platform.harness.fund(2 * SOL);
+
+    platform.open_dispute(&carol, &match_id, 1).unwrap();
+    let treasury_before = platform.harness.lamports(&treasury_pda());
+    let fees_before = platform
+        .harness
+        .state::<TreasuryState>(&treasury_pda())
+        .platform_fees;
+
+    platform
+        .vote_dispute(&arbiter_a, &match_id, &carol, true)
+        .unwrap();
+    platform
+        .vote_dispute(&arbiter_b, &match_id, &carol, false)
+        .unwrap();
+    platform
+        .vote_dispute(&arbiter_c, &match_id, &carol, false)
+        .unwrap();
+    let resolved = &platform.harness.events::<DisputeResolved>()[0];
+    assert!(!resolved.upheld);
+    assert_eq!(resolved.winning_team, 0);
+
+    assert_eq!(
+        platform.harness.lamports(&treasury_pda()),
+        treasury_before + DEFAULT_DISPUTE_BOND
+    );
+    let treasury: TreasuryState = platform.harness.state(&treasury_pda());
+    assert_eq!(treasury.platform_fees, fees_before + DEFAULT_DISPUTE_BOND);
+    assert_error(
+        platform.vote_dispute(&arbiter_a, &match_id, &carol, false),
+        TrendXBetError::DisputeNotOpen,
+    );
+
+    // The kept result is final at once
+    let state = match_state(&platform, &match_id);
+    assert_eq!(state.winning_team, Some(0));
+    assert_eq!(state.dispute_deadline, platform.harness.now());
+    platform.settle_bet(&alice, &match_id, &alice).unwrap();
+    let bet: BetState = platform.harness.state(&bet_pda(&alice, &match_id));
+    assert!(bet.status == BetStatus::Won);
+}
+
+#[test]
+fn disputes_open_only_within_the_window() {
+    let mut platform = Platform::new();
+    platform.set_dispute_terms(2 * HOUR, SOL).unwrap();
+    assert_error(
+        platform.set_dispute_terms(MAX_DISPUTE_WINDOW + 1, SOL),
+        TrendXBetError::InvalidDisputeWindow,
+    );
+    assert_error(
+        platform.set_dispute_terms(HOUR, 0),
+        TrendXBetError::InvalidDisputeBond,
+    );
+
+    let (match_id, _, _) = settled_match(&mut platform);
+    let carol = platform.harness.fund(3 * SOL);
+
+    // Results cannot be disputed before there are arbiters to decide
+    assert_eq!(
+        platform.open_dispute(&carol, &match_id, 1),
+        Err(ProgramError::Custom(
+            ErrorCode::AccountNotInitialized.into()
+        ))
+    );
+    arbiters(&mut platform);
+
+    assert_eq!(
+        match_state(&platform, &match_id).dispute_deadline,
+        platform.harness.now() + 2 * HOUR
+    );
+
+    // The challenger has to propose a different, valid winner
+    assert_error(
+        platform.open_dispute(&carol, &match_id, 0),
+        TrendXBetError::ProposedWinnerUnchanged,
+    );
+    assert_error(
+        platform.open_dispute(&carol, &match_id, 2),
+        TrendXBetError::InvalidTeam,
+    );
+
+    platform.close_dispute_window(&match_id);
+    assert_error(
+        platform.open_dispute(&carol, &match_id, 1),
+        TrendXBetError::DisputeWindowClosed,
+    );
+
+    // Nor can a match still waiting for its result be disputed
+    let pending = platform.create_match(HOUR, HOUR);
+    assert_error(
+        platform.open_dispute(&carol, &pending, 1),
+        TrendXBetError::DisputeWindowClosed,
+    );
+}
+
+#[test]
+fn undecided_disputes_expire_and_refund_the_bond() {
+    let mut platform = Platform::new();
+    let [arbiter_a, arbiter_b, arbiter_c] = arbiters(&mut platform);
+    let (match_id, alice, _) = settled_match(&mut platform);
+    let carol = platform.harness.fund(2 * SOL);
+    platform.open_dispute(&carol, &match_id, 1).unwrap();
+    let carol_after_bond = platform.harness.lamports(&carol);
+
+    // A split vote short of quorum leaves the dispute open
+    platform
+        .vote_dispute(&arbiter_a, &match_id, &carol, true)
+        .unwrap();
+    platform
+        .vote_dispute(&arbiter_b, &match_id, &carol, false)
+        .unwrap();
+    let caller = platform.harness.fund(SOL);
+    assert_error(
+        platform.expire_dispute(&match_id, &carol, &caller),
+        TrendXBetError::DisputeVotingOpen,
+    );
+
+    // Past the deadline votes are refused and anyone may expire the dispute
+    platform.harness.warp_by(DISPUTE_VOTING_PERIOD);
+    assert_error(
+        platform.vote_dispute(&arbiter_c, &match_id, &carol, true),
+        TrendXBetError::DisputeVotingClosed,
+    );
+    platform.expire_dispute(&match_id, &carol, &caller).unwrap();
+    let expired = &platform.harness.events::<DisputeExpired>()[0];
+    assert_eq!(
+        (expired.challenger, expired.bond),
+        (carol, DEFAULT_DISPUTE_BOND)
+    );
+    assert_eq!(
+        platform.harness.lamports(&carol),
+        carol_after_bond + DEFAULT_DISPUTE_BOND
+    );
+    let dispute: DisputeState = platform.harness.state(&dispute_pda(&match_id));
+    assert!(dispute.status == DisputeStatus::Expired);
+    assert_error(
+        platform.expire_dispute(&match_id, &carol, &caller),
+        TrendXBetError::DisputeNotOpen,
+    );
+
+    // The oracle result stands and bets settle on it
+    let state = match_state(&platform, &match_id);
+    assert!(!state.is_disputed);
+    assert_eq!(state.winning_team, Some(0));
+    platform.settle_bet(&alice, &match_id, &alice).unwrap();
+    let bet: BetState = platform.harness.state(&bet_pda(&alice, &match_id));
+    assert!(bet.status == BetStatus::Won);
+}
+
+#[test]
+fn arbiter_sets_are_validated() {
+    let mut platform = Platform::new();
+    let keys: Vec<Pubkey> = (0..=MAX_ARBITERS).map(|_| Pubkey::new_unique()).collect();
+
+    let invalid: [(&[Pubkey], u8); 5] = [
+        (&[], 0),
+        (&keys[..2], 0),
+        (&keys[..2], 3),
+        (&[keys[0], keys[0]], 1),
+        (&keys, 1),
+    ];
+    for (arbiters, quorum) in invalid {
+        assert_error(
+            platform.create_arbiter_set(arbiters, quorum),
+            TrendXBetError::InvalidArbiterSet,
+        );
+    }
+
+    platform.create_arbiter_set(&keys[..3], 2).unwrap();
+    assert_error(
+        platform.update_arbiter_set(&keys[..1], 2),
+        TrendXBetError::InvalidArbiterSet,
+    );
+    platform
+        .update_arbiter_set(&keys[..MAX_ARBITERS], 4)
+        .unwrap();
+}
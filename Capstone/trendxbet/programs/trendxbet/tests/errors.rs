@@ -0,0 +1,331 @@
+//! Error taxonomy: every failure path reports its own code, and comparisons log
+//! the values that failed them.
+
+mod common;
+
+use anchor_lang::prelude::{AccountMeta, Pubkey};
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::system_program;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use common::*;
+use trendxbet::{
+    accounts, instruction as ix, ExposureLimits, MatchState, MatchStatus, TrendXBetError,
+    ValidationUtils, BASIS_POINTS, MAX_BETTING_DURATION, MAX_DISPUTE_WINDOW, MAX_SCORE_LENGTH,
+    MIN_BETTING_DURATION, MIN_BET_AMOUNT, MIN_ORACLE_STAKE,
+};
+
+const HOUR: i64 = 3600;
+
+/// Create a match between `team1` and `team2` with a fresh id, unlisted
+fn create_match_between(platform: &mut Platform, team1: &str, team2: &str) -> TxResult {
+    let admin = platform.admin;
+    let match_id = Pubkey::new_unique();
+    let now = platform.harness.now();
+    platform.harness.send(
+        accounts::CreateMatch {
+            match_state: match_pda(&match_id),
+            global_state: platform_pda(),
+            match_id,
+            authority: admin,
+            system_program: system_program::ID,
+            registry: None,
+            status_index: None,
+            start_day_index: None,
+            league_index: None,
+        },
+        ix::CreateMatch {
+            team1: team1.to_string(),
+            team2: team2.to_string(),
+            start_time: now + HOUR,
+            end_time: now + 2 * HOUR,
+            description: "Test match".to_string(),
+            creator_fee: 0,
+        },
+        &[admin],
+    )
+}
+
+#[test]
+fn configuration_errors_name_the_setting() {
+    let mut platform = Platform::new();
+
+    assert_error(
+        platform.set_bet_limits(Some(0), None),
+        TrendXBetError::InvalidBetLimits,
+    );
+    let result = platform.set_bet_limits(Some(SOL), Some(SOL));
+    assert_error(result, TrendXBetError::InvalidBetLimits);
+    platform.set_bet_limits(Some(SOL), Some(2 * SOL)).unwrap();
+
+    assert_error(
+        platform.unpause_platform(),
+        TrendXBetError::PlatformNotPaused,
+    );
+    platform.pause_platform().unwrap();
+    assert_error(platform.pause_platform(), TrendXBetError::PlatformPaused);
+    platform.unpause_platform().unwrap();
+
+    let alice = platform.user("alice");
+    assert_error(platform.deposit(&alice, 0), TrendXBetError::ZeroAmount);
+    assert_error(platform.withdraw(&alice, 0), TrendXBetError::ZeroAmount);
+    assert_error(
+        platform.withdraw_platform_fees(0),
+        TrendXBetError::ZeroAmount,
+    );
+    let result = platform.withdraw(&alice, SOL);
+    assert_error(result, TrendXBetError::InsufficientBalance);
+
+    for window in [-1, MAX_DISPUTE_WINDOW + 1] {
+        assert_error(
+            platform.set_dispute_terms(window, SOL),
+            TrendXBetError::InvalidDisputeWindow,
+        );
+    }
+    assert_error(
+        platform.set_dispute_terms(HOUR, 0),
+        TrendXBetError::InvalidDisputeBond,
+    );
+    assert_error(
+        platform.set_betting_defaults(MIN_BETTING_DURATION as u32, 0),
+        TrendXBetError::InvalidBettingCutoff,
+    );
+    let limits = ExposureLimits {
+        max_match_pool: MIN_BET_AMOUNT - 1,
+        max_pool_imbalance: 0,
+        max_user_match_stake: 0,
+    };
+    assert_error(
+        platform.set_exposure_limits(limits),
+        TrendXBetError::InvalidExposureLimit,
+    );
+}
+
+#[test]
+fn bet_errors_name_the_check_that_failed() {
+    let mut platform = Platform::new();
+    let alice = platform.user("alice");
+    let bob = platform.user("bob");
+    platform.deposit(&alice, 5 * SOL).unwrap();
+    platform.deposit(&bob, 5 * SOL).unwrap();
+    let match_id = platform.create_match(HOUR, 2 * HOUR);
+    platform.harness.warp_by(HOUR);
+
+    let result = platform.place_bet(&alice, &match_id, MIN_BET_AMOUNT - 1, 0);
+    assert_error(result, TrendXBetError::BetAmountTooLow);
+    let result = platform.place_bet(&alice, &match_id, 6 * SOL, 0);
+    assert_error(result, TrendXBetError::InsufficientBalance);
+
+    // Odds that moved against the bettor are slippage, not an oracle fault
+    platform.place_bet(&bob, &match_id, SOL, 1).unwrap();
+    let accepted = 2 * BASIS_POINTS;
+    let result = platform.harness.send(
+        accounts::PlaceBet {
+            bet_state: bet_pda(&alice, &match_id),
+            user_state: user_pda(&alice),
+            match_state: match_pda(&match_id),
+            global_state: platform_pda(),
+            treasury: treasury_pda(),
+            match_id,
+            bettor: alice,
+            authority: alice,
+            system_program: system_program::ID,
+            session: None,
+            private_pool: None,
+            odds_history: None,
+        },
+        ix::PlaceBet {
+            amount: SOL,
+            predicted_team: 1,
+            odds_accepted: accepted,
+            use_bonus: false,
+        },
+        &[alice],
+    );
+    assert_error(result, TrendXBetError::SlippageExceeded);
+
+    // Settling waits for a result rather than for the match to start
+    platform.place_bet(&alice, &match_id, SOL, 0).unwrap();
+    assert_error(
+        platform.settle_bet(&alice, &match_id, &alice),
+        TrendXBetError::MatchNotSettled,
+    );
+    assert_error(
+        platform.claim_winnings(&alice, &match_id),
+        TrendXBetError::NoWinningsToClaim,
+    );
+
+    // A live match takes no changes and cannot end before its end time
+    platform.harness.warp_by(HOUR / 2);
+    platform
+        .update_match_status(&match_id, MatchStatus::Live)
+        .unwrap();
+    assert_error(
+        platform.modify_bet(&alice, &match_id, 2 * SOL, 0),
+        TrendXBetError::CannotModifyBet,
+    );
+    assert_error(
+        platform.update_match_status(&match_id, MatchStatus::Ended),
+        TrendXBetError::MatchNotEnded,
+    );
+
+    // A settled bet is no longer active
+    let end_time = platform
+        .harness
+        .state::<MatchState>(&match_pda(&match_id))
+        .end_time;
+    platform.harness.warp_to(end_time);
+    platform.crank_match(&match_id, &bob).unwrap();
+    platform.resolve_match(&match_id, 0);
+    platform.settle_bet(&alice, &match_id, &alice).unwrap();
+    assert_error(
+        platform.settle_bet(&alice, &match_id, &alice),
+        TrendXBetError::BetNotActive,
+    );
+}
+
+#[test]
+fn match_and_oracle_inputs_fail_with_their_own_codes() {
+    let mut platform = Platform::new();
+    let user = platform.harness.fund(SOL);
+    assert_error(
+        platform.create_user_profile(&user, "not valid"),
+        TrendXBetError::InvalidUsername,
+    );
+
+    let admin = platform.admin;
+    let identical = create_match_between(&mut platform, "India", "India");
+    assert_error(identical, TrendXBetError::IdenticalTeams);
+    let blank = create_match_between(&mut platform, " ", "India");
+    assert_error(blank, TrendXBetError::InvalidTeamName);
+
+    let now = platform.harness.now();
+    let result = platform.try_create_match(now + HOUR, now + HOUR + HOUR / 2);
+    assert_error(result.map(drop), TrendXBetError::InvalidMatchDuration);
+    let match_id = platform.create_match(HOUR, 2 * HOUR);
+    let state: MatchState = platform.harness.state(&match_pda(&match_id));
+    assert_error(
+        platform.set_betting_window(&match_id, state.start_time, state.start_time, 0),
+        TrendXBetError::InvalidBettingWindow,
+    );
+
+    // An ended match settles only once a result is validated
+    let match_id = platform.create_match(HOUR, 2 * HOUR);
+    platform.harness.warp_by(3 * HOUR);
+    let cranker = platform.harness.fund(SOL);
+    platform.crank_match(&match_id, &cranker).unwrap();
+    assert_error(
+        platform.update_match_status(&match_id, MatchStatus::Settled),
+        TrendXBetError::ResultNotReported,
+    );
+
+    let oracle = platform.register_oracle(&match_id);
+    let reporter = platform.register_oracle(&match_id);
+    let long_score = "9".repeat(MAX_SCORE_LENGTH + 1);
+    for (authority, score) in [(&oracle, long_score.as_str()), (&reporter, "final")] {
+        platform
+            .commit_result(authority, &match_id, 0, score, None)
+            .unwrap();
+    }
+    platform.open_reveals(&match_id);
+    assert_error(
+        platform.reveal_result(&oracle, &match_id, 0, &long_score, None),
+        TrendXBetError::FinalScoreTooLong,
+    );
+    platform
+        .reveal_result(&reporter, &match_id, 0, "final", None)
+        .unwrap();
+    assert_error(
+        platform.validate_oracle(&oracle, &match_id, &[]),
+        TrendXBetError::ResultNotRevealed,
+    );
+
+    // Peers must have reported on the same match
+    let other_match = platform.create_match(HOUR, 2 * HOUR);
+    let stranger = platform.register_oracle(&other_match);
+    platform
+        .update_match_status(&other_match, MatchStatus::Cancelled)
+        .unwrap();
+    assert_error(
+        platform.settle_creator(&other_match, &cranker),
+        TrendXBetError::NotCommunityMarket,
+    );
+    let mut metas = accounts::ValidateOracleUpdate {
+        oracle_state: oracle_pda(&reporter, &match_id),
+        match_state: match_pda(&match_id),
+        global_state: platform_pda(),
+        oracle_authority: reporter,
+        match_id,
+        admin,
+        old_status_index: None,
+        new_status_index: None,
+        audit_log: None,
+    }
+    .to_account_metas(None);
+    metas.push(AccountMeta::new_readonly(
+        oracle_pda(&stranger, &other_match),
+        false,
+    ));
+    let result = platform.harness.process(
+        Instruction {
+            program_id: trendxbet::ID,
+            accounts: metas,
+            data: ix::ValidateOracleUpdate {}.data(),
+        },
+        &[admin],
+    );
+    assert_error(result, TrendXBetError::PeerOracleMatchMismatch);
+    assert_error(
+        platform.validate_oracle(&reporter, &match_id, &[reporter]),
+        TrendXBetError::DuplicatePeerOracle,
+    );
+}
+
+#[test]
+fn dispute_and_stake_errors_fail_with_their_own_codes() {
+    let mut platform = Platform::new();
+    let arbiters = [(); 3].map(|_| platform.harness.fund(SOL));
+    platform.create_arbiter_set(&arbiters, 2).unwrap();
+    let match_id = platform.create_match(HOUR, 2 * HOUR);
+    platform.harness.warp_by(3 * HOUR);
+    let cranker = platform.harness.fund(SOL);
+    platform.crank_match(&match_id, &cranker).unwrap();
+    platform.resolve_match_in_window(&match_id, 0, None);
+
+    let challenger = platform.harness.fund(3 * SOL);
+    assert_error(
+        platform.open_dispute(&challenger, &match_id, 0),
+        TrendXBetError::ProposedWinnerUnchanged,
+    );
+
+    let oracle = platform.oracle(MIN_ORACLE_STAKE);
+    assert_error(
+        platform.withdraw_oracle_stake(&oracle),
+        TrendXBetError::NothingUnbonding,
+    );
+}
+
+#[test]
+fn comparisons_log_the_values_that_failed() {
+    let amount = MIN_BET_AMOUNT - 1;
+    let result = ValidationUtils::validate_bet_amount(amount, MIN_BET_AMOUNT, SOL);
+    assert_eq!(
+        compared_values(result),
+        Some((amount.to_string(), MIN_BET_AMOUNT.to_string()))
+    );
+    let result = ValidationUtils::validate_bet_amount(2 * SOL, MIN_BET_AMOUNT, SOL);
+    assert_eq!(
+        compared_values(result),
+        Some((SOL.to_string(), (2 * SOL).to_string()))
+    );
+
+    let duration = MAX_BETTING_DURATION + 1;
+    let result = ValidationUtils::validate_match_times(HOUR, HOUR + duration, 0);
+    assert_eq!(
+        compared_values(result),
+        Some((MAX_BETTING_DURATION.to_string(), duration.to_string()))
+    );
+    assert_eq!(
+        compared_values(ValidationUtils::validate_team_selection(2)),
+        Some(("1".to_string(), "2".to_string()))
+    );
+}
@@ -0,0 +1,131 @@
+//! Admin exposure caps on match pools, pool imbalance and per-user stakes.
+
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use common::*;
+use trendxbet::{
+    ExposureLimits, ExposureLimitsUpdated, GlobalState, MatchState, TrendXBetError, MIN_BET_AMOUNT,
+};
+
+const HOUR: i64 = 3600;
+
+/// Fund `names` and open a match for betting
+fn open_match(platform: &mut Platform, names: &[&str]) -> (Pubkey, Vec<Pubkey>) {
+    let users: Vec<Pubkey> = names.iter().map(|name| platform.user(name)).collect();
+    for user in &users {
+        platform.deposit(user, 10 * SOL).unwrap();
+    }
+    let match_id = platform.create_match(HOUR, 2 * HOUR);
+    platform.harness.warp_by(HOUR);
+    (match_id, users)
+}
+
+#[test]
+fn match_pool_cap_limits_total_stake() {
+    let mut platform = Platform::new();
+    platform
+        .set_exposure_limits(ExposureLimits {
+            max_match_pool: 3 * SOL,
+            ..Default::default()
+        })
+        .unwrap();
+    let (match_id, users) = open_match(&mut platform, &["alice", "bob", "carol"]);
+
+    platform
+        .place_bet(&users[0], &match_id, 2 * SOL, 0)
+        .unwrap();
+    assert_error(
+        platform.place_bet(&users[1], &match_id, 2 * SOL, 1),
+        TrendXBetError::MatchPoolCapExceeded,
+    );
+    platform.place_bet(&users[2], &match_id, SOL, 1).unwrap();
+
+    let state: MatchState = platform.harness.state(&match_pda(&match_id));
+    assert_eq!(state.total_pool, 3 * SOL);
+}
+
+#[test]
+fn imbalance_cap_still_accepts_balancing_bets() {
+    let mut platform = Platform::new();
+    let (match_id, users) = open_match(&mut platform, &["alice", "bob", "carol", "dave"]);
+
+    // Lowering the cap below an existing imbalance leaves the match open to the other side
+    platform
+        .place_bet(&users[0], &match_id, 3 * SOL, 0)
+        .unwrap();
+    platform
+        .set_exposure_limits(ExposureLimits {
+            max_pool_imbalance: SOL,
+            ..Default::default()
+        })
+        .unwrap();
+
+    assert_error(
+        platform.place_bet(&users[1], &match_id, SOL, 0),
+        TrendXBetError::PoolImbalanceCapExceeded,
+    );
+    platform.place_bet(&users[2], &match_id, SOL, 1).unwrap();
+    platform
+        .place_bet(&users[3], &match_id, 3 * SOL, 1)
+        .unwrap();
+    platform.place_bet(&users[1], &match_id, SOL, 0).unwrap();
+
+    let state: MatchState = platform.harness.state(&match_pda(&match_id));
+    assert_eq!((state.team1_pool, state.team2_pool), (4 * SOL, 4 * SOL));
+}
+
+#[test]
+fn user_stake_cap_limits_each_bettor() {
+    let mut platform = Platform::new();
+    platform
+        .set_exposure_limits(ExposureLimits {
+            max_user_match_stake: SOL,
+            ..Default::default()
+        })
+        .unwrap();
+    let (match_id, users) = open_match(&mut platform, &["alice"]);
+
+    assert_error(
+        platform.place_bet(&users[0], &match_id, SOL + 1, 0),
+        TrendXBetError::UserMatchStakeCapExceeded,
+    );
+    platform.place_bet(&users[0], &match_id, SOL, 0).unwrap();
+}
+
+#[test]
+fn limits_are_validated_and_announced() {
+    let mut platform = Platform::new();
+    let limits = ExposureLimits {
+        max_match_pool: 50 * SOL,
+        max_pool_imbalance: 10 * SOL,
+        max_user_match_stake: 0,
+    };
+
+    for invalid in [
+        ExposureLimits {
+            max_match_pool: MIN_BET_AMOUNT - 1,
+            ..limits
+        },
+        ExposureLimits {
+            max_pool_imbalance: 1,
+            ..limits
+        },
+        ExposureLimits {
+            max_user_match_stake: MIN_BET_AMOUNT - 1,
+            ..limits
+        },
+    ] {
+        assert_error(
+            platform.set_exposure_limits(invalid),
+            TrendXBetError::InvalidExposureLimit,
+        );
+    }
+
+    platform.set_exposure_limits(limits).unwrap();
+    let event = &platform.harness.events::<ExposureLimitsUpdated>()[0];
+    assert_eq!(event.old_limits, ExposureLimits::default());
+    assert_eq!(event.new_limits, limits);
+    let global: GlobalState = platform.harness.state(&platform_pda());
+    assert_eq!(global.exposure_limits, limits);
+}
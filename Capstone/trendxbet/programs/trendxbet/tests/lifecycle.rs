@@ -0,0 +1,604 @@
+//! End-to-end lifecycle: initialize → profiles → deposits → match → bets and
+//! cancellation → oracle reports → settlement → claims → withdrawals.
+
+mod common;
+
+use common::*;
+use trendxbet::{
+    BetState, BetStatus, ConsensusReached, MatchState, MatchStatus, OracleState, TreasuryState,
+    TrendXBetError, UserState, DEFAULT_DISPUTE_WINDOW, ORACLE_UPDATE_WINDOW,
+};
+
+const HOUR: i64 = 3600;
+
+fn treasury(platform: &Platform) -> TreasuryState {
+    platform.harness.state(&treasury_pda())
+}
+
+fn user(platform: &Platform, user: &anchor_lang::prelude::Pubkey) -> UserState {
+    platform.harness.state(&user_pda(user))
+}
+
+/// Assert every `TreasuryState` counter at once
+fn assert_treasury(
+    platform: &Platform,
+    total_deposits: u64,
+    total_withdrawals: u64,
+    platform_fees: u64,
+    pending_payouts: u64,
+    last_fee_collection: i64,
+) {
+    let treasury = treasury(platform);
+    assert_eq!(treasury.total_deposits, total_deposits, "total_deposits");
+    assert_eq!(
+        treasury.total_withdrawals, total_withdrawals,
+        "total_withdrawals"
+    );
+    assert_eq!(treasury.platform_fees, platform_fees, "platform_fees");
+    assert_eq!(treasury.pending_payouts, pending_payouts, "pending_payouts");
+    assert_eq!(
+        treasury.last_fee_collection, last_fee_collection,
+        "last_fee_collection"
+    );
+    assert_eq!(treasury.authority, platform.admin);
+}
+
+#[test]
+fn full_match_lifecycle() {
+    let mut platform = Platform::new();
+    let genesis = platform.harness.now();
+    let treasury_rent = platform.harness.lamports(&treasury_pda());
+    assert_treasury(&platform, 0, 0, 0, 0, genesis);
+
+    // Profiles and deposits
+    let alice = platform.user("alice");
+    let bob = platform.user("bob");
+    let carol = platform.user("carol");
+
+    platform.deposit(&alice, 10 * SOL).unwrap();
+    platform.deposit(&bob, 10 * SOL).unwrap();
+    platform.deposit(&carol, 5 * SOL).unwrap();
+    assert_treasury(&platform, 25 * SOL, 0, 0, 0, genesis);
+    assert_eq!(
+        platform.harness.lamports(&treasury_pda()),
+        treasury_rent + 25 * SOL
+    );
+    assert_eq!(user(&platform, &alice).balance, 10 * SOL);
+
+    // Betting only opens at start_time
+    let match_id = platform.create_match(HOUR, 2 * HOUR);
+    let match_state: MatchState = platform.harness.state(&match_pda(&match_id));
+    assert_error(
+        platform.place_bet(&alice, &match_id, 2 * SOL, 0),
+        TrendXBetError::BettingClosed,
+    );
+
+    platform.harness.warp_to(match_state.start_time);
+    platform.place_bet(&alice, &match_id, 2 * SOL, 0).unwrap();
+    platform.place_bet(&bob, &match_id, SOL, 1).unwrap();
+    platform.place_bet(&carol, &match_id, SOL, 0).unwrap();
+
+    // 5% house edge on 4 SOL staked
+    assert_treasury(&platform, 25 * SOL, 0, SOL / 5, 0, genesis);
+    assert_eq!(user(&platform, &alice).balance, 8 * SOL);
+
+    // Only the bettor can cancel, and cancelling returns the stake and the fee
+    assert_error(
+        platform.cancel_bet(&carol, &match_id, &bob),
+        TrendXBetError::Unauthorized,
+    );
+    platform.cancel_bet(&carol, &match_id, &carol).unwrap();
+    assert_treasury(&platform, 25 * SOL, 0, 3 * SOL / 20, 0, genesis);
+    assert_eq!(user(&platform, &carol).balance, 5 * SOL);
+    let carol_bet: BetState = platform.harness.state(&bet_pda(&carol, &match_id));
+    assert!(carol_bet.status == BetStatus::Cancelled);
+    assert_error(
+        platform.claim_winnings(&carol, &match_id),
+        TrendXBetError::NoWinningsToClaim,
+    );
+
+    let match_state: MatchState = platform.harness.state(&match_pda(&match_id));
+    assert_eq!(
+        (
+            match_state.team1_pool,
+            match_state.team2_pool,
+            match_state.total_bets
+        ),
+        (2 * SOL, SOL, 2)
+    );
+
+    // Going live closes betting and cancellation
+    platform.harness.warp_by(HOUR / 2);
+    platform
+        .update_match_status(&match_id, MatchStatus::Live)
+        .unwrap();
+    assert_error(
+        platform.cancel_bet(&alice, &match_id, &alice),
+        TrendXBetError::CannotCancelBet,
+    );
+    assert_error(
+        platform.update_match_status(&match_id, MatchStatus::Ended),
+        TrendXBetError::MatchNotEnded,
+    );
+
+    // Three oracles commit once the match has ended, one of them disagreeing
+    let oracle_a = platform.register_oracle(&match_id);
+    let oracle_b = platform.register_oracle(&match_id);
+    let oracle_c = platform.register_oracle(&match_id);
+    assert_error(
+        platform.commit_result(&oracle_a, &match_id, 0, "287/4 - 250/10", None),
+        TrendXBetError::InvalidMatchStatus,
+    );
+
+    platform.harness.warp_to(match_state.end_time);
+    platform
+        .update_match_status(&match_id, MatchStatus::Ended)
+        .unwrap();
+    platform.harness.warp_by(HOUR / 4);
+    let reports = [
+        (oracle_a, 0, "287/4 - 250/10"),
+        (oracle_b, 0, "287/4 - 250/10"),
+        (oracle_c, 1, "250/10 - 287/4"),
+    ];
+    for (oracle, team, score) in reports {
+        platform
+            .commit_result(&oracle, &match_id, team, score, None)
+            .unwrap();
+    }
+
+    // Then reveal what they committed to
+    platform.open_reveals(&match_id);
+    for (oracle, team, score) in reports {
+        platform
+            .reveal_result(&oracle, &match_id, team, score, None)
+            .unwrap();
+    }
+
+    // One report alone, or with only a dissenting peer, is not enough
+    assert_error(
+        platform.validate_oracle(&oracle_a, &match_id, &[]),
+        TrendXBetError::InsufficientOracleConfirmations,
+    );
+    assert_error(
+        platform.validate_oracle(&oracle_a, &match_id, &[oracle_c]),
+        TrendXBetError::InsufficientOracleConfirmations,
+    );
+    assert_error(
+        platform.validate_oracle(&oracle_a, &match_id, &[oracle_a]),
+        TrendXBetError::DuplicatePeerOracle,
+    );
+    assert_error(
+        platform.validate_oracle(&oracle_a, &match_id, &[oracle_b, oracle_b]),
+        TrendXBetError::DuplicatePeerOracle,
+    );
+
+    platform
+        .validate_oracle(&oracle_a, &match_id, &[oracle_b, oracle_c])
+        .unwrap();
+    let consensus = platform.harness.events::<ConsensusReached>();
+    assert_eq!(consensus.len(), 1);
+    assert_eq!(consensus[0].winning_team, 0);
+    assert_eq!(consensus[0].total_confirmations, 2);
+
+    let oracle: OracleState = platform.harness.state(&oracle_pda(&oracle_a, &match_id));
+    assert!(oracle.is_validated);
+    let match_state: MatchState = platform.harness.state(&match_pda(&match_id));
+    assert!(match_state.is_settled && match_state.status == MatchStatus::Settled);
+    assert_eq!(match_state.winning_team, Some(0));
+    assert_error(
+        platform.validate_oracle(&oracle_a, &match_id, &[oracle_b]),
+        TrendXBetError::OracleResultExists,
+    );
+
+    // Bets settle only once the result can no longer be disputed
+    assert_eq!(
+        match_state.dispute_deadline,
+        platform.harness.now() + DEFAULT_DISPUTE_WINDOW
+    );
+    assert_error(
+        platform.settle_bet(&alice, &match_id, &carol),
+        TrendXBetError::DisputeWindowOpen,
+    );
+    platform.close_dispute_window(&match_id);
+
+    // Anyone can settle; the winner takes the pool net of the house edge
+    platform.settle_bet(&alice, &match_id, &carol).unwrap();
+    platform.settle_bet(&bob, &match_id, &carol).unwrap();
+    assert_error(
+        platform.settle_bet(&alice, &match_id, &carol),
+        TrendXBetError::BetNotActive,
+    );
+
+    let payout = 3 * SOL - 3 * SOL / 20;
+    let alice_bet: BetState = platform.harness.state(&bet_pda(&alice, &match_id));
+    assert!(alice_bet.status == BetStatus::Won);
+    assert_eq!(alice_bet.payout_amount, Some(payout));
+    let bob_bet: BetState = platform.harness.state(&bet_pda(&bob, &match_id));
+    assert!(bob_bet.status == BetStatus::Lost);
+    assert_treasury(&platform, 25 * SOL, 0, 3 * SOL / 20, payout, genesis);
+
+    // Claims move winnings from pending payouts into the bettor's balance
+    assert_error(
+        platform.claim_winnings(&bob, &match_id),
+        TrendXBetError::NoWinningsToClaim,
+    );
+    platform.claim_winnings(&alice, &match_id).unwrap();
+    assert_error(
+        platform.claim_winnings(&alice, &match_id),
+        TrendXBetError::NoWinningsToClaim,
+    );
+    assert_treasury(&platform, 25 * SOL, 0, 3 * SOL / 20, 0, genesis);
+    assert_eq!(user(&platform, &alice).balance, 8 * SOL + payout);
+    assert_eq!(user(&platform, &alice).total_winnings, payout);
+
+    // Withdrawals pay out of the treasury PDA
+    let alice_wallet = platform.harness.lamports(&alice);
+    platform.withdraw(&alice, 8 * SOL + payout).unwrap();
+    assert_eq!(
+        platform.harness.lamports(&alice),
+        alice_wallet + 8 * SOL + payout
+    );
+    assert_eq!(user(&platform, &alice).balance, 0);
+    assert_error(
+        platform.withdraw(&bob, 10 * SOL),
+        TrendXBetError::InsufficientBalance,
+    );
+    let withdrawn = 8 * SOL + payout;
+    assert_treasury(&platform, 25 * SOL, withdrawn, 3 * SOL / 20, 0, genesis);
+
+    // Fee withdrawal resets the fee counter and stamps the collection time
+    platform.harness.warp_by(HOUR);
+    let fee_time = platform.harness.now();
+    assert_error(
+        platform.withdraw_platform_fees(SOL),
+        TrendXBetError::TreasuryInsufficientFunds,
+    );
+    platform.withdraw_platform_fees(3 * SOL / 20).unwrap();
+    let withdrawn = withdrawn + 3 * SOL / 20;
+    assert_treasury(&platform, 25 * SOL, withdrawn, 0, 0, fee_time);
+
+    // Emergency withdrawal is capped at the treasury's available balance
+    let available = treasury(&platform).available_balance() as u64;
+    assert_eq!(available, 14 * SOL);
+    assert_error(
+        platform.emergency_withdraw(available + 1),
+        TrendXBetError::TreasuryInsufficientFunds,
+    );
+    // Amounts past `i64::MAX` must not wrap around the check
+    assert_error(
+        platform.emergency_withdraw(u64::MAX),
+        TrendXBetError::TreasuryInsufficientFunds,
+    );
+    platform.emergency_withdraw(SOL).unwrap();
+    let withdrawn = withdrawn + SOL;
+    assert_treasury(&platform, 25 * SOL, withdrawn, 0, 0, fee_time);
+
+    // The PDA's lamports always match the treasury's books
+    assert_eq!(
+        platform.harness.lamports(&treasury_pda()),
+        treasury_rent + 25 * SOL - withdrawn
+    );
+}
+
+#[test]
+fn match_times_are_validated_against_the_clock() {
+    let mut platform = Platform::new();
+    let now = platform.harness.now();
+
+    assert_error(
+        platform.try_create_match(now, now + 2 * HOUR).map(drop),
+        TrendXBetError::StartTimeInPast,
+    );
+    assert_error(
+        platform.try_create_match(now + HOUR, now + HOUR).map(drop),
+        TrendXBetError::EndTimeBeforeStart,
+    );
+    assert_error(
+        platform
+            .try_create_match(now + HOUR, now + HOUR + HOUR / 2)
+            .map(drop),
+        TrendXBetError::InvalidMatchDuration,
+    );
+
+    // Status transitions wait for the scheduled times
+    let match_id = platform.create_match(HOUR, 2 * HOUR);
+    assert_error(
+        platform.update_match_status(&match_id, MatchStatus::Live),
+        TrendXBetError::MatchNotStarted,
+    );
+    platform.harness.warp_by(HOUR);
+    platform
+        .update_match_status(&match_id, MatchStatus::Live)
+        .unwrap();
+    assert_error(
+        platform.update_match_status(&match_id, MatchStatus::Scheduled),
+        TrendXBetError::InvalidMatchStatus,
+    );
+}
+
+#[test]
+fn betting_window_closes_at_end_time() {
+    let mut platform = Platform::new();
+    let alice = platform.user("alice");
+    platform.deposit(&alice, 5 * SOL).unwrap();
+
+    let match_id = platform.create_match(HOUR, 2 * HOUR);
+    platform.harness.warp_by(3 * HOUR + 1);
+    assert_error(
+        platform.place_bet(&alice, &match_id, SOL, 0),
+        TrendXBetError::BettingClosed,
+    );
+    assert_treasury(&platform, 5 * SOL, 0, 0, 0, common::GENESIS_TIME);
+}
+
+#[test]
+fn oracle_reports_expire_after_update_window() {
+    let mut platform = Platform::new();
+    let match_id = platform.create_match(HOUR, 2 * HOUR);
+    let oracle = platform.register_oracle(&match_id);
+    let late = platform.register_oracle(&match_id);
+
+    platform.harness.warp_by(HOUR);
+    platform
+        .update_match_status(&match_id, MatchStatus::Live)
+        .unwrap();
+    platform.harness.warp_by(2 * HOUR);
+    platform
+        .update_match_status(&match_id, MatchStatus::Ended)
+        .unwrap();
+
+    platform
+        .commit_result(&oracle, &match_id, 0, "1-0", None)
+        .unwrap();
+
+    platform.harness.warp_by(ORACLE_UPDATE_WINDOW + 1);
+    assert_error(
+        platform.reveal_result(&oracle, &match_id, 0, "1-0", None),
+        TrendXBetError::OracleUpdateExpired,
+    );
+    assert_error(
+        platform.commit_result(&late, &match_id, 0, "1-0", None),
+        TrendXBetError::CommitWindowClosed,
+    );
+}
+
+#[test]
+fn withdrawals_only_debit_the_treasury_pda() {
+    let mut platform = Platform::new();
+    let alice = platform.user("alice");
+    platform.deposit(&alice, 5 * SOL).unwrap();
+
+    let decoy = platform.harness.fund(10 * SOL);
+    let result = platform.harness.send(
+        trendxbet::accounts::WithdrawFunds {
+            user_state: user_pda(&alice),
+            treasury: treasury_pda(),
+            authority: alice,
+            user: alice,
+            treasury_account: decoy,
+            system_program: anchor_lang::system_program::ID,
+        },
+        trendxbet::instruction::WithdrawFunds { amount: SOL },
+        &[alice],
+    );
+    assert_error(result, TrendXBetError::InvalidAccount);
+    assert_eq!(platform.harness.lamports(&decoy), 10 * SOL);
+    assert_treasury(&platform, 5 * SOL, 0, 0, 0, common::GENESIS_TIME);
+}
+
+#[test]
+fn peer_reports_confirm_an_oracle_result() {
+    let mut platform = Platform::new();
+    let match_id = platform.create_match(HOUR, 2 * HOUR);
+    let oracle_a = platform.register_oracle(&match_id);
+    let oracle_b = platform.register_oracle(&match_id);
+    let oracle_c = platform.register_oracle(&match_id);
+
+    platform.harness.warp_by(HOUR);
+    platform
+        .update_match_status(&match_id, MatchStatus::Live)
+        .unwrap();
+    platform.harness.warp_by(2 * HOUR);
+    platform
+        .update_match_status(&match_id, MatchStatus::Ended)
+        .unwrap();
+    let reports = [
+        (oracle_a, 0, "1-0"),
+        (oracle_b, 0, "1-0"),
+        (oracle_c, 1, "0-1"),
+    ];
+    for (oracle, team, score) in reports {
+        platform
+            .commit_result(&oracle, &match_id, team, score, None)
+            .unwrap();
+    }
+    platform.open_reveals(&match_id);
+    for (oracle, team, score) in reports {
+        platform
+            .reveal_result(&oracle, &match_id, team, score, None)
+            .unwrap();
+    }
+
+    // Only peers that reported the same result confirm it, each counted once
+    assert_error(
+        platform.validate_oracle(&oracle_a, &match_id, &[oracle_c]),
+        TrendXBetError::InsufficientOracleConfirmations,
+    );
+    assert_error(
+        platform.validate_oracle(&oracle_a, &match_id, &[oracle_a]),
+        TrendXBetError::DuplicatePeerOracle,
+    );
+    assert_error(
+        platform.validate_oracle(&oracle_a, &match_id, &[oracle_b, oracle_b]),
+        TrendXBetError::DuplicatePeerOracle,
+    );
+
+    platform
+        .validate_oracle(&oracle_a, &match_id, &[oracle_b, oracle_c])
+        .unwrap();
+    let consensus = platform.harness.events::<ConsensusReached>();
+    assert_eq!(consensus[0].total_confirmations, 2);
+    let match_state: MatchState = platform.harness.state(&match_pda(&match_id));
+    assert!(match_state.is_settled && match_state.winning_team == Some(0));
+}
+
+#[test]
+fn full_length_results_fit_their_accounts() {
+    let mut platform = Platform::new();
+    let alice = platform.user("alice");
+    let bob = platform.user("bob");
+    platform.deposit(&alice, 5 * SOL).unwrap();
+    platform.deposit(&bob, 5 * SOL).unwrap();
+    let match_id = platform.create_match(HOUR, 2 * HOUR);
+    let oracle_a = platform.register_oracle(&match_id);
+    let oracle_b = platform.register_oracle(&match_id);
+
+    platform.harness.warp_by(HOUR);
+    platform.place_bet(&alice, &match_id, SOL, 0).unwrap();
+    platform.place_bet(&bob, &match_id, SOL, 1).unwrap();
+    platform
+        .update_match_status(&match_id, MatchStatus::Live)
+        .unwrap();
+    platform.harness.warp_by(2 * HOUR);
+    platform
+        .update_match_status(&match_id, MatchStatus::Ended)
+        .unwrap();
+
+    // Every optional field is set once a longest-possible score settles the bets
+    let score = "9".repeat(trendxbet::MAX_SCORE_LENGTH);
+    for oracle in [oracle_a, oracle_b] {
+        platform
+            .commit_result(&oracle, &match_id, 0, &score, None)
+            .unwrap();
+    }
+    platform.open_reveals(&match_id);
+    for oracle in [oracle_a, oracle_b] {
+        platform
+            .reveal_result(&oracle, &match_id, 0, &score, None)
+            .unwrap();
+    }
+    platform
+        .validate_oracle(&oracle_a, &match_id, &[oracle_b])
+        .unwrap();
+    platform.close_dispute_window(&match_id);
+    platform.settle_bet(&alice, &match_id, &alice).unwrap();
+    platform.settle_bet(&bob, &match_id, &bob).unwrap();
+
+    let match_state: MatchState = platform.harness.state(&match_pda(&match_id));
+    assert_eq!(match_state.final_score, Some(score));
+    let bet: BetState = platform.harness.state(&bet_pda(&alice, &match_id));
+    assert!(bet.status == BetStatus::Won && bet.settled_at.is_some());
+}
+
+#[test]
+fn bets_can_be_cancelled_while_betting_is_open() {
+    let mut platform = Platform::new();
+    let alice = platform.user("alice");
+    platform.deposit(&alice, 5 * SOL).unwrap();
+    let match_id = platform.create_match(HOUR, 2 * HOUR);
+
+    // Betting opens at start_time, and so does cancelling
+    platform.harness.warp_by(HOUR);
+    platform.place_bet(&alice, &match_id, SOL, 0).unwrap();
+    platform.cancel_bet(&alice, &match_id, &alice).unwrap();
+    let bet: BetState = platform.harness.state(&bet_pda(&alice, &match_id));
+    assert!(bet.status == BetStatus::Cancelled);
+    let match_state: MatchState = platform.harness.state(&match_pda(&match_id));
+    assert_eq!((match_state.total_pool, match_state.total_bets), (0, 0));
+
+    // Going live closes both
+    let bob = platform.user("bob");
+    platform.deposit(&bob, 5 * SOL).unwrap();
+    platform.place_bet(&bob, &match_id, SOL, 1).unwrap();
+    platform
+        .update_match_status(&match_id, MatchStatus::Live)
+        .unwrap();
+    assert_error(
+        platform.cancel_bet(&bob, &match_id, &bob),
+        TrendXBetError::CannotCancelBet,
+    );
+}
+
+#[test]
+fn only_the_bettor_cancels_a_bet() {
+    let mut platform = Platform::new();
+    let alice = platform.user("alice");
+    let bob = platform.user("bob");
+    platform.deposit(&alice, 5 * SOL).unwrap();
+    let match_id = platform.create_match(HOUR, 2 * HOUR);
+    platform.harness.warp_by(HOUR);
+    platform.place_bet(&alice, &match_id, SOL, 0).unwrap();
+
+    assert_error(
+        platform.cancel_bet(&alice, &match_id, &bob),
+        TrendXBetError::Unauthorized,
+    );
+    let bet: BetState = platform.harness.state(&bet_pda(&alice, &match_id));
+    assert!(bet.status == BetStatus::Active);
+    platform.cancel_bet(&alice, &match_id, &alice).unwrap();
+}
+
+#[test]
+fn cancelling_a_bet_returns_its_platform_fee() {
+    let mut platform = Platform::new();
+    let alice = platform.user("alice");
+    platform.deposit(&alice, 5 * SOL).unwrap();
+    let match_id = platform.create_match(HOUR, 2 * HOUR);
+    platform.harness.warp_by(HOUR);
+
+    // 5% house edge on the stake, recorded on the bet
+    platform.place_bet(&alice, &match_id, SOL, 0).unwrap();
+    let bet: BetState = platform.harness.state(&bet_pda(&alice, &match_id));
+    assert_eq!(bet.platform_fee, SOL / 20);
+    assert_treasury(&platform, 5 * SOL, 0, SOL / 20, 0, common::GENESIS_TIME);
+
+    platform.cancel_bet(&alice, &match_id, &alice).unwrap();
+    assert_treasury(&platform, 5 * SOL, 0, 0, 0, common::GENESIS_TIME);
+    assert_eq!(user(&platform, &alice).balance, 5 * SOL);
+}
+
+#[test]
+fn cancelled_bets_are_not_claimable() {
+    let mut platform = Platform::new();
+    let alice = platform.user("alice");
+    platform.deposit(&alice, 5 * SOL).unwrap();
+    let match_id = platform.create_match(HOUR, 2 * HOUR);
+    platform.harness.warp_by(HOUR);
+    platform.place_bet(&alice, &match_id, SOL, 0).unwrap();
+
+    // The stake was refunded on cancellation, so there is nothing left to claim
+    platform.cancel_bet(&alice, &match_id, &alice).unwrap();
+    assert_error(
+        platform.claim_winnings(&alice, &match_id),
+        TrendXBetError::NoWinningsToClaim,
+    );
+    assert_eq!(user(&platform, &alice).balance, 5 * SOL);
+}
+
+#[test]
+fn withdrawals_pay_out_of_the_treasury_pda() {
+    let mut platform = Platform::new();
+    let treasury_rent = platform.harness.lamports(&treasury_pda());
+    let alice = platform.user("alice");
+    platform.deposit(&alice, 5 * SOL).unwrap();
+
+    // The treasury PDA carries data, so it is debited directly rather than by the system program
+    let alice_wallet = platform.harness.lamports(&alice);
+    platform.withdraw(&alice, 2 * SOL).unwrap();
+    assert_eq!(platform.harness.lamports(&alice), alice_wallet + 2 * SOL);
+
+    // An emergency withdrawal pays the admin the same way
+    let admin_wallet = platform.harness.lamports(&platform.admin);
+    platform.emergency_withdraw(SOL).unwrap();
+    assert_eq!(
+        platform.harness.lamports(&platform.admin),
+        admin_wallet + SOL
+    );
+
+    let withdrawn = 2 * SOL + SOL;
+    assert_treasury(&platform, 5 * SOL, withdrawn, 0, 0, common::GENESIS_TIME);
+    assert_eq!(
+        platform.harness.lamports(&treasury_pda()),
+        treasury_rent + 5 * SOL - withdrawn
+    );
+}
@@ -0,0 +1,581 @@
+//! Versioned account layouts: loaders refuse outdated and unknown versions, and
+//! `migrate_account` upgrades legacy accounts in place.
+
+mod common;
+
+use anchor_lang::error::ErrorCode;
+use anchor_lang::prelude::{ProgramError, Pubkey, Rent};
+use common::*;
+use trendxbet::{
+    AccountMigrated, BetState, ExposureLimits, GlobalState, MatchState, MatchStatus, OracleState,
+    TreasuryState, TrendXBetError, UserState, Versioned, BET_STATE_SPACE, DEFAULT_CRANK_BOUNTY,
+    DEFAULT_CREATOR_BOND, DEFAULT_DISPUTE_BOND, DEFAULT_DISPUTE_WINDOW, DEFAULT_MAX_CREATOR_FEE,
+    EXPOSURE_LIMITS_SPACE, GLOBAL_STATE_SPACE, LEGACY_ACCOUNT_VERSION, MATCH_STATE_SPACE,
+    ORACLE_STATE_SPACE, PYTH_ORACLE_PROGRAM_ID, REPORT_EVIDENCE_SPACE, USER_STATE_SPACE,
+};
+
+const HOUR: i64 = 3600;
+
+/// Length of the global state before it gained an audit log
+const V3_GLOBAL_STATE_SPACE: usize = GLOBAL_STATE_SPACE - 1 - 8;
+
+/// Length of the global state before it gained community market terms
+const V2_GLOBAL_STATE_SPACE: usize = V3_GLOBAL_STATE_SPACE - 8 - 2;
+
+/// Length of the global state before it gained exposure limits
+const V1_GLOBAL_STATE_SPACE: usize = V2_GLOBAL_STATE_SPACE - EXPOSURE_LIMITS_SPACE;
+
+/// Length of the global state before it gained a version byte and reserved space
+const LEGACY_GLOBAL_STATE_SPACE: usize = V1_GLOBAL_STATE_SPACE - 1 - 64;
+
+/// Length of the oracle state before it gained report evidence
+const V2_ORACLE_STATE_SPACE: usize = ORACLE_STATE_SPACE - (1 + REPORT_EVIDENCE_SPACE);
+
+/// Length of the oracle state before it gained a commitment and more reserved space
+const V1_ORACLE_STATE_SPACE: usize = V2_ORACLE_STATE_SPACE - (1 + 32) - (1 + 8) - 32 + 6;
+
+/// Length of the match state before it gained a creator, bond and fee
+const V2_MATCH_STATE_SPACE: usize = MATCH_STATE_SPACE - 32 - 8 - 2 - 1;
+
+/// Length of the match state before it gained a betting window and bonus weights
+const V1_MATCH_STATE_SPACE: usize = V2_MATCH_STATE_SPACE - 8 - 8 - 2 - 8 - 8;
+
+/// Length of the user state before it gained a bonus balance and wagering
+const V1_USER_STATE_SPACE: usize = USER_STATE_SPACE - 8 - 8 - 8;
+
+/// Length of the bet state before it recorded staked bonus winnings
+const V1_BET_STATE_SPACE: usize = BET_STATE_SPACE - 8;
+
+fn set_version<T: Versioned>(platform: &mut Platform, key: &Pubkey, version: u8) {
+    let mut state: T = platform.harness.state(key);
+    state.set_version(version);
+    platform.harness.set_state(key, &state);
+}
+
+#[test]
+fn legacy_global_state_is_grown_and_upgraded() {
+    let mut platform = Platform::new();
+    let admin = platform.admin;
+    let legacy_rent = Rent::default().minimum_balance(LEGACY_GLOBAL_STATE_SPACE);
+
+    // Strip the version byte and reserved space, as written by the first release
+    let legacy = platform.harness.account_mut(&platform_pda());
+    legacy.data.truncate(LEGACY_GLOBAL_STATE_SPACE);
+    legacy.lamports = legacy_rent;
+
+    let result =
+        platform.try_create_match(common::GENESIS_TIME + HOUR, common::GENESIS_TIME + 3 * HOUR);
+    assert_eq!(
+        result,
+        Err(ProgramError::Custom(
+            ErrorCode::AccountDidNotDeserialize.into()
+        ))
+    );
+
+    let admin_before = platform.harness.lamports(&admin);
+    platform.migrate_account(&platform_pda(), &admin).unwrap();
+
+    let shortfall = Rent::default().minimum_balance(GLOBAL_STATE_SPACE) - legacy_rent;
+    assert_eq!(platform.harness.lamports(&admin), admin_before - shortfall);
+    assert_eq!(
+        platform
+            .harness
+            .account(&platform_pda())
+            .unwrap()
+            .data
+            .len(),
+        GLOBAL_STATE_SPACE
+    );
+
+    let global: GlobalState = platform.harness.state(&platform_pda());
+    assert_eq!(global.version, GlobalState::VERSION);
+    assert_eq!(global.admin, admin);
+    assert_eq!(global.crank_bounty, DEFAULT_CRANK_BOUNTY);
+    assert_eq!(global.price_feed_program, PYTH_ORACLE_PROGRAM_ID);
+    assert_eq!(
+        (global.dispute_window, global.dispute_bond),
+        (DEFAULT_DISPUTE_WINDOW, DEFAULT_DISPUTE_BOND)
+    );
+
+    let events = platform.harness.events::<AccountMigrated>();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].account, platform_pda());
+    assert_eq!(events[0].account_type, "global");
+    assert_eq!(events[0].from_version, LEGACY_ACCOUNT_VERSION);
+    assert_eq!(events[0].to_version, GlobalState::VERSION);
+
+    platform.create_match(HOUR, 2 * HOUR);
+}
+
+#[test]
+fn outdated_accounts_are_refused_until_migrated() {
+    let mut platform = Platform::new();
+    let admin = platform.admin;
+    let alice = platform.user("alice");
+    platform.deposit(&alice, 5 * SOL).unwrap();
+    let match_id = platform.create_match(HOUR, 2 * HOUR);
+    platform.harness.warp_by(HOUR);
+
+    set_version::<MatchState>(&mut platform, &match_pda(&match_id), LEGACY_ACCOUNT_VERSION);
+    assert_error(
+        platform.place_bet(&alice, &match_id, SOL, 0),
+        TrendXBetError::UnsupportedAccountVersion,
+    );
+
+    // Only the platform admin may migrate
+    assert_error(
+        platform.migrate_account(&match_pda(&match_id), &alice),
+        TrendXBetError::Unauthorized,
+    );
+
+    platform
+        .migrate_account(&match_pda(&match_id), &admin)
+        .unwrap();
+    let events = platform.harness.events::<AccountMigrated>();
+    assert_eq!(events[0].account_type, "match");
+    assert_eq!(events[0].from_version, LEGACY_ACCOUNT_VERSION);
+
+    let state: MatchState = platform.harness.state(&match_pda(&match_id));
+    assert_eq!(state.version, MatchState::VERSION);
+    assert_eq!(state.team1, "India");
+
+    platform.place_bet(&alice, &match_id, SOL, 0).unwrap();
+    assert_error(
+        platform.migrate_account(&match_pda(&match_id), &admin),
+        TrendXBetError::AccountAlreadyMigrated,
+    );
+}
+
+#[test]
+fn unknown_versions_are_refused() {
+    let mut platform = Platform::new();
+    let admin = platform.admin;
+    let alice = platform.user("alice");
+
+    set_version::<UserState>(&mut platform, &user_pda(&alice), UserState::VERSION + 1);
+    assert_error(
+        platform.deposit(&alice, SOL),
+        TrendXBetError::UnsupportedAccountVersion,
+    );
+    assert_error(
+        platform.migrate_account(&user_pda(&alice), &admin),
+        TrendXBetError::UnsupportedAccountVersion,
+    );
+
+    // Accounts the program does not own cannot be migrated
+    assert_eq!(
+        platform.migrate_account(&alice, &admin),
+        Err(ProgramError::Custom(ErrorCode::ConstraintOwner.into()))
+    );
+}
+
+#[test]
+fn oracle_state_is_grown_for_commitments() {
+    let mut platform = Platform::new();
+    let admin = platform.admin;
+    let match_id = platform.create_match(HOUR, 2 * HOUR);
+    let oracle = platform.register_oracle(&match_id);
+    let oracle_state = oracle_pda(&oracle, &match_id);
+
+    set_version::<OracleState>(&mut platform, &oracle_state, 1);
+    platform
+        .harness
+        .account_mut(&oracle_state)
+        .data
+        .truncate(V1_ORACLE_STATE_SPACE);
+
+    platform.harness.warp_by(3 * HOUR);
+    let cranker = platform.harness.fund(SOL);
+    platform.crank_match(&match_id, &cranker).unwrap();
+    assert_error(
+        platform.commit_result(&oracle, &match_id, 0, "1-0", None),
+        TrendXBetError::UnsupportedAccountVersion,
+    );
+
+    platform.migrate_account(&oracle_state, &admin).unwrap();
+    let events = platform.harness.events::<AccountMigrated>();
+    assert_eq!(events[0].account_type, "oracle");
+    assert_eq!(
+        (events[0].from_version, events[0].to_version),
+        (1, OracleState::VERSION)
+    );
+    assert_eq!(
+        platform.harness.account(&oracle_state).unwrap().data.len(),
+        ORACLE_STATE_SPACE
+    );
+
+    platform
+        .commit_result(&oracle, &match_id, 0, "1-0", None)
+        .unwrap();
+}
+
+#[test]
+fn oracle_state_is_grown_for_evidence() {
+    let mut platform = Platform::new();
+    let admin = platform.admin;
+    let match_id = platform.create_match(HOUR, 2 * HOUR);
+    let oracle = platform.register_oracle(&match_id);
+    let oracle_state = oracle_pda(&oracle, &match_id);
+    platform.harness.warp_by(3 * HOUR);
+    let cranker = platform.harness.fund(SOL);
+    platform.crank_match(&match_id, &cranker).unwrap();
+    platform
+        .commit_result(&oracle, &match_id, 0, "1-0", None)
+        .unwrap();
+
+    // A commitment made before the upgrade can still be revealed after it
+    set_version::<OracleState>(&mut platform, &oracle_state, 2);
+    platform
+        .harness
+        .account_mut(&oracle_state)
+        .data
+        .truncate(V2_ORACLE_STATE_SPACE);
+    platform.open_reveals(&match_id);
+    assert_error(
+        platform.reveal_result(&oracle, &match_id, 0, "1-0", None),
+        TrendXBetError::UnsupportedAccountVersion,
+    );
+
+    platform.migrate_account(&oracle_state, &admin).unwrap();
+    let events = platform.harness.events::<AccountMigrated>();
+    assert_eq!((events[0].from_version, events[0].to_version), (2, 3));
+    let state: OracleState = platform.harness.state(&oracle_state);
+    assert!(state.commitment.is_some());
+    assert_eq!(state.evidence, None);
+
+    platform
+        .reveal_result(&oracle, &match_id, 0, "1-0", None)
+        .unwrap();
+}
+
+#[test]
+fn global_state_is_grown_for_exposure_limits() {
+    let mut platform = Platform::new();
+    let admin = platform.admin;
+    let alice = platform.user("alice");
+    platform.deposit(&alice, 5 * SOL).unwrap();
+    let match_id = platform.create_match(HOUR, 2 * HOUR);
+    platform.harness.warp_by(HOUR);
+
+    set_version::<GlobalState>(&mut platform, &platform_pda(), 1);
+    platform
+        .harness
+        .account_mut(&platform_pda())
+        .data
+        .truncate(V1_GLOBAL_STATE_SPACE);
+    assert_eq!(
+        platform.place_bet(&alice, &match_id, SOL, 0),
+        Err(ProgramError::Custom(
+            ErrorCode::AccountDidNotDeserialize.into()
+        ))
+    );
+
+    platform.migrate_account(&platform_pda(), &admin).unwrap();
+    let events = platform.harness.events::<AccountMigrated>();
+    assert_eq!((events[0].from_version, events[0].to_version), (1, 4));
+
+    // Upgraded platforms start without caps
+    let global: GlobalState = platform.harness.state(&platform_pda());
+    assert_eq!(global.exposure_limits, ExposureLimits::default());
+    assert_eq!(global.dispute_bond, DEFAULT_DISPUTE_BOND);
+    platform.place_bet(&alice, &match_id, SOL, 0).unwrap();
+}
+
+#[test]
+fn global_state_gains_crank_price_feed_and_dispute_defaults() {
+    let mut platform = Platform::new();
+    let admin = platform.admin;
+
+    // Early version 1 accounts had none of these settings
+    let mut global: GlobalState = platform.harness.state(&platform_pda());
+    global.version = 1;
+    global.crank_bounty = 0;
+    global.price_feed_program = Pubkey::default();
+    global.dispute_window = 0;
+    global.dispute_bond = 0;
+    platform.harness.set_state(&platform_pda(), &global);
+    platform
+        .harness
+        .account_mut(&platform_pda())
+        .data
+        .truncate(V1_GLOBAL_STATE_SPACE);
+
+    platform.migrate_account(&platform_pda(), &admin).unwrap();
+    let global: GlobalState = platform.harness.state(&platform_pda());
+    assert_eq!(global.crank_bounty, DEFAULT_CRANK_BOUNTY);
+    assert_eq!(global.price_feed_program, PYTH_ORACLE_PROGRAM_ID);
+    assert_eq!(global.dispute_window, DEFAULT_DISPUTE_WINDOW);
+    assert_eq!(global.dispute_bond, DEFAULT_DISPUTE_BOND);
+
+    // Settings chosen at version 1 are kept
+    let mut global = global;
+    global.version = 1;
+    global.crank_bounty = 1;
+    global.dispute_window = 60;
+    platform.harness.set_state(&platform_pda(), &global);
+    platform.migrate_account(&platform_pda(), &admin).unwrap();
+    let global: GlobalState = platform.harness.state(&platform_pda());
+    assert_eq!((global.crank_bounty, global.dispute_window), (1, 60));
+}
+
+#[test]
+fn match_state_is_grown_for_betting_windows() {
+    let mut platform = Platform::new();
+    let admin = platform.admin;
+    let alice = platform.user("alice");
+    platform.deposit(&alice, 5 * SOL).unwrap();
+    let match_id = platform.create_match(HOUR, 2 * HOUR);
+    platform.harness.warp_by(HOUR);
+
+    // Version 1 had no betting window; loading it zero-fills the new fields
+    let mut state: MatchState = platform.harness.state(&match_pda(&match_id));
+    state.version = 1;
+    state.betting_opens_at = 0;
+    state.betting_closes_at = 0;
+    platform.harness.set_state(&match_pda(&match_id), &state);
+    platform
+        .harness
+        .account_mut(&match_pda(&match_id))
+        .data
+        .truncate(V1_MATCH_STATE_SPACE);
+    assert_error(
+        platform.place_bet(&alice, &match_id, SOL, 0),
+        TrendXBetError::UnsupportedAccountVersion,
+    );
+
+    platform
+        .migrate_account(&match_pda(&match_id), &admin)
+        .unwrap();
+    let events = platform.harness.events::<AccountMigrated>();
+    assert_eq!((events[0].from_version, events[0].to_version), (1, 4));
+
+    // Upgraded matches keep taking bets for as long as they run
+    let state: MatchState = platform.harness.state(&match_pda(&match_id));
+    assert_eq!(
+        (state.betting_opens_at, state.betting_closes_at),
+        (state.start_time, state.end_time)
+    );
+    assert_eq!(state.early_bet_bonus, 0);
+    platform.place_bet(&alice, &match_id, SOL, 0).unwrap();
+}
+
+#[test]
+fn global_state_is_grown_for_community_markets() {
+    let mut platform = Platform::new();
+    let admin = platform.admin;
+    let creator = platform.harness.fund(5 * SOL);
+
+    set_version::<GlobalState>(&mut platform, &platform_pda(), 2);
+    platform
+        .harness
+        .account_mut(&platform_pda())
+        .data
+        .truncate(V2_GLOBAL_STATE_SPACE);
+    assert_eq!(
+        platform
+            .create_community_match(&creator, 0, HOUR, 2 * HOUR)
+            .map(|_| ()),
+        Err(ProgramError::Custom(
+            ErrorCode::AccountDidNotDeserialize.into()
+        ))
+    );
+
+    platform.migrate_account(&platform_pda(), &admin).unwrap();
+    let events = platform.harness.events::<AccountMigrated>();
+    assert_eq!((events[0].from_version, events[0].to_version), (2, 4));
+
+    // Upgraded platforms take community markets on the default terms
+    let global: GlobalState = platform.harness.state(&platform_pda());
+    assert_eq!(
+        (global.creator_bond, global.max_creator_fee),
+        (DEFAULT_CREATOR_BOND, DEFAULT_MAX_CREATOR_FEE)
+    );
+    platform
+        .create_community_match(&creator, DEFAULT_MAX_CREATOR_FEE, HOUR, 2 * HOUR)
+        .unwrap();
+}
+
+#[test]
+fn match_state_is_grown_for_creators() {
+    let mut platform = Platform::new();
+    let admin = platform.admin;
+    let alice = platform.user("alice");
+    platform.deposit(&alice, 5 * SOL).unwrap();
+    let match_id = platform.create_match(HOUR, 2 * HOUR);
+    platform.harness.warp_by(HOUR);
+
+    set_version::<MatchState>(&mut platform, &match_pda(&match_id), 2);
+    platform
+        .harness
+        .account_mut(&match_pda(&match_id))
+        .data
+        .truncate(V2_MATCH_STATE_SPACE);
+    assert_error(
+        platform.place_bet(&alice, &match_id, SOL, 0),
+        TrendXBetError::UnsupportedAccountVersion,
+    );
+
+    platform
+        .migrate_account(&match_pda(&match_id), &admin)
+        .unwrap();
+    let events = platform.harness.events::<AccountMigrated>();
+    assert_eq!((events[0].from_version, events[0].to_version), (2, 4));
+
+    // Upgraded matches have no creator and take no creator fee
+    let state: MatchState = platform.harness.state(&match_pda(&match_id));
+    assert!(!state.has_creator());
+    assert_eq!((state.creator_bond, state.creator_fee), (0, 0));
+    platform.place_bet(&alice, &match_id, SOL, 0).unwrap();
+}
+
+#[test]
+fn matches_under_way_count_their_crank_bounty_as_paid() {
+    let mut platform = Platform::new();
+    let admin = platform.admin;
+    let scheduled = platform.create_match(2 * HOUR, 2 * HOUR);
+    let live = platform.create_match(HOUR, 2 * HOUR);
+    platform.harness.warp_by(HOUR);
+    platform
+        .update_match_status(&live, MatchStatus::Live)
+        .unwrap();
+
+    // Version 3 paid a bounty on every crank
+    for match_id in [scheduled, live] {
+        set_version::<MatchState>(&mut platform, &match_pda(&match_id), 3);
+        platform
+            .migrate_account(&match_pda(&match_id), &admin)
+            .unwrap();
+        let events = platform.harness.events::<AccountMigrated>();
+        assert_eq!((events[0].from_version, events[0].to_version), (3, 4));
+    }
+    let state: MatchState = platform.harness.state(&match_pda(&scheduled));
+    assert!(!state.is_crank_bounty_paid);
+    let state: MatchState = platform.harness.state(&match_pda(&live));
+    assert!(state.is_crank_bounty_paid);
+}
+
+#[test]
+fn user_state_is_grown_for_bonus_balance() {
+    let mut platform = Platform::new();
+    let admin = platform.admin;
+    let alice = platform.user("alice");
+    platform.deposit(&alice, 5 * SOL).unwrap();
+
+    set_version::<UserState>(&mut platform, &user_pda(&alice), 1);
+    platform
+        .harness
+        .account_mut(&user_pda(&alice))
+        .data
+        .truncate(V1_USER_STATE_SPACE);
+    assert_error(
+        platform.withdraw(&alice, SOL),
+        TrendXBetError::UnsupportedAccountVersion,
+    );
+
+    platform.migrate_account(&user_pda(&alice), &admin).unwrap();
+    let events = platform.harness.events::<AccountMigrated>();
+    assert_eq!((events[0].from_version, events[0].to_version), (1, 2));
+
+    // Upgraded profiles keep their balance and start without a bonus
+    let state: UserState = platform.harness.state(&user_pda(&alice));
+    assert_eq!(state.balance, 5 * SOL);
+    assert_eq!((state.bonus_balance, state.wagering_remaining), (0, 0));
+    platform.withdraw(&alice, SOL).unwrap();
+}
+
+#[test]
+fn bet_state_is_grown_for_locked_stakes() {
+    let mut platform = Platform::new();
+    let admin = platform.admin;
+    let alice = platform.user("alice");
+    platform.deposit(&alice, 5 * SOL).unwrap();
+    let match_id = platform.create_match(HOUR, 2 * HOUR);
+    platform.harness.warp_by(HOUR);
+    platform.place_bet(&alice, &match_id, SOL, 0).unwrap();
+
+    let bet = bet_pda(&alice, &match_id);
+    set_version::<BetState>(&mut platform, &bet, 1);
+    platform
+        .harness
+        .account_mut(&bet)
+        .data
+        .truncate(V1_BET_STATE_SPACE);
+    assert_error(
+        platform.cancel_bet(&alice, &match_id, &alice),
+        TrendXBetError::UnsupportedAccountVersion,
+    );
+
+    platform.migrate_account(&bet, &admin).unwrap();
+    let events = platform.harness.events::<AccountMigrated>();
+    assert_eq!(events[0].account_type, "bet");
+    assert_eq!((events[0].from_version, events[0].to_version), (1, 2));
+
+    // Upgraded bets keep their stake and staked no locked winnings
+    let state: BetState = platform.harness.state(&bet);
+    assert_eq!((state.amount, state.locked_stake), (SOL, 0));
+    platform.cancel_bet(&alice, &match_id, &alice).unwrap();
+}
+
+#[test]
+fn treasury_gains_unearned_fees() {
+    let mut platform = Platform::new();
+    let admin = platform.admin;
+    let alice = platform.user("alice");
+    platform.deposit(&alice, 5 * SOL).unwrap();
+    let match_id = platform.create_match(HOUR, 2 * HOUR);
+    platform.harness.warp_by(HOUR);
+    platform.place_bet(&alice, &match_id, SOL, 0).unwrap();
+
+    // Version 1 kept no unearned fees in what was then reserved space
+    let mut treasury: TreasuryState = platform.harness.state(&treasury_pda());
+    treasury.unearned_fees = 0;
+    treasury.set_version(1);
+    platform.harness.set_state(&treasury_pda(), &treasury);
+    assert_error(
+        platform.cancel_bet(&alice, &match_id, &alice),
+        TrendXBetError::UnsupportedAccountVersion,
+    );
+
+    platform.migrate_account(&treasury_pda(), &admin).unwrap();
+    let events = platform.harness.events::<AccountMigrated>();
+    assert_eq!(events[0].account_type, "treasury");
+    assert_eq!((events[0].from_version, events[0].to_version), (1, 2));
+
+    // Upgraded treasuries count the fees they already hold as earned
+    let state: TreasuryState = platform.harness.state(&treasury_pda());
+    assert_eq!(state.unearned_fees, 0);
+    assert_eq!(state.available_platform_fees(), treasury.platform_fees);
+    platform.cancel_bet(&alice, &match_id, &alice).unwrap();
+}
+
+#[test]
+fn global_state_is_grown_for_the_audit_log() {
+    let mut platform = Platform::new();
+    let admin = platform.admin;
+
+    set_version::<GlobalState>(&mut platform, &platform_pda(), 3);
+    platform
+        .harness
+        .account_mut(&platform_pda())
+        .data
+        .truncate(V3_GLOBAL_STATE_SPACE);
+    let now = platform.harness.now();
+    assert_eq!(
+        platform.try_create_match(now + HOUR, now + 2 * HOUR),
+        Err(ProgramError::Custom(
+            ErrorCode::AccountDidNotDeserialize.into()
+        ))
+    );
+
+    platform.migrate_account(&platform_pda(), &admin).unwrap();
+    let events = platform.harness.events::<AccountMigrated>();
+    assert_eq!((events[0].from_version, events[0].to_version), (3, 4));
+
+    // Upgraded platforms record nothing until the audit log is enabled
+    let global: GlobalState = platform.harness.state(&platform_pda());
+    assert!(!global.has_audit_log);
+    assert_eq!(global.audit_entries, 0);
+    platform.pause_platform().unwrap();
+    platform.enable_audit_log().unwrap();
+    platform.unpause_platform().unwrap();
+}
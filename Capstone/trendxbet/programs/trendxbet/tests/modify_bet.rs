@@ -0,0 +1,239 @@
+//! Modifying an open bet: topping up, partially withdrawing (less the
+//! cancellation fee) or switching side while betting is open.
+
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use common::*;
+use trendxbet::{
+    BetCancelled, BetModified, BetState, ExposureLimits, MatchState, SessionKey, TreasuryState,
+    TrendXBetError, UserState, BASIS_POINTS, DEFAULT_HOUSE_EDGE, MAX_CANCELLATION_FEE,
+    SESSION_PLACE_BET,
+};
+
+const HOUR: i64 = 3600;
+
+/// Open a match with `alice` holding `amount` on team 1 and a second bettor on team 2
+fn open_bet(platform: &mut Platform, amount: u64) -> (Pubkey, Pubkey) {
+    let alice = platform.user("alice");
+    let bob = platform.user("bob");
+    platform.deposit(&alice, 10 * SOL).unwrap();
+    platform.deposit(&bob, 10 * SOL).unwrap();
+    let match_id = platform.create_match(HOUR, 2 * HOUR);
+    platform.harness.warp_by(HOUR);
+    platform.place_bet(&alice, &match_id, amount, 0).unwrap();
+    platform.place_bet(&bob, &match_id, SOL, 1).unwrap();
+    (alice, match_id)
+}
+
+fn pools(platform: &Platform, match_id: &Pubkey) -> (u64, u64, u64) {
+    let state: MatchState = platform.harness.state(&match_pda(match_id));
+    (state.team1_pool, state.team2_pool, state.total_pool)
+}
+
+fn platform_fees(platform: &Platform) -> u64 {
+    platform
+        .harness
+        .state::<TreasuryState>(&treasury_pda())
+        .platform_fees
+}
+
+#[test]
+fn bets_can_be_topped_up_reduced_and_switched() {
+    let mut platform = Platform::new();
+    let (alice, match_id) = open_bet(&mut platform, 2 * SOL);
+
+    platform.modify_bet(&alice, &match_id, 3 * SOL, 0).unwrap();
+    assert_eq!(pools(&platform, &match_id), (3 * SOL, SOL, 4 * SOL));
+    let bet: BetState = platform.harness.state(&bet_pda(&alice, &match_id));
+    assert_eq!(bet.amount, 3 * SOL);
+    assert_eq!(
+        bet.platform_fee,
+        3 * SOL * DEFAULT_HOUSE_EDGE as u64 / BASIS_POINTS
+    );
+    let user: UserState = platform.harness.state(&user_pda(&alice));
+    assert_eq!((user.balance, user.total_volume), (7 * SOL, 3 * SOL));
+    assert_eq!(user.total_bets_placed, 1);
+
+    let fees_before = platform_fees(&platform);
+    platform.modify_bet(&alice, &match_id, SOL, 0).unwrap();
+    assert_eq!(pools(&platform, &match_id), (SOL, SOL, 2 * SOL));
+    assert_eq!(
+        platform_fees(&platform),
+        fees_before - 2 * SOL * DEFAULT_HOUSE_EDGE as u64 / BASIS_POINTS
+    );
+    let user: UserState = platform.harness.state(&user_pda(&alice));
+    assert_eq!((user.balance, user.total_volume), (9 * SOL, SOL));
+
+    // Switching side moves the whole stake at the odds of the other side
+    platform.modify_bet(&alice, &match_id, 2 * SOL, 1).unwrap();
+    let event = &platform.harness.events::<BetModified>()[0];
+    assert_eq!(
+        (
+            event.old_amount,
+            event.new_amount,
+            event.old_team,
+            event.new_team
+        ),
+        (SOL, 2 * SOL, 0, 1)
+    );
+    assert_eq!(pools(&platform, &match_id), (0, 3 * SOL, 3 * SOL));
+    let bet: BetState = platform.harness.state(&bet_pda(&alice, &match_id));
+    assert_eq!((bet.amount, bet.predicted_team), (2 * SOL, 1));
+    let user: UserState = platform.harness.state(&user_pda(&alice));
+    assert_eq!((user.balance, user.total_volume), (8 * SOL, 2 * SOL));
+
+    assert_error(
+        platform.modify_bet(&alice, &match_id, 2 * SOL, 1),
+        TrendXBetError::BetNotModified,
+    );
+    assert_error(
+        platform.modify_bet(&alice, &match_id, 11 * SOL, 1),
+        TrendXBetError::InsufficientBalance,
+    );
+}
+
+#[test]
+fn withdrawn_stake_pays_the_cancellation_fee() {
+    let mut platform = Platform::new();
+    let (alice, match_id) = open_bet(&mut platform, 3 * SOL);
+
+    assert_error(
+        platform.set_cancellation_fee(MAX_CANCELLATION_FEE + 1),
+        TrendXBetError::InvalidCancellationFee,
+    );
+    platform.set_cancellation_fee(100).unwrap();
+
+    // Reducing keeps 1% of the withdrawn stake as platform fees
+    let fees_before = platform_fees(&platform);
+    let bet: BetState = platform.harness.state(&bet_pda(&alice, &match_id));
+    platform.modify_bet(&alice, &match_id, SOL, 0).unwrap();
+    let fee = 2 * SOL / 100;
+    assert_eq!(
+        platform.harness.events::<BetModified>()[0].cancellation_fee,
+        fee
+    );
+    let user: UserState = platform.harness.state(&user_pda(&alice));
+    assert_eq!(user.balance, 7 * SOL + 2 * SOL - fee);
+    assert_eq!(
+        platform_fees(&platform),
+        fees_before - bet.platform_fee * 2 / 3 + fee
+    );
+
+    // Topping up is free, and cancelling the whole bet still refunds it in full
+    platform.modify_bet(&alice, &match_id, 2 * SOL, 0).unwrap();
+    let fees_before = platform_fees(&platform);
+    platform.cancel_bet(&alice, &match_id, &alice).unwrap();
+    let event = &platform.harness.events::<BetCancelled>()[0];
+    assert_eq!((event.amount, event.refund_amount), (2 * SOL, 2 * SOL));
+    let user: UserState = platform.harness.state(&user_pda(&alice));
+    assert_eq!(user.balance, 10 * SOL - fee);
+    let bet: BetState = platform.harness.state(&bet_pda(&alice, &match_id));
+    assert_eq!(platform_fees(&platform), fees_before - bet.platform_fee);
+}
+
+#[test]
+fn session_changes_count_toward_its_limits() {
+    let mut platform = Platform::new();
+    let alice = platform.user("alice");
+    platform.deposit(&alice, 10 * SOL).unwrap();
+    let key = platform.harness.fund(SOL);
+    let expires_at = platform.harness.now() + 3 * HOUR;
+    platform
+        .create_session_key(
+            &alice,
+            &key,
+            expires_at,
+            SOL,
+            3 * SOL / 2,
+            SESSION_PLACE_BET,
+        )
+        .unwrap();
+    let session = Some(session_pda(&alice, &key));
+    let match_id = platform.create_match(HOUR, 2 * HOUR);
+    let other_match = platform.create_match(HOUR, 2 * HOUR);
+    platform.harness.warp_by(HOUR);
+    platform
+        .place_bet_as(&alice, &match_id, SOL / 2, 0, &key, session)
+        .unwrap();
+
+    // Only the top-up counts toward the spend
+    platform
+        .modify_bet_as(&alice, &match_id, SOL, 0, &key, session)
+        .unwrap();
+    let state: SessionKey = platform.harness.state(&session_pda(&alice, &key));
+    assert_eq!(state.spent, SOL);
+
+    // The topped-up bet is held to the per-bet cap
+    assert_error(
+        platform.modify_bet_as(&alice, &match_id, 3 * SOL / 2, 0, &key, session),
+        TrendXBetError::SessionLimitExceeded,
+    );
+
+    platform
+        .place_bet_as(&alice, &other_match, SOL / 2, 0, &key, session)
+        .unwrap();
+    assert_error(
+        platform.modify_bet_as(&alice, &other_match, SOL, 0, &key, session),
+        TrendXBetError::SessionLimitExceeded,
+    );
+    let state: SessionKey = platform.harness.state(&session_pda(&alice, &key));
+    assert_eq!(state.spent, 3 * SOL / 2);
+
+    // Withdrawing any stake needs the cancel permission, switching side included
+    assert_error(
+        platform.modify_bet_as(&alice, &match_id, SOL / 2, 0, &key, session),
+        TrendXBetError::SessionNotPermitted,
+    );
+    assert_error(
+        platform.modify_bet_as(&alice, &match_id, SOL / 4, 1, &key, session),
+        TrendXBetError::SessionNotPermitted,
+    );
+    platform.modify_bet(&alice, &match_id, SOL, 1).unwrap();
+}
+
+#[test]
+fn added_stake_is_held_to_the_checks_of_a_new_bet() {
+    let mut platform = Platform::new();
+    let admin = platform.admin;
+    let alice = platform.user("alice");
+    platform.deposit(&alice, 10 * SOL).unwrap();
+    platform
+        .set_exposure_limits(ExposureLimits {
+            max_match_pool: 0,
+            max_pool_imbalance: 0,
+            max_user_match_stake: 2 * SOL,
+        })
+        .unwrap();
+    let match_id = platform.create_match(HOUR, 2 * HOUR);
+    platform
+        .create_private_pool(&admin, &match_id, &[alice], None, false)
+        .unwrap();
+    platform.harness.warp_by(HOUR);
+    platform.place_bet(&alice, &match_id, SOL, 0).unwrap();
+
+    // The per-user cap applies to the whole stake once changed
+    assert_error(
+        platform.modify_bet(&alice, &match_id, 3 * SOL, 0),
+        TrendXBetError::UserMatchStakeCapExceeded,
+    );
+    platform.modify_bet(&alice, &match_id, 2 * SOL, 0).unwrap();
+
+    // Bettors dropped from a private pool can only take stake out
+    platform
+        .update_private_pool(&admin, &match_id, &[], None)
+        .unwrap();
+    assert_error(
+        platform.modify_bet(&alice, &match_id, 3 * SOL, 0),
+        TrendXBetError::NotInvitedToPool,
+    );
+    platform.modify_bet(&alice, &match_id, SOL, 0).unwrap();
+
+    platform
+        .close_private_pool_betting(&admin, &match_id)
+        .unwrap();
+    assert_error(
+        platform.modify_bet(&alice, &match_id, 2 * SOL, 0),
+        TrendXBetError::CannotModifyBet,
+    );
+}
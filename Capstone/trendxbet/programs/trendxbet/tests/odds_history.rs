@@ -0,0 +1,137 @@
+//! Odds history: a per-match ring buffer of pools and implied odds that every
+//! bet placed, modified or cancelled appends to, readable a page at a time.
+
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::system_program;
+use common::*;
+use trendxbet::{
+    accounts, instruction as ix, MatchState, OddsHistory, OddsHistoryCreated, TrendXBetError,
+    BASIS_POINTS, ODDS_HISTORY_CAPACITY, ODDS_HISTORY_PAGE_SIZE,
+};
+
+const HOUR: i64 = 3600;
+
+/// Open a charted match and a funded bettor
+fn charted_match(platform: &mut Platform) -> (Pubkey, Pubkey) {
+    let alice = platform.user("alice");
+    platform.deposit(&alice, 50 * SOL).unwrap();
+    let match_id = platform.create_match(HOUR, 2 * HOUR);
+    let payer = platform.harness.fund(SOL);
+    platform.create_odds_history(&match_id, &payer).unwrap();
+    platform.harness.warp_by(HOUR);
+    (alice, match_id)
+}
+
+#[test]
+fn bets_append_their_pools_and_odds() {
+    let mut platform = Platform::new();
+    let (alice, match_id) = charted_match(&mut platform);
+    let event = &platform.harness.events::<OddsHistoryCreated>()[0];
+    assert_eq!(event.match_id, match_id);
+    let bob = platform.user("bob");
+    platform.deposit(&bob, 5 * SOL).unwrap();
+
+    platform.place_bet(&alice, &match_id, 3 * SOL, 0).unwrap();
+    platform.harness.warp_by(60);
+    platform.place_bet(&bob, &match_id, SOL, 1).unwrap();
+    platform.modify_bet(&alice, &match_id, SOL, 0).unwrap();
+    platform.cancel_bet(&bob, &match_id, &bob).unwrap();
+
+    let series = platform.odds_history(&match_id, 0);
+    let pools: Vec<(u64, u64)> = series
+        .iter()
+        .map(|point| (point.team1_pool, point.team2_pool))
+        .collect();
+    assert_eq!(
+        pools,
+        vec![(0, 0), (3 * SOL, 0), (3 * SOL, SOL), (SOL, SOL), (SOL, 0)]
+    );
+    assert_eq!(series[2].timestamp, series[1].timestamp + 60);
+
+    // Each point carries the odds bettors were quoted at that moment
+    let state: MatchState = platform.harness.state(&match_pda(&match_id));
+    let latest = series.last().unwrap();
+    assert_eq!(
+        (latest.team1_odds, latest.team2_odds),
+        (state.get_team_odds(0), state.get_team_odds(1))
+    );
+    assert_eq!(series[0].team1_odds, BASIS_POINTS);
+}
+
+#[test]
+fn history_keeps_the_latest_points_and_reads_in_pages() {
+    let mut platform = Platform::new();
+    let (alice, match_id) = charted_match(&mut platform);
+    platform.place_bet(&alice, &match_id, SOL, 0).unwrap();
+    let changes = ODDS_HISTORY_CAPACITY as u64 + 5;
+    for change in 0..changes {
+        platform
+            .modify_bet(&alice, &match_id, SOL + (change % 2 + 1) * SOL, 0)
+            .unwrap();
+    }
+
+    // The creation point, the bet and the oldest modifications were overwritten
+    let history: OddsHistory = platform.harness.state(&odds_history_pda(&match_id));
+    let series = history.series();
+    assert_eq!(series.len(), ODDS_HISTORY_CAPACITY);
+    assert_eq!(
+        series.last().unwrap().team1_pool,
+        SOL + ((changes - 1) % 2 + 1) * SOL
+    );
+
+    let first_page = platform.odds_history(&match_id, 0);
+    assert_eq!(first_page, series[..ODDS_HISTORY_PAGE_SIZE]);
+    let last_offset = (ODDS_HISTORY_CAPACITY - 4) as u16;
+    assert_eq!(
+        platform.odds_history(&match_id, last_offset),
+        series[ODDS_HISTORY_CAPACITY - 4..]
+    );
+    assert!(platform
+        .odds_history(&match_id, ODDS_HISTORY_CAPACITY as u16)
+        .is_empty());
+}
+
+#[test]
+fn charted_matches_require_the_history_on_every_bet() {
+    let mut platform = Platform::new();
+    let (alice, match_id) = charted_match(&mut platform);
+    let payer = platform.harness.fund(SOL);
+    assert!(platform.create_odds_history(&match_id, &payer).is_err());
+
+    let unrecorded = platform.harness.send(
+        accounts::PlaceBet {
+            bet_state: bet_pda(&alice, &match_id),
+            user_state: user_pda(&alice),
+            match_state: match_pda(&match_id),
+            global_state: platform_pda(),
+            treasury: treasury_pda(),
+            match_id,
+            bettor: alice,
+            authority: alice,
+            system_program: system_program::ID,
+            session: None,
+            private_pool: None,
+            odds_history: None,
+        },
+        ix::PlaceBet {
+            amount: SOL,
+            predicted_team: 0,
+            odds_accepted: 0,
+            use_bonus: false,
+        },
+        &[alice],
+    );
+    assert_error(unrecorded, TrendXBetError::OddsHistoryRequired);
+
+    // Only matches still taking bets or awaiting their result can be charted
+    let match_id = platform.create_match(HOUR, 2 * HOUR);
+    platform
+        .update_match_status(&match_id, trendxbet::MatchStatus::Cancelled)
+        .unwrap();
+    assert_error(
+        platform.create_odds_history(&match_id, &payer),
+        TrendXBetError::InvalidMatchStatus,
+    );
+}
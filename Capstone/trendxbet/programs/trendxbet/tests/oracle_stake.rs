@@ -0,0 +1,238 @@
+//! Oracle staking: bonded stake gates registration, reports that match
+//! consensus earn a share of the match fees and reports against it are slashed.
+
+mod common;
+
+use common::*;
+use trendxbet::{
+    MatchState, OracleProfile, OracleReportResolved, OracleStakeBonded, OracleStakeUnbonding,
+    TreasuryState, TrendXBetError, MIN_ORACLE_STAKE, ORACLE_INITIAL_REPUTATION,
+    ORACLE_REPUTATION_GAIN, ORACLE_REPUTATION_PENALTY, ORACLE_UNBONDING_PERIOD,
+};
+
+const HOUR: i64 = 3600;
+
+fn profile(platform: &Platform, oracle: &anchor_lang::prelude::Pubkey) -> OracleProfile {
+    platform.harness.state(&oracle_profile_pda(oracle))
+}
+
+#[test]
+fn registration_needs_bonded_stake_and_reputation() {
+    let mut platform = Platform::new();
+    let match_id = platform.create_match(HOUR, 2 * HOUR);
+    let oracle = platform.oracle(0);
+
+    assert_error(
+        platform.register_oracle_authority(&oracle, &match_id),
+        TrendXBetError::InsufficientOracleStake,
+    );
+    platform
+        .bond_oracle_stake(&oracle, MIN_ORACLE_STAKE / 2)
+        .unwrap();
+    assert_error(
+        platform.register_oracle_authority(&oracle, &match_id),
+        TrendXBetError::InsufficientOracleStake,
+    );
+    platform
+        .bond_oracle_stake(&oracle, MIN_ORACLE_STAKE / 2)
+        .unwrap();
+    assert_eq!(
+        platform.harness.events::<OracleStakeBonded>()[0].total_stake,
+        MIN_ORACLE_STAKE
+    );
+    platform
+        .register_oracle_authority(&oracle, &match_id)
+        .unwrap();
+
+    // A poor track record keeps an oracle out however much it bonds
+    let second_match = platform.create_match(HOUR, 2 * HOUR);
+    let mut state = profile(&platform, &oracle);
+    state.reputation = 1_000;
+    platform
+        .harness
+        .set_state(&oracle_profile_pda(&oracle), &state);
+    assert_error(
+        platform.register_oracle_authority(&oracle, &second_match),
+        TrendXBetError::OracleReputationTooLow,
+    );
+}
+
+#[test]
+fn unbonded_stake_is_withdrawn_after_the_unbonding_period() {
+    let mut platform = Platform::new();
+    let oracle = platform.oracle(MIN_ORACLE_STAKE);
+
+    assert_error(
+        platform.unbond_oracle_stake(&oracle, MIN_ORACLE_STAKE + 1),
+        TrendXBetError::InsufficientOracleStake,
+    );
+    platform
+        .unbond_oracle_stake(&oracle, MIN_ORACLE_STAKE / 4)
+        .unwrap();
+    let unbonding = &platform.harness.events::<OracleStakeUnbonding>()[0];
+    assert_eq!(
+        unbonding.available_at,
+        platform.harness.now() + ORACLE_UNBONDING_PERIOD
+    );
+
+    // Unbonding stake no longer counts towards registration
+    let match_id = platform.create_match(HOUR, 2 * HOUR);
+    assert_error(
+        platform.register_oracle_authority(&oracle, &match_id),
+        TrendXBetError::InsufficientOracleStake,
+    );
+
+    assert_error(
+        platform.withdraw_oracle_stake(&oracle),
+        TrendXBetError::StakeStillUnbonding,
+    );
+    platform.harness.warp_by(ORACLE_UNBONDING_PERIOD);
+    let before = platform.harness.lamports(&oracle);
+    platform.withdraw_oracle_stake(&oracle).unwrap();
+    assert_eq!(
+        platform.harness.lamports(&oracle),
+        before + MIN_ORACLE_STAKE / 4
+    );
+    assert_error(
+        platform.withdraw_oracle_stake(&oracle),
+        TrendXBetError::NothingUnbonding,
+    );
+
+    let state = profile(&platform, &oracle);
+    assert_eq!(state.stake, MIN_ORACLE_STAKE * 3 / 4);
+    assert_eq!(state.unbonding_amount, 0);
+}
+
+#[test]
+fn reports_are_rewarded_or_slashed_against_consensus() {
+    let mut platform = Platform::new();
+    let alice = platform.user("alice");
+    let bob = platform.user("bob");
+    let carol = platform.user("carol");
+    for user in [alice, bob, carol] {
+        platform.deposit(&user, 5 * SOL).unwrap();
+    }
+
+    let match_id = platform.create_match(HOUR, 2 * HOUR);
+    platform.harness.warp_by(HOUR);
+    platform.place_bet(&alice, &match_id, 2 * SOL, 0).unwrap();
+    platform.place_bet(&bob, &match_id, 2 * SOL, 1).unwrap();
+
+    // Cancelled bets take their fee back out of the match
+    platform.place_bet(&carol, &match_id, SOL, 1).unwrap();
+    platform.cancel_bet(&carol, &match_id, &carol).unwrap();
+    let match_fees = platform
+        .harness
+        .state::<MatchState>(&match_pda(&match_id))
+        .platform_fees;
+    assert_eq!(match_fees, 4 * SOL / 20);
+
+    let [honest_a, honest_b, dissenter, withholder, absent] =
+        [(); 5].map(|_| platform.register_oracle(&match_id));
+    platform.harness.warp_by(2 * HOUR);
+    let caller = platform.harness.fund(SOL);
+    platform.crank_match(&match_id, &caller).unwrap();
+
+    for oracle in [honest_a, honest_b, withholder] {
+        platform
+            .commit_result(&oracle, &match_id, 0, "3-1", None)
+            .unwrap();
+    }
+    platform
+        .commit_result(&dissenter, &match_id, 1, "1-3", None)
+        .unwrap();
+    platform.open_reveals(&match_id);
+    for oracle in [honest_a, honest_b] {
+        platform
+            .reveal_result(&oracle, &match_id, 0, "3-1", None)
+            .unwrap();
+    }
+    platform
+        .reveal_result(&dissenter, &match_id, 1, "1-3", None)
+        .unwrap();
+
+    assert_error(
+        platform.resolve_oracle_report(&honest_a, &match_id, &caller),
+        TrendXBetError::NoOracleConsensus,
+    );
+    platform
+        .validate_oracle(&honest_a, &match_id, &[honest_b, dissenter])
+        .unwrap();
+
+    // Reports are resolved against a final result only
+    assert_error(
+        platform.resolve_oracle_report(&honest_a, &match_id, &caller),
+        TrendXBetError::DisputeWindowOpen,
+    );
+    platform.close_dispute_window(&match_id);
+    platform.settle_bet(&alice, &match_id, &alice).unwrap();
+    platform.settle_bet(&bob, &match_id, &bob).unwrap();
+
+    // Agreeing oracles split the fee share across the three revealed reports
+    let reward = match_fees * 2_000 / 10_000 / 3;
+    let before = platform.harness.lamports(&honest_a);
+    platform
+        .resolve_oracle_report(&honest_a, &match_id, &caller)
+        .unwrap();
+    assert_eq!(platform.harness.lamports(&honest_a), before + reward);
+    let resolved = &platform.harness.events::<OracleReportResolved>()[0];
+    assert!(resolved.agreed);
+    assert_eq!((resolved.reward, resolved.slashed), (reward, 0));
+    let state = profile(&platform, &honest_a);
+    assert_eq!(state.reports_agreed, 1);
+    assert_eq!(state.total_rewards, reward);
+    assert_eq!(
+        state.reputation,
+        ORACLE_INITIAL_REPUTATION + ORACLE_REPUTATION_GAIN
+    );
+    assert_error(
+        platform.resolve_oracle_report(&honest_a, &match_id, &caller),
+        TrendXBetError::OracleReportAlreadyResolved,
+    );
+
+    // Reporting against consensus, or withholding a committed report, is slashed
+    let slash = MIN_ORACLE_STAKE / 10;
+    for oracle in [dissenter, withholder] {
+        let treasury_before = platform.harness.lamports(&treasury_pda());
+        let fees_before = platform
+            .harness
+            .state::<TreasuryState>(&treasury_pda())
+            .platform_fees;
+        platform
+            .resolve_oracle_report(&oracle, &match_id, &caller)
+            .unwrap();
+        assert_eq!(
+            platform.harness.lamports(&treasury_pda()),
+            treasury_before + slash
+        );
+        let treasury: TreasuryState = platform.harness.state(&treasury_pda());
+        assert_eq!(treasury.platform_fees, fees_before + slash);
+
+        let state = profile(&platform, &oracle);
+        assert_eq!(state.stake, MIN_ORACLE_STAKE - slash);
+        assert_eq!(state.reports_slashed, 1);
+        assert_eq!(
+            state.reputation,
+            ORACLE_INITIAL_REPUTATION - ORACLE_REPUTATION_PENALTY
+        );
+    }
+    let slashed = &platform.harness.events::<OracleReportResolved>()[0];
+    assert!(!slashed.agreed);
+    assert_eq!((slashed.reward, slashed.slashed), (0, slash));
+
+    assert_error(
+        platform.resolve_oracle_report(&absent, &match_id, &caller),
+        TrendXBetError::OracleNotCommitted,
+    );
+
+    // Slashed below the minimum, the dissenter must top up before its next match
+    let next_match = platform.create_match(HOUR, 2 * HOUR);
+    assert_error(
+        platform.register_oracle_authority(&dissenter, &next_match),
+        TrendXBetError::InsufficientOracleStake,
+    );
+    platform.bond_oracle_stake(&dissenter, slash).unwrap();
+    platform
+        .register_oracle_authority(&dissenter, &next_match)
+        .unwrap();
+}
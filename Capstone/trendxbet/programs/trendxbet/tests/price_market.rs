@@ -0,0 +1,289 @@
+//! Price-trend markets: "will the asset be above the strike at end time",
+//! resolved by anyone from a Pyth-layout price feed instead of oracles.
+
+mod common;
+
+use anchor_lang::error::ErrorCode;
+use anchor_lang::prelude::{ProgramError, Pubkey, Rent};
+use common::*;
+use mock_price_feed::{PriceUpdate, STATUS_HALTED, STATUS_TRADING};
+use trendxbet::{
+    BetState, BetStatus, CommunityMarketCreated, MarketKind, MarketVoidedForAbuse, MatchState,
+    MatchStatus, PriceMarket, PriceMarketCreated, PriceMarketResolved, TrendXBetError,
+    DEFAULT_CREATOR_BOND, MATCH_STATE_SPACE, ORACLE_UPDATE_WINDOW, PRICE_RESOLUTION_TOLERANCE,
+};
+
+const HOUR: i64 = 3600;
+
+/// $150.00 with two decimals
+const STRIKE: i64 = 15_000;
+
+fn update(price: i64, publish_time: i64) -> PriceUpdate {
+    PriceUpdate {
+        price,
+        conf: 10,
+        expo: -2,
+        publish_time,
+        status: STATUS_TRADING,
+    }
+}
+
+fn match_state(platform: &Platform, match_id: &Pubkey) -> MatchState {
+    platform.harness.state(&match_pda(match_id))
+}
+
+/// Platform that accepts mock feeds, with a feed published at the current time
+fn setup() -> (Platform, Pubkey) {
+    let mut platform = Platform::new();
+    platform
+        .set_price_feed_program(mock_price_feed::ID)
+        .unwrap();
+    let now = platform.harness.now();
+    let feed = platform.price_feed(update(14_900, now));
+    (platform, feed)
+}
+
+#[test]
+fn price_market_settles_from_the_feed() {
+    let (mut platform, feed) = setup();
+    let alice = platform.user("alice");
+    let bob = platform.user("bob");
+    platform.deposit(&alice, 5 * SOL).unwrap();
+    platform.deposit(&bob, 5 * SOL).unwrap();
+    let resolver = platform.harness.fund(SOL);
+
+    let match_id = platform
+        .create_price_market(&feed, STRIKE, HOUR, HOUR)
+        .unwrap();
+    let state = match_state(&platform, &match_id);
+    assert!(state.market_kind == MarketKind::PriceTrend);
+    let created = &platform.harness.events::<PriceMarketCreated>()[0];
+    assert_eq!(created.price_feed, feed);
+    assert_eq!((created.strike_price, created.expo), (STRIKE, -2));
+    assert_eq!(created.resolution_time, state.end_time);
+
+    // Pools work as for any match: team 1 is above the strike, team 2 at or below
+    platform.harness.warp_to(state.start_time);
+    platform.place_bet(&alice, &match_id, SOL, 0).unwrap();
+    platform.place_bet(&bob, &match_id, 2 * SOL, 1).unwrap();
+
+    assert_error(
+        platform.resolve_price_market(&match_id, &feed, &resolver),
+        TrendXBetError::SettlementPeriodNotReached,
+    );
+
+    // The feed has not published since the end time yet
+    platform.harness.warp_to(state.end_time + 5);
+    assert_error(
+        platform.resolve_price_market(&match_id, &feed, &resolver),
+        TrendXBetError::PriceNotAtResolutionTime,
+    );
+
+    platform.set_price(&feed, update(15_050, state.end_time + 2));
+    platform
+        .resolve_price_market(&match_id, &feed, &resolver)
+        .unwrap();
+
+    let state = match_state(&platform, &match_id);
+    assert!(state.status == MatchStatus::Settled);
+    assert!(state.is_settled && state.is_betting_closed);
+    assert_eq!(state.winning_team, Some(0));
+    assert_eq!(state.final_score.as_deref(), Some("15050e-2"));
+
+    let market: PriceMarket = platform.harness.state(&price_market_pda(&match_id));
+    assert_eq!(market.resolved_price, Some(15_050));
+    assert_eq!(market.resolved_publish_time, Some(state.end_time + 2));
+
+    let resolved = &platform.harness.events::<PriceMarketResolved>()[0];
+    assert_eq!(resolved.winning_team, 0);
+    assert_eq!(resolved.resolver, resolver);
+
+    assert_error(
+        platform.resolve_price_market(&match_id, &feed, &resolver),
+        TrendXBetError::InvalidMatchStatus,
+    );
+
+    // Parimutuel settlement: alice takes the whole pool net of fees
+    platform.settle_bet(&alice, &match_id, &resolver).unwrap();
+    platform.settle_bet(&bob, &match_id, &resolver).unwrap();
+    let won: BetState = platform.harness.state(&bet_pda(&alice, &match_id));
+    assert!(won.status == BetStatus::Won);
+    assert_eq!(won.payout_amount, Some(3 * SOL * 95 / 100));
+    let lost: BetState = platform.harness.state(&bet_pda(&bob, &match_id));
+    assert!(lost.status == BetStatus::Lost);
+    platform.claim_winnings(&alice, &match_id).unwrap();
+}
+
+#[test]
+fn price_at_the_strike_resolves_below() {
+    let (mut platform, feed) = setup();
+    let resolver = platform.harness.fund(SOL);
+    let match_id = platform
+        .create_price_market(&feed, STRIKE, HOUR, HOUR)
+        .unwrap();
+    let end_time = match_state(&platform, &match_id).end_time;
+
+    platform
+        .harness
+        .warp_to(end_time + PRICE_RESOLUTION_TOLERANCE);
+    platform.set_price(&feed, update(STRIKE, end_time + PRICE_RESOLUTION_TOLERANCE));
+    platform
+        .resolve_price_market(&match_id, &feed, &resolver)
+        .unwrap();
+    assert_eq!(match_state(&platform, &match_id).winning_team, Some(1));
+}
+
+#[test]
+fn feeds_are_checked_before_they_are_trusted() {
+    let mut platform = Platform::new();
+    let resolver = platform.harness.fund(SOL);
+    let now = platform.harness.now();
+    let feed = platform.price_feed(update(14_900, now));
+
+    // Only feeds owned by the configured program are accepted
+    assert_error(
+        platform
+            .create_price_market(&feed, STRIKE, HOUR, HOUR)
+            .map(|_| ()),
+        TrendXBetError::InvalidPriceFeed,
+    );
+    platform
+        .set_price_feed_program(mock_price_feed::ID)
+        .unwrap();
+
+    let garbage = Pubkey::new_unique();
+    platform.harness.set_account(
+        garbage,
+        TestAccount {
+            lamports: SOL,
+            data: vec![7; 240],
+            owner: mock_price_feed::ID,
+            executable: false,
+        },
+    );
+    assert_error(
+        platform
+            .create_price_market(&garbage, STRIKE, HOUR, HOUR)
+            .map(|_| ()),
+        TrendXBetError::InvalidPriceFeed,
+    );
+
+    let match_id = platform
+        .create_price_market(&feed, STRIKE, HOUR, HOUR)
+        .unwrap();
+    let end_time = match_state(&platform, &match_id).end_time;
+    platform.harness.warp_to(end_time + 1);
+
+    // Only the feed recorded on the market can resolve it
+    let other = platform.price_feed(update(20_000, end_time + 1));
+    assert_error(
+        platform.resolve_price_market(&match_id, &other, &resolver),
+        TrendXBetError::InvalidPriceFeed,
+    );
+
+    let mut halted = update(15_100, end_time + 1);
+    halted.status = STATUS_HALTED;
+    platform.set_price(&feed, halted);
+    assert_error(
+        platform.resolve_price_market(&match_id, &feed, &resolver),
+        TrendXBetError::PriceFeedUnavailable,
+    );
+
+    let mut rescaled = update(1_510_000, end_time + 1);
+    rescaled.expo = -4;
+    platform.set_price(&feed, rescaled);
+    assert_error(
+        platform.resolve_price_market(&match_id, &feed, &resolver),
+        TrendXBetError::InvalidPriceFeed,
+    );
+
+    let mut uncertain = update(15_100, end_time + 1);
+    uncertain.conf = 500;
+    platform.set_price(&feed, uncertain);
+    assert_error(
+        platform.resolve_price_market(&match_id, &feed, &resolver),
+        TrendXBetError::PriceConfidenceTooWide,
+    );
+
+    // Team matches are left to their oracles and have no price market
+    let team_match = platform.create_match(HOUR, 2 * HOUR);
+    assert_eq!(
+        platform.resolve_price_market(&team_match, &feed, &resolver),
+        Err(ProgramError::Custom(
+            ErrorCode::AccountNotInitialized.into()
+        ))
+    );
+}
+
+#[test]
+fn price_market_without_a_timely_price_is_voided() {
+    let (mut platform, feed) = setup();
+    let alice = platform.user("alice");
+    platform.deposit(&alice, 5 * SOL).unwrap();
+    let stranger = platform.harness.fund(SOL);
+
+    let match_id = platform
+        .create_price_market(&feed, STRIKE, HOUR, HOUR)
+        .unwrap();
+    let end_time = match_state(&platform, &match_id).end_time;
+    platform.harness.warp_by(HOUR);
+    platform.place_bet(&alice, &match_id, SOL, 0).unwrap();
+
+    // The first price after the end time came too late to count
+    let late = end_time + PRICE_RESOLUTION_TOLERANCE + 1;
+    platform.harness.warp_to(late);
+    platform.set_price(&feed, update(15_500, late));
+    assert_error(
+        platform.resolve_price_market(&match_id, &feed, &stranger),
+        TrendXBetError::PriceNotAtResolutionTime,
+    );
+
+    platform
+        .harness
+        .warp_to(end_time + ORACLE_UPDATE_WINDOW + 1);
+    platform.void_match(&match_id, &stranger).unwrap();
+    platform.refund_bet(&alice, &match_id, &stranger).unwrap();
+    let bet: BetState = platform.harness.state(&bet_pda(&alice, &match_id));
+    assert!(bet.status == BetStatus::Refunded);
+}
+
+#[test]
+fn community_price_markets_bond_their_creator() {
+    let (mut platform, feed) = setup();
+    let admin = platform.admin;
+    let creator = platform.harness.fund(5 * SOL);
+
+    // Admin markets take no bond and pay no creator
+    assert_error(
+        platform
+            .create_community_price_market(&admin, 1, &feed, STRIKE, HOUR, HOUR)
+            .map(|_| ()),
+        TrendXBetError::InvalidCreatorFee,
+    );
+
+    let match_id = platform
+        .create_community_price_market(&creator, 100, &feed, STRIKE, HOUR, HOUR)
+        .unwrap();
+    let event = &platform.harness.events::<CommunityMarketCreated>()[0];
+    assert_eq!(
+        (event.creator, event.creator_bond, event.creator_fee),
+        (creator, DEFAULT_CREATOR_BOND, 100)
+    );
+    let state = match_state(&platform, &match_id);
+    assert_eq!(
+        (state.creator, state.creator_bond, state.creator_fee),
+        (creator, DEFAULT_CREATOR_BOND, 100)
+    );
+    assert_eq!(
+        platform.harness.lamports(&match_pda(&match_id)),
+        Rent::default().minimum_balance(MATCH_STATE_SPACE) + DEFAULT_CREATOR_BOND
+    );
+
+    // The bond is at stake like on any community market
+    platform.void_market_for_abuse(&match_id, &admin).unwrap();
+    let event = &platform.harness.events::<MarketVoidedForAbuse>()[0];
+    assert_eq!(
+        (event.creator, event.bond_forfeited),
+        (creator, DEFAULT_CREATOR_BOND)
+    );
+}
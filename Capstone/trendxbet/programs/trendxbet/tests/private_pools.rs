@@ -0,0 +1,167 @@
+//! Private pools: matches only invited bettors may back, optionally without a
+//! house edge, which their organiser can close or cancel.
+
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use common::*;
+use trendxbet::{
+    BetState, MatchState, MatchStatus, PrivatePool, PrivatePoolCancelled, PrivatePoolCreated,
+    TrendXBetError, MAX_POOL_MEMBERS,
+};
+
+const HOUR: i64 = 3600;
+
+/// Create a user with `5 * SOL` deposited
+fn bettor(platform: &mut Platform, username: &str) -> Pubkey {
+    let user = platform.user(username);
+    platform.deposit(&user, 5 * SOL).unwrap();
+    user
+}
+
+#[test]
+fn only_invited_bettors_can_bet_on_a_private_pool() {
+    let mut platform = Platform::new();
+    let admin = platform.admin;
+    let alice = bettor(&mut platform, "alice");
+    let bob = bettor(&mut platform, "bob");
+    let mallory = bettor(&mut platform, "mallory");
+    let match_id = platform.create_match(HOUR, 2 * HOUR);
+
+    assert_error(
+        platform.create_private_pool(&mallory, &match_id, &[alice], None, false),
+        TrendXBetError::Unauthorized,
+    );
+    platform
+        .create_private_pool(&admin, &match_id, &[alice], Some("friends"), false)
+        .unwrap();
+    let event = &platform.harness.events::<PrivatePoolCreated>()[0];
+    assert_eq!((event.members, event.has_invite_code), (1, true));
+    assert!(
+        platform
+            .harness
+            .state::<MatchState>(&match_pda(&match_id))
+            .is_private
+    );
+
+    platform.harness.warp_by(HOUR);
+    platform.place_bet(&alice, &match_id, SOL, 0).unwrap();
+    assert_error(
+        platform.place_bet(&bob, &match_id, SOL, 1),
+        TrendXBetError::NotInvitedToPool,
+    );
+
+    // The invite code lets anyone who knows it join
+    assert_error(
+        platform.join_private_pool(&bob, &match_id, "enemies"),
+        TrendXBetError::InvalidInviteCode,
+    );
+    platform
+        .join_private_pool(&bob, &match_id, "friends")
+        .unwrap();
+    platform.place_bet(&bob, &match_id, SOL, 1).unwrap();
+
+    // Dropping the code and listing only alice shuts mallory out for good
+    assert_error(
+        platform.update_private_pool(&mallory, &match_id, &[mallory], None),
+        TrendXBetError::Unauthorized,
+    );
+    platform
+        .update_private_pool(&admin, &match_id, &[alice], None)
+        .unwrap();
+    assert_error(
+        platform.join_private_pool(&mallory, &match_id, "friends"),
+        TrendXBetError::InvalidInviteCode,
+    );
+    let pool: PrivatePool = platform.harness.state(&private_pool_pda(&match_id));
+    assert!(!pool.is_member(&bob));
+
+    let too_many: Vec<Pubkey> = (0..=MAX_POOL_MEMBERS)
+        .map(|_| Pubkey::new_unique())
+        .collect();
+    assert_error(
+        platform.update_private_pool(&admin, &match_id, &too_many, None),
+        TrendXBetError::PrivatePoolFull,
+    );
+}
+
+#[test]
+fn private_pools_can_waive_the_house_edge() {
+    let mut platform = Platform::new();
+    let creator = platform.harness.fund(5 * SOL);
+    let alice = bettor(&mut platform, "alice");
+    let bob = bettor(&mut platform, "bob");
+    let match_id = platform
+        .create_community_match(&creator, 0, HOUR, 2 * HOUR)
+        .unwrap();
+    platform
+        .create_private_pool(&creator, &match_id, &[alice, bob], None, true)
+        .unwrap();
+
+    platform.harness.warp_by(HOUR);
+    platform.place_bet(&alice, &match_id, SOL, 0).unwrap();
+    platform.place_bet(&bob, &match_id, SOL, 1).unwrap();
+    let bet: BetState = platform.harness.state(&bet_pda(&alice, &match_id));
+    assert_eq!(bet.platform_fee, 0);
+
+    platform.harness.warp_by(2 * HOUR);
+    let cranker = platform.harness.fund(SOL);
+    platform.crank_match(&match_id, &cranker).unwrap();
+    platform.resolve_match(&match_id, 0);
+    platform.settle_bet(&alice, &match_id, &alice).unwrap();
+
+    // Winners take the whole pool
+    let bet: BetState = platform.harness.state(&bet_pda(&alice, &match_id));
+    assert_eq!(bet.payout_amount, Some(2 * SOL));
+}
+
+#[test]
+fn organiser_can_close_or_cancel_the_pool() {
+    let mut platform = Platform::new();
+    let creator = platform.harness.fund(5 * SOL);
+    let alice = bettor(&mut platform, "alice");
+    let bob = bettor(&mut platform, "bob");
+    let match_id = platform
+        .create_community_match(&creator, 0, HOUR, 2 * HOUR)
+        .unwrap();
+    platform
+        .create_private_pool(&creator, &match_id, &[alice, bob], None, false)
+        .unwrap();
+    platform.harness.warp_by(HOUR);
+    platform.place_bet(&alice, &match_id, SOL, 0).unwrap();
+
+    assert_error(
+        platform.close_private_pool_betting(&alice, &match_id),
+        TrendXBetError::Unauthorized,
+    );
+    platform
+        .close_private_pool_betting(&creator, &match_id)
+        .unwrap();
+    assert_error(
+        platform.place_bet(&bob, &match_id, SOL, 1),
+        TrendXBetError::BettingClosed,
+    );
+
+    assert_error(
+        platform.cancel_private_pool(&alice, &match_id),
+        TrendXBetError::Unauthorized,
+    );
+    platform.cancel_private_pool(&creator, &match_id).unwrap();
+    let event = &platform.harness.events::<PrivatePoolCancelled>()[0];
+    assert_eq!((event.total_pool, event.total_bets), (SOL, 1));
+    let state: MatchState = platform.harness.state(&match_pda(&match_id));
+    assert!(state.status == MatchStatus::Cancelled);
+
+    platform.refund_bet(&alice, &match_id, &alice).unwrap();
+
+    // Pools can only be made private before anyone has bet
+    let match_id = platform
+        .create_community_match(&creator, 0, HOUR, 2 * HOUR)
+        .unwrap();
+    platform.harness.warp_by(HOUR);
+    platform.place_bet(&alice, &match_id, SOL, 0).unwrap();
+    assert_error(
+        platform.create_private_pool(&creator, &match_id, &[alice], None, false),
+        TrendXBetError::InvalidMatchStatus,
+    );
+}
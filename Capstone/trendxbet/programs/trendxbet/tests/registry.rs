@@ -0,0 +1,235 @@
+//! Match registry and its paged league, status and start-day indexes, kept in
+//! step as matches change status.
+
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use common::*;
+use trendxbet::{
+    accounts, instruction as ix, MatchIndexKey, MatchIndexPage, MatchRegistry, MatchState,
+    MatchStatus, TrendXBetError, MATCH_INDEX_PAGE_SIZE, SECONDS_PER_DAY,
+};
+
+const HOUR: i64 = 3600;
+
+fn match_state(platform: &Platform, match_id: &Pubkey) -> MatchState {
+    platform.harness.state(&match_pda(match_id))
+}
+
+fn status_index(platform: &Platform, status: MatchStatus) -> Vec<Pubkey> {
+    platform.index(MatchIndexKey::status(&status))
+}
+
+#[test]
+fn matches_are_listed_by_status_day_and_league() {
+    let mut platform = Platform::new();
+    let unlisted = platform.create_match(HOUR, HOUR);
+    platform.create_match_registry().unwrap();
+
+    let start_time = platform.harness.now() + HOUR;
+    let listing = platform.listing(start_time, Some(7));
+    let in_league = platform
+        .try_create_listed_match(start_time, start_time + HOUR, listing)
+        .unwrap();
+    let same_day = platform.create_match(HOUR, HOUR);
+    let next_day = platform.create_match(SECONDS_PER_DAY + HOUR, HOUR);
+
+    assert_eq!(
+        status_index(&platform, MatchStatus::Scheduled),
+        vec![in_league, same_day, next_day]
+    );
+    assert_eq!(
+        platform.index(MatchIndexKey::start_day(start_time)),
+        vec![in_league, same_day]
+    );
+    assert_eq!(
+        platform.index(MatchIndexKey::start_day(start_time + SECONDS_PER_DAY)),
+        vec![next_day]
+    );
+    assert_eq!(platform.index(MatchIndexKey::League(7)), vec![in_league]);
+
+    let state = match_state(&platform, &in_league);
+    assert!(state.is_indexed);
+    assert_eq!(state.league, 7);
+    assert!(!match_state(&platform, &unlisted).is_indexed);
+    assert_eq!(
+        platform
+            .harness
+            .state::<MatchRegistry>(&match_registry_pda())
+            .total_matches,
+        3
+    );
+}
+
+#[test]
+fn status_index_follows_the_match_to_settlement() {
+    let mut platform = Platform::new();
+    platform.create_match_registry().unwrap();
+    let cranker = platform.harness.fund(SOL);
+    let match_id = platform.create_match(HOUR, HOUR);
+    let state = match_state(&platform, &match_id);
+
+    platform.harness.warp_to(state.live_at());
+    platform.crank_match(&match_id, &cranker).unwrap();
+    assert!(status_index(&platform, MatchStatus::Scheduled).is_empty());
+    assert_eq!(status_index(&platform, MatchStatus::Live), vec![match_id]);
+
+    platform.harness.warp_to(state.end_time);
+    platform.crank_match(&match_id, &cranker).unwrap();
+    assert!(status_index(&platform, MatchStatus::Live).is_empty());
+    assert_eq!(status_index(&platform, MatchStatus::Ended), vec![match_id]);
+
+    platform.resolve_match(&match_id, 0);
+    assert!(status_index(&platform, MatchStatus::Ended).is_empty());
+    assert_eq!(
+        status_index(&platform, MatchStatus::Settled),
+        vec![match_id]
+    );
+    // Day indexes do not change with the status
+    assert_eq!(
+        platform.index(MatchIndexKey::start_day(state.start_time)),
+        vec![match_id]
+    );
+}
+
+#[test]
+fn indexed_matches_require_their_pages() {
+    let mut platform = Platform::new();
+    platform.create_match_registry().unwrap();
+    let start_time = platform.harness.now() + HOUR;
+
+    let listing = platform.listing(start_time, None);
+    assert_error(
+        platform
+            .try_create_listed_match(
+                start_time,
+                start_time + HOUR,
+                Listing {
+                    status_index: None,
+                    ..listing
+                },
+            )
+            .map(drop),
+        TrendXBetError::MatchIndexRequired,
+    );
+    // Pages must belong to the index they are passed as
+    assert_error(
+        platform
+            .try_create_listed_match(
+                start_time,
+                start_time + HOUR,
+                Listing {
+                    league_index: listing.status_index,
+                    ..listing
+                },
+            )
+            .map(drop),
+        TrendXBetError::MatchIndexMismatch,
+    );
+    assert_error(
+        platform
+            .try_create_listed_match(
+                start_time,
+                start_time + HOUR,
+                Listing {
+                    status_index: listing.start_day_index,
+                    ..listing
+                },
+            )
+            .map(drop),
+        TrendXBetError::MatchIndexMismatch,
+    );
+
+    let match_id = platform
+        .try_create_listed_match(start_time, start_time + HOUR, listing)
+        .unwrap();
+    let cranker = platform.harness.fund(SOL);
+    platform
+        .harness
+        .warp_to(match_state(&platform, &match_id).live_at());
+    assert_error(
+        platform.harness.send(
+            accounts::CrankMatch {
+                match_state: match_pda(&match_id),
+                global_state: platform_pda(),
+                treasury: treasury_pda(),
+                treasury_account: treasury_pda(),
+                match_id,
+                cranker,
+                old_status_index: None,
+                new_status_index: None,
+            },
+            ix::CrankMatch {},
+            &[cranker],
+        ),
+        TrendXBetError::MatchIndexRequired,
+    );
+    platform.crank_match(&match_id, &cranker).unwrap();
+}
+
+#[test]
+fn index_pages_are_added_once_the_last_one_fills() {
+    let mut platform = Platform::new();
+    let payer = platform.harness.fund(SOL);
+    let league = MatchIndexKey::League(3);
+    assert!(platform.create_index_page(&payer, league, 0).is_err());
+
+    platform.create_match_registry().unwrap();
+    assert_error(
+        platform.create_index_page(&payer, MatchIndexKey::Status(42), 0),
+        TrendXBetError::InvalidMatchStatus,
+    );
+    platform.create_index_page(&payer, league, 0).unwrap();
+    assert_error(
+        platform.create_index_page(&payer, league, 1),
+        TrendXBetError::IndexPageNotFull,
+    );
+    // Skipping a page is caught by the page number of the previous one
+    assert!(platform.create_index_page(&payer, league, 2).is_err());
+
+    let start_time = platform.harness.now() + HOUR;
+    let mut listed = Vec::new();
+    for _ in 0..MATCH_INDEX_PAGE_SIZE {
+        let listing = platform.listing(start_time, Some(3));
+        listed.push(
+            platform
+                .try_create_listed_match(start_time, start_time + HOUR, listing)
+                .unwrap(),
+        );
+    }
+    let first_page = match_index_pda(&league, 0);
+    assert!(platform
+        .harness
+        .state::<MatchIndexPage>(&first_page)
+        .is_full());
+
+    // A full page refuses more matches
+    let listing = platform.listing(start_time, None);
+    assert_error(
+        platform
+            .try_create_listed_match(
+                start_time,
+                start_time + HOUR,
+                Listing {
+                    league_index: Some(first_page),
+                    ..listing
+                },
+            )
+            .map(drop),
+        TrendXBetError::IndexPageFull,
+    );
+
+    platform.create_index_page(&payer, league, 1).unwrap();
+    let listing = platform.listing(start_time, Some(3));
+    assert_eq!(listing.league_index, Some(match_index_pda(&league, 1)));
+    listed.push(
+        platform
+            .try_create_listed_match(start_time, start_time + HOUR, listing)
+            .unwrap(),
+    );
+    assert_eq!(platform.index(league), listed);
+    assert_eq!(
+        platform.index(MatchIndexKey::status(&MatchStatus::Scheduled)),
+        listed
+    );
+}
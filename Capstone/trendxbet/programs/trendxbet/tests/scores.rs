@@ -0,0 +1,243 @@
+//! Structured scores reported by oracles, and the totals and spread markets
+//! that settle deterministically from them.
+
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use common::*;
+use trendxbet::{
+    BetState, BetStatus, LineMarketCreated, MarketKind, MatchResultUpdated, MatchScore, MatchState,
+    MatchStatus, MatchVoided, PeriodScore, TrendXBetError, PUSH_RESULT,
+};
+
+const HOUR: i64 = 3600;
+
+fn score(home: u16, away: u16) -> MatchScore {
+    MatchScore {
+        home,
+        away,
+        periods: Vec::new(),
+    }
+}
+
+fn match_state(platform: &Platform, match_id: &Pubkey) -> MatchState {
+    platform.harness.state(&match_pda(match_id))
+}
+
+/// Move past the end of `match_id` and crank it to Ended
+fn end_match(platform: &mut Platform, match_id: &Pubkey) {
+    let end_time = match_state(platform, match_id).end_time;
+    platform.harness.warp_to(end_time);
+    let cranker = platform.harness.fund(SOL);
+    platform.crank_match(match_id, &cranker).unwrap();
+}
+
+#[test]
+fn totals_market_settles_on_combined_points() {
+    let mut platform = Platform::new();
+    let alice = platform.user("alice");
+    let bob = platform.user("bob");
+    platform.deposit(&alice, 5 * SOL).unwrap();
+    platform.deposit(&bob, 5 * SOL).unwrap();
+
+    // Over/under 2.5 goals
+    let match_id = platform
+        .create_line_market(MarketKind::Totals, 25, HOUR, 2 * HOUR)
+        .unwrap();
+    let created = &platform.harness.events::<LineMarketCreated>()[0];
+    assert!(created.market_kind == MarketKind::Totals);
+    assert_eq!(created.line, 25);
+
+    platform.harness.warp_by(HOUR);
+    platform.place_bet(&alice, &match_id, SOL, 0).unwrap();
+    platform.place_bet(&bob, &match_id, SOL, 1).unwrap();
+    end_match(&mut platform, &match_id);
+
+    let final_score = MatchScore {
+        home: 2,
+        away: 1,
+        periods: vec![
+            PeriodScore { home: 1, away: 0 },
+            PeriodScore { home: 1, away: 1 },
+        ],
+    };
+    let unscored = platform.register_oracle(&match_id);
+    let wrong_side = platform.register_oracle(&match_id);
+    let oracle_a = platform.register_oracle(&match_id);
+    let oracle_b = platform.register_oracle(&match_id);
+    platform
+        .commit_result(&unscored, &match_id, 0, "2-1", None)
+        .unwrap();
+    platform
+        .commit_result(&wrong_side, &match_id, 1, "2-1", Some(score(2, 1)))
+        .unwrap();
+    for oracle in [oracle_a, oracle_b] {
+        platform
+            .commit_result(&oracle, &match_id, 0, "2-1", Some(final_score.clone()))
+            .unwrap();
+    }
+
+    // The revealed result must follow from the score: 2-1 is over the line
+    platform.open_reveals(&match_id);
+    assert_error(
+        platform.reveal_result(&unscored, &match_id, 0, "2-1", None),
+        TrendXBetError::ScoreRequired,
+    );
+    assert_error(
+        platform.reveal_result(&wrong_side, &match_id, 1, "2-1", Some(score(2, 1))),
+        TrendXBetError::ScoreMismatch,
+    );
+    for oracle in [oracle_a, oracle_b] {
+        platform
+            .reveal_result(&oracle, &match_id, 0, "2-1", Some(final_score.clone()))
+            .unwrap();
+    }
+    let reported = &platform.harness.events::<MatchResultUpdated>()[0];
+    assert_eq!(reported.match_score, Some(final_score.clone()));
+
+    platform
+        .validate_oracle(&oracle_a, &match_id, &[oracle_b])
+        .unwrap();
+    let state = match_state(&platform, &match_id);
+    assert!(state.status == MatchStatus::Settled);
+    assert_eq!(state.winning_team, Some(0));
+    assert_eq!(state.match_score, Some(final_score));
+
+    platform.close_dispute_window(&match_id);
+    platform.settle_bet(&alice, &match_id, &alice).unwrap();
+    platform.settle_bet(&bob, &match_id, &bob).unwrap();
+    let over: BetState = platform.harness.state(&bet_pda(&alice, &match_id));
+    assert!(over.status == BetStatus::Won);
+    let under: BetState = platform.harness.state(&bet_pda(&bob, &match_id));
+    assert!(under.status == BetStatus::Lost);
+}
+
+#[test]
+fn spread_market_on_the_line_is_voided_as_a_push() {
+    let mut platform = Platform::new();
+    let alice = platform.user("alice");
+    platform.deposit(&alice, 5 * SOL).unwrap();
+
+    // Team 1 gives a one point handicap
+    let match_id = platform
+        .create_line_market(MarketKind::Spread, -10, HOUR, 2 * HOUR)
+        .unwrap();
+    platform.harness.warp_by(HOUR);
+    platform.place_bet(&alice, &match_id, SOL, 0).unwrap();
+    end_match(&mut platform, &match_id);
+
+    // Winning by exactly the handicap neither covers nor fails to
+    let careless = platform.register_oracle(&match_id);
+    platform
+        .commit_result(&careless, &match_id, 0, "3-2", Some(score(3, 2)))
+        .unwrap();
+    platform.resolve_match_with_score(&match_id, PUSH_RESULT, Some(score(3, 2)));
+
+    // Reveals after the push find the market already voided
+    assert_error(
+        platform.reveal_result(&careless, &match_id, 0, "3-2", Some(score(3, 2))),
+        TrendXBetError::InvalidMatchStatus,
+    );
+
+    let state = match_state(&platform, &match_id);
+    assert!(state.status == MatchStatus::Voided);
+    assert!(!state.is_settled);
+    assert_eq!(state.winning_team, None);
+    assert_eq!(state.match_score, Some(score(3, 2)));
+    assert_eq!(platform.harness.events::<MatchVoided>()[0].total_pool, SOL);
+
+    platform.refund_bet(&alice, &match_id, &alice).unwrap();
+    let bet: BetState = platform.harness.state(&bet_pda(&alice, &match_id));
+    assert!(bet.status == BetStatus::Refunded);
+
+    // A two point win covers
+    let covered = platform
+        .create_line_market(MarketKind::Spread, -10, HOUR, 2 * HOUR)
+        .unwrap();
+    end_match(&mut platform, &covered);
+    platform.resolve_match_with_score(&covered, 0, Some(score(3, 1)));
+    assert_eq!(match_state(&platform, &covered).winning_team, Some(0));
+}
+
+#[test]
+fn team_match_scores_agree_with_the_winner() {
+    let mut platform = Platform::new();
+    let match_id = platform.create_match(HOUR, 2 * HOUR);
+    end_match(&mut platform, &match_id);
+
+    let mut long = score(2, 0);
+    long.periods = vec![PeriodScore::default(); 5];
+    let reports = [
+        (1, "2-0", Some(score(2, 0))),
+        (PUSH_RESULT, "2-2", None),
+        (0, "2-0", Some(long)),
+        // A drawn score leaves the winner to the oracles, e.g. after a shootout
+        (1, "1-1 (3-4 pens)", Some(score(1, 1))),
+        (1, "1-1", Some(score(2, 2))),
+        (1, "1-1", Some(score(1, 1))),
+    ];
+    let oracles: Vec<Pubkey> = reports
+        .iter()
+        .map(|_| platform.register_oracle(&match_id))
+        .collect();
+    for (oracle, (team, text, match_score)) in oracles.iter().zip(reports.iter()) {
+        platform
+            .commit_result(oracle, &match_id, *team, text, match_score.clone())
+            .unwrap();
+    }
+
+    platform.open_reveals(&match_id);
+    let errors = [
+        TrendXBetError::ScoreMismatch,
+        TrendXBetError::InvalidTeam,
+        TrendXBetError::InvalidScore,
+    ];
+    for (i, error) in errors.into_iter().enumerate() {
+        let (team, text, match_score) = reports[i].clone();
+        assert_error(
+            platform.reveal_result(&oracles[i], &match_id, team, text, match_score),
+            error,
+        );
+    }
+    for (oracle, (team, text, match_score)) in oracles.iter().zip(reports.iter()).skip(3) {
+        platform
+            .reveal_result(oracle, &match_id, *team, text, match_score.clone())
+            .unwrap();
+    }
+
+    // Oracles that disagree on the score do not confirm each other
+    let (drawn, disputed, agreeing) = (oracles[3], oracles[4], oracles[5]);
+    assert_error(
+        platform.validate_oracle(&drawn, &match_id, &[disputed]),
+        TrendXBetError::InsufficientOracleConfirmations,
+    );
+    platform
+        .validate_oracle(&drawn, &match_id, &[disputed, agreeing])
+        .unwrap();
+    let state = match_state(&platform, &match_id);
+    assert_eq!(state.winning_team, Some(1));
+    assert_eq!(state.match_score, Some(score(1, 1)));
+}
+
+#[test]
+fn line_markets_need_a_valid_line() {
+    let mut platform = Platform::new();
+    assert_error(
+        platform
+            .create_line_market(MarketKind::Totals, 0, HOUR, 2 * HOUR)
+            .map(|_| ()),
+        TrendXBetError::InvalidLine,
+    );
+    assert_error(
+        platform
+            .create_line_market(MarketKind::TeamMatch, 25, HOUR, 2 * HOUR)
+            .map(|_| ()),
+        TrendXBetError::InvalidMarketType,
+    );
+    assert_error(
+        platform
+            .create_line_market(MarketKind::PriceTrend, 25, HOUR, 2 * HOUR)
+            .map(|_| ()),
+        TrendXBetError::InvalidMarketType,
+    );
+}
@@ -0,0 +1,177 @@
+//! Session keys signing bets for a profile within their expiry, limits and permissions.
+
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::system_program;
+use common::*;
+use trendxbet::{
+    accounts, instruction as ix, SessionKey, SessionKeyCreated, SessionKeyRevoked, TrendXBetError,
+    UserState, MAX_SESSION_DURATION, SESSION_CANCEL_BET, SESSION_PERMISSIONS, SESSION_PLACE_BET,
+};
+
+const HOUR: i64 = 3600;
+
+/// Open `count` matches for betting
+fn open_matches(platform: &mut Platform, count: usize) -> Vec<Pubkey> {
+    let matches = (0..count)
+        .map(|_| platform.create_match(HOUR, 2 * HOUR))
+        .collect();
+    platform.harness.warp_by(HOUR);
+    matches
+}
+
+#[test]
+fn session_places_bets_within_its_limits() {
+    let mut platform = Platform::new();
+    let alice = platform.user("alice");
+    platform.deposit(&alice, 10 * SOL).unwrap();
+    let key = platform.harness.fund(SOL);
+    let expires_at = platform.harness.now() + 3 * HOUR;
+    platform
+        .create_session_key(&alice, &key, expires_at, SOL, 2 * SOL, SESSION_PLACE_BET)
+        .unwrap();
+    let event = &platform.harness.events::<SessionKeyCreated>()[0];
+    assert_eq!((event.user, event.session_key), (alice, key));
+
+    let session = Some(session_pda(&alice, &key));
+    let matches = open_matches(&mut platform, 4);
+
+    assert_error(
+        platform.place_bet_as(&alice, &matches[0], SOL + 1, 0, &key, session),
+        TrendXBetError::SessionLimitExceeded,
+    );
+    platform
+        .place_bet_as(&alice, &matches[0], SOL, 0, &key, session)
+        .unwrap();
+    platform
+        .place_bet_as(&alice, &matches[1], SOL / 2, 1, &key, session)
+        .unwrap();
+    assert_error(
+        platform.place_bet_as(&alice, &matches[2], SOL, 0, &key, session),
+        TrendXBetError::SessionLimitExceeded,
+    );
+
+    let state: SessionKey = platform.harness.state(&session_pda(&alice, &key));
+    assert_eq!(state.spent, 3 * SOL / 2);
+    let user: UserState = platform.harness.state(&user_pda(&alice));
+    assert_eq!(user.balance, 10 * SOL - 3 * SOL / 2);
+
+    // Without the session the key has no say over the profile
+    assert_error(
+        platform.place_bet_as(&alice, &matches[2], SOL / 4, 0, &key, None),
+        TrendXBetError::Unauthorized,
+    );
+
+    platform.harness.warp_to(expires_at + 1);
+    assert_error(
+        platform.place_bet_as(&alice, &matches[3], SOL / 4, 0, &key, session),
+        TrendXBetError::SessionExpired,
+    );
+}
+
+#[test]
+fn session_permissions_never_cover_withdrawals() {
+    let mut platform = Platform::new();
+    let alice = platform.user("alice");
+    platform.deposit(&alice, 10 * SOL).unwrap();
+    let key = platform.harness.fund(SOL);
+    let expires_at = platform.harness.now() + 3 * HOUR;
+    platform
+        .create_session_key(&alice, &key, expires_at, SOL, 5 * SOL, SESSION_PLACE_BET)
+        .unwrap();
+    let session = Some(session_pda(&alice, &key));
+    let matches = open_matches(&mut platform, 1);
+    platform
+        .place_bet_as(&alice, &matches[0], SOL, 0, &key, session)
+        .unwrap();
+
+    assert_error(
+        platform.cancel_bet_as(&alice, &matches[0], &key, session),
+        TrendXBetError::SessionNotPermitted,
+    );
+
+    let withdrawal = platform.harness.send(
+        accounts::WithdrawFunds {
+            user_state: user_pda(&alice),
+            treasury: treasury_pda(),
+            authority: key,
+            user: key,
+            treasury_account: treasury_pda(),
+            system_program: system_program::ID,
+        },
+        ix::WithdrawFunds { amount: SOL },
+        &[key],
+    );
+    // Withdrawals derive the profile from their signer, so a session key cannot address it
+    assert!(withdrawal.is_err());
+
+    // A session granted cancellation can take the bet back
+    platform.revoke_session_key(&alice, &key, &alice).unwrap();
+    platform
+        .create_session_key(&alice, &key, expires_at, SOL, 5 * SOL, SESSION_PERMISSIONS)
+        .unwrap();
+    platform
+        .cancel_bet_as(&alice, &matches[0], &key, session)
+        .unwrap();
+    let user: UserState = platform.harness.state(&user_pda(&alice));
+    assert_eq!(user.balance, 10 * SOL);
+}
+
+#[test]
+fn sessions_are_validated_and_revocable() {
+    let mut platform = Platform::new();
+    let alice = platform.user("alice");
+    let mallory = platform.user("mallory");
+    let key = platform.harness.fund(SOL);
+    let now = platform.harness.now();
+
+    for (session_key, expires_at, max_bet, limit, permissions) in [
+        (key, now, SOL, SOL, SESSION_PLACE_BET),
+        (
+            key,
+            now + MAX_SESSION_DURATION + 1,
+            SOL,
+            SOL,
+            SESSION_PLACE_BET,
+        ),
+        (key, now + HOUR, 0, SOL, SESSION_PLACE_BET),
+        (key, now + HOUR, 2 * SOL, SOL, SESSION_PLACE_BET),
+        (key, now + HOUR, SOL, SOL, 0),
+        (key, now + HOUR, SOL, SOL, SESSION_CANCEL_BET << 1),
+        (alice, now + HOUR, SOL, SOL, SESSION_PLACE_BET),
+    ] {
+        assert_error(
+            platform.create_session_key(
+                &alice,
+                &session_key,
+                expires_at,
+                max_bet,
+                limit,
+                permissions,
+            ),
+            TrendXBetError::InvalidSessionConfig,
+        );
+    }
+
+    let session = session_pda(&alice, &key);
+    platform
+        .create_session_key(&alice, &key, now + HOUR, SOL, SOL, SESSION_PLACE_BET)
+        .unwrap();
+    assert_error(
+        platform.revoke_session_key(&alice, &key, &mallory),
+        TrendXBetError::Unauthorized,
+    );
+
+    // The session key can give up its own access
+    platform.revoke_session_key(&alice, &key, &key).unwrap();
+    let event = &platform.harness.events::<SessionKeyRevoked>()[0];
+    assert_eq!((event.user, event.revoked_by), (alice, key));
+    assert!(platform.harness.account(&session).is_none());
+
+    platform
+        .create_session_key(&alice, &key, now + HOUR, SOL, SOL, SESSION_PLACE_BET)
+        .unwrap();
+    platform.revoke_session_key(&alice, &key, &alice).unwrap();
+    assert!(platform.harness.account(&session).is_none());
+}
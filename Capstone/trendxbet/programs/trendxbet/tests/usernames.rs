@@ -0,0 +1,129 @@
+//! Usernames claimed through case-insensitive username records, released and
+//! re-claimed on rename.
+
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use common::*;
+use trendxbet::{TrendXBetError, UserState, UsernameClaimed, UsernameRecord, UsernameReleased};
+
+fn record(platform: &Platform, username: &str) -> Option<UsernameRecord> {
+    platform
+        .harness
+        .account(&username_pda(username))
+        .map(|_| platform.harness.state(&username_pda(username)))
+}
+
+fn user_state(platform: &Platform, user: &Pubkey) -> UserState {
+    platform.harness.state(&user_pda(user))
+}
+
+#[test]
+fn usernames_are_unique_ignoring_case() {
+    let mut platform = Platform::new();
+    let alice = platform.user("Alice");
+
+    let claim = record(&platform, "alice").expect("username is claimed");
+    assert_eq!(claim.authority, alice);
+    assert_eq!(claim.user_state, user_pda(&alice));
+    assert_eq!(claim.username, "Alice");
+    assert_eq!(username_pda("ALICE"), username_pda("alice"));
+    assert!(user_state(&platform, &alice).has_username_record);
+
+    let impostor = platform.harness.fund(10 * SOL);
+    assert!(platform.create_user_profile(&impostor, "ALICE").is_err());
+    assert!(platform.harness.account(&user_pda(&impostor)).is_none());
+    platform.create_user_profile(&impostor, "Al1ce").unwrap();
+}
+
+#[test]
+fn renaming_releases_the_old_username() {
+    let mut platform = Platform::new();
+    let alice = platform.user("alice");
+    let bob = platform.user("bob");
+
+    assert!(platform.rename_user(&bob, "Alice").is_err());
+    // The current record must be released and the new one claimed
+    assert_error(
+        platform.update_username(&alice, "alicia", None, Some(username_pda("alicia"))),
+        TrendXBetError::UsernameRecordRequired,
+    );
+    assert_error(
+        platform.update_username(&alice, "alicia", Some(username_pda("alice")), None),
+        TrendXBetError::UsernameRecordRequired,
+    );
+    // Records are found by the profile's own username
+    assert!(platform
+        .update_username(
+            &alice,
+            "alicia",
+            Some(username_pda("bob")),
+            Some(username_pda("alicia")),
+        )
+        .is_err());
+
+    platform.rename_user(&alice, "alicia").unwrap();
+    let released = platform.harness.events::<UsernameReleased>();
+    assert_eq!(released.len(), 1);
+    assert_eq!(released[0].username, "alice");
+    assert_eq!(
+        platform.harness.events::<UsernameClaimed>()[0].username,
+        "alicia"
+    );
+    assert!(record(&platform, "alice").is_none());
+    assert_eq!(
+        record(&platform, "alicia").unwrap().user_state,
+        user_pda(&alice)
+    );
+
+    // The released name is free again
+    platform.rename_user(&bob, "ALICE").unwrap();
+    assert_eq!(record(&platform, "alice").unwrap().authority, bob);
+    assert!(record(&platform, "bob").is_none());
+
+    // Changing only the casing keeps the same record
+    platform.rename_user(&alice, "Alicia").unwrap();
+    assert!(platform.harness.events::<UsernameReleased>().is_empty());
+    assert_eq!(record(&platform, "alicia").unwrap().username, "Alicia");
+    assert_eq!(user_state(&platform, &alice).username, "Alicia");
+}
+
+#[test]
+fn profiles_without_a_record_claim_one_on_rename() {
+    let mut platform = Platform::new();
+    let carol = platform.user("carol");
+
+    // A profile created before usernames were unique holds no record
+    let mut state = user_state(&platform, &carol);
+    state.username = "caroline".to_string();
+    state.has_username_record = false;
+    platform.harness.set_state(&user_pda(&carol), &state);
+
+    assert_error(
+        platform.update_username(&carol, "caroline", None, None),
+        TrendXBetError::UsernameRecordRequired,
+    );
+    // Nor does it own the record of whoever holds its name now
+    let dave = platform.user("dave");
+    let mut legacy = user_state(&platform, &carol);
+    legacy.username = "Dave".to_string();
+    platform.harness.set_state(&user_pda(&carol), &legacy);
+    assert_error(
+        platform.update_username(
+            &carol,
+            "caroline",
+            Some(username_pda("dave")),
+            Some(username_pda("caroline")),
+        ),
+        TrendXBetError::UsernameRecordMismatch,
+    );
+    assert_eq!(record(&platform, "dave").unwrap().authority, dave);
+
+    platform.rename_user(&carol, "Caroline").unwrap();
+    assert!(platform.harness.events::<UsernameReleased>().is_empty());
+    assert_eq!(
+        record(&platform, "caroline").unwrap().user_state,
+        user_pda(&carol)
+    );
+    assert!(user_state(&platform, &carol).has_username_record);
+}
@@ -0,0 +1,187 @@
+//! Oracle timeout fallback: a match the oracles fail to resolve can be voided by
+//! anyone after the deadline, and every bet on it refunded in full.
+
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use common::*;
+use trendxbet::{
+    BetRefunded, BetState, BetStatus, MatchState, MatchStatus, MatchStatusUpdated, MatchVoided,
+    TreasuryState, TrendXBetError, UserState, ORACLE_UPDATE_WINDOW, SETTLEMENT_WINDOW,
+};
+
+const HOUR: i64 = 3600;
+
+fn match_state(platform: &Platform, match_id: &Pubkey) -> MatchState {
+    platform.harness.state(&match_pda(match_id))
+}
+
+fn balance(platform: &Platform, user: &Pubkey) -> u64 {
+    platform.harness.state::<UserState>(&user_pda(user)).balance
+}
+
+#[test]
+fn unreported_match_is_voided_after_the_oracle_window() {
+    let mut platform = Platform::new();
+    let alice = platform.user("alice");
+    let bob = platform.user("bob");
+    platform.deposit(&alice, 5 * SOL).unwrap();
+    platform.deposit(&bob, 5 * SOL).unwrap();
+    let stranger = platform.harness.fund(SOL);
+
+    let match_id = platform.create_match(HOUR, 2 * HOUR);
+    platform.harness.warp_by(HOUR);
+    platform.place_bet(&alice, &match_id, SOL, 0).unwrap();
+    platform.place_bet(&bob, &match_id, 2 * SOL, 1).unwrap();
+
+    let end_time = match_state(&platform, &match_id).end_time;
+    platform.harness.warp_to(end_time);
+    platform.crank_match(&match_id, &stranger).unwrap();
+
+    // Oracles still have their update window
+    platform.harness.warp_to(end_time + ORACLE_UPDATE_WINDOW);
+    assert_error(
+        platform.void_match(&match_id, &stranger),
+        TrendXBetError::VoidDeadlineNotReached,
+    );
+    assert_error(
+        platform.refund_bet(&alice, &match_id, &stranger),
+        TrendXBetError::MatchNotVoided,
+    );
+
+    platform
+        .harness
+        .warp_to(end_time + ORACLE_UPDATE_WINDOW + 1);
+    platform.void_match(&match_id, &stranger).unwrap();
+
+    let state = match_state(&platform, &match_id);
+    assert!(state.status == MatchStatus::Voided);
+    assert!(state.is_betting_closed);
+    let updated = &platform.harness.events::<MatchStatusUpdated>()[0];
+    assert_eq!((updated.old_status, updated.new_status), (2, 5));
+    let voided = &platform.harness.events::<MatchVoided>()[0];
+    assert_eq!(voided.voided_by, stranger);
+    assert_eq!(voided.oracle_reports, 0);
+    assert_eq!(voided.total_pool, 3 * SOL);
+    assert_eq!(voided.total_bets, 2);
+
+    assert_error(
+        platform.void_match(&match_id, &stranger),
+        TrendXBetError::InvalidMatchStatus,
+    );
+
+    // Anyone may refund; the full stake returns, fee included
+    platform.refund_bet(&alice, &match_id, &stranger).unwrap();
+    assert_eq!(balance(&platform, &alice), 5 * SOL);
+    let bet: BetState = platform.harness.state(&bet_pda(&alice, &match_id));
+    assert!(bet.status == BetStatus::Refunded);
+    assert_eq!(bet.payout_amount, Some(SOL));
+    assert_eq!(bet.get_profit_loss(), 0);
+
+    let refunded = &platform.harness.events::<BetRefunded>()[0];
+    assert_eq!(refunded.bettor, alice);
+    assert_eq!(refunded.amount, SOL);
+    assert_eq!(refunded.refunded_by, stranger);
+
+    assert_error(
+        platform.refund_bet(&alice, &match_id, &stranger),
+        TrendXBetError::BetNotActive,
+    );
+
+    platform.refund_bet(&bob, &match_id, &bob).unwrap();
+    assert_eq!(balance(&platform, &bob), 5 * SOL);
+
+    let treasury: TreasuryState = platform.harness.state(&treasury_pda());
+    assert_eq!(treasury.platform_fees, 0);
+    assert_eq!(treasury.unearned_fees, 0);
+}
+
+#[test]
+fn reported_match_is_voided_after_the_settlement_window() {
+    let mut platform = Platform::new();
+    let alice = platform.user("alice");
+    platform.deposit(&alice, 5 * SOL).unwrap();
+    let stranger = platform.harness.fund(SOL);
+
+    let match_id = platform.create_match(HOUR, 2 * HOUR);
+    platform.harness.warp_by(HOUR);
+    platform.place_bet(&alice, &match_id, SOL, 0).unwrap();
+
+    let end_time = match_state(&platform, &match_id).end_time;
+    platform.harness.warp_to(end_time);
+    platform.crank_match(&match_id, &stranger).unwrap();
+
+    // A lone report never reaches consensus
+    let oracle = platform.register_oracle(&match_id);
+    platform
+        .report_result(&oracle, &match_id, 0, "1-0")
+        .unwrap();
+    assert_eq!(match_state(&platform, &match_id).oracle_reports, 1);
+
+    platform
+        .harness
+        .warp_to(end_time + ORACLE_UPDATE_WINDOW + 1);
+    assert_error(
+        platform.void_match(&match_id, &stranger),
+        TrendXBetError::VoidDeadlineNotReached,
+    );
+
+    platform.harness.warp_to(end_time + SETTLEMENT_WINDOW);
+    platform.void_match(&match_id, &stranger).unwrap();
+    assert_eq!(
+        platform.harness.events::<MatchVoided>()[0].oracle_reports,
+        1
+    );
+
+    // A voided match takes no further oracle reports
+    assert_error(
+        platform.report_result(&oracle, &match_id, 0, "1-0"),
+        TrendXBetError::InvalidMatchStatus,
+    );
+    platform.refund_bet(&alice, &match_id, &alice).unwrap();
+    assert_eq!(balance(&platform, &alice), 5 * SOL);
+}
+
+#[test]
+fn settled_matches_cannot_be_voided() {
+    let mut platform = Platform::new();
+    let alice = platform.user("alice");
+    platform.deposit(&alice, 5 * SOL).unwrap();
+    let stranger = platform.harness.fund(SOL);
+
+    let match_id = platform.create_match(HOUR, 2 * HOUR);
+    platform.harness.warp_by(HOUR);
+    platform.place_bet(&alice, &match_id, SOL, 0).unwrap();
+    let fee = platform
+        .harness
+        .state::<BetState>(&bet_pda(&alice, &match_id))
+        .platform_fee;
+
+    // The fee of an unsettled bet is not withdrawable
+    assert_error(
+        platform.withdraw_platform_fees(fee),
+        TrendXBetError::TreasuryInsufficientFunds,
+    );
+
+    platform.harness.warp_by(2 * HOUR);
+    platform.crank_match(&match_id, &stranger).unwrap();
+    platform.resolve_match(&match_id, 0);
+    platform.settle_bet(&alice, &match_id, &alice).unwrap();
+
+    let treasury: TreasuryState = platform.harness.state(&treasury_pda());
+    assert_eq!(treasury.unearned_fees, 0);
+    assert_eq!(treasury.available_platform_fees(), fee);
+
+    platform
+        .harness
+        .warp_by(SETTLEMENT_WINDOW + ORACLE_UPDATE_WINDOW);
+    assert_error(
+        platform.void_match(&match_id, &stranger),
+        TrendXBetError::InvalidMatchStatus,
+    );
+    assert_error(
+        platform.refund_bet(&alice, &match_id, &stranger),
+        TrendXBetError::MatchNotVoided,
+    );
+    platform.withdraw_platform_fees(fee).unwrap();
+}
@@ -3,6 +3,7 @@ import { Program } from "@coral-xyz/anchor";
 import { Trendxbet } from "../target/types/trendxbet";
 import { expect } from "chai";
 import { PublicKey, Keypair, SystemProgram, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import { createHash } from "crypto";
 
 describe("TrendXBet - Cricket Betting Platform", () => {
   // Configure the client to use the local cluster
@@ -23,6 +24,46 @@ describe("TrendXBet - Cricket Betting Platform", () => {
   const BET_SEED = "bet";
   const TREASURY_SEED = "treasury";
   const ORACLE_SEED = "oracle";
+  const ORACLE_PROFILE_SEED = "oracle_profile";
+  const USERNAME_SEED = "username";
+  const MIN_ORACLE_STAKE = 1 * LAMPORTS_PER_SOL;
+
+  // Usernames are claimed case-insensitively, keyed on the hash of the lowercased name
+  const usernamePda = (username: string) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from(USERNAME_SEED), createHash("sha256").update(username.toLowerCase()).digest()],
+      program.programId
+    )[0];
+
+  // Oracles must bond stake before they can be registered for a match
+  const bondOracleStake = async (oracleKeypair: Keypair) => {
+    const [oracleProfilePda] = PublicKey.findProgramAddressSync(
+      [Buffer.from(ORACLE_PROFILE_SEED), oracleKeypair.publicKey.toBuffer()],
+      program.programId
+    );
+
+    await program.methods
+      .createOracleProfile()
+      .accountsPartial({
+        oracleProfile: oracleProfilePda,
+        authority: oracleKeypair.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([oracleKeypair])
+      .rpc();
+
+    await program.methods
+      .bondOracleStake(new anchor.BN(MIN_ORACLE_STAKE))
+      .accountsPartial({
+        oracleProfile: oracleProfilePda,
+        authority: oracleKeypair.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([oracleKeypair])
+      .rpc();
+
+    return oracleProfilePda;
+  };
 
   // Test data
   const matchId = Keypair.generate();
@@ -42,7 +83,7 @@ describe("TrendXBet - Cricket Betting Platform", () => {
     await provider.connection.requestAirdrop(admin.publicKey, 10 * LAMPORTS_PER_SOL);
     await provider.connection.requestAirdrop(user1.publicKey, 5 * LAMPORTS_PER_SOL);
     await provider.connection.requestAirdrop(user2.publicKey, 5 * LAMPORTS_PER_SOL);
-    await provider.connection.requestAirdrop(oracle.publicKey, 2 * LAMPORTS_PER_SOL);
+    await provider.connection.requestAirdrop(oracle.publicKey, 3 * LAMPORTS_PER_SOL);
 
     // Wait for airdrops to confirm
     await new Promise(resolve => setTimeout(resolve, 1000));
@@ -111,6 +152,7 @@ describe("TrendXBet - Cricket Betting Platform", () => {
           userState: user1StatePda,
           user: user1.publicKey,
           systemProgram: SystemProgram.programId,
+          usernameRecord: usernamePda("CricketFan1"),
         })
         .signers([user1])
         .rpc();
@@ -122,6 +164,7 @@ describe("TrendXBet - Cricket Betting Platform", () => {
           userState: user2StatePda,
           user: user2.publicKey,
           systemProgram: SystemProgram.programId,
+          usernameRecord: usernamePda("CricketFan2"),
         })
         .signers([user2])
         .rpc();
@@ -165,7 +208,7 @@ describe("TrendXBet - Cricket Betting Platform", () => {
       const endTime = startTime + 10800; // 3 hours match duration
 
       const tx = await program.methods
-        .createMatch(team1, team2, new anchor.BN(startTime), new anchor.BN(endTime), description)
+        .createMatch(team1, team2, new anchor.BN(startTime), new anchor.BN(endTime), description, 0)
         .accountsPartial({
           matchState: matchStatePda,
           globalState: globalStatePda,
@@ -189,12 +232,15 @@ describe("TrendXBet - Cricket Betting Platform", () => {
 
   describe("Oracle Management", () => {
     it("Should register oracle for match", async () => {
+      const oracleProfilePda = await bondOracleStake(oracle);
+
       await program.methods
         .registerOracle(oracle.publicKey)
         .accountsPartial({
           oracleState: oracleStatePda,
           globalState: globalStatePda,
           matchState: matchStatePda,
+          oracleProfile: oracleProfilePda,
           oracleAuthority: oracle.publicKey,
           matchId: matchId.publicKey,
           admin: admin.publicKey,
@@ -225,7 +271,7 @@ describe("TrendXBet - Cricket Betting Platform", () => {
 
       // Create the betting match
       await program.methods
-        .createMatch("Team A", "Team B", new anchor.BN(bettingStartTime), new anchor.BN(bettingEndTime), "Test betting match")
+        .createMatch("Team A", "Team B", new anchor.BN(bettingStartTime), new anchor.BN(bettingEndTime), "Test betting match", 0)
         .accountsPartial({
           matchState: bettingMatchStatePda,
           globalState: globalStatePda,
@@ -250,7 +296,7 @@ describe("TrendXBet - Cricket Betting Platform", () => {
       );
 
       await program.methods
-        .placeBet(new anchor.BN(betAmount), predictedTeam, new anchor.BN(oddsAccepted))
+        .placeBet(new anchor.BN(betAmount), predictedTeam, new anchor.BN(oddsAccepted), false)
         .accountsPartial({
           betState: betStatePda,
           userState: user1StatePda,
@@ -284,7 +330,7 @@ describe("TrendXBet - Cricket Betting Platform", () => {
       const newMaxBet = 50000000000; // 50 SOL
 
       await program.methods
-        .updatePlatformConfig(newHouseEdge, new anchor.BN(newMinBet), new anchor.BN(newMaxBet))
+        .updatePlatformConfig(newHouseEdge, new anchor.BN(newMinBet), new anchor.BN(newMaxBet), null, null, null, null, null, null, null, null, null)
         .accountsPartial({
           globalState: globalStatePda,
           admin: admin.publicKey,
@@ -342,7 +388,7 @@ describe("TrendXBet - Cricket Betting Platform", () => {
 
       // Create another betting match
       await program.methods
-        .createMatch("Team C", "Team D", new anchor.BN(bettingStartTime2), new anchor.BN(bettingEndTime2), "Treasury test match")
+        .createMatch("Team C", "Team D", new anchor.BN(bettingStartTime2), new anchor.BN(bettingEndTime2), "Treasury test match", 0)
         .accountsPartial({
           matchState: bettingMatchStatePda2,
           globalState: globalStatePda,
@@ -382,7 +428,7 @@ describe("TrendXBet - Cricket Betting Platform", () => {
 
       // Place a bet to generate treasury activity
       await program.methods
-        .placeBet(new anchor.BN(betAmount), predictedTeam, new anchor.BN(oddsAccepted))
+        .placeBet(new anchor.BN(betAmount), predictedTeam, new anchor.BN(oddsAccepted), false)
         .accountsPartial({
           betState: betStatePda2,
           userState: user2StatePda,
@@ -419,7 +465,7 @@ describe("TrendXBet - Cricket Betting Platform", () => {
 
       // Create the error test match
       await program.methods
-        .createMatch("Error Team 1", "Error Team 2", new anchor.BN(errorTestStartTime), new anchor.BN(errorTestEndTime), "Error test match")
+        .createMatch("Error Team 1", "Error Team 2", new anchor.BN(errorTestStartTime), new anchor.BN(errorTestEndTime), "Error test match", 0)
         .accountsPartial({
           matchState: errorTestMatchStatePda,
           globalState: globalStatePda,
@@ -444,7 +490,7 @@ describe("TrendXBet - Cricket Betting Platform", () => {
 
       try {
         await program.methods
-          .placeBet(new anchor.BN(invalidBetAmount), predictedTeam, new anchor.BN(oddsAccepted))
+          .placeBet(new anchor.BN(invalidBetAmount), predictedTeam, new anchor.BN(oddsAccepted), false)
           .accountsPartial({
             betState: betStatePda,
             userState: user2StatePda,
@@ -497,7 +543,7 @@ describe("TrendXBet - Cricket Betting Platform", () => {
 
       // Create match
       await program.methods
-        .createMatch("Status Team 1", "Status Team 2", new anchor.BN(statusTestStartTime), new anchor.BN(statusTestEndTime), "Status test match")
+        .createMatch("Status Team 1", "Status Team 2", new anchor.BN(statusTestStartTime), new anchor.BN(statusTestEndTime), "Status test match", 0)
         .accountsPartial({
           matchState: statusTestMatchStatePda,
           globalState: globalStatePda,
@@ -544,7 +590,7 @@ describe("TrendXBet - Cricket Betting Platform", () => {
 
       // Create match and immediately set to Live
       await program.methods
-        .createMatch("Live Team 1", "Live Team 2", new anchor.BN(liveMatchStartTime), new anchor.BN(liveMatchEndTime), "Live test match")
+        .createMatch("Live Team 1", "Live Team 2", new anchor.BN(liveMatchStartTime), new anchor.BN(liveMatchEndTime), "Live test match", 0)
         .accountsPartial({
           matchState: liveMatchStatePda,
           globalState: globalStatePda,
@@ -578,7 +624,7 @@ describe("TrendXBet - Cricket Betting Platform", () => {
 
       try {
         await program.methods
-          .placeBet(new anchor.BN(0.1 * LAMPORTS_PER_SOL), 0, new anchor.BN(10000))
+          .placeBet(new anchor.BN(0.1 * LAMPORTS_PER_SOL), 0, new anchor.BN(10000), false)
           .accountsPartial({
             betState: liveBetStatePda,
             userState: user1StatePda,
@@ -611,7 +657,7 @@ describe("TrendXBet - Cricket Betting Platform", () => {
 
       // Create match
       await program.methods
-        .createMatch("Closing Team 1", "Closing Team 2", new anchor.BN(closingStartTime), new anchor.BN(closingEndTime), "Closing test match")
+        .createMatch("Closing Team 1", "Closing Team 2", new anchor.BN(closingStartTime), new anchor.BN(closingEndTime), "Closing test match", 0)
         .accountsPartial({
           matchState: closingMatchStatePda,
           globalState: globalStatePda,
@@ -671,6 +717,7 @@ describe("TrendXBet - Cricket Betting Platform", () => {
             userState: user1StatePda,
             user: user1.publicKey,
             systemProgram: SystemProgram.programId,
+            usernameRecord: usernamePda("CricketFan1"),
           })
           .signers([user1])
           .rpc();
@@ -694,7 +741,7 @@ describe("TrendXBet - Cricket Betting Platform", () => {
 
       // Create match
       await program.methods
-        .createMatch("Max Bet Team 1", "Max Bet Team 2", new anchor.BN(maxBetStartTime), new anchor.BN(maxBetEndTime), "Max bet test")
+        .createMatch("Max Bet Team 1", "Max Bet Team 2", new anchor.BN(maxBetStartTime), new anchor.BN(maxBetEndTime), "Max bet test", 0)
         .accountsPartial({
           matchState: maxBetMatchStatePda,
           globalState: globalStatePda,
@@ -716,7 +763,7 @@ describe("TrendXBet - Cricket Betting Platform", () => {
       try {
         const excessiveAmount = new anchor.BN("60000000000"); // 60 SOL (above 50 SOL limit)
         await program.methods
-          .placeBet(excessiveAmount, 0, new anchor.BN(10000))
+          .placeBet(excessiveAmount, 0, new anchor.BN(10000), false)
           .accountsPartial({
             betState: maxBetStatePda,
             userState: user1StatePda,
@@ -754,6 +801,7 @@ describe("TrendXBet - Cricket Betting Platform", () => {
           userState: insufficientUserStatePda,
           user: insufficientBalanceUser.publicKey,
           systemProgram: SystemProgram.programId,
+          usernameRecord: usernamePda("PoorUser"),
         })
         .signers([insufficientBalanceUser])
         .rpc();
@@ -769,7 +817,7 @@ describe("TrendXBet - Cricket Betting Platform", () => {
 
       // Create match
       await program.methods
-        .createMatch("Poor Team 1", "Poor Team 2", new anchor.BN(insufficientStartTime), new anchor.BN(insufficientEndTime), "Insufficient test")
+        .createMatch("Poor Team 1", "Poor Team 2", new anchor.BN(insufficientStartTime), new anchor.BN(insufficientEndTime), "Insufficient test", 0)
         .accountsPartial({
           matchState: insufficientMatchStatePda,
           globalState: globalStatePda,
@@ -789,7 +837,7 @@ describe("TrendXBet - Cricket Betting Platform", () => {
 
       try {
         await program.methods
-          .placeBet(new anchor.BN(1 * LAMPORTS_PER_SOL), 0, new anchor.BN(10000))
+          .placeBet(new anchor.BN(1 * LAMPORTS_PER_SOL), 0, new anchor.BN(10000), false)
           .accountsPartial({
             betState: insufficientBetStatePda,
             userState: insufficientUserStatePda,
@@ -818,7 +866,7 @@ describe("TrendXBet - Cricket Betting Platform", () => {
       // Try platform config update
       try {
         await program.methods
-          .updatePlatformConfig(200, new anchor.BN(100000), new anchor.BN(10000000000))
+          .updatePlatformConfig(200, new anchor.BN(100000), new anchor.BN(10000000000), null, null, null, null, null, null, null, null, null)
           .accountsPartial({
             globalState: globalStatePda,
             admin: unauthorizedUser.publicKey,
@@ -855,7 +903,7 @@ describe("TrendXBet - Cricket Betting Platform", () => {
   describe("Oracle Management Extended", () => {
     it("Should handle multiple oracles for same match", async () => {
       const oracle2 = Keypair.generate();
-      await provider.connection.requestAirdrop(oracle2.publicKey, 1 * LAMPORTS_PER_SOL);
+      await provider.connection.requestAirdrop(oracle2.publicKey, 2 * LAMPORTS_PER_SOL);
       await new Promise(resolve => setTimeout(resolve, 1000));
 
       const multiOracleMatchId = Keypair.generate();
@@ -871,7 +919,7 @@ describe("TrendXBet - Cricket Betting Platform", () => {
 
       // Create match
       await program.methods
-        .createMatch("Oracle Team 1", "Oracle Team 2", new anchor.BN(Math.floor(Date.now() / 1000) + 300), new anchor.BN(Math.floor(Date.now() / 1000) + 3900), "Multi oracle test")
+        .createMatch("Oracle Team 1", "Oracle Team 2", new anchor.BN(Math.floor(Date.now() / 1000) + 300), new anchor.BN(Math.floor(Date.now() / 1000) + 3900), "Multi oracle test", 0)
         .accountsPartial({
           matchState: multiOracleMatchStatePda,
           globalState: globalStatePda,
@@ -883,12 +931,14 @@ describe("TrendXBet - Cricket Betting Platform", () => {
         .rpc();
 
       // Register second oracle
+      const oracle2ProfilePda = await bondOracleStake(oracle2);
       await program.methods
         .registerOracle(oracle2.publicKey)
         .accountsPartial({
           oracleState: oracle2StatePda,
           globalState: globalStatePda,
           matchState: multiOracleMatchStatePda,
+          oracleProfile: oracle2ProfilePda,
           oracleAuthority: oracle2.publicKey,
           matchId: multiOracleMatchId.publicKey,
           admin: admin.publicKey,
@@ -916,7 +966,7 @@ describe("TrendXBet - Cricket Betting Platform", () => {
 
       try {
         await program.methods
-          .updateMatchResult(1, "Unauthorized: 100 - Authorized: 50")
+          .commitMatchResult(Array(32).fill(1))
           .accountsPartial({
             oracleState: fakeOracleStatePda, // This PDA doesn't exist
             matchState: matchStatePda,
@@ -948,7 +998,7 @@ describe("TrendXBet - Cricket Betting Platform", () => {
 
       // Create match
       await program.methods
-        .createMatch("Pause Team 1", "Pause Team 2", new anchor.BN(pauseStartTime), new anchor.BN(pauseEndTime), "Pause test")
+        .createMatch("Pause Team 1", "Pause Team 2", new anchor.BN(pauseStartTime), new anchor.BN(pauseEndTime), "Pause test", 0)
         .accountsPartial({
           matchState: pauseMatchStatePda,
           globalState: globalStatePda,
@@ -979,7 +1029,7 @@ describe("TrendXBet - Cricket Betting Platform", () => {
 
       try {
         await program.methods
-          .placeBet(new anchor.BN(0.1 * LAMPORTS_PER_SOL), 0, new anchor.BN(10000))
+          .placeBet(new anchor.BN(0.1 * LAMPORTS_PER_SOL), 0, new anchor.BN(10000), false)
           .accountsPartial({
             betState: pauseBetStatePda,
             userState: user1StatePda,
@@ -1011,7 +1061,7 @@ describe("TrendXBet - Cricket Betting Platform", () => {
 
       // Now betting should work
       await program.methods
-        .placeBet(new anchor.BN(0.1 * LAMPORTS_PER_SOL), 0, new anchor.BN(10000))
+        .placeBet(new anchor.BN(0.1 * LAMPORTS_PER_SOL), 0, new anchor.BN(10000), false)
         .accountsPartial({
           betState: pauseBetStatePda,
           userState: user1StatePda,
@@ -1051,6 +1101,9 @@ describe("TrendXBet - Cricket Betting Platform", () => {
           userState: user1StatePda,
           user: user1.publicKey,
           authority: user1.publicKey,
+          systemProgram: SystemProgram.programId,
+          currentUsernameRecord: usernamePda("CricketFan1"),
+          newUsernameRecord: usernamePda("UpdatedCricketFan1"),
         })
         .signers([user1])
         .rpc();